@@ -1,23 +1,46 @@
-use std::time::Duration;
-
 use dioxus::{prelude::*, signals::Signal};
 
+use serde_json::json;
+use tracing::{debug, info, instrument, trace, warn};
+
 use crate::{
-    domain::{AppState, CacheResource, SellLocation},
-    infra::uex::{CacheStatus, UexClient},
+    domain::{
+        adjust_cargo, calculate_best_value_plan, calculate_one_stop_plan, evaluate_cargo_items,
+        extract_locations, match_commodity, rank_best_prices_with, resolve_location, AppState,
+        CacheResource, CargoAdjustResult, Commodity, DetectedPosition, Location, LogTrade,
+        PositionTracker, RefineryJob, SellLocation, TradeRecord, TradeSide, TradeTracker,
+        history::unix_now,
+    },
+    infra::{
+        api::{self, ApiCommand, ApiRequest, ApiResponse, SellPlanMode},
+        config::{self, GameLogConfig},
+        export::{best_price_rows, evaluation_rows, sell_plan_rows},
+        game_log::{resolve_game_log_path, LogTail},
+        uex::{CacheStatus, UexClient},
+    },
     ui::{
-        components::toast::{push_toast, Toast, ToastKind, ToastMessage},
-        pages::{BestPricePage, CargoPage, PlannerPage, RoutesPage, SettingsPage},
+        components::{
+            recovery_prompt::{RecoveryIssue, RecoveryPrompt},
+            toast::{push_toast, Toast, ToastKind, ToastMessage},
+        },
+        pages::{
+            cargo::request_price_fetch,
+            crew,
+            BestPricePage, CargoPage, CrewPage, FencePage, HistoryPage, OrePricesPage, PlannerPage, RefineryPage,
+            ReportsPage, RoutesPage, SettingsPage, TrafficPage, WatchlistPage,
+        },
+        i18n::{self, tr},
         shell::Shell,
     },
     util::{
-        assets,
-        persistence::{load_persisted_state, save_persisted_state},
+        assets, format,
+        notify::desktop_notify,
+        persistence::{
+            load_history, load_persisted_state, save_history, save_persisted_state, StoreFile,
+        },
     },
 };
 
-/// Shared TTL for API cache before a refresh is triggered.
-pub const CACHE_TTL: Duration = Duration::from_secs(60 * 60);
 
 #[derive(Routable, Clone, PartialEq)]
 pub enum Route {
@@ -30,6 +53,22 @@ pub enum Route {
     BestPrice {},
     #[route("/routes")]
     Routes {},
+    #[route("/traffic")]
+    Traffic {},
+    #[route("/fence")]
+    Fence {},
+    #[route("/refinery")]
+    Refinery {},
+    #[route("/ore-prices")]
+    OrePrices {},
+    #[route("/history")]
+    History {},
+    #[route("/watchlist")]
+    Watchlist {},
+    #[route("/crew")]
+    Crew {},
+    #[route("/reports")]
+    Reports {},
     #[route("/settings")]
     Settings {},
 }
@@ -37,22 +76,91 @@ pub enum Route {
 #[component]
 pub fn App() -> Element {
     let state = use_signal(AppState::default);
+    let toasts = use_signal(Vec::<ToastMessage>::new);
+    let mut recovery = use_signal(Vec::<RecoveryIssue>::new);
     use_hook({
         let mut state = state.clone();
         move || {
-            if let Some(saved) = load_persisted_state() {
-                state.with_mut(|st| st.apply_persisted(saved));
+            let problems = config::init();
+            let config_clean = problems.is_empty();
+            i18n::set_locale(config::current().ui.locale);
+            for problem in problems {
+                warn!(%problem, "config problem, using defaults");
+                if problem.is_damaged() {
+                    recovery.with_mut(|issues| {
+                        issues.push(RecoveryIssue {
+                            file: StoreFile::Config,
+                            reason: problem.to_string(),
+                        })
+                    });
+                    continue;
+                }
+                push_toast(
+                    toasts,
+                    ToastKind::Warning,
+                    tr!("app.toast.config_problem", problem = problem),
+                );
             }
+            let defaults = config::current().profiles;
+            state.with_mut(|st| {
+                st.profile = defaults.default_profile;
+                st.profitability = defaults.default_profitability;
+            });
+
+            match load_persisted_state() {
+                Ok(Some(saved)) => {
+                    state.with_mut(|st| st.apply_persisted(saved));
+                    if config_clean {
+                        adopt_legacy_watchlist(&mut state, toasts);
+                    }
+                }
+                Ok(None) => {}
+                Err(err) if err.is_damaged() => recovery.with_mut(|issues| {
+                    issues.push(RecoveryIssue {
+                        file: StoreFile::State,
+                        reason: err.to_string(),
+                    })
+                }),
+                Err(err) => push_toast(
+                    toasts,
+                    ToastKind::Warning,
+                    tr!("app.toast.load_failed", file = StoreFile::State.label(), error = err),
+                ),
+            }
+            let history = match load_history() {
+                Ok(history) => history.unwrap_or_default(),
+                Err(err) if !err.is_damaged() => {
+                    push_toast(
+                        toasts,
+                        ToastKind::Warning,
+                        tr!("app.toast.load_failed", file = StoreFile::History.label(), error = err),
+                    );
+                    Default::default()
+                }
+                Err(err) => {
+                    recovery.with_mut(|issues| {
+                        issues.push(RecoveryIssue {
+                            file: StoreFile::History,
+                            reason: err.to_string(),
+                        })
+                    });
+                    Default::default()
+                }
+            };
+            state.with_mut(|st| {
+                st.history = history;
+                st.session_id = uuid::Uuid::new_v4().to_string();
+                st.active_manifest_mut();
+            });
         }
     });
     use_context_provider(|| state.clone());
-
-    let toasts = use_signal(Vec::<ToastMessage>::new);
     use_context_provider(|| toasts.clone());
 
     // Price fetch trigger shared across routes.
     let price_request = use_signal(|| None::<String>);
     use_context_provider(|| price_request.clone());
+    crew::use_crew_session_provider(state);
 
     let _commodities = use_resource({
         let state = state.clone();
@@ -81,11 +189,71 @@ pub fn App() -> Element {
         move || async move { fetch_prices_for_cargo(state.clone(), toasts.clone()).await }
     });
 
+    // Background watchlist poller
+    use_future(move || async move {
+        let Ok(client) = UexClient::new() else {
+            return;
+        };
+        loop {
+            poll_watchlist(&client, state, toasts).await;
+            // Re-read each round so Settings changes apply without a restart.
+            tokio::time::sleep(config::current().network.watch_poll_interval()).await;
+        }
+    });
+
+    // Refinery jobs: notify once per finished job, whichever page is open.
+    use_future(move || async move {
+        loop {
+            notify_finished_refinery_jobs(state, toasts);
+            tokio::time::sleep(std::time::Duration::from_secs(15)).await;
+        }
+    });
+
+    // Local HTTP API for overlays and companion tools (off by default).
+    use_future(move || async move {
+        let settings = config::current().api;
+        if !settings.enabled {
+            return;
+        }
+        let listener = match api::bind(settings.port).await {
+            Ok(listener) => listener,
+            Err(err) => {
+                push_toast(toasts, ToastKind::Error, tr!("app.toast.api_disabled", error = err));
+                return;
+            }
+        };
+        info!(port = settings.port, "API listening on http://127.0.0.1:{}/api/v1", settings.port);
+        let (sender, mut requests) = tokio::sync::mpsc::channel::<ApiRequest>(16);
+        tokio::spawn(api::serve(listener, sender));
+        while let Some(request) = requests.recv().await {
+            let response = handle_api_command(state, toasts, price_request, request.command);
+            let _ = request.reply.send(response);
+        }
+    });
+
+    // Game.log watcher: keeps the planner position in sync with the game (off by default).
+    use_future(move || async move {
+        let settings = config::current().game_log;
+        if !settings.enabled {
+            return;
+        }
+        let Some(path) = resolve_game_log_path(&settings.path) else {
+            push_toast(
+                toasts,
+                ToastKind::Warning,
+                tr!("app.toast.game_log_missing"),
+            );
+            return;
+        };
+        watch_game_log(LogTail::new(path), settings, state, toasts, price_request).await;
+    });
+
     rsx! {
         document::Link { rel: "icon", href: assets::favicon_data_uri() }
         document::Style { "{assets::main_css()}" }
         document::Style { "{assets::tailwind_css()}" }
         Router::<Route> {}
+        RecoveryPrompt { issues: recovery }
         Toast {}
     }
 }
@@ -93,7 +261,418 @@ pub fn App() -> Element {
 pub fn persist_user_state(state: &Signal<AppState>) {
     let snapshot = state.with(|st| st.to_persisted());
     if let Err(err) = save_persisted_state(&snapshot) {
-        println!("Failed to persist user state: {err}");
+        warn!(%err, "failed to persist user state");
+    }
+}
+
+pub fn persist_history(state: &Signal<AppState>) {
+    let snapshot = state.with(|st| st.history.clone());
+    if let Err(err) = save_history(&snapshot) {
+        warn!(%err, "failed to persist trade history");
+    }
+}
+
+/// Answer one local API request from the current app state.
+fn handle_api_command(
+    mut state: Signal<AppState>,
+    toasts: Signal<Vec<ToastMessage>>,
+    price_request: Signal<Option<String>>,
+    command: ApiCommand,
+) -> ApiResponse {
+    match command {
+        ApiCommand::Cargo => state.with(|st| {
+            let manifest = st.active_manifest();
+            let items = st.cargo_items();
+            ApiResponse::ok(json!({
+                "manifest": manifest.map(|m| json!({ "id": m.id, "name": m.name, "ship": m.ship })),
+                "total_scu": items.iter().map(|item| item.scu).sum::<u32>(),
+                "items": items,
+            }))
+        }),
+        ApiCommand::Summary => state.with(|st| {
+            let items = st.cargo_items();
+            let summary = evaluate_cargo_items(items, &st.price_points);
+            let missing_prices: Vec<&str> = items
+                .iter()
+                .filter(|item| st.price_points.get(&item.commodity_id).is_none_or(|p| p.is_empty()))
+                .map(|item| item.commodity_name.as_str())
+                .collect();
+            ApiResponse::ok(json!({
+                "total_scu": items.iter().map(|item| item.scu).sum::<u32>(),
+                "total_ev": summary.total_ev,
+                "average_confidence": summary.average_confidence,
+                "missing_prices": missing_prices,
+                "items": evaluation_rows(items, &summary),
+            }))
+        }),
+        ApiCommand::BestPrices => state.with(|st| {
+            let summary = rank_best_prices_with(
+                st.cargo_items(),
+                &st.price_points,
+                &st.sell_locations,
+                &config::current().ranking.params(),
+            );
+            ApiResponse::ok(json!({ "suggestions": best_price_rows(&summary) }))
+        }),
+        ApiCommand::SellPlan(mode) => state.with(|st| {
+            let items = st.cargo_items();
+            let plan = match mode {
+                SellPlanMode::OneStop => {
+                    calculate_one_stop_plan(items, &st.price_points, &st.nqa_terminal_ids)
+                }
+                SellPlanMode::BestValue => {
+                    calculate_best_value_plan(items, &st.price_points, &st.nqa_terminal_ids)
+                }
+            };
+            ApiResponse::ok(json!({
+                "mode": mode.key(),
+                "total_value": plan.total_value,
+                "stops": plan.stops.len(),
+                "rows": sell_plan_rows(&plan),
+            }))
+        }),
+        ApiCommand::AddCargo(add) => {
+            let Some(commodity) = state.with(|st| {
+                match_commodity(&add.commodity, &st.commodities).map(|(c, _)| c.clone())
+            }) else {
+                return ApiResponse::error(404, format!("no commodity matches {:?}", add.commodity));
+            };
+            let delta = add.scu.min(i32::MAX as u32) as i32;
+            let (result, item) = state.with_mut(|st| {
+                let cargo_items = st.cargo_items_mut();
+                let result = adjust_cargo(cargo_items, &commodity, delta, add.hot);
+                let item = cargo_items
+                    .iter()
+                    .find(|item| item.commodity_id == commodity.id && item.is_hot == add.hot)
+                    .cloned();
+                (result, item)
+            });
+            if result == CargoAdjustResult::Error {
+                return ApiResponse::error(
+                    422,
+                    format!("could not add {} SCU of {}", add.scu, commodity.name),
+                );
+            }
+            persist_user_state(&state);
+            if matches!(result, CargoAdjustResult::Added(..)) {
+                request_price_fetch(state, price_request, &commodity.id);
+            }
+            push_toast(
+                toasts,
+                ToastKind::Info,
+                tr!("app.toast.api_added", scu = add.scu, commodity = commodity.name),
+            );
+            ApiResponse::ok(json!({ "item": item }))
+        }
+        ApiCommand::RemoveCargo { target, scu } => {
+            let found = state.with(|st| {
+                let items = st.cargo_items();
+                items
+                    .iter()
+                    .find(|item| item.id == target)
+                    .or_else(|| {
+                        let (commodity, _) = match_commodity(&target, &st.commodities)?;
+                        items.iter().find(|item| item.commodity_id == commodity.id)
+                    })
+                    .and_then(|item| {
+                        let commodity = st.commodities.iter().find(|c| c.id == item.commodity_id)?;
+                        Some((commodity.clone(), item.scu, item.is_hot))
+                    })
+            });
+            let Some((commodity, current, is_hot)) = found else {
+                return ApiResponse::error(404, format!("{target:?} is not in the active manifest"));
+            };
+            let delta = -(scu.unwrap_or(current).min(current) as i32);
+            let item = state.with_mut(|st| {
+                let cargo_items = st.cargo_items_mut();
+                adjust_cargo(cargo_items, &commodity, delta, is_hot);
+                cargo_items
+                    .iter()
+                    .find(|item| item.commodity_id == commodity.id && item.is_hot == is_hot)
+                    .cloned()
+            });
+            persist_user_state(&state);
+            push_toast(
+                toasts,
+                ToastKind::Info,
+                tr!("app.toast.api_removed", scu = -delta, commodity = commodity.name),
+            );
+            ApiResponse::ok(json!({ "removed_scu": -delta, "item": item }))
+        }
+    }
+}
+
+/// Follow the game log: publish position changes to `AppState` and, when
+/// enabled, turn kiosk trades into cargo adjustments and ledger entries.
+async fn watch_game_log(
+    mut tail: LogTail,
+    settings: GameLogConfig,
+    mut state: Signal<AppState>,
+    toasts: Signal<Vec<ToastMessage>>,
+    price_request: Signal<Option<String>>,
+) {
+    let terminals = match UexClient::new() {
+        Ok(client) => client.get_terminals().await.ok(),
+        Err(_) => None,
+    };
+    let game_version = terminals.as_ref().map(|cache| cache.game_version.clone());
+    let locations = terminals
+        .map(|cache| extract_locations(&cache.terminals))
+        .unwrap_or_default();
+    info!(path = %tail.path().display(), "watching game log");
+
+    let mut positions = PositionTracker::default();
+    let mut trades = TradeTracker::new(game_version.as_deref());
+    // Trades already in the log at startup were captured by an earlier run.
+    let mut catching_up = true;
+    let mut last_error = None;
+    loop {
+        match tail.poll() {
+            Ok(lines) => {
+                last_error = None;
+                let mut latest = None;
+                for line in &lines {
+                    if let Some(raw) = positions.feed_line(line) {
+                        latest = Some(raw);
+                    }
+                    if let Some(trade) = trades.feed_line(line) {
+                        if settings.capture_trades && !catching_up {
+                            apply_log_trade(state, toasts, price_request, &locations, trade);
+                        }
+                    }
+                }
+                catching_up = false;
+                if let Some(raw) = latest {
+                    let location = resolve_location(&raw, &locations).cloned();
+                    state.with_mut(|st| {
+                        st.detected_position = Some(DetectedPosition {
+                            raw,
+                            location,
+                            detected_at: std::time::SystemTime::now(),
+                        })
+                    });
+                }
+            }
+            Err(err) => {
+                let message = err.to_string();
+                if last_error.as_ref() != Some(&message) {
+                    warn!(path = %tail.path().display(), error = %message, "failed to read game log");
+                    last_error = Some(message);
+                }
+            }
+        }
+        tokio::time::sleep(settings.poll_interval()).await;
+    }
+}
+
+/// Apply a kiosk trade from the game log to the active manifest and the ledger.
+fn apply_log_trade(
+    mut state: Signal<AppState>,
+    toasts: Signal<Vec<ToastMessage>>,
+    price_request: Signal<Option<String>>,
+    locations: &[Location],
+    trade: LogTrade,
+) {
+    let Some(commodity) = state.with(|st| {
+        match_commodity(&trade.commodity, &st.commodities).map(|(commodity, _)| commodity.clone())
+    }) else {
+        push_toast(
+            toasts,
+            ToastKind::Warning,
+            tr!("app.toast.log_unknown_commodity", commodity = trade.commodity),
+        );
+        return;
+    };
+    let location = trade
+        .shop_location()
+        .and_then(|shop| resolve_location(shop, locations));
+
+    let Some(result) = state.with_mut(|st| record_log_trade(st, &commodity, &trade, location)) else {
+        let message = tr!("app.toast.log_untracked", scu = trade.scu, commodity = commodity.name);
+        push_toast(toasts, ToastKind::Warning, format!("🛰 {message}"));
+        return;
+    };
+    persist_user_state(&state);
+    persist_history(&state);
+    if matches!(result, CargoAdjustResult::Added(..)) {
+        request_price_fetch(state, price_request, &commodity.id);
+    }
+
+    let message = match (trade.side, location) {
+        (TradeSide::Buy, Some(location)) => {
+            tr!("app.toast.log_bought_at", scu = trade.scu, commodity = commodity.name, location = location.name)
+        }
+        (TradeSide::Buy, None) => tr!("app.toast.log_bought", scu = trade.scu, commodity = commodity.name),
+        (TradeSide::Sell, Some(location)) => {
+            tr!("app.toast.log_sold_at", scu = trade.scu, commodity = commodity.name, location = location.name)
+        }
+        (TradeSide::Sell, None) => tr!("app.toast.log_sold", scu = trade.scu, commodity = commodity.name),
+    };
+    push_toast(toasts, ToastKind::Success, format!("🛰 {message}"));
+}
+
+/// Applies a game-log trade to the active manifest and, only once the cargo
+/// was updated, records it in the ledger. A sale of cargo that isn't tracked
+/// changes nothing and returns `None`.
+fn record_log_trade(
+    st: &mut AppState,
+    commodity: &Commodity,
+    trade: &LogTrade,
+    location: Option<&Location>,
+) -> Option<CargoAdjustResult> {
+    let delta = trade.scu.min(i32::MAX as u32) as i32;
+    let delta = match trade.side {
+        TradeSide::Buy => delta,
+        TradeSide::Sell => -delta,
+    };
+    let result = adjust_cargo(st.cargo_items_mut(), commodity, delta, false);
+    if result == CargoAdjustResult::Error {
+        return None;
+    }
+    if let Some(price) = trade.price_per_scu() {
+        let mut record = TradeRecord::new(
+            &st.session_id,
+            trade.side,
+            commodity.id.clone(),
+            commodity.name.clone(),
+            trade.scu,
+            price,
+        );
+        if let Some(location) = location {
+            record = record.at_terminal(
+                Some(location.terminal_id),
+                location.name.clone(),
+                location.system.clone(),
+            );
+        }
+        st.history.record(record);
+    }
+    Some(result)
+}
+
+/// Moves watchlist rules from an older `state.json` into `config.json`. Only
+/// called after a clean config load; the rules stay in `state.json` until the
+/// config save succeeds.
+fn adopt_legacy_watchlist(state: &mut Signal<AppState>, toasts: Signal<Vec<ToastMessage>>) {
+    let rules = state.with(|st| st.legacy_watchlist.clone());
+    if rules.is_empty() {
+        return;
+    }
+    let mut next = config::file_config();
+    for rule in rules {
+        if !next.watchlist.rules.iter().any(|existing| existing.id == rule.id) {
+            next.watchlist.rules.push(rule);
+        }
+    }
+    match config::update(next) {
+        Ok(()) => state.with_mut(|st| st.legacy_watchlist.clear()),
+        Err(err) => {
+            warn!(%err, "could not move the watchlist into config.json");
+            push_toast(
+                toasts,
+                ToastKind::Warning,
+                tr!("settings.toast.config_not_saved", error = err),
+            );
+        }
+    }
+}
+
+/// Refresh stale prices for watched commodities, then raise alerts for rules
+/// that just started matching.
+#[instrument(skip_all)]
+pub async fn poll_watchlist(
+    client: &UexClient,
+    mut state: Signal<AppState>,
+    toasts: Signal<Vec<ToastMessage>>,
+) {
+    let rules = config::current().watchlist.rules;
+    let watched: Vec<(String, String)> = {
+        let mut seen = std::collections::HashSet::new();
+        rules
+            .iter()
+            .filter(|rule| rule.enabled && seen.insert(rule.commodity_id.clone()))
+            .map(|rule| (rule.commodity_id.clone(), rule.commodity_name.clone()))
+            .collect()
+    };
+
+    for (commodity_id, commodity_name) in watched {
+        let needs_fetch = state.with(|st| {
+            st.is_stale(
+                &CacheResource::Prices(commodity_id.clone()),
+                config::current().cache.price_ttl(),
+            )
+                || !st.price_points.contains_key(&commodity_id)
+        });
+        if !needs_fetch {
+            continue;
+        }
+        match client.get_prices(&commodity_id, Some(&commodity_name)).await {
+            Ok(payload) => {
+                state.with_mut(|st| {
+                    st.price_points.insert(commodity_id.clone(), payload.data);
+                    st.cache.record_fetch(
+                        CacheResource::Prices(commodity_id.clone()),
+                        payload.fetched_at,
+                    );
+                });
+            }
+            Err(err) => {
+                warn!(%commodity_id, %err, "failed to refresh watchlist prices");
+            }
+        }
+    }
+
+    let fired = state.with_mut(|st| {
+        let AppState {
+            watch_alerts,
+            price_points,
+            ..
+        } = st;
+        let hits = watch_alerts.update(&rules, price_points);
+        hits.into_iter()
+            .filter_map(|hit| {
+                let rule = rules.iter().find(|rule| rule.id == hit.rule_id)?;
+                Some((rule.describe(), hit))
+            })
+            .collect::<Vec<_>>()
+    });
+
+    for (description, hit) in fired {
+        let location = match &hit.system {
+            Some(system) => format!("{} · {system}", hit.terminal_name),
+            None => hit.terminal_name.clone(),
+        };
+        let body = tr!("watch.hit", price = format::integer(hit.price), terminal = location);
+        push_toast(
+            toasts,
+            ToastKind::Success,
+            format!("🔔 {description}: {body}"),
+        );
+        desktop_notify(&tr!("app.notify.price_alert", rule = description), &body);
+    }
+}
+
+fn notify_finished_refinery_jobs(mut state: Signal<AppState>, toasts: Signal<Vec<ToastMessage>>) {
+    let now = unix_now();
+    let pending = |job: &RefineryJob| !job.notified && job.is_done(now);
+    if !state.with(|st| st.refinery_jobs.iter().any(pending)) {
+        return;
+    }
+    let finished: Vec<String> = state.with_mut(|st| {
+        st.refinery_jobs
+            .iter_mut()
+            .filter(|job| pending(job))
+            .map(|job| {
+                job.notified = true;
+                job.refinery.clone()
+            })
+            .collect()
+    });
+    persist_user_state(&state);
+    for refinery in finished {
+        let message = tr!("app.notify.refinery_done", refinery = refinery);
+        push_toast(toasts, ToastKind::Success, format!("⚗️ {message}"));
+        desktop_notify(&message, &tr!("app.notify.refinery_done_body"));
     }
 }
 
@@ -105,7 +684,7 @@ async fn fetch_terminals(
         push_toast(
             toasts.clone(),
             ToastKind::Warning,
-            "Failed to initialise UEX client for terminals.",
+            tr!("app.toast.uex_init_terminals"),
         );
         return;
     };
@@ -114,9 +693,11 @@ async fn fetch_terminals(
         Ok(cache) => {
             let nqa_count = cache.nqa_terminal_ids().len();
             let total_count = cache.terminals.len();
-            println!(
-                "[app] Loaded {} terminals ({} NQA) for game version {}",
-                total_count, nqa_count, cache.game_version
+            info!(
+                terminals = total_count,
+                nqa = nqa_count,
+                game_version = %cache.game_version,
+                "loaded terminals"
             );
             state.with_mut(|st| {
                 st.nqa_terminal_ids = cache.nqa_terminal_ids();
@@ -125,30 +706,30 @@ async fn fetch_terminals(
                 push_toast(
                     toasts.clone(),
                     ToastKind::Warning,
-                    "No NQA terminals found in API data. Hot cargo filtering may not work.",
+                    tr!("app.toast.no_nqa"),
                 );
             }
         }
         Err(err) => {
-            println!("[app] Failed to load terminals: {err}");
+            warn!(%err, "failed to load terminals");
             push_toast(
                 toasts.clone(),
                 ToastKind::Warning,
-                format!("Failed to load terminal data: {err}. Hot cargo filtering disabled."),
+                tr!("app.toast.terminals_failed", error = err),
             );
         }
     }
 }
 
 /// Fetch prices for all cargo items on startup.
+#[instrument(skip_all)]
 async fn fetch_prices_for_cargo(
     mut state: Signal<AppState>,
     toasts: Signal<Vec<ToastMessage>>,
 ) {
     // Get unique commodity IDs from cargo
     let commodity_ids: Vec<(String, Option<String>)> = state.with(|st| {
-        st.cargo_items
-            .iter()
+        st.all_cargo_items()
             .map(|item| (item.commodity_id.clone(), Some(item.commodity_name.clone())))
             .collect::<std::collections::HashSet<_>>()
             .into_iter()
@@ -159,7 +740,7 @@ async fn fetch_prices_for_cargo(
         return;
     }
 
-    println!("[startup] Loading prices for {} cargo commodities...", commodity_ids.len());
+    info!(commodities = commodity_ids.len(), "loading prices for cargo");
 
     let Ok(client) = UexClient::new() else {
         return;
@@ -191,17 +772,17 @@ async fn fetch_prices_for_cargo(
                 loaded += 1;
             }
             Err(err) => {
-                println!("[startup] Failed to load prices for {commodity_id}: {err}");
+                warn!(%commodity_id, %err, "failed to load startup prices");
             }
         }
     }
 
     if loaded > 0 {
-        println!("[startup] Loaded prices for {loaded} commodities");
+        info!(commodities = loaded, "loaded startup prices");
         push_toast(
             toasts.clone(),
             ToastKind::Info,
-            format!("Loaded prices for {loaded} saved cargo item(s)."),
+            tr!("app.toast.startup_prices", count = loaded),
         );
     }
 }
@@ -222,7 +803,7 @@ async fn fetch_commodities(
                     push_toast(
                         toasts.clone(),
                         ToastKind::Warning,
-                        "Loaded cached commodities; data might be stale.",
+                        tr!("app.toast.commodities_stale"),
                     );
                 }
                 return Some(payload.status);
@@ -231,7 +812,7 @@ async fn fetch_commodities(
                 push_toast(
                     toasts.clone(),
                     ToastKind::Error,
-                    format!("Failed to load commodities: {err}"),
+                    tr!("app.toast.commodities_failed", error = err),
                 );
             }
         }
@@ -239,7 +820,7 @@ async fn fetch_commodities(
         push_toast(
             toasts.clone(),
             ToastKind::Error,
-            "Failed to initialise UEX client.",
+            tr!("app.toast.uex_init"),
         );
     }
     None
@@ -251,9 +832,8 @@ async fn fetch_prices(
     mut price_request: Signal<Option<String>>,
 ) -> Option<(String, CacheStatus)> {
     let requested = price_request();
-    println!("fetch_prices invoked with request: {:?}", requested);
     let Some(commodity_id) = requested else {
-        println!("No commodity queued for price fetch; skipping API call.");
+        debug!("no commodity queued for price fetch");
         return None;
     };
 
@@ -261,7 +841,7 @@ async fn fetch_prices(
         push_toast(
             toasts.clone(),
             ToastKind::Error,
-            "Failed to initialise UEX client for prices.",
+            tr!("app.toast.uex_init_prices"),
         );
         return None;
     };
@@ -273,7 +853,7 @@ async fn fetch_prices(
             .map(|c| c.name.clone())
     });
 
-    println!("Starting UEX price fetch for commodity {commodity_id}");
+    debug!(%commodity_id, "fetching prices");
 
     match client
         .get_prices(&commodity_id, commodity_name.as_deref())
@@ -281,23 +861,23 @@ async fn fetch_prices(
     {
         Ok(payload) => {
             price_request.set(None);
-            println!(
-                "Fetched {} price points for commodity {} (status: {:?}).",
-                payload.data.len(),
-                commodity_id,
-                payload.status
+            debug!(
+                %commodity_id,
+                points = payload.data.len(),
+                status = ?payload.status,
+                "fetched prices"
             );
             if payload.data.is_empty() {
-                println!("Warning: UEX returned zero price points for {commodity_id}");
+                warn!(%commodity_id, "UEX returned no price points");
             }
-            for (idx, point) in payload.data.iter().take(5).enumerate() {
-                println!(
-                    "Point #{idx} -> terminal: {}, sell(min/max): {:?}/{:?}, buy(min/max): {:?}/{:?}",
-                    point.terminal_name,
-                    point.price_sell_min,
-                    point.price_sell_max,
-                    point.price_buy_min,
-                    point.price_buy_max
+            for point in payload.data.iter().take(5) {
+                trace!(
+                    terminal = %point.terminal_name,
+                    sell_min = ?point.price_sell_min,
+                    sell_max = ?point.price_sell_max,
+                    buy_min = ?point.price_buy_min,
+                    buy_max = ?point.price_buy_max,
+                    "price point"
                 );
             }
             state.with_mut(|st| {
@@ -331,7 +911,7 @@ async fn fetch_prices(
                 push_toast(
                     toasts.clone(),
                     ToastKind::Info,
-                    format!("Prices for {} served from cache.", commodity_id),
+                    tr!("app.toast.prices_cached", commodity = commodity_id),
                 );
             }
 
@@ -339,11 +919,11 @@ async fn fetch_prices(
         }
         Err(err) => {
             price_request.set(None);
-            println!("UEX client failed to load prices for {commodity_id}: {err}");
+            warn!(%commodity_id, %err, "failed to load prices");
             push_toast(
                 toasts.clone(),
                 ToastKind::Error,
-                format!("Failed to load prices: {err}"),
+                tr!("app.toast.prices_failed", error = err),
             );
             None
         }
@@ -365,6 +945,16 @@ pub fn BestPrice() -> Element {
     rsx! { Shell { BestPricePage {} } }
 }
 
+#[component]
+pub fn Crew() -> Element {
+    rsx! { Shell { CrewPage {} } }
+}
+
+#[component]
+pub fn Reports() -> Element {
+    rsx! { Shell { ReportsPage {} } }
+}
+
 #[component]
 pub fn Settings() -> Element {
     rsx! { Shell { SettingsPage {} } }
@@ -374,3 +964,77 @@ pub fn Settings() -> Element {
 pub fn Routes() -> Element {
     rsx! { Shell { RoutesPage {} } }
 }
+
+#[component]
+pub fn Traffic() -> Element {
+    rsx! { Shell { TrafficPage {} } }
+}
+
+#[component]
+pub fn Fence() -> Element {
+    rsx! { Shell { FencePage {} } }
+}
+
+#[component]
+pub fn Refinery() -> Element {
+    rsx! { Shell { RefineryPage {} } }
+}
+
+#[component]
+pub fn OrePrices() -> Element {
+    rsx! { Shell { OrePricesPage {} } }
+}
+
+#[component]
+pub fn History() -> Element {
+    rsx! { Shell { HistoryPage {} } }
+}
+
+#[component]
+pub fn Watchlist() -> Element {
+    rsx! { Shell { WatchlistPage {} } }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gold() -> Commodity {
+        Commodity {
+            id: "gold".to_string(),
+            name: "Gold".to_string(),
+            category: "Metal".to_string(),
+            code: None,
+            weight_scu: None,
+            is_raw: false,
+            is_mineral: false,
+        }
+    }
+
+    fn trade(side: TradeSide, scu: u32) -> LogTrade {
+        LogTrade {
+            side,
+            commodity: "Gold".to_string(),
+            scu,
+            total_price: Some(scu as f64 * 6_000.0),
+            shop: None,
+        }
+    }
+
+    #[test]
+    fn log_trades_only_reach_the_ledger_when_the_cargo_changes() {
+        let mut st = AppState::default();
+        let result = record_log_trade(&mut st, &gold(), &trade(TradeSide::Sell, 10), None);
+        assert_eq!(result, None);
+        assert!(st.history.records.is_empty());
+        assert!(st.cargo_items().is_empty());
+
+        let result = record_log_trade(&mut st, &gold(), &trade(TradeSide::Buy, 12), None);
+        assert!(matches!(result, Some(CargoAdjustResult::Added(..))));
+        let result = record_log_trade(&mut st, &gold(), &trade(TradeSide::Sell, 4), None);
+        assert!(matches!(result, Some(CargoAdjustResult::Updated(_))));
+        assert_eq!(st.cargo_items()[0].scu, 8);
+        let sides: Vec<_> = st.history.records.iter().map(|r| (r.side, r.scu)).collect();
+        assert_eq!(sides, vec![(TradeSide::Buy, 12), (TradeSide::Sell, 4)]);
+    }
+}
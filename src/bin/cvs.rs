@@ -0,0 +1,742 @@
+//! `cvs` — headless command-line access to prices, evaluations, routes and sell plans.
+//!
+//! Output is a readable table by default; `--format csv|json` (or `--json`)
+//! prints the same column layouts as the app's exports, for scripts and bots.
+
+use std::collections::{HashMap, HashSet};
+use std::io::Read;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::SystemTime;
+
+use clap::{Parser, Subcommand, ValueEnum};
+use serde::Serialize;
+use thiserror::Error;
+
+use cargo_value_scanner::{
+    domain::{
+        add_distances_to_plan, calculate_best_value_plan, calculate_one_stop_plan,
+        evaluate_cargo_items, match_commodity, match_lines, parse_import, sort_by_nearest_neighbor,
+        sort_routes, CargoItem, Commodity, PricePoint, SellPlan, TradeRouteSort,
+    },
+    infra::{
+        config,
+        export::{
+            evaluation_rows, route_rows, sell_plan_rows, to_csv, to_json, ExportError, ExportRow,
+        },
+        logging,
+        routes::load_routes,
+        uex::{UexClient, UexClientError},
+    },
+    util::{
+        format, generate_id,
+        persistence::{load_persisted_state, PersistLoadError},
+    },
+};
+
+#[derive(Parser)]
+#[command(
+    name = "cvs",
+    version,
+    about = "Cargo Value Scanner on the command line"
+)]
+struct Cli {
+    /// Output format.
+    #[arg(long, value_enum, global = true, default_value_t = Format::Table)]
+    format: Format,
+    /// Shorthand for `--format json`.
+    #[arg(long, global = true)]
+    json: bool,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    Table,
+    Csv,
+    Json,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Current terminal prices for a commodity (name or code, typos allowed).
+    Prices {
+        commodity: String,
+        /// Only terminals in this star system.
+        #[arg(long)]
+        system: Option<String>,
+        /// Which price to rank by: what terminals pay you (sell) or charge you (buy).
+        #[arg(long, value_enum, default_value_t = Side::Sell)]
+        side: Side,
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+    },
+    /// Expected value of a cargo list (JSON, CSV, or text like "120 Laranite"; `-` reads stdin).
+    Evaluate { manifest: PathBuf },
+    /// Most profitable trade routes.
+    Routes {
+        /// Star system to buy in.
+        #[arg(long)]
+        from: Option<String>,
+        /// Star system to sell in.
+        #[arg(long)]
+        to: Option<String>,
+        /// Cargo capacity used for profit and investment figures.
+        #[arg(long, default_value_t = 100)]
+        scu: u32,
+        #[arg(long, value_enum, default_value_t = RouteSort::ProfitPerGm)]
+        sort: RouteSort,
+        /// Skip routes that cost more than this to fill `--scu`.
+        #[arg(long)]
+        max_invest: Option<f64>,
+        /// Skip planetary terminals (cities and outposts).
+        #[arg(long)]
+        stations_only: bool,
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+        /// Ignore the route cache and fetch fresh prices.
+        #[arg(long)]
+        refresh: bool,
+    },
+    /// Where to sell a cargo list.
+    Plan {
+        /// Terminal you're at; adds distances and orders multi-stop plans.
+        #[arg(long)]
+        position: Option<String>,
+        /// Cargo list file; defaults to the app's active manifest.
+        #[arg(long)]
+        manifest: Option<PathBuf>,
+        #[arg(long, value_enum, default_value_t = PlanMode::BestValue)]
+        mode: PlanMode,
+    },
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Side {
+    Sell,
+    Buy,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum RouteSort {
+    Profit,
+    Roi,
+    ProfitPerGm,
+    Traffic,
+    Distance,
+    Value,
+}
+
+impl From<RouteSort> for TradeRouteSort {
+    fn from(sort: RouteSort) -> Self {
+        match sort {
+            RouteSort::Profit => TradeRouteSort::ProfitTotal,
+            RouteSort::Roi => TradeRouteSort::RoiPercent,
+            RouteSort::ProfitPerGm => TradeRouteSort::ProfitPerGm,
+            RouteSort::Traffic => TradeRouteSort::ActivityScore,
+            RouteSort::Distance => TradeRouteSort::Distance,
+            RouteSort::Value => TradeRouteSort::CargoValue,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum PlanMode {
+    /// Everything at the single best terminal.
+    OneStop,
+    /// Each item where it pays most.
+    BestValue,
+}
+
+#[derive(Debug, Error)]
+enum CliError {
+    #[error(transparent)]
+    Uex(#[from] UexClientError),
+    #[error(transparent)]
+    Export(#[from] ExportError),
+    #[error(transparent)]
+    Persist(#[from] PersistLoadError),
+    #[error("could not read {path}: {source}")]
+    Read {
+        path: String,
+        source: std::io::Error,
+    },
+    #[error("{0}")]
+    NotFound(String),
+    #[error("unrecognized cargo lines: {}", .0.join(" · "))]
+    Unmatched(Vec<String>),
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+struct PriceRow {
+    terminal: String,
+    system: Option<String>,
+    sell_price: Option<f64>,
+    buy_price: Option<f64>,
+    sell_demand: Option<f64>,
+    buy_stock: Option<f64>,
+    age_minutes: u64,
+}
+
+impl ExportRow for PriceRow {
+    const COLUMNS: &'static [&'static str] = &[
+        "terminal",
+        "system",
+        "sell_price",
+        "buy_price",
+        "sell_demand",
+        "buy_stock",
+        "age_minutes",
+    ];
+
+    fn cells(&self) -> Vec<String> {
+        let num = |value: Option<f64>| value.map(|v| format!("{v:.0}")).unwrap_or_default();
+        vec![
+            self.terminal.clone(),
+            self.system.clone().unwrap_or_default(),
+            num(self.sell_price),
+            num(self.buy_price),
+            num(self.sell_demand),
+            num(self.buy_stock),
+            self.age_minutes.to_string(),
+        ]
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let mut cli = Cli::parse();
+    if cli.json {
+        cli.format = Format::Json;
+    }
+    for problem in config::init() {
+        eprintln!("warning: config problem, using defaults: {problem}");
+    }
+    // Log to the file only so stdout/stderr stay the CLI's own output.
+    let _log_guard = logging::init(&config::current().logging, "cvs", false);
+
+    match run(cli).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn run(cli: Cli) -> Result<(), CliError> {
+    let client = UexClient::new()?;
+    let format = cli.format;
+
+    match cli.command {
+        Command::Prices {
+            commodity,
+            system,
+            side,
+            limit,
+        } => {
+            let commodities = client.get_commodities().await?.data;
+            let commodity = find_commodity(&commodity, &commodities)?;
+            let points = client
+                .get_prices(&commodity.id, Some(&commodity.name))
+                .await?
+                .data;
+            let mut rows: Vec<PriceRow> = points
+                .iter()
+                .filter(|p| system_matches(p.system.as_deref(), system.as_deref()))
+                .map(price_row)
+                .filter(|row| match side {
+                    Side::Sell => row.sell_price.is_some(),
+                    Side::Buy => row.buy_price.is_some(),
+                })
+                .collect();
+            match side {
+                Side::Sell => rows.sort_by(|a, b| {
+                    b.sell_price
+                        .partial_cmp(&a.sell_price)
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                }),
+                Side::Buy => rows.sort_by(|a, b| {
+                    a.buy_price
+                        .partial_cmp(&b.buy_price)
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                }),
+            }
+            rows.truncate(limit);
+            if format == Format::Table {
+                println!(
+                    "{} ({})",
+                    commodity.name,
+                    commodity.code.as_deref().unwrap_or(&commodity.id)
+                );
+            }
+            emit(format, "prices", &rows, PriceRow::COLUMNS)?;
+        }
+        Command::Evaluate { manifest } => {
+            let commodities = client.get_commodities().await?.data;
+            let items = read_manifest(&manifest, &commodities)?;
+            let prices = fetch_prices(&client, &items).await;
+            let summary = evaluate_cargo_items(&items, &prices);
+            let rows = evaluation_rows(&items, &summary);
+            emit(
+                format,
+                "evaluation",
+                &rows,
+                &[
+                    "commodity_name",
+                    "scu",
+                    "is_hot",
+                    "expected_value",
+                    "min_value",
+                    "max_value",
+                    "confidence",
+                ],
+            )?;
+            if format == Format::Table {
+                println!(
+                    "\nTotal EV: {} · confidence {:.0}%",
+                    format::auec(summary.total_ev),
+                    summary.average_confidence * 100.0
+                );
+            }
+        }
+        Command::Routes {
+            from,
+            to,
+            scu,
+            sort,
+            max_invest,
+            stations_only,
+            limit,
+            refresh,
+        } => {
+            let nqa = client.get_nqa_terminal_ids().await.unwrap_or_default();
+            let mut routes = load_routes(&client, &nqa, refresh).await?;
+            routes.retain(|r| {
+                system_matches(r.buy_system.as_deref(), from.as_deref())
+                    && system_matches(r.sell_system.as_deref(), to.as_deref())
+                    && (!stations_only || (!r.buy_is_planetary && !r.sell_is_planetary))
+                    && max_invest
+                        .map(|max| r.buy_price * scu as f64 <= max)
+                        .unwrap_or(true)
+            });
+            sort_routes(&mut routes, sort.into(), scu, true);
+            routes.truncate(limit);
+            let rows = route_rows(&routes, scu);
+            emit(
+                format,
+                "routes",
+                &rows,
+                &[
+                    "commodity_name",
+                    "buy_terminal",
+                    "sell_terminal",
+                    "buy_price",
+                    "sell_price",
+                    "distance_gm",
+                    "quantity",
+                    "profit_total",
+                    "roi_percent",
+                ],
+            )?;
+        }
+        Command::Plan {
+            position,
+            manifest,
+            mode,
+        } => {
+            let items = match manifest {
+                Some(path) => {
+                    let commodities = client.get_commodities().await?.data;
+                    read_manifest(&path, &commodities)?
+                }
+                None => saved_manifest()?,
+            };
+            let prices = fetch_prices(&client, &items).await;
+            let nqa = client.get_nqa_terminal_ids().await.unwrap_or_default();
+            let mut plan = match mode {
+                PlanMode::OneStop => calculate_one_stop_plan(&items, &prices, &nqa),
+                PlanMode::BestValue => calculate_best_value_plan(&items, &prices, &nqa),
+            };
+            if let Some(position) = position {
+                let origin = find_terminal(&client, &position).await?;
+                plan = with_distances(&client, plan, origin, mode).await?;
+            }
+            let rows = sell_plan_rows(&plan);
+            emit(
+                format,
+                "sell_plan",
+                &rows,
+                &[
+                    "stop",
+                    "terminal_name",
+                    "system",
+                    "distance_gm",
+                    "commodity_name",
+                    "scu",
+                    "price_per_scu",
+                    "total_value",
+                ],
+            )?;
+            if format == Format::Table {
+                let distance = plan
+                    .total_distance
+                    .map(|d| format!(" · {} Gm", format::integer(d)))
+                    .unwrap_or_default();
+                println!(
+                    "\nTotal: {} · {} stop(s){distance}",
+                    format::auec(plan.total_value),
+                    plan.stops.len()
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Print rows as a table (only `table_columns`), CSV, or JSON.
+fn emit<R: ExportRow>(
+    format: Format,
+    kind: &str,
+    rows: &[R],
+    table_columns: &[&str],
+) -> Result<(), CliError> {
+    match format {
+        Format::Json => print!("{}", to_json(kind, rows)?),
+        Format::Csv => print!("{}", to_csv(rows)),
+        Format::Table => print_table(rows, table_columns),
+    }
+    Ok(())
+}
+
+fn print_table<R: ExportRow>(rows: &[R], columns: &[&str]) {
+    if rows.is_empty() {
+        println!("(no results)");
+        return;
+    }
+    let indices: Vec<usize> = columns
+        .iter()
+        .filter_map(|name| R::COLUMNS.iter().position(|c| c == name))
+        .collect();
+    let cells: Vec<Vec<String>> = rows
+        .iter()
+        .map(|row| {
+            let all = row.cells();
+            indices.iter().map(|&i| all[i].clone()).collect()
+        })
+        .collect();
+    let widths: Vec<usize> = indices
+        .iter()
+        .enumerate()
+        .map(|(col, &i)| {
+            cells
+                .iter()
+                .map(|row| row[col].chars().count())
+                .chain(std::iter::once(R::COLUMNS[i].len()))
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let header: Vec<String> = indices
+        .iter()
+        .zip(&widths)
+        .map(|(&i, &w)| format!("{:<w$}", R::COLUMNS[i]))
+        .collect();
+    println!("{}", header.join("  ").trim_end());
+    println!(
+        "{}",
+        widths
+            .iter()
+            .map(|w| "-".repeat(*w))
+            .collect::<Vec<_>>()
+            .join("  ")
+    );
+    for row in cells {
+        let line: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, &w)| format!("{cell:<w$}"))
+            .collect();
+        println!("{}", line.join("  ").trim_end());
+    }
+}
+
+fn find_commodity<'a>(
+    query: &str,
+    commodities: &'a [Commodity],
+) -> Result<&'a Commodity, CliError> {
+    match_commodity(query, commodities)
+        .map(|(commodity, _)| commodity)
+        .ok_or_else(|| CliError::NotFound(format!("no commodity matches \"{query}\"")))
+}
+
+async fn find_terminal(client: &UexClient, query: &str) -> Result<i32, CliError> {
+    let terminals = client.get_terminals().await?.terminals;
+    let needle = query.to_lowercase();
+    terminals
+        .iter()
+        .find(|t| t.name.to_lowercase() == needle)
+        .or_else(|| {
+            terminals
+                .iter()
+                .find(|t| t.name.to_lowercase().contains(&needle))
+        })
+        .map(|t| t.id)
+        .ok_or_else(|| CliError::NotFound(format!("no terminal matches \"{query}\"")))
+}
+
+async fn with_distances(
+    client: &UexClient,
+    plan: SellPlan,
+    origin: i32,
+    mode: PlanMode,
+) -> Result<SellPlan, CliError> {
+    let stops: Vec<i32> = plan.stops.iter().filter_map(|s| s.terminal_id).collect();
+    let distances = client.get_terminal_distances(origin, &stops).await?;
+    Ok(match mode {
+        PlanMode::BestValue => sort_by_nearest_neighbor(plan, origin, &distances),
+        PlanMode::OneStop => add_distances_to_plan(plan, origin, &distances),
+    })
+}
+
+/// Parse a cargo list file (or stdin for `-`) and match it against the commodity list.
+fn read_manifest(path: &PathBuf, commodities: &[Commodity]) -> Result<Vec<CargoItem>, CliError> {
+    let read_error = |source| CliError::Read {
+        path: path.display().to_string(),
+        source,
+    };
+    let text = if path.as_os_str() == "-" {
+        let mut buffer = String::new();
+        std::io::stdin()
+            .read_to_string(&mut buffer)
+            .map_err(read_error)?;
+        buffer
+    } else {
+        std::fs::read_to_string(path).map_err(read_error)?
+    };
+
+    let parsed = parse_import(&text);
+    let matches = match_lines(&parsed.lines, commodities);
+    let mut unmatched: Vec<String> = matches
+        .iter()
+        .filter(|m| m.commodity_id.is_none())
+        .map(|m| m.line.raw.clone())
+        .collect();
+    unmatched.extend(parsed.rejected);
+    if !unmatched.is_empty() {
+        return Err(CliError::Unmatched(unmatched));
+    }
+
+    Ok(matches
+        .into_iter()
+        .filter_map(|m| {
+            let commodity = commodities
+                .iter()
+                .find(|c| Some(&c.id) == m.commodity_id.as_ref())?;
+            Some(CargoItem {
+                id: generate_id("cargo"),
+                commodity_id: commodity.id.clone(),
+                commodity_name: commodity.name.clone(),
+                scu: m.line.scu,
+                is_hot: m.line.is_hot,
+            })
+        })
+        .collect())
+}
+
+/// The active manifest saved by the desktop app.
+fn saved_manifest() -> Result<Vec<CargoItem>, CliError> {
+    let saved = load_persisted_state()?
+        .ok_or_else(|| CliError::NotFound("no saved cargo; pass --manifest <file>".into()))?;
+    let manifest = saved
+        .manifests
+        .iter()
+        .find(|m| m.id == saved.active_manifest_id)
+        .or_else(|| saved.manifests.first())
+        .ok_or_else(|| CliError::NotFound("no saved cargo; pass --manifest <file>".into()))?;
+    Ok(manifest.items.clone())
+}
+
+async fn fetch_prices(client: &UexClient, items: &[CargoItem]) -> HashMap<String, Vec<PricePoint>> {
+    let mut prices = HashMap::new();
+    let unique: HashSet<(&str, &str)> = items
+        .iter()
+        .map(|item| (item.commodity_id.as_str(), item.commodity_name.as_str()))
+        .collect();
+    for (id, name) in unique {
+        match client.get_prices(id, Some(name)).await {
+            Ok(payload) => {
+                prices.insert(id.to_string(), payload.data);
+            }
+            Err(err) => eprintln!("warning: no prices for {name}: {err}"),
+        }
+    }
+    prices
+}
+
+fn price_row(point: &PricePoint) -> PriceRow {
+    let positive = |value: Option<f64>| value.filter(|v| v.is_finite() && *v > 0.0);
+    PriceRow {
+        terminal: point.terminal_name.clone(),
+        system: point.system.clone(),
+        sell_price: positive(
+            point
+                .price_sell_max
+                .or(point.price_sell)
+                .or(point.price_sell_min),
+        ),
+        buy_price: positive(
+            point
+                .price_buy_min
+                .or(point.price_buy)
+                .or(point.price_buy_max),
+        ),
+        sell_demand: point.scu_sell_stock,
+        buy_stock: point.scu_buy,
+        age_minutes: SystemTime::now()
+            .duration_since(point.updated_at)
+            .map(|age| age.as_secs() / 60)
+            .unwrap_or(0),
+    }
+}
+
+fn system_matches(system: Option<&str>, wanted: Option<&str>) -> bool {
+    match wanted {
+        Some(wanted) => system
+            .map(|s| s.eq_ignore_ascii_case(wanted))
+            .unwrap_or(false),
+        None => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::{error::ErrorKind, CommandFactory};
+
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Cli, clap::Error> {
+        Cli::try_parse_from(std::iter::once("cvs").chain(args.iter().copied()))
+    }
+
+    #[test]
+    fn command_definition_is_consistent() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn parses_subcommands_and_defaults() {
+        let cli = parse(&[
+            "routes",
+            "--from",
+            "Stanton",
+            "--sort",
+            "roi",
+            "--max-invest",
+            "50000",
+            "--json",
+        ])
+        .unwrap();
+        assert!(cli.json);
+        assert!(cli.format == Format::Table);
+        let Command::Routes {
+            from,
+            to,
+            scu,
+            sort,
+            max_invest,
+            stations_only,
+            limit,
+            refresh,
+        } = cli.command
+        else {
+            panic!("expected routes");
+        };
+        assert_eq!(from.as_deref(), Some("Stanton"));
+        assert_eq!(to, None);
+        assert_eq!((scu, limit), (100, 20));
+        assert!(sort == RouteSort::Roi);
+        assert_eq!(max_invest, Some(50_000.0));
+        assert!(!stations_only && !refresh);
+
+        let cli = parse(&[
+            "--format",
+            "csv",
+            "plan",
+            "--mode",
+            "one-stop",
+            "--manifest",
+            "-",
+        ])
+        .unwrap();
+        assert!(cli.format == Format::Csv);
+        let Command::Plan {
+            position,
+            manifest,
+            mode,
+        } = cli.command
+        else {
+            panic!("expected plan");
+        };
+        assert_eq!(position, None);
+        assert_eq!(manifest, Some(PathBuf::from("-")));
+        assert!(mode == PlanMode::OneStop);
+
+        let cli = parse(&["prices", "lara", "--side", "buy"]).unwrap();
+        let Command::Prices {
+            commodity,
+            system,
+            side,
+            limit,
+        } = cli.command
+        else {
+            panic!("expected prices");
+        };
+        assert_eq!((commodity.as_str(), system, limit), ("lara", None, 20));
+        assert!(side == Side::Buy);
+    }
+
+    #[test]
+    fn rejects_bad_arguments() {
+        let kind = |args: &[&str]| parse(args).err().map(|err| err.kind());
+        assert_eq!(
+            kind(&[]),
+            Some(ErrorKind::DisplayHelpOnMissingArgumentOrSubcommand)
+        );
+        assert_eq!(
+            kind(&["routes", "--sort", "fastest"]),
+            Some(ErrorKind::InvalidValue)
+        );
+        assert_eq!(
+            kind(&["routes", "--scu", "-5"]),
+            Some(ErrorKind::UnknownArgument)
+        );
+        assert_eq!(
+            kind(&["evaluate"]),
+            Some(ErrorKind::MissingRequiredArgument)
+        );
+        assert_eq!(kind(&["teleport"]), Some(ErrorKind::InvalidSubcommand));
+
+        let matches = Cli::command()
+            .try_get_matches_from(["cvs", "evaluate", "cargo.txt", "--format", "json"])
+            .unwrap();
+        assert_eq!(matches.subcommand_name(), Some("evaluate"));
+        assert_eq!(
+            matches
+                .get_one::<Format>("format")
+                .copied()
+                .map(|f| f == Format::Json),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn system_filter_ignores_case_and_needs_a_system() {
+        assert!(system_matches(Some("Stanton"), Some("stanton")));
+        assert!(system_matches(None, None));
+        assert!(!system_matches(None, Some("Pyro")));
+        assert!(!system_matches(Some("Stanton"), Some("Pyro")));
+    }
+}
@@ -6,10 +6,17 @@ use std::{
 };
 
 use super::entities::{
-    CargoItem, Commodity, CommodityId, PricePoint, ProfitabilityParams, SellLocation,
+    CargoItem, Commodity, CommodityId, Location, PricePoint, ProfitabilityParams, SellLocation,
 };
+use super::crew_session::CREW_MANIFEST_ID;
+use super::history::TradeHistory;
+use super::manifest::CargoManifest;
+use super::mining::{OreEntry, RefineryJob};
+use super::watchlist::{WatchAlerts, WatchRule};
 use serde::{Deserialize, Serialize};
 
+use crate::util::i18n::t;
+
 /// Player profile / playstyle for the current session.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Profile {
@@ -21,13 +28,14 @@ pub enum Profile {
 }
 
 impl Profile {
-    pub fn name(&self) -> &'static str {
-        match self {
-            Profile::None => "None",
-            Profile::Pirate => "Pirat",
-            Profile::Trader => "Händler", 
-            Profile::Miner => "Miner",
-        }
+    /// Display name in the current locale.
+    pub fn name(&self) -> String {
+        t(match self {
+            Profile::None => "profile.none",
+            Profile::Pirate => "profile.pirate",
+            Profile::Trader => "profile.trader",
+            Profile::Miner => "profile.miner",
+        })
     }
     
     pub fn emoji(&self) -> &'static str {
@@ -48,7 +56,9 @@ impl Profile {
 pub struct AppState {
     /// Currently selected player profile.
     pub profile: Profile,
-    pub cargo_items: Vec<CargoItem>,
+    /// Named cargo loads; the Cargo, Planner and Best Price pages work on the active one.
+    pub manifests: Vec<CargoManifest>,
+    pub active_manifest_id: String,
     pub commodities: Vec<Commodity>,
     pub price_points: HashMap<CommodityId, Vec<PricePoint>>,
     pub sell_locations: HashMap<String, SellLocation>,
@@ -57,6 +67,31 @@ pub struct AppState {
     /// Terminal IDs that are "no questions asked" (accept hot cargo).
     /// Loaded from API and cached locally with game version tracking.
     pub nqa_terminal_ids: HashSet<i32>,
+    /// Logged buys and sells across all sessions (persisted separately).
+    pub history: TradeHistory,
+    /// Identifies the current app run in the trade history.
+    pub session_id: String,
+    /// Watchlist rules currently firing (not persisted, used to deduplicate alerts).
+    pub watch_alerts: WatchAlerts,
+    /// Rules from an older `state.json` that have not made it into
+    /// `config.json` yet. Written back until they are adopted.
+    pub legacy_watchlist: Vec<WatchRule>,
+    /// Position read from `Game.log`; the planner follows it when set.
+    pub detected_position: Option<DetectedPosition>,
+    /// Raw ore loads on the Refinery page (Miner profile).
+    pub ore_entries: Vec<OreEntry>,
+    /// Running and finished refinery jobs until collected.
+    pub refinery_jobs: Vec<RefineryJob>,
+}
+
+/// A location recognized in the game log.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DetectedPosition {
+    /// Internal game name as logged, e.g. `Stanton1_Lorville`.
+    pub raw: String,
+    /// Matching planner location, if one is known.
+    pub location: Option<Location>,
+    pub detected_at: SystemTime,
 }
 
 impl AppState {
@@ -64,17 +99,85 @@ impl AppState {
         self.cache.is_stale(resource, ttl)
     }
 
+    /// The manifest being edited. Falls back to the first one if the id is stale.
+    pub fn active_manifest(&self) -> Option<&CargoManifest> {
+        self.manifests
+            .iter()
+            .find(|m| m.id == self.active_manifest_id)
+            .or_else(|| self.manifests.first())
+    }
+
+    /// Like [`Self::active_manifest`], creating the default manifest if none exist.
+    pub fn active_manifest_mut(&mut self) -> &mut CargoManifest {
+        if self.manifests.is_empty() {
+            self.manifests.push(CargoManifest::default_manifest());
+        }
+        let index = self
+            .manifests
+            .iter()
+            .position(|m| m.id == self.active_manifest_id)
+            .unwrap_or(0);
+        self.active_manifest_id = self.manifests[index].id.clone();
+        &mut self.manifests[index]
+    }
+
+    /// Items of the active manifest.
+    pub fn cargo_items(&self) -> &[CargoItem] {
+        self.active_manifest()
+            .map(|m| m.items.as_slice())
+            .unwrap_or(&[])
+    }
+
+    pub fn cargo_items_mut(&mut self) -> &mut Vec<CargoItem> {
+        &mut self.active_manifest_mut().items
+    }
+
+    /// The player's own manifests, without a crew session's copy of the host's.
+    pub fn own_manifests(&self) -> impl Iterator<Item = &CargoManifest> {
+        self.manifests.iter().filter(|m| m.id != CREW_MANIFEST_ID)
+    }
+
+    /// Items across the player's own manifests.
+    pub fn all_cargo_items(&self) -> impl Iterator<Item = &CargoItem> {
+        self.own_manifests().flat_map(|m| m.items.iter())
+    }
+
+    /// Shows a crew session host's manifest as [`CREW_MANIFEST_ID`], replacing
+    /// the previous copy.
+    pub fn mirror_crew_manifest(&mut self, shared: &CargoManifest, name: String) {
+        let manifest = CargoManifest {
+            id: CREW_MANIFEST_ID.to_string(),
+            name,
+            ship: shared.ship.clone(),
+            items: shared.items.clone(),
+        };
+        match self.manifests.iter_mut().find(|m| m.id == CREW_MANIFEST_ID) {
+            Some(existing) => *existing = manifest,
+            None => self.manifests.push(manifest),
+        }
+    }
+
     pub fn apply_persisted(&mut self, persisted: PersistedState) {
         self.profile = persisted.profile;
-        self.cargo_items = persisted.cargo_items;
+        self.manifests = persisted.manifests;
+        self.active_manifest_id = persisted.active_manifest_id;
+        self.active_manifest_mut();
         self.profitability = persisted.profitability;
+        self.legacy_watchlist = persisted.watchlist;
+        self.ore_entries = persisted.ore_entries;
+        self.refinery_jobs = persisted.refinery_jobs;
     }
 
     pub fn to_persisted(&self) -> PersistedState {
         PersistedState {
+            schema_version: STATE_SCHEMA_VERSION,
             profile: self.profile,
-            cargo_items: self.cargo_items.clone(),
+            manifests: self.own_manifests().cloned().collect(),
+            active_manifest_id: self.active_manifest_id.clone(),
             profitability: self.profitability.clone(),
+            watchlist: self.legacy_watchlist.clone(),
+            ore_entries: self.ore_entries.clone(),
+            refinery_jobs: self.refinery_jobs.clone(),
         }
     }
 }
@@ -115,10 +218,92 @@ pub enum CacheResource {
     SellLocations,
 }
 
+/// Bump together with a new entry in the persistence migration chain.
+pub const STATE_SCHEMA_VERSION: u32 = 2;
+
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct PersistedState {
+    #[serde(default)]
+    pub schema_version: u32,
     #[serde(default)]
     pub profile: Profile,
-    pub cargo_items: Vec<CargoItem>,
+    #[serde(default)]
+    pub manifests: Vec<CargoManifest>,
+    #[serde(default)]
+    pub active_manifest_id: String,
     pub profitability: ProfitabilityParams,
+    /// Rules saved before the watchlist moved to `config.json`. They are
+    /// moved there on a start with a readable config, and dropped from here
+    /// once that save succeeds.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub watchlist: Vec<WatchRule>,
+    #[serde(default)]
+    pub ore_entries: Vec<OreEntry>,
+    #[serde(default)]
+    pub refinery_jobs: Vec<RefineryJob>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(commodity: &str, scu: u32) -> CargoItem {
+        CargoItem {
+            id: format!("cargo-{commodity}"),
+            commodity_id: commodity.to_lowercase(),
+            commodity_name: commodity.to_string(),
+            scu,
+            is_hot: false,
+        }
+    }
+
+    #[test]
+    fn crew_mirror_is_neither_saved_nor_counted() {
+        let mut state = AppState::default();
+        state.active_manifest_mut().items.push(item("Gold", 10));
+
+        let mut shared = CargoManifest::new("Host Ship");
+        shared.items.push(item("Gold", 32));
+        state.mirror_crew_manifest(&shared, "Crew: Host Ship".to_string());
+        shared.items.push(item("Laranite", 8));
+        state.mirror_crew_manifest(&shared, "Crew: Host Ship".to_string());
+
+        assert_eq!(state.manifests.len(), 2);
+        let mirror = state.manifests.iter().find(|m| m.id == CREW_MANIFEST_ID).unwrap();
+        assert_eq!(mirror.items.len(), 2);
+        assert_eq!(state.own_manifests().count(), 1);
+        assert_eq!(state.all_cargo_items().map(|i| i.scu).sum::<u32>(), 10);
+
+        state.active_manifest_id = CREW_MANIFEST_ID.to_string();
+        let persisted = state.to_persisted();
+        assert_eq!(persisted.manifests.len(), 1);
+        assert!(persisted.manifests.iter().all(|m| m.id != CREW_MANIFEST_ID));
+
+        let mut restored = AppState::default();
+        restored.apply_persisted(persisted);
+        assert_eq!(restored.active_manifest().unwrap().items, vec![item("Gold", 10)]);
+    }
+
+    #[test]
+    fn legacy_watchlist_is_kept_until_adopted() {
+        let json = r#"[{
+            "id": "r1", "commodity_id": "lara", "commodity_name": "Laranite",
+            "side": "Sell", "threshold": 3000.0, "direction": "Above"
+        }]"#;
+        let persisted = PersistedState {
+            watchlist: serde_json::from_str(json).unwrap(),
+            ..PersistedState::default()
+        };
+        let reloaded: PersistedState =
+            serde_json::from_value(serde_json::to_value(&persisted).unwrap()).unwrap();
+        assert_eq!(reloaded.watchlist, persisted.watchlist);
+
+        let mut state = AppState::default();
+        state.apply_persisted(persisted.clone());
+        assert_eq!(state.to_persisted().watchlist, persisted.watchlist);
+
+        state.legacy_watchlist.clear();
+        let saved = serde_json::to_value(state.to_persisted()).unwrap();
+        assert!(saved.get("watchlist").is_none());
+    }
 }
@@ -0,0 +1,253 @@
+//! Crew sessions: what a host shares with their crew, and the incremental
+//! diffs that keep every member's copy in step with the host's.
+//!
+//! The host is authoritative. Members start from a full [`SessionSnapshot`]
+//! and then only receive [`SessionOp`]s computed by [`diff`].
+
+use serde::{Deserialize, Serialize};
+
+use super::entities::{CargoItem, CrewMember};
+use super::manifest::CargoManifest;
+use super::sell_plan::SellPlan;
+
+/// Crew id of the session host.
+pub const HOST_MEMBER_ID: &str = "host";
+
+/// Crew roles; the UI translates them.
+pub const ROLE_HOST: &str = "host";
+pub const ROLE_MEMBER: &str = "member";
+
+/// Manifest a member's copy of the host's cargo is kept in. It isn't saved
+/// and doesn't count towards the member's own totals.
+pub const CREW_MANIFEST_ID: &str = "crew-session";
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SessionSnapshot {
+    /// The host's active manifest.
+    pub manifest: CargoManifest,
+    /// The host's sell plan for that manifest, once prices are known.
+    pub plan: Option<SellPlan>,
+    /// Host first, then members in join order.
+    pub crew: Vec<CrewMember>,
+}
+
+impl SessionSnapshot {
+    pub fn new(manifest: CargoManifest, plan: Option<SellPlan>, host_name: &str) -> Self {
+        Self {
+            manifest,
+            plan,
+            crew: vec![CrewMember {
+                id: HOST_MEMBER_ID.to_string(),
+                name: host_name.to_string(),
+                role: ROLE_HOST.to_string(),
+                weight: 1.0,
+            }],
+        }
+    }
+
+    /// Each crew member's cut of the plan's total value, by weight.
+    pub fn split(&self) -> Vec<(&CrewMember, f64)> {
+        let total = self
+            .plan
+            .as_ref()
+            .map(|plan| plan.total_value)
+            .unwrap_or(0.0);
+        let weights: f64 = self.crew.iter().map(|m| m.weight.max(0.0) as f64).sum();
+        self.crew
+            .iter()
+            .map(|member| {
+                let share = if weights > 0.0 {
+                    total * member.weight.max(0.0) as f64 / weights
+                } else {
+                    0.0
+                };
+                (member, share)
+            })
+            .collect()
+    }
+
+    /// Applies `ops` in order; ops naming items or members that are gone are skipped.
+    pub fn apply(&mut self, ops: &[SessionOp]) {
+        for op in ops {
+            match op {
+                SessionOp::Manifest { id, name, ship } => {
+                    self.manifest.id = id.clone();
+                    self.manifest.name = name.clone();
+                    self.manifest.ship = ship.clone();
+                }
+                SessionOp::UpsertItem(item) => {
+                    upsert(&mut self.manifest.items, item.clone(), |i| &i.id)
+                }
+                SessionOp::RemoveItem { id } => self.manifest.items.retain(|i| &i.id != id),
+                SessionOp::ItemOrder(ids) => reorder(&mut self.manifest.items, ids, |i| &i.id),
+                SessionOp::Plan(plan) => self.plan = plan.clone(),
+                SessionOp::UpsertMember(member) => {
+                    upsert(&mut self.crew, member.clone(), |m| &m.id)
+                }
+                SessionOp::RemoveMember { id } => self.crew.retain(|m| &m.id != id),
+            }
+        }
+    }
+}
+
+/// One change to a [`SessionSnapshot`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", content = "value", rename_all = "snake_case")]
+pub enum SessionOp {
+    /// The host switched manifests or renamed the current one.
+    Manifest {
+        id: String,
+        name: String,
+        ship: Option<String>,
+    },
+    UpsertItem(CargoItem),
+    RemoveItem {
+        id: String,
+    },
+    /// Item ids in their new order, sent when upserts alone would misorder them.
+    ItemOrder(Vec<String>),
+    Plan(Option<SellPlan>),
+    UpsertMember(CrewMember),
+    RemoveMember {
+        id: String,
+    },
+}
+
+/// The ops that turn `old` into `new`; empty when nothing changed.
+pub fn diff(old: &SessionSnapshot, new: &SessionSnapshot) -> Vec<SessionOp> {
+    let mut ops = Vec::new();
+    let (a, b) = (&old.manifest, &new.manifest);
+    if a.id != b.id || a.name != b.name || a.ship != b.ship {
+        ops.push(SessionOp::Manifest {
+            id: b.id.clone(),
+            name: b.name.clone(),
+            ship: b.ship.clone(),
+        });
+    }
+    diff_list(
+        &a.items,
+        &b.items,
+        |i| &i.id,
+        &mut ops,
+        SessionOp::UpsertItem,
+        |id| SessionOp::RemoveItem { id },
+    );
+    if old.plan != new.plan {
+        ops.push(SessionOp::Plan(new.plan.clone()));
+    }
+    diff_list(
+        &old.crew,
+        &new.crew,
+        |m| &m.id,
+        &mut ops,
+        SessionOp::UpsertMember,
+        |id| SessionOp::RemoveMember { id },
+    );
+
+    // Upserts append new items; only send the order when that isn't enough.
+    let mut replay = old.clone();
+    replay.apply(&ops);
+    if replay
+        .manifest
+        .items
+        .iter()
+        .map(|i| &i.id)
+        .ne(b.items.iter().map(|i| &i.id))
+    {
+        ops.push(SessionOp::ItemOrder(
+            b.items.iter().map(|i| i.id.clone()).collect(),
+        ));
+    }
+    ops
+}
+
+fn diff_list<T: Clone + PartialEq>(
+    old: &[T],
+    new: &[T],
+    id: impl Fn(&T) -> &String,
+    ops: &mut Vec<SessionOp>,
+    upsert: impl Fn(T) -> SessionOp,
+    remove: impl Fn(String) -> SessionOp,
+) {
+    for gone in old.iter().filter(|o| !new.iter().any(|n| id(n) == id(o))) {
+        ops.push(remove(id(gone).clone()));
+    }
+    for entry in new {
+        if old.iter().find(|o| id(o) == id(entry)) != Some(entry) {
+            ops.push(upsert(entry.clone()));
+        }
+    }
+}
+
+fn upsert<T>(list: &mut Vec<T>, entry: T, id: impl Fn(&T) -> &String) {
+    match list.iter_mut().find(|e| id(e) == id(&entry)) {
+        Some(existing) => *existing = entry,
+        None => list.push(entry),
+    }
+}
+
+fn reorder<T>(list: &mut [T], ids: &[String], id: impl Fn(&T) -> &String) {
+    list.sort_by_key(|e| ids.iter().position(|i| i == id(e)).unwrap_or(usize::MAX));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(id: &str, commodity: &str, scu: u32) -> CargoItem {
+        CargoItem {
+            id: id.to_string(),
+            commodity_id: commodity.to_lowercase(),
+            commodity_name: commodity.to_string(),
+            scu,
+            is_hot: false,
+        }
+    }
+
+    fn member(id: &str, weight: f32) -> CrewMember {
+        CrewMember {
+            id: id.to_string(),
+            name: id.to_uppercase(),
+            role: ROLE_MEMBER.to_string(),
+            weight,
+        }
+    }
+
+    #[test]
+    fn diffs_replay_into_the_new_snapshot() {
+        let mut manifest = CargoManifest::default_manifest();
+        manifest.items = vec![
+            item("a", "Gold", 10),
+            item("b", "Laranite", 4),
+            item("c", "Agricium", 2),
+        ];
+        let old = SessionSnapshot::new(manifest, None, "Captain");
+
+        let mut new = old.clone();
+        new.manifest.ship = Some("Caterpillar".to_string());
+        new.manifest.items = vec![
+            item("d", "Titanium", 8),
+            item("c", "Agricium", 6),
+            item("a", "Gold", 10),
+        ];
+        new.plan = Some(SellPlan {
+            stops: Vec::new(),
+            total_value: 9_000.0,
+            total_distance: None,
+        });
+        new.crew.push(member("m1", 2.0));
+
+        let ops = diff(&old, &new);
+        assert!(!ops
+            .iter()
+            .any(|op| matches!(op, SessionOp::UpsertItem(i) if i.id == "a")));
+        assert!(ops.iter().any(|op| matches!(op, SessionOp::ItemOrder(_))));
+        let mut replayed = old.clone();
+        replayed.apply(&ops);
+        assert_eq!(replayed, new);
+        assert!(diff(&new, &replayed).is_empty());
+
+        let split: Vec<f64> = new.split().into_iter().map(|(_, cut)| cut).collect();
+        assert_eq!(split, vec![3_000.0, 6_000.0]);
+    }
+}
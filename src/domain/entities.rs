@@ -1,6 +1,6 @@
 #![allow(dead_code)]
 
-use std::time::SystemTime;
+use std::{collections::HashMap, time::SystemTime};
 
 use serde::{Deserialize, Serialize};
 
@@ -60,6 +60,33 @@ pub struct Location {
     pub terminal_id: i32,
 }
 
+/// Extract unique locations from terminals.
+pub fn extract_locations(terminals: &[Terminal]) -> Vec<Location> {
+    let mut seen: HashMap<String, Location> = HashMap::new();
+    
+    for t in terminals {
+        let name = t.location_name();
+        if !seen.contains_key(&name) {
+            seen.insert(name.clone(), Location {
+                name,
+                system: t.system.clone(),
+                terminal_id: t.id,
+            });
+        }
+    }
+    
+    let mut locations: Vec<Location> = seen.into_values().collect();
+    locations.sort_by(|a, b| {
+        match (&a.system, &b.system) {
+            (Some(sa), Some(sb)) => sa.cmp(sb).then(a.name.cmp(&b.name)),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => a.name.cmp(&b.name),
+        }
+    });
+    locations
+}
+
 /// Identifier for commodities returned by the UEX API.
 pub type CommodityId = String;
 
@@ -70,6 +97,9 @@ pub struct Commodity {
     pub category: String,
     pub code: Option<String>,
     pub weight_scu: Option<f64>,
+    /// UEX flags: the unrefined form of an ore, and a mined mineral.
+    pub is_raw: bool,
+    pub is_mineral: bool,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -169,8 +199,8 @@ pub struct BestPrice {
     pub notes: Option<String>,
 }
 
-#[allow(dead_code)]
-#[derive(Clone, Debug, PartialEq)]
+/// Someone sharing in a run's payout; `weight` scales their cut.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct CrewMember {
     pub id: String,
     pub name: String,
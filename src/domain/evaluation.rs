@@ -5,9 +5,15 @@ use std::{
     time::{Duration, SystemTime},
 };
 
+use serde::{Deserialize, Serialize};
+
 use super::entities::{
     CargoEvaluation, CargoItem, CommodityId, PricePoint, ProfitabilityParams, SellLocation,
 };
+use crate::util::{
+    format,
+    i18n::{t, tf},
+};
 
 pub struct EvaluationSummary {
     pub total_ev: f64,
@@ -217,7 +223,7 @@ pub fn profitability_indicator(total_ev: f64, params: &ProfitabilityParams) -> P
         return ProfitIndicator {
             status: ProfitIndicatorStatus::Red,
             score,
-            rationale: "No estimated value yet".to_string(),
+            rationale: t("eval.no_value"),
         };
     }
 
@@ -232,9 +238,13 @@ pub fn profitability_indicator(total_ev: f64, params: &ProfitabilityParams) -> P
         ProfitIndicatorStatus::Red
     };
 
-    let rationale = format!(
-        "Net = {:.0} - risk {:.0} - crew {:.0}",
-        total_ev, risk_penalty, crew_cost
+    let rationale = tf(
+        "eval.rationale",
+        &[
+            ("ev", format::integer(total_ev)),
+            ("risk", format::integer(risk_penalty)),
+            ("crew", format::integer(crew_cost)),
+        ],
     );
 
     ProfitIndicator {
@@ -282,7 +292,87 @@ pub struct BestPriceEntry {
     pub status_sell: Option<i32>,
     pub status_buy: Option<i32>,
     pub container_sizes: Vec<f64>,
-    pub notes: Option<String>,
+    pub notes: Vec<PriceNote>,
+}
+
+impl BestPriceEntry {
+    /// Notes in the UI language, e.g. "Cross-system, Low stock".
+    pub fn notes_label(&self) -> Option<String> {
+        if self.notes.is_empty() {
+            return None;
+        }
+        let labels: Vec<String> = self.notes.iter().map(|note| note.label()).collect();
+        Some(labels.join(", "))
+    }
+}
+
+/// Something worth knowing about a sell spot, usually why it was discounted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PriceNote {
+    CrossSystem,
+    Armistice,
+    Hotspot,
+    /// Terminal demand when you sell: `high`, `normal`, `low` or `offline`.
+    Sell(&'static str),
+    /// Terminal supply when you buy, same levels as `Sell`.
+    Buy(&'static str),
+    LowStock,
+    HighStock,
+}
+
+impl PriceNote {
+    /// Stable identifier used in exports, independent of the UI language.
+    pub fn code(self) -> String {
+        match self {
+            PriceNote::CrossSystem => "cross_system".to_string(),
+            PriceNote::Armistice => "armistice".to_string(),
+            PriceNote::Hotspot => "hotspot".to_string(),
+            PriceNote::Sell(level) => format!("sell_{level}"),
+            PriceNote::Buy(level) => format!("buy_{level}"),
+            PriceNote::LowStock => "low_stock".to_string(),
+            PriceNote::HighStock => "high_stock".to_string(),
+        }
+    }
+
+    pub fn label(self) -> String {
+        match self {
+            PriceNote::CrossSystem => t("eval.note.cross_system"),
+            PriceNote::Armistice => t("eval.note.armistice"),
+            PriceNote::Hotspot => t("eval.note.hotspot"),
+            PriceNote::Sell(level) => tf("eval.note.sell", &[("level", t(&format!("status.{level}")))]),
+            PriceNote::Buy(level) => tf("eval.note.buy", &[("level", t(&format!("status.{level}")))]),
+            PriceNote::LowStock => t("eval.note.low_stock"),
+            PriceNote::HighStock => t("eval.note.high_stock"),
+        }
+    }
+}
+
+/// Tunables for `rank_best_prices_with`: how much to discount risky sell spots.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RankingParams {
+    pub home_system: String,
+    pub cross_system_penalty: f64,
+    pub armistice_penalty: f64,
+    pub hotspot_penalty: f64,
+    pub hotspots: Vec<String>,
+    pub suggestions_per_item: usize,
+}
+
+impl Default for RankingParams {
+    fn default() -> Self {
+        Self {
+            home_system: "Stanton".to_string(),
+            cross_system_penalty: 75.0,
+            armistice_penalty: 25.0,
+            hotspot_penalty: 40.0,
+            hotspots: vec![
+                "Grim Hex".to_string(),
+                "Spider".to_string(),
+                "Jumptown".to_string(),
+            ],
+            suggestions_per_item: 3,
+        }
+    }
 }
 
 pub fn rank_best_prices(
@@ -290,13 +380,15 @@ pub fn rank_best_prices(
     prices: &HashMap<CommodityId, Vec<PricePoint>>,
     locations: &HashMap<String, SellLocation>,
 ) -> BestPriceSummary {
-    const CROSS_SYSTEM_PENALTY: f64 = 75.0;
-    const ARMISTICE_PENALTY: f64 = 25.0;
-    const HOTSPOT_PENALTY: f64 = 40.0;
-    const HOME_SYSTEM: &str = "Stanton";
-
-    let hotspots = ["Grim Hex", "Spider", "Jumptown"];
+    rank_best_prices_with(items, prices, locations, &RankingParams::default())
+}
 
+pub fn rank_best_prices_with(
+    items: &[CargoItem],
+    prices: &HashMap<CommodityId, Vec<PricePoint>>,
+    locations: &HashMap<String, SellLocation>,
+    params: &RankingParams,
+) -> BestPriceSummary {
     let mut suggestions = Vec::new();
     let mut best_overall: Option<BestPriceEntry> = None;
 
@@ -338,19 +430,20 @@ pub fn rank_best_prices(
                     .or_else(|| point.system.clone())
                     .unwrap_or_else(|| "Unknown".to_string());
 
-                let cross_system = system_name != HOME_SYSTEM;
-                let hotspot = hotspots
+                let cross_system = system_name != params.home_system;
+                let hotspot = params
+                    .hotspots
                     .iter()
-                    .any(|spot| point.terminal_name.contains(spot));
+                    .any(|spot| point.terminal_name.contains(spot.as_str()));
 
                 if cross_system {
-                    penalty += CROSS_SYSTEM_PENALTY;
+                    penalty += params.cross_system_penalty;
                 }
                 if armistice {
-                    penalty += ARMISTICE_PENALTY;
+                    penalty += params.armistice_penalty;
                 }
                 if hotspot {
-                    penalty += HOTSPOT_PENALTY;
+                    penalty += params.hotspot_penalty;
                 }
 
                 let adjusted_price = sell_price - penalty;
@@ -384,7 +477,7 @@ pub fn rank_best_prices(
             .collect();
 
         entries.sort_by(|a, b| b.adjusted_price.partial_cmp(&a.adjusted_price).unwrap());
-        entries.truncate(3);
+        entries.truncate(params.suggestions_per_item);
 
         if let Some(top) = entries.first() {
             if let Some(current) = best_overall.as_ref() {
@@ -418,46 +511,41 @@ fn build_notes(
     stock: Option<f64>,
     status_sell: Option<i32>,
     status_buy: Option<i32>,
-) -> Option<String> {
+) -> Vec<PriceNote> {
     let mut notes = Vec::new();
     if cross_system {
-        notes.push("Cross-system".to_string());
+        notes.push(PriceNote::CrossSystem);
     }
     if armistice {
-        notes.push("Armistice".to_string());
+        notes.push(PriceNote::Armistice);
     }
     if hotspot {
-        notes.push("Hotspot".to_string());
+        notes.push(PriceNote::Hotspot);
     }
-    if let Some(level) = status_label(status_sell) {
-        notes.push(format!("Sell {level}"));
+    if let Some(level) = status_level(status_sell) {
+        notes.push(PriceNote::Sell(level));
     }
-    if let Some(level) = status_label(status_buy) {
-        notes.push(format!("Buy {level}"));
+    if let Some(level) = status_level(status_buy) {
+        notes.push(PriceNote::Buy(level));
     }
     if let Some(stock_value) = stock {
         if stock_value.is_finite() {
             if stock_value < 500.0 {
-                notes.push("Low stock".to_string());
+                notes.push(PriceNote::LowStock);
             } else if stock_value > 5000.0 {
-                notes.push("High stock".to_string());
+                notes.push(PriceNote::HighStock);
             }
         }
     }
-
-    if notes.is_empty() {
-        None
-    } else {
-        Some(notes.join(", "))
-    }
+    notes
 }
 
-fn status_label(value: Option<i32>) -> Option<&'static str> {
+fn status_level(value: Option<i32>) -> Option<&'static str> {
     match value {
-        Some(3) => Some("High"),
-        Some(2) => Some("Normal"),
-        Some(1) => Some("Low"),
-        Some(0) => Some("Offline"),
+        Some(3) => Some("high"),
+        Some(2) => Some("normal"),
+        Some(1) => Some("low"),
+        Some(0) => Some("offline"),
         _ => None,
     }
 }
@@ -0,0 +1,245 @@
+//! Fencing hot cargo: what "no questions asked" terminals pay now against
+//! the price the same cargo would fetch on the legal market.
+//!
+//! Hot cargo only sells at NQA terminals. Quoting both sides per item shows
+//! the discount a fence costs, and grouping the offers per terminal gives the
+//! outlets worth flying to.
+
+use std::collections::{HashMap, HashSet};
+
+use super::entities::{CargoItem, CommodityId, PricePoint};
+use super::sell_plan::best_sell_price;
+
+/// One terminal's sell price for a commodity.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FenceOffer {
+    pub terminal_id: i32,
+    pub terminal_name: String,
+    pub system: Option<String>,
+    pub price: f64,
+}
+
+/// A hot cargo item quoted at NQA terminals and on the legal market.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FenceQuote {
+    pub item_id: String,
+    pub commodity_id: CommodityId,
+    pub commodity_name: String,
+    pub scu: u32,
+    /// Best price at a terminal that is not NQA.
+    pub legal: Option<FenceOffer>,
+    /// Every NQA terminal buying the commodity, best price first.
+    pub offers: Vec<FenceOffer>,
+}
+
+impl FenceQuote {
+    pub fn best_offer(&self) -> Option<&FenceOffer> {
+        self.offers.first()
+    }
+
+    pub fn fence_value(&self) -> Option<f64> {
+        self.best_offer().map(|offer| offer.price * self.scu as f64)
+    }
+
+    pub fn legal_value(&self) -> Option<f64> {
+        self.legal.as_ref().map(|offer| offer.price * self.scu as f64)
+    }
+
+    /// aUEC given up by fencing instead of selling legally. Without an NQA
+    /// buyer the whole legal value is lost.
+    pub fn discount(&self) -> Option<f64> {
+        let legal = self.legal_value()?;
+        Some((legal - self.fence_value().unwrap_or(0.0)).max(0.0))
+    }
+
+    /// [`Self::discount`] as a percentage of the legal value.
+    pub fn discount_percent(&self) -> Option<f64> {
+        let legal = self.legal_value().filter(|v| *v > 0.0)?;
+        Some(self.discount()? / legal * 100.0)
+    }
+
+    /// No NQA terminal currently buys this commodity.
+    pub fn unfenceable(&self) -> bool {
+        self.offers.is_empty()
+    }
+}
+
+/// Quotes every hot item in `items`, in cargo order.
+pub fn fence_quotes(
+    items: &[CargoItem],
+    prices: &HashMap<CommodityId, Vec<PricePoint>>,
+    nqa_terminal_ids: &HashSet<i32>,
+) -> Vec<FenceQuote> {
+    items
+        .iter()
+        .filter(|item| item.is_hot)
+        .map(|item| {
+            let mut offers = Vec::new();
+            let mut legal: Option<FenceOffer> = None;
+            for point in prices.get(&item.commodity_id).into_iter().flatten() {
+                let (Some(terminal_id), Some(price)) = (point.terminal_id, best_sell_price(point)) else {
+                    continue;
+                };
+                let offer = FenceOffer {
+                    terminal_id,
+                    terminal_name: point.terminal_name.clone(),
+                    system: point.system.clone(),
+                    price,
+                };
+                if nqa_terminal_ids.contains(&terminal_id) {
+                    offers.push(offer);
+                } else if legal.as_ref().is_none_or(|best| price > best.price) {
+                    legal = Some(offer);
+                }
+            }
+            offers.sort_by(|a, b| b.price.total_cmp(&a.price).then_with(|| a.terminal_name.cmp(&b.terminal_name)));
+            FenceQuote {
+                item_id: item.id.clone(),
+                commodity_id: item.commodity_id.clone(),
+                commodity_name: item.commodity_name.clone(),
+                scu: item.scu,
+                legal,
+                offers,
+            }
+        })
+        .collect()
+}
+
+/// A hot item an outlet buys, at that outlet's price.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OutletItem {
+    pub item_id: String,
+    pub commodity_name: String,
+    pub scu: u32,
+    pub price: f64,
+    /// Another outlet pays more for this item.
+    pub better_elsewhere: bool,
+}
+
+/// An NQA terminal and the hot cargo it would take.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FenceOutlet {
+    pub terminal_id: i32,
+    pub terminal_name: String,
+    pub system: Option<String>,
+    pub items: Vec<OutletItem>,
+    /// Distance from the current position in Gm, when known.
+    pub distance_gm: Option<f64>,
+}
+
+impl FenceOutlet {
+    pub fn total_value(&self) -> f64 {
+        self.items.iter().map(|i| i.price * i.scu as f64).sum()
+    }
+}
+
+/// Groups the NQA offers of `quotes` per terminal. Outlets with a known
+/// distance (Gm, keyed by terminal id) come first, nearest first; the rest
+/// follow by the value they'd pay.
+pub fn fence_outlets(quotes: &[FenceQuote], distances: &HashMap<i32, f64>) -> Vec<FenceOutlet> {
+    let mut outlets: HashMap<i32, FenceOutlet> = HashMap::new();
+    for quote in quotes {
+        let best = quote.best_offer().map(|o| o.price).unwrap_or_default();
+        for offer in &quote.offers {
+            let outlet = outlets.entry(offer.terminal_id).or_insert_with(|| FenceOutlet {
+                terminal_id: offer.terminal_id,
+                terminal_name: offer.terminal_name.clone(),
+                system: offer.system.clone(),
+                items: Vec::new(),
+                distance_gm: distances.get(&offer.terminal_id).copied(),
+            });
+            outlet.items.push(OutletItem {
+                item_id: quote.item_id.clone(),
+                commodity_name: quote.commodity_name.clone(),
+                scu: quote.scu,
+                price: offer.price,
+                better_elsewhere: offer.price < best,
+            });
+        }
+    }
+
+    let mut outlets: Vec<FenceOutlet> = outlets.into_values().collect();
+    outlets.sort_by(|a, b| match (a.distance_gm, b.distance_gm) {
+        (Some(x), Some(y)) => x.total_cmp(&y),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => b.total_value().total_cmp(&a.total_value()),
+    }
+    .then_with(|| a.terminal_name.cmp(&b.terminal_name)));
+    outlets
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::SystemTime;
+
+    use super::*;
+
+    fn point(terminal_id: i32, sell: f64) -> PricePoint {
+        PricePoint {
+            terminal_id: Some(terminal_id),
+            terminal_name: format!("TDD {terminal_id}"),
+            system: Some("Stanton".to_string()),
+            terminal_code: None,
+            price_sell_min: None,
+            price_sell: Some(sell),
+            price_sell_max: None,
+            price_buy_max: None,
+            price_buy: None,
+            price_buy_min: None,
+            price_average: None,
+            container_sizes: Vec::new(),
+            scu_buy: None,
+            scu_sell_stock: None,
+            status_sell: None,
+            status_buy: None,
+            city_name: None,
+            outpost_name: None,
+            space_station_name: None,
+            volatility_sell: None,
+            buy_user_rows: None,
+            sell_user_rows: None,
+            updated_at: SystemTime::now(),
+        }
+    }
+
+    fn item(id: &str, commodity: &str, scu: u32, is_hot: bool) -> CargoItem {
+        CargoItem {
+            id: id.to_string(),
+            commodity_id: commodity.to_lowercase(),
+            commodity_name: commodity.to_string(),
+            scu,
+            is_hot,
+        }
+    }
+
+    #[test]
+    fn quotes_hot_cargo_against_the_legal_market() {
+        let items = vec![
+            item("a", "Gold", 10, true),
+            item("b", "Agricium", 4, true),
+            item("c", "Laranite", 8, false),
+        ];
+        let prices = HashMap::from([
+            ("gold".to_string(), vec![point(1, 100.0), point(2, 60.0), point(3, 70.0)]),
+            ("agricium".to_string(), vec![point(1, 50.0)]),
+            ("laranite".to_string(), vec![point(2, 30.0)]),
+        ]);
+        let nqa = HashSet::from([2, 3]);
+        let quotes = fence_quotes(&items, &prices, &nqa);
+
+        assert_eq!(quotes.len(), 2);
+        let gold = &quotes[0];
+        assert_eq!(gold.best_offer().map(|o| o.terminal_id), Some(3));
+        assert_eq!(gold.discount(), Some(300.0));
+        assert_eq!(gold.discount_percent(), Some(30.0));
+        let agricium = &quotes[1];
+        assert!(agricium.unfenceable());
+        assert_eq!(agricium.discount(), Some(200.0));
+
+        let outlets = fence_outlets(&quotes, &HashMap::from([(2, 5.0)]));
+        assert_eq!(outlets.iter().map(|o| o.terminal_id).collect::<Vec<_>>(), vec![2, 3]);
+        assert!(outlets[0].items[0].better_elsewhere);
+        assert_eq!(outlets[1].total_value(), 700.0);
+    }
+}
@@ -0,0 +1,533 @@
+//! Star Citizen `Game.log` parsing: turn location, zone and quantum lines into
+//! the player's current position, and commodity kiosk lines into trades.
+//!
+//! The game only logs internal object names (`Stanton1_Lorville`, `RR_HUR_LEO`),
+//! so [`resolve_location`] maps them onto the UEX [`Location`] list.
+
+use std::cmp::Ordering;
+
+use super::entities::Location;
+use super::history::TradeSide;
+
+/// Text between `before` and `after` on lines containing `marker`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Capture {
+    pub marker: &'static str,
+    pub before: &'static str,
+    pub after: &'static str,
+}
+
+impl Capture {
+    fn extract<'a>(&self, line: &'a str) -> Option<&'a str> {
+        if !line.contains(self.marker) {
+            return None;
+        }
+        let start = line.find(self.before)? + self.before.len();
+        let rest = &line[start..];
+        let value = rest[..rest.find(self.after)?].trim();
+        (!value.is_empty()).then_some(value)
+    }
+}
+
+/// Log line markers for one family of game builds. CIG rewords log lines
+/// between patches; add a new set in front of [`PATTERNS`] instead of editing
+/// an old one, so older logs keep parsing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LogPatterns {
+    pub builds: &'static str,
+    /// Inventory requests name the location the player is standing in.
+    pub inventory: Capture,
+    pub zone: Capture,
+    pub quantum_target: Capture,
+    pub quantum_arrived: &'static str,
+}
+
+/// Known pattern sets, newest first.
+pub const PATTERNS: &[LogPatterns] = &[LogPatterns {
+    builds: "3.23 – 4.x",
+    inventory: Capture {
+        marker: "<RequestLocationInventory>",
+        before: "Location[",
+        after: "]",
+    },
+    zone: Capture {
+        marker: "<OnEntityEnterZone>",
+        before: "entered zone [",
+        after: "]",
+    },
+    quantum_target: Capture {
+        marker: "<Player Selected Quantum Target",
+        before: "has selected point ",
+        after: " as their destination",
+    },
+    quantum_arrived: "<Quantum Drive Arrived",
+}];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LogEvent {
+    LocationInventory(String),
+    ZoneEntered(String),
+    QuantumTargetSelected(String),
+    QuantumArrived,
+}
+
+/// Parse one log line with the first pattern set that recognizes it.
+pub fn parse_line(line: &str) -> Option<LogEvent> {
+    PATTERNS
+        .iter()
+        .find_map(|patterns| parse_with(patterns, line))
+}
+
+fn parse_with(patterns: &LogPatterns, line: &str) -> Option<LogEvent> {
+    if let Some(id) = patterns.inventory.extract(line) {
+        return Some(LogEvent::LocationInventory(id.to_string()));
+    }
+    if let Some(id) = patterns.zone.extract(line) {
+        return Some(LogEvent::ZoneEntered(id.to_string()));
+    }
+    if let Some(id) = patterns.quantum_target.extract(line) {
+        return Some(LogEvent::QuantumTargetSelected(id.to_string()));
+    }
+    line.contains(patterns.quantum_arrived)
+        .then_some(LogEvent::QuantumArrived)
+}
+
+/// Follows log events and reports the raw location id whenever it changes.
+#[derive(Clone, Debug, Default)]
+pub struct PositionTracker {
+    /// Last quantum target; becomes the position when the jump completes.
+    pending_target: Option<String>,
+    current: Option<String>,
+}
+
+impl PositionTracker {
+    pub fn current(&self) -> Option<&str> {
+        self.current.as_deref()
+    }
+
+    /// Feed one line; returns the new location id if the position changed.
+    pub fn feed_line(&mut self, line: &str) -> Option<String> {
+        let location = match parse_line(line)? {
+            LogEvent::LocationInventory(id) | LogEvent::ZoneEntered(id) => Some(id),
+            LogEvent::QuantumTargetSelected(id) => {
+                self.pending_target = Some(id);
+                None
+            }
+            LogEvent::QuantumArrived => self.pending_target.take(),
+        }?;
+        if is_transient(&location) || self.current.as_deref() == Some(location.as_str()) {
+            return None;
+        }
+        self.current = Some(location.clone());
+        Some(location)
+    }
+}
+
+/// Shop transaction lines for one family of game builds. Values are read
+/// from `key[value]` fields.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TradePatterns {
+    /// First game version (as reported by UEX) that writes these lines.
+    pub since: &'static str,
+    pub marker: &'static str,
+    pub buy_tag: &'static str,
+    pub sell_tag: &'static str,
+    pub commodity_key: &'static str,
+    pub quantity_key: &'static str,
+    /// Logged quantity units per SCU (centi-SCU before 4.0).
+    pub units_per_scu: f64,
+    /// Total transaction price keys, tried in order.
+    pub price_keys: &'static [&'static str],
+    pub shop_key: &'static str,
+    /// Result line that must follow before the trade counts; `None` when the
+    /// marker line is only written for completed trades.
+    pub confirmation: Option<Confirmation>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Confirmation {
+    pub marker: &'static str,
+    pub success: &'static str,
+}
+
+/// Known shop pattern sets, newest first.
+pub const TRADE_PATTERNS: &[TradePatterns] = &[
+    TradePatterns {
+        since: "4.0",
+        marker: "<CCommodityKioskController::OnTransactionComplete>",
+        buy_tag: "type[Purchase]",
+        sell_tag: "type[Sale]",
+        commodity_key: "commodity",
+        quantity_key: "scu",
+        units_per_scu: 1.0,
+        price_keys: &["totalPrice"],
+        shop_key: "shopName",
+        confirmation: None,
+    },
+    TradePatterns {
+        since: "3.23",
+        marker: "<CEntityComponentCommodityUIProvider::SendCommodity",
+        buy_tag: "SendCommodityBuyRequest>",
+        sell_tag: "SendCommoditySellRequest>",
+        commodity_key: "resourceName",
+        quantity_key: "quantity",
+        units_per_scu: 100.0,
+        price_keys: &["price", "amount"],
+        shop_key: "shopName",
+        confirmation: Some(Confirmation {
+            marker: "<CEntityComponentCommodityUIProvider::OnCommodityTransactionResult>",
+            success: "result[Success]",
+        }),
+    },
+];
+
+/// Pattern sets that apply to `game_version`, newest first. Sets introduced
+/// after that version are skipped; an unknown version tries them all.
+pub fn trade_patterns_for(game_version: Option<&str>) -> Vec<&'static TradePatterns> {
+    TRADE_PATTERNS
+        .iter()
+        .filter(|patterns| {
+            game_version.is_none_or(|version| {
+                compare_versions(patterns.since, version) != Ordering::Greater
+            })
+        })
+        .collect()
+}
+
+/// Compare dotted numeric versions ("4.0" vs "3.24.2"), ignoring suffixes.
+fn compare_versions(a: &str, b: &str) -> Ordering {
+    let parts = |version: &str| -> Vec<u32> {
+        version
+            .split('.')
+            .map_while(|part| {
+                let digits: String = part.chars().take_while(char::is_ascii_digit).collect();
+                digits.parse().ok()
+            })
+            .collect()
+    };
+    parts(a).cmp(&parts(b))
+}
+
+/// A commodity purchase or sale read from the log.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LogTrade {
+    pub side: TradeSide,
+    /// Commodity name as logged; match it with `match_commodity`.
+    pub commodity: String,
+    pub scu: u32,
+    pub total_price: Option<f64>,
+    /// Internal shop name, e.g. `SCShop_Stanton3_Area18_TDD`.
+    pub shop: Option<String>,
+}
+
+impl LogTrade {
+    pub fn price_per_scu(&self) -> Option<f64> {
+        self.total_price
+            .filter(|_| self.scu > 0)
+            .map(|total| total / self.scu as f64)
+    }
+
+    /// Shop name without the `SCShop_` prefix, for [`resolve_location`].
+    pub fn shop_location(&self) -> Option<&str> {
+        let shop = self.shop.as_deref()?;
+        Some(shop.strip_prefix("SCShop_").unwrap_or(shop))
+    }
+}
+
+/// Follows shop lines and reports trades once they are known to have gone through.
+#[derive(Clone, Debug)]
+pub struct TradeTracker {
+    patterns: Vec<&'static TradePatterns>,
+    /// Request waiting for its result line.
+    pending: Option<LogTrade>,
+}
+
+impl TradeTracker {
+    pub fn new(game_version: Option<&str>) -> Self {
+        Self {
+            patterns: trade_patterns_for(game_version),
+            pending: None,
+        }
+    }
+
+    pub fn feed_line(&mut self, line: &str) -> Option<LogTrade> {
+        for patterns in &self.patterns {
+            if let Some(confirmation) = patterns.confirmation {
+                if line.contains(confirmation.marker) {
+                    let pending = self.pending.take();
+                    return pending.filter(|_| line.contains(confirmation.success));
+                }
+            }
+            if !line.contains(patterns.marker) {
+                continue;
+            }
+            let trade = parse_trade(patterns, line)?;
+            if patterns.confirmation.is_some() {
+                self.pending = Some(trade);
+                return None;
+            }
+            return Some(trade);
+        }
+        None
+    }
+}
+
+fn parse_trade(patterns: &TradePatterns, line: &str) -> Option<LogTrade> {
+    let side = if line.contains(patterns.buy_tag) {
+        TradeSide::Buy
+    } else if line.contains(patterns.sell_tag) {
+        TradeSide::Sell
+    } else {
+        return None;
+    };
+    let commodity = field(line, patterns.commodity_key)?.trim().to_string();
+    let units: f64 = field(line, patterns.quantity_key)?.trim().parse().ok()?;
+    let scu = (units / patterns.units_per_scu).round();
+    if commodity.is_empty() || !scu.is_finite() || scu < 1.0 {
+        return None;
+    }
+    let total_price = patterns
+        .price_keys
+        .iter()
+        .find_map(|key| field(line, key)?.trim().parse::<f64>().ok())
+        .filter(|price| price.is_finite() && *price > 0.0);
+    Some(LogTrade {
+        side,
+        commodity,
+        scu: scu as u32,
+        total_price,
+        shop: field(line, patterns.shop_key).map(str::to_string),
+    })
+}
+
+/// Value of a ` key[value]` field.
+fn field<'a>(line: &'a str, key: &str) -> Option<&'a str> {
+    let needle = format!(" {key}[");
+    let start = line.find(&needle)? + needle.len();
+    let rest = &line[start..];
+    Some(&rest[..rest.find(']')?])
+}
+
+/// Ships, cargo grids and personal containers also request inventories.
+fn is_transient(id: &str) -> bool {
+    let lower = id.to_ascii_lowercase();
+    ["ship_", "vehicle_", "cargo_grid", "container"]
+        .iter()
+        .any(|needle| lower.contains(needle))
+}
+
+/// Internal station names that don't spell out the UEX name.
+const LOCATION_ALIASES: &[(&str, &str)] = &[
+    ("RR_HUR_LEO", "Everus Harbor"),
+    ("RR_CRU_LEO", "Seraphim Station"),
+    ("RR_ARC_LEO", "Baijini Point"),
+    ("RR_MIC_LEO", "Port Tressler"),
+    ("Stanton4_NewBabbage", "New Babbage"),
+    ("Stanton1_Lorville", "Lorville"),
+];
+
+/// Map a raw log id onto a known location: alias table, then exact name,
+/// then the longest location name contained in the id, then a unique prefix.
+pub fn resolve_location<'a>(raw: &str, locations: &'a [Location]) -> Option<&'a Location> {
+    let by_name = |name: &str| {
+        let wanted = normalize(name);
+        locations.iter().find(|l| normalize(&l.name) == wanted)
+    };
+
+    if let Some((_, alias)) = LOCATION_ALIASES
+        .iter()
+        .find(|(id, _)| id.eq_ignore_ascii_case(raw))
+    {
+        if let Some(location) = by_name(alias) {
+            return Some(location);
+        }
+    }
+
+    let key = location_key(raw);
+    if key.len() < 3 {
+        return None;
+    }
+    if let Some(location) = locations.iter().find(|l| normalize(&l.name) == key) {
+        return Some(location);
+    }
+
+    let contained = locations
+        .iter()
+        .filter(|l| {
+            let name = normalize(&l.name);
+            name.len() >= 4 && key.contains(&name)
+        })
+        .max_by_key(|l| normalize(&l.name).len());
+    if contained.is_some() {
+        return contained;
+    }
+
+    let mut prefixed = locations
+        .iter()
+        .filter(|l| normalize(&l.name).starts_with(&key));
+    match (prefixed.next(), prefixed.next()) {
+        (Some(only), None) => Some(only),
+        _ => None,
+    }
+}
+
+/// `OOC_Stanton_2b_Daymar` → `daymar`, `RR_HUR_L1` → `hurl1`: drop system,
+/// orbit and rest-stop prefixes and landing-zone suffixes.
+fn location_key(raw: &str) -> String {
+    raw.split('_')
+        .filter(|token| {
+            let lower = token.to_ascii_lowercase();
+            let is_system = ["stanton", "pyro", "nyx"].iter().any(|system| {
+                lower
+                    .strip_prefix(system)
+                    .is_some_and(|rest| rest.len() <= 2)
+            });
+            let is_orbit_index =
+                lower.starts_with(|c: char| c.is_ascii_digit()) && lower.len() <= 3;
+            let is_noise = matches!(lower.as_str(), "rr" | "ooc" | "landingzone" | "lz");
+            !(is_system || is_orbit_index || is_noise)
+        })
+        .map(normalize)
+        .collect()
+}
+
+fn normalize(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STANTON: &str = include_str!("../../tests/fixtures/game_log/session_stanton.log");
+    const PYRO: &str = include_str!("../../tests/fixtures/game_log/session_pyro.log");
+
+    fn location(name: &str, terminal_id: i32) -> Location {
+        Location {
+            name: name.to_string(),
+            system: None,
+            terminal_id,
+        }
+    }
+
+    const TRADES_3_24: &str = include_str!("../../tests/fixtures/game_log/trades_3_24.log");
+    const TRADES_4_0: &str = include_str!("../../tests/fixtures/game_log/trades_4_0.log");
+
+    fn replay_trades(log: &str, game_version: Option<&str>) -> Vec<LogTrade> {
+        let mut tracker = TradeTracker::new(game_version);
+        log.lines()
+            .filter_map(|line| tracker.feed_line(line))
+            .collect()
+    }
+
+    fn replay(log: &str) -> Vec<String> {
+        let mut tracker = PositionTracker::default();
+        log.lines()
+            .filter_map(|line| tracker.feed_line(line))
+            .collect()
+    }
+
+    #[test]
+    fn stanton_session_reports_each_position_change() {
+        assert_eq!(
+            replay(STANTON),
+            [
+                "Stanton1_Lorville",
+                "RR_HUR_LEO",
+                "OOC_Stanton_2b_Daymar",
+                "Stanton3_Area18_LandingZone",
+                "Stanton3_Area18",
+            ]
+        );
+    }
+
+    #[test]
+    fn pyro_session_skips_ship_inventories() {
+        assert_eq!(replay(PYRO), ["RR_P2_L4", "Pyro1_RuinStation"]);
+    }
+
+    #[test]
+    fn quantum_arrival_without_target_is_ignored() {
+        let mut tracker = PositionTracker::default();
+        let arrived = STANTON
+            .lines()
+            .find(|l| l.contains("Quantum Drive Arrived"))
+            .unwrap();
+        assert_eq!(tracker.feed_line(arrived), None);
+        assert_eq!(tracker.current(), None);
+    }
+
+    #[test]
+    fn resolves_log_ids_to_locations() {
+        let locations = vec![
+            location("Lorville", 1),
+            location("Everus Harbor", 2),
+            location("Area 18", 3),
+            location("HUR-L1 Green Glade Station", 4),
+            location("Ruin Station", 5),
+            location("New Babbage", 6),
+        ];
+        let resolve = |raw| resolve_location(raw, &locations).map(|l| l.terminal_id);
+
+        assert_eq!(resolve("Stanton1_Lorville"), Some(1));
+        assert_eq!(resolve("RR_HUR_LEO"), Some(2));
+        assert_eq!(resolve("Stanton3_Area18_LandingZone"), Some(3));
+        assert_eq!(resolve("RR_HUR_L1"), Some(4));
+        assert_eq!(resolve("Pyro1_RuinStation"), Some(5));
+        assert_eq!(resolve("Stanton4_NewBabbage"), Some(6));
+        assert_eq!(resolve("OOC_Stanton_2b_Daymar"), None);
+    }
+
+    #[test]
+    fn pre_4_0_trades_wait_for_a_successful_result() {
+        let trades = replay_trades(TRADES_3_24, Some("3.24.3"));
+        assert_eq!(
+            trades,
+            [
+                LogTrade {
+                    side: TradeSide::Buy,
+                    commodity: "Agricium".into(),
+                    scu: 96,
+                    total_price: Some(259_200.0),
+                    shop: Some("SCShop_Stanton3_Area18_TDD".into()),
+                },
+                LogTrade {
+                    side: TradeSide::Sell,
+                    commodity: "Agricium".into(),
+                    scu: 96,
+                    total_price: Some(302_400.0),
+                    shop: Some("SCShop_Stanton1_Lorville_TDD".into()),
+                },
+            ]
+        );
+        assert_eq!(trades[0].price_per_scu(), Some(2_700.0));
+        assert_eq!(trades[1].shop_location(), Some("Stanton1_Lorville_TDD"));
+    }
+
+    #[test]
+    fn trades_from_4_0_are_final_and_skip_other_types() {
+        let trades = replay_trades(TRADES_4_0, Some("4.1.1"));
+        let summary: Vec<_> = trades
+            .iter()
+            .map(|t| (t.side, t.scu, t.total_price))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (TradeSide::Buy, 32, Some(281_600.0)),
+                (TradeSide::Sell, 12, Some(110_400.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn newer_patterns_are_not_used_for_older_builds() {
+        assert!(replay_trades(TRADES_4_0, Some("3.24.3")).is_empty());
+        assert_eq!(replay_trades(TRADES_4_0, None).len(), 2);
+        assert_eq!(trade_patterns_for(Some("3.22")).len(), 0);
+    }
+}
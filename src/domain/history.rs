@@ -0,0 +1,438 @@
+//! Trade history ledger with per-session and per-run analytics.
+
+use std::{
+    collections::HashMap,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use super::entities::CommodityId;
+
+/// Which side of a trade a ledger entry records.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TradeSide {
+    Buy,
+    Sell,
+}
+
+/// A completed buy or sell, as logged by the player.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TradeRecord {
+    pub id: String,
+    pub side: TradeSide,
+    pub commodity_id: CommodityId,
+    pub commodity_name: String,
+    pub scu: u32,
+    pub price_per_scu: f64,
+    pub terminal_id: Option<i32>,
+    pub terminal_name: String,
+    pub system: Option<String>,
+    /// Unix timestamp (seconds) when the trade was logged.
+    pub timestamp: u64,
+    /// EV from `evaluate_item` at the time of the sale (sells only).
+    #[serde(default)]
+    pub predicted_value: Option<f64>,
+    /// App session the trade was logged in.
+    pub session_id: String,
+    /// Buy → sell cycle within the session (1-based).
+    pub run_id: u32,
+}
+
+impl TradeRecord {
+    /// New ledger entry stamped with the current time. The run number is
+    /// assigned when the record is added to a [`TradeHistory`].
+    pub fn new(
+        session_id: &str,
+        side: TradeSide,
+        commodity_id: CommodityId,
+        commodity_name: String,
+        scu: u32,
+        price_per_scu: f64,
+    ) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            side,
+            commodity_id,
+            commodity_name,
+            scu,
+            price_per_scu,
+            terminal_id: None,
+            terminal_name: "Unknown".to_string(),
+            system: None,
+            timestamp: unix_now(),
+            predicted_value: None,
+            session_id: session_id.to_string(),
+            run_id: 0,
+        }
+    }
+
+    pub fn at_terminal(
+        mut self,
+        terminal_id: Option<i32>,
+        name: String,
+        system: Option<String>,
+    ) -> Self {
+        self.terminal_id = terminal_id;
+        self.terminal_name = name;
+        self.system = system;
+        self
+    }
+
+    pub fn with_prediction(mut self, predicted_value: f64) -> Self {
+        self.predicted_value = Some(predicted_value);
+        self
+    }
+
+    /// Total aUEC moved by this trade.
+    pub fn total(&self) -> f64 {
+        self.price_per_scu * self.scu as f64
+    }
+
+    /// Signed cash flow: negative for buys, positive for sells.
+    pub fn cash_flow(&self) -> f64 {
+        match self.side {
+            TradeSide::Buy => -self.total(),
+            TradeSide::Sell => self.total(),
+        }
+    }
+}
+
+/// Persisted ledger of all logged trades, oldest first.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct TradeHistory {
+    #[serde(default)]
+    pub records: Vec<TradeRecord>,
+}
+
+impl TradeHistory {
+    /// Run number for a new trade in `session_id`.
+    /// A buy that follows a sell starts a new run.
+    pub fn next_run_id(&self, session_id: &str, side: TradeSide) -> u32 {
+        let last = self
+            .records
+            .iter()
+            .rev()
+            .find(|record| record.session_id == session_id);
+
+        match last {
+            None => 1,
+            Some(record) if record.side == TradeSide::Sell && side == TradeSide::Buy => {
+                record.run_id + 1
+            }
+            Some(record) => record.run_id,
+        }
+    }
+
+    /// Append a trade, assigning its run number.
+    pub fn record(&mut self, mut record: TradeRecord) {
+        record.run_id = self.next_run_id(&record.session_id, record.side);
+        self.records.push(record);
+    }
+
+    pub fn remove(&mut self, id: &str) {
+        self.records.retain(|record| record.id != id);
+    }
+
+    /// Session IDs, most recent first.
+    pub fn session_ids(&self) -> Vec<String> {
+        let mut last_seen: HashMap<&str, u64> = HashMap::new();
+        for record in &self.records {
+            let entry = last_seen.entry(record.session_id.as_str()).or_insert(0);
+            *entry = (*entry).max(record.timestamp);
+        }
+        let mut ids: Vec<(&str, u64)> = last_seen.into_iter().collect();
+        ids.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        ids.into_iter().map(|(id, _)| id.to_string()).collect()
+    }
+
+    pub fn session_records(&self, session_id: &str) -> Vec<&TradeRecord> {
+        self.records
+            .iter()
+            .filter(|record| record.session_id == session_id)
+            .collect()
+    }
+
+    /// Summaries for every session, most recent first.
+    pub fn session_summaries(&self) -> Vec<(String, HistorySummary)> {
+        self.session_ids()
+            .into_iter()
+            .map(|id| {
+                let summary = summarize(&self.session_records(&id));
+                (id, summary)
+            })
+            .collect()
+    }
+
+    /// Summaries for each run inside a session, in run order.
+    pub fn run_summaries(&self, session_id: &str) -> Vec<(u32, HistorySummary)> {
+        let mut runs: Vec<u32> = self
+            .session_records(session_id)
+            .iter()
+            .map(|record| record.run_id)
+            .collect();
+        runs.sort_unstable();
+        runs.dedup();
+
+        runs.into_iter()
+            .map(|run_id| {
+                let records: Vec<&TradeRecord> = self
+                    .records
+                    .iter()
+                    .filter(|record| record.session_id == session_id && record.run_id == run_id)
+                    .collect();
+                (run_id, summarize(&records))
+            })
+            .collect()
+    }
+}
+
+/// Aggregated figures for a set of trades (a session, a run, or everything).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HistorySummary {
+    pub started_at: u64,
+    pub ended_at: u64,
+    pub trade_count: usize,
+    pub spent: f64,
+    pub revenue: f64,
+    pub profit: f64,
+    pub profit_per_hour: Option<f64>,
+    /// Commodities ranked by profit (revenue minus spend), best first.
+    pub best_commodities: Vec<(String, f64)>,
+    /// Sell terminals ranked by revenue, best first.
+    pub best_terminals: Vec<(String, f64)>,
+    /// Mean absolute error of predicted EV vs realized sell value, in percent.
+    pub prediction_error_pct: Option<f64>,
+    /// Mean signed error (positive = sold above prediction), in percent.
+    pub prediction_bias_pct: Option<f64>,
+}
+
+impl HistorySummary {
+    pub fn duration_secs(&self) -> u64 {
+        self.ended_at.saturating_sub(self.started_at)
+    }
+}
+
+pub fn summarize(records: &[&TradeRecord]) -> HistorySummary {
+    const TOP_N: usize = 3;
+    const MIN_RATE_WINDOW_SECS: u64 = 60;
+
+    if records.is_empty() {
+        return HistorySummary::default();
+    }
+
+    let started_at = records.iter().map(|r| r.timestamp).min().unwrap_or(0);
+    let ended_at = records.iter().map(|r| r.timestamp).max().unwrap_or(0);
+
+    let mut spent = 0.0;
+    let mut revenue = 0.0;
+    let mut by_commodity: HashMap<&str, f64> = HashMap::new();
+    let mut by_terminal: HashMap<&str, f64> = HashMap::new();
+    let mut abs_errors = Vec::new();
+    let mut signed_errors = Vec::new();
+
+    for record in records {
+        *by_commodity
+            .entry(record.commodity_name.as_str())
+            .or_insert(0.0) += record.cash_flow();
+        match record.side {
+            TradeSide::Buy => spent += record.total(),
+            TradeSide::Sell => {
+                revenue += record.total();
+                *by_terminal
+                    .entry(record.terminal_name.as_str())
+                    .or_insert(0.0) += record.total();
+                if let Some(predicted) = record.predicted_value.filter(|p| *p > 0.0) {
+                    let error = (record.total() - predicted) / predicted * 100.0;
+                    abs_errors.push(error.abs());
+                    signed_errors.push(error);
+                }
+            }
+        }
+    }
+
+    let profit = revenue - spent;
+    let duration = ended_at.saturating_sub(started_at);
+    let profit_per_hour = if duration >= MIN_RATE_WINDOW_SECS {
+        Some(profit / (duration as f64 / 3600.0))
+    } else {
+        None
+    };
+
+    HistorySummary {
+        started_at,
+        ended_at,
+        trade_count: records.len(),
+        spent,
+        revenue,
+        profit,
+        profit_per_hour,
+        best_commodities: top_entries(by_commodity, TOP_N),
+        best_terminals: top_entries(by_terminal, TOP_N),
+        prediction_error_pct: mean(&abs_errors),
+        prediction_bias_pct: mean(&signed_errors),
+    }
+}
+
+/// Cumulative profit after each trade, as `(timestamp, running_total)` points.
+pub fn profit_timeline(records: &[&TradeRecord]) -> Vec<(u64, f64)> {
+    let mut sorted: Vec<&&TradeRecord> = records.iter().collect();
+    sorted.sort_by_key(|record| record.timestamp);
+
+    let mut running = 0.0;
+    sorted
+        .into_iter()
+        .map(|record| {
+            running += record.cash_flow();
+            (record.timestamp, running)
+        })
+        .collect()
+}
+
+/// Current time as Unix seconds.
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn top_entries(map: HashMap<&str, f64>, limit: usize) -> Vec<(String, f64)> {
+    let mut entries: Vec<(String, f64)> = map
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), value))
+        .collect();
+    entries.sort_by(|a, b| {
+        b.1.partial_cmp(&a.1)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.0.cmp(&b.0))
+    });
+    entries.truncate(limit);
+    entries
+}
+
+fn mean(values: &[f64]) -> Option<f64> {
+    if values.is_empty() {
+        None
+    } else {
+        Some(values.iter().sum::<f64>() / values.len() as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trade(
+        session: &str,
+        side: TradeSide,
+        commodity: &str,
+        scu: u32,
+        price: f64,
+        at: u64,
+    ) -> TradeRecord {
+        let mut record = TradeRecord::new(
+            session,
+            side,
+            commodity.to_lowercase(),
+            commodity.to_string(),
+            scu,
+            price,
+        )
+        .at_terminal(None, format!("TDD {commodity}"), None);
+        record.timestamp = at;
+        record
+    }
+
+    #[test]
+    fn a_buy_after_a_sell_starts_the_next_run() {
+        let mut history = TradeHistory::default();
+        assert_eq!(history.next_run_id("s1", TradeSide::Sell), 1);
+
+        history.record(trade("s1", TradeSide::Buy, "Gold", 10, 100.0, 1));
+        history.record(trade("s1", TradeSide::Buy, "Agricium", 10, 100.0, 2));
+        history.record(trade("s1", TradeSide::Sell, "Gold", 10, 150.0, 3));
+        history.record(trade("s1", TradeSide::Sell, "Agricium", 10, 150.0, 4));
+        assert_eq!(history.next_run_id("s1", TradeSide::Sell), 1);
+        assert_eq!(history.next_run_id("s1", TradeSide::Buy), 2);
+
+        history.record(trade("s1", TradeSide::Buy, "Gold", 5, 100.0, 5));
+        // Other sessions number their runs on their own.
+        history.record(trade("s2", TradeSide::Buy, "Gold", 5, 100.0, 6));
+
+        let runs: Vec<u32> = history.records.iter().map(|r| r.run_id).collect();
+        assert_eq!(runs, vec![1, 1, 1, 1, 2, 1]);
+        assert_eq!(
+            history
+                .run_summaries("s1")
+                .iter()
+                .map(|(run, _)| *run)
+                .collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        assert_eq!(
+            history.session_ids(),
+            vec!["s2".to_string(), "s1".to_string()]
+        );
+    }
+
+    #[test]
+    fn summarize_totals_profit_rate_and_rankings() {
+        assert_eq!(summarize(&[]), HistorySummary::default());
+
+        let records = [
+            trade("s1", TradeSide::Buy, "Gold", 10, 100.0, 0),
+            trade("s1", TradeSide::Buy, "Agricium", 20, 50.0, 600),
+            trade("s1", TradeSide::Sell, "Gold", 10, 180.0, 1_200),
+            trade("s1", TradeSide::Sell, "Agricium", 20, 45.0, 1_800),
+        ];
+        let refs: Vec<&TradeRecord> = records.iter().collect();
+        let summary = summarize(&refs);
+
+        assert_eq!(summary.trade_count, 4);
+        assert_eq!(summary.duration_secs(), 1_800);
+        assert_eq!(summary.spent, 2_000.0);
+        assert_eq!(summary.revenue, 2_700.0);
+        assert_eq!(summary.profit, 700.0);
+        assert_eq!(summary.profit_per_hour, Some(1_400.0));
+        assert_eq!(
+            summary.best_commodities,
+            vec![
+                ("Gold".to_string(), 800.0),
+                ("Agricium".to_string(), -100.0)
+            ]
+        );
+        assert_eq!(summary.best_terminals[0], ("TDD Gold".to_string(), 1_800.0));
+
+        // Under a minute there is no meaningful hourly rate.
+        let quick = [trade("s1", TradeSide::Sell, "Gold", 1, 100.0, 10)];
+        assert_eq!(
+            summarize(&quick.iter().collect::<Vec<_>>()).profit_per_hour,
+            None
+        );
+    }
+
+    #[test]
+    fn prediction_error_compares_sells_with_their_prediction() {
+        let records = [
+            trade("s1", TradeSide::Sell, "Gold", 10, 110.0, 0).with_prediction(1_000.0),
+            trade("s1", TradeSide::Sell, "Gold", 10, 70.0, 1).with_prediction(1_000.0),
+            // No prediction, or a zero one, is left out.
+            trade("s1", TradeSide::Sell, "Gold", 10, 500.0, 2),
+            trade("s1", TradeSide::Sell, "Gold", 10, 500.0, 3).with_prediction(0.0),
+            trade("s1", TradeSide::Buy, "Gold", 10, 500.0, 4).with_prediction(1.0),
+        ];
+        let summary = summarize(&records.iter().collect::<Vec<_>>());
+
+        let error = summary.prediction_error_pct.unwrap();
+        let bias = summary.prediction_bias_pct.unwrap();
+        assert!((error - 20.0).abs() < 1e-9, "{error}");
+        assert!((bias + 10.0).abs() < 1e-9, "{bias}");
+
+        let unpredicted = [trade("s1", TradeSide::Sell, "Gold", 10, 500.0, 0)];
+        assert_eq!(
+            summarize(&unpredicted.iter().collect::<Vec<_>>()).prediction_error_pct,
+            None
+        );
+    }
+}
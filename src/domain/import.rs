@@ -0,0 +1,436 @@
+//! Cargo list import: parse CSV, JSON or pasted text and match commodity names.
+
+use serde_json::Value;
+
+use super::entities::Commodity;
+
+/// One cargo line as written by the user, before matching.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImportLine {
+    /// The original text, shown in the review dialog.
+    pub raw: String,
+    pub name: String,
+    pub scu: u32,
+    pub is_hot: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MatchKind {
+    /// Name or code matched exactly (ignoring case and punctuation).
+    Exact,
+    /// Close enough to accept, but worth a glance.
+    Fuzzy,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImportMatch {
+    pub line: ImportLine,
+    /// Matched commodity id, or `None` if nothing was close enough.
+    pub commodity_id: Option<String>,
+    pub kind: Option<MatchKind>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImportParse {
+    pub lines: Vec<ImportLine>,
+    /// Input fragments that didn't look like "<qty> <name>" at all.
+    pub rejected: Vec<String>,
+}
+
+/// Below this similarity a fuzzy candidate is not offered.
+const FUZZY_THRESHOLD: f64 = 0.75;
+
+/// Detect the format (JSON, CSV with header, or free text) and parse it.
+pub fn parse_import(input: &str) -> ImportParse {
+    let trimmed = input.trim();
+    if trimmed.starts_with('[') || trimmed.starts_with('{') {
+        if let Ok(value) = serde_json::from_str::<Value>(trimmed) {
+            return parse_json(&value);
+        }
+    }
+    if let Some(parsed) = parse_csv(trimmed) {
+        return parsed;
+    }
+    parse_text(trimmed)
+}
+
+/// Free text like "120 Laranite, 32 Quantanium" or one entry per line.
+pub fn parse_text(input: &str) -> ImportParse {
+    let mut lines = Vec::new();
+    let mut rejected = Vec::new();
+    for fragment in split_entries(input) {
+        let fragment = fragment.trim();
+        if fragment.is_empty() {
+            continue;
+        }
+        match parse_fragment(fragment) {
+            Some(line) => lines.push(line),
+            None => rejected.push(fragment.to_string()),
+        }
+    }
+    ImportParse { lines, rejected }
+}
+
+/// CSV with a header naming a commodity column and an SCU column. Exports
+/// from this app (`commodity_name`, `scu`, `is_hot`) import directly.
+pub fn parse_csv(input: &str) -> Option<ImportParse> {
+    let mut rows = input.lines().filter(|line| !line.trim().is_empty());
+    let header = split_csv_line(rows.next()?);
+    let column = |names: &[&str]| {
+        header
+            .iter()
+            .position(|h| names.contains(&h.trim().to_ascii_lowercase().as_str()))
+    };
+    let name_col = column(&["commodity_name", "commodity", "name", "code", "commodity_code"])?;
+    let scu_col = column(&["scu", "quantity", "qty", "amount"])?;
+    let hot_col = column(&["is_hot", "hot"]);
+
+    let mut lines = Vec::new();
+    let mut rejected = Vec::new();
+    for raw in rows {
+        let cells = split_csv_line(raw);
+        let name = cells.get(name_col).map(|c| c.trim().to_string()).unwrap_or_default();
+        let scu = cells.get(scu_col).and_then(|c| parse_scu(c));
+        let is_hot = hot_col
+            .and_then(|col| cells.get(col))
+            .map(|c| matches!(c.trim().to_ascii_lowercase().as_str(), "true" | "yes" | "1"))
+            .unwrap_or(false);
+        match scu {
+            Some(scu) if !name.is_empty() => lines.push(ImportLine {
+                raw: raw.to_string(),
+                name,
+                scu,
+                is_hot,
+            }),
+            _ => rejected.push(raw.to_string()),
+        }
+    }
+    Some(ImportParse { lines, rejected })
+}
+
+/// A JSON array of objects, or an export document with a `rows` array.
+pub fn parse_json(value: &Value) -> ImportParse {
+    let entries = match value {
+        Value::Array(items) => items.as_slice(),
+        Value::Object(map) => match map.get("rows").or_else(|| map.get("items")) {
+            Some(Value::Array(items)) => items.as_slice(),
+            _ => std::slice::from_ref(value),
+        },
+        _ => &[],
+    };
+
+    let mut lines = Vec::new();
+    let mut rejected = Vec::new();
+    for entry in entries {
+        let field = |keys: &[&str]| keys.iter().find_map(|key| entry.get(*key));
+        let name = field(&["commodity_name", "commodity", "name", "code", "commodity_code"])
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .unwrap_or_default();
+        let scu = field(&["scu", "quantity", "qty", "amount"]).and_then(|v| match v {
+            Value::Number(n) => n.as_f64().filter(|n| *n >= 1.0).map(|n| n.round() as u32),
+            Value::String(s) => parse_scu(s),
+            _ => None,
+        });
+        let is_hot = field(&["is_hot", "hot"]).and_then(Value::as_bool).unwrap_or(false);
+        match scu {
+            Some(scu) if !name.is_empty() => lines.push(ImportLine {
+                raw: entry.to_string(),
+                name: name.to_string(),
+                scu,
+                is_hot,
+            }),
+            _ => rejected.push(entry.to_string()),
+        }
+    }
+    ImportParse { lines, rejected }
+}
+
+/// Match every line against the commodity list.
+pub fn match_lines(lines: &[ImportLine], commodities: &[Commodity]) -> Vec<ImportMatch> {
+    lines
+        .iter()
+        .map(|line| {
+            let (commodity_id, kind) = match match_commodity(&line.name, commodities) {
+                Some((commodity, kind)) => (Some(commodity.id.clone()), Some(kind)),
+                None => (None, None),
+            };
+            ImportMatch {
+                line: line.clone(),
+                commodity_id,
+                kind,
+            }
+        })
+        .collect()
+}
+
+/// Best commodity for `query` by exact name/code, then prefix, then edit distance.
+pub fn match_commodity<'a>(
+    query: &str,
+    commodities: &'a [Commodity],
+) -> Option<(&'a Commodity, MatchKind)> {
+    let needle = normalize(query);
+    if needle.is_empty() {
+        return None;
+    }
+
+    let exact = commodities.iter().find(|c| {
+        normalize(&c.name) == needle
+            || normalize(&c.id) == needle
+            || c.code.as_deref().map(normalize).as_deref() == Some(needle.as_str())
+    });
+    if let Some(commodity) = exact {
+        return Some((commodity, MatchKind::Exact));
+    }
+
+    let mut prefixed = commodities
+        .iter()
+        .filter(|c| needle.len() >= 3 && normalize(&c.name).starts_with(&needle));
+    if let (Some(only), None) = (prefixed.next(), prefixed.next()) {
+        return Some((only, MatchKind::Fuzzy));
+    }
+
+    commodities
+        .iter()
+        .map(|c| (c, similarity(&needle, &normalize(&c.name))))
+        .filter(|(_, score)| *score >= FUZZY_THRESHOLD)
+        .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+        .map(|(c, _)| (c, MatchKind::Fuzzy))
+}
+
+/// Splits pasted text into entries at line breaks, `;` and `,`, except for a
+/// comma between digits, which belongs to a number ("1,200", "12,5").
+fn split_entries(input: &str) -> Vec<&str> {
+    let bytes = input.as_bytes();
+    let mut entries = Vec::new();
+    let mut start = 0;
+    for (index, byte) in bytes.iter().enumerate() {
+        let in_number = *byte == b','
+            && index > 0
+            && bytes[index - 1].is_ascii_digit()
+            && bytes.get(index + 1).is_some_and(u8::is_ascii_digit);
+        if matches!(byte, b'\n' | b';') || (*byte == b',' && !in_number) {
+            entries.push(&input[start..index]);
+            start = index + 1;
+        }
+    }
+    entries.push(&input[start..]);
+    entries
+}
+
+fn parse_fragment(fragment: &str) -> Option<ImportLine> {
+    let is_hot = fragment.to_ascii_lowercase().contains("(hot)");
+    let cleaned = fragment.replace("(hot)", "").replace("(HOT)", "").replace(':', " ");
+    // Unit words on their own ("96 SCU", "Laranite x 120") aren't part of the name.
+    let tokens: Vec<&str> = cleaned
+        .split_whitespace()
+        .filter(|t| !t.eq_ignore_ascii_case("scu") && !t.eq_ignore_ascii_case("x"))
+        .collect();
+
+    // Quantity is the first or last token ("120 Laranite", "Laranite x120").
+    let (scu, name_tokens) = if let Some(scu) = tokens.first().and_then(|t| parse_scu(t)) {
+        (scu, &tokens[1..])
+    } else if let Some(scu) = tokens.last().and_then(|t| parse_scu(t)) {
+        (scu, &tokens[..tokens.len() - 1])
+    } else {
+        return None;
+    };
+
+    let name = name_tokens.join(" ");
+    if name.is_empty() {
+        return None;
+    }
+    Some(ImportLine {
+        raw: fragment.to_string(),
+        name,
+        scu,
+        is_hot,
+    })
+}
+
+/// "120", "120scu", "x120", "120.0", "1,200", "1.200" → 120 or 1200.
+fn parse_scu(token: &str) -> Option<u32> {
+    let lower = token.trim().to_ascii_lowercase();
+    let digits = lower
+        .trim_start_matches('x')
+        .trim_end_matches("scu")
+        .trim_end_matches('x');
+    let value = parse_number(digits)?;
+    (value.is_finite() && value >= 1.0).then(|| value.round() as u32)
+}
+
+/// A number in English or German notation: "1,200.5", "1.200,5", "1200",
+/// "12.5", "12,5". A lone separator followed by exactly three digits groups
+/// thousands ("1.200" and "1,200" are both 1200); otherwise it's the decimal
+/// point.
+pub fn parse_number(text: &str) -> Option<f64> {
+    if text.is_empty() || !text.chars().all(|c| c.is_ascii_digit() || c == '.' || c == ',') {
+        return None;
+    }
+    let decimal = match (text.rfind('.'), text.rfind(',')) {
+        (Some(dot), Some(comma)) => Some(dot.max(comma)),
+        (Some(at), None) | (None, Some(at)) => {
+            let separator = text.as_bytes()[at];
+            let single = text.bytes().filter(|b| *b == separator).count() == 1;
+            let groups_thousands = text.len() - at - 1 == 3 && !text.starts_with('0');
+            (single && !groups_thousands).then_some(at)
+        }
+        (None, None) => None,
+    };
+    let (whole, fraction) = match decimal {
+        Some(at) => (&text[..at], &text[at + 1..]),
+        None => (text, ""),
+    };
+    // Only the other separator may group the whole part.
+    let point = decimal.map(|at| text.as_bytes()[at] as char);
+    if point.is_some_and(|point| whole.contains(point)) || fraction.contains(['.', ',']) {
+        return None;
+    }
+    let whole: String = whole.chars().filter(char::is_ascii_digit).collect();
+    if whole.is_empty() && fraction.is_empty() {
+        return None;
+    }
+    format!("{}.{fraction}", if whole.is_empty() { "0" } else { &whole }).parse().ok()
+}
+
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                cell.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' | ';' | '\t' if !quoted => cells.push(std::mem::take(&mut cell)),
+            _ => cell.push(ch),
+        }
+    }
+    cells.push(cell);
+    cells
+}
+
+fn normalize(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// 1.0 for identical strings, falling towards 0.0 with edit distance.
+fn similarity(a: &str, b: &str) -> f64 {
+    let longest = a.chars().count().max(b.chars().count());
+    if longest == 0 {
+        return 1.0;
+    }
+    1.0 - levenshtein(a, b) as f64 / longest as f64
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for (i, ca) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != *cb);
+            current[j + 1] = (previous[j + 1] + 1)
+                .min(current[j] + 1)
+                .min(previous[j] + cost);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parsed(input: &str) -> Vec<(String, u32, bool)> {
+        parse_import(input)
+            .lines
+            .into_iter()
+            .map(|line| (line.name, line.scu, line.is_hot))
+            .collect()
+    }
+
+    fn line(name: &str, scu: u32) -> (String, u32, bool) {
+        (name.to_string(), scu, false)
+    }
+
+    #[test]
+    fn reads_english_and_german_numbers() {
+        for (token, expected) in [
+            ("120", Some(120)),
+            ("1,200", Some(1200)),
+            ("1.200", Some(1200)),
+            ("1,200.4", Some(1200)),
+            ("1.200,6", Some(1201)),
+            ("12.5", Some(13)),
+            ("12,5", Some(13)),
+            ("120.0", Some(120)),
+            ("1.234.567", Some(1_234_567)),
+            ("0.5", None),
+            ("1.2.3,4.5", None),
+            ("twelve", None),
+        ] {
+            assert_eq!(parse_scu(token), expected, "{token}");
+        }
+    }
+
+    #[test]
+    fn splits_text_without_breaking_numbers() {
+        assert_eq!(
+            parsed("1,200 Laranite, 32 Quantanium\n1.200 Gold; Agricium 12,5"),
+            vec![
+                line("Laranite", 1200),
+                line("Quantanium", 32),
+                line("Gold", 1200),
+                line("Agricium", 13),
+            ]
+        );
+    }
+
+    #[test]
+    fn skips_unit_words_around_the_quantity() {
+        assert_eq!(
+            parsed("Quantanium 96 SCU\n96 scu Laranite\nGold x 12\nTitanium: 8scu\nx4 Beryl (hot)"),
+            vec![
+                line("Quantanium", 96),
+                line("Laranite", 96),
+                line("Gold", 12),
+                line("Titanium", 8),
+                ("Beryl".to_string(), 4, true),
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_lines_without_a_name_or_quantity() {
+        let parse = parse_text("Laranite\n120\n96 SCU\n0 Gold\n\n12 Agricium");
+        assert_eq!(parse.lines.len(), 1);
+        assert_eq!(parse.rejected, vec!["Laranite", "120", "96 SCU", "0 Gold"]);
+    }
+
+    #[test]
+    fn reads_csv_and_json_exports() {
+        let csv = "commodity_name;scu;is_hot\nLaranite;\"1.200\";false\nGold;12;yes\nBroken;;";
+        let parse = parse_import(csv);
+        assert_eq!(parse.lines.len(), 2);
+        assert_eq!((parse.lines[0].scu, parse.lines[1].is_hot), (1200, true));
+        assert_eq!(parse.rejected, vec!["Broken;;"]);
+
+        let json = r#"{"rows": [{"commodity": "Gold", "scu": 12.0}, {"name": "Laranite", "qty": "1,200"}, {"name": "Nothing"}]}"#;
+        let parse = parse_import(json);
+        assert_eq!(
+            parse.lines.iter().map(|l| (l.name.as_str(), l.scu)).collect::<Vec<_>>(),
+            vec![("Gold", 12), ("Laranite", 1200)]
+        );
+        assert_eq!(parse.rejected.len(), 1);
+    }
+}
@@ -0,0 +1,279 @@
+//! Interdiction candidates: quantum lanes shared by many valuable routes.
+//!
+//! Ships quantum-travel between markers — a station, or the planet or moon a
+//! city or outpost sits on — and cross-system trips pass the jump points.
+//! Every route is split into those lanes; lanes that many high-value routes
+//! share are where a snare catches the most cargo.
+
+use std::collections::HashMap;
+
+use super::entities::Terminal;
+use super::trade_route::TradeRoute;
+use crate::util::i18n::tf;
+
+/// Most cargo one hauler run carries (a C2 Hercules); caps terminals that
+/// list far more stock than any ship moves at once.
+pub const HAULER_SCU: f64 = 696.0;
+
+/// A quantum lane and the cargo expected to travel it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InterdictionPoint {
+    pub system: String,
+    /// Quantum markers at either end, alphabetically.
+    pub ends: (String, String),
+    /// Analysed routes that use this lane.
+    pub routes: usize,
+    /// Sum of [`route_flow`] over those routes.
+    pub value_flow: f64,
+    /// Share of the analysed routes' total flow passing here, 0–1.
+    pub share: f64,
+    /// Commodities carrying the most flow, at most three.
+    pub commodities: Vec<String>,
+    /// Buy and sell terminal of the busiest route flying exactly this lane,
+    /// for looking up its length; `None` for jump point lanes.
+    pub sample_terminals: Option<(i32, i32)>,
+}
+
+/// Expected cargo value a route moves: one hauler load (limited by stock,
+/// demand and [`HAULER_SCU`]) times its reports as a trip count.
+pub fn route_flow(route: &TradeRoute) -> f64 {
+    let scu = route
+        .buy_stock
+        .min(route.sell_demand)
+        .clamp(0.0, HAULER_SCU);
+    route.buy_price * scu * route.activity_score().max(1) as f64
+}
+
+/// Where ships quantum to for `terminal`: its station, otherwise the body it's on.
+pub fn quantum_marker(terminal: &Terminal) -> String {
+    terminal
+        .space_station_name
+        .clone()
+        .or_else(|| terminal.orbit_name.clone())
+        .or_else(|| terminal.planet_name.clone())
+        .unwrap_or_else(|| terminal.location_name())
+}
+
+/// Marker of the jump point in one system that leads to `other_system`.
+fn jump_point(other_system: &str) -> String {
+    tf(
+        "interdiction.jump_point",
+        &[("system", other_system.to_string())],
+    )
+}
+
+/// Lanes of `route` as (system, marker, marker); routes between two
+/// terminals at the same marker fly none.
+fn route_lanes(
+    route: &TradeRoute,
+    terminals: &HashMap<i32, &Terminal>,
+) -> Vec<(String, String, String)> {
+    let marker = |id: i32, name: &str| {
+        terminals
+            .get(&id)
+            .map(|t| quantum_marker(t))
+            .unwrap_or_else(|| name.to_string())
+    };
+    let from = marker(route.buy_terminal_id, &route.buy_terminal_name);
+    let to = marker(route.sell_terminal_id, &route.sell_terminal_name);
+    let from_system = route.buy_system.clone().unwrap_or_default();
+    let to_system = route
+        .sell_system
+        .clone()
+        .unwrap_or_else(|| from_system.clone());
+
+    let lanes = if from_system == to_system || from_system.is_empty() {
+        vec![(to_system, from, to)]
+    } else {
+        vec![
+            (from_system.clone(), from, jump_point(&to_system)),
+            (to_system, jump_point(&from_system), to),
+        ]
+    };
+    lanes.into_iter().filter(|(_, a, b)| a != b).collect()
+}
+
+#[derive(Default)]
+struct LaneTotals {
+    routes: usize,
+    flow: f64,
+    commodities: HashMap<String, f64>,
+    sample: Option<(f64, (i32, i32))>,
+}
+
+/// Splits the `top_routes` quantum-travelling routes with the highest
+/// [`route_flow`] into lanes and ranks the lanes by the flow they share,
+/// highest first.
+pub fn interdiction_points(
+    routes: &[TradeRoute],
+    terminals: &[Terminal],
+    top_routes: usize,
+) -> Vec<InterdictionPoint> {
+    let terminals: HashMap<i32, &Terminal> = terminals.iter().map(|t| (t.id, t)).collect();
+    let mut ranked: Vec<(f64, &TradeRoute)> = routes
+        .iter()
+        .map(|route| (route_flow(route), route))
+        .filter(|(flow, _)| *flow > 0.0)
+        .collect();
+    ranked.sort_by(|a, b| b.0.total_cmp(&a.0));
+    let ranked: Vec<_> = ranked
+        .into_iter()
+        .map(|(flow, route)| (flow, route, route_lanes(route, &terminals)))
+        .filter(|(_, _, lanes)| !lanes.is_empty())
+        .take(top_routes)
+        .collect();
+    let total: f64 = ranked.iter().map(|(flow, _, _)| flow).sum();
+
+    let mut lanes: HashMap<(String, String, String), LaneTotals> = HashMap::new();
+    for (flow, route, route_lanes) in ranked {
+        let direct = route_lanes.len() == 1;
+        for (system, a, b) in route_lanes {
+            let key = if a <= b {
+                (system, a, b)
+            } else {
+                (system, b, a)
+            };
+            let lane = lanes.entry(key).or_default();
+            lane.routes += 1;
+            lane.flow += flow;
+            *lane
+                .commodities
+                .entry(route.commodity_name.clone())
+                .or_default() += flow;
+            if direct && lane.sample.is_none_or(|(best, _)| flow > best) {
+                lane.sample = Some((flow, (route.buy_terminal_id, route.sell_terminal_id)));
+            }
+        }
+    }
+
+    let mut points: Vec<InterdictionPoint> = lanes
+        .into_iter()
+        .map(|((system, a, b), lane)| {
+            let mut commodities: Vec<(String, f64)> = lane.commodities.into_iter().collect();
+            commodities.sort_by(|x, y| y.1.total_cmp(&x.1).then_with(|| x.0.cmp(&y.0)));
+            InterdictionPoint {
+                system,
+                ends: (a, b),
+                routes: lane.routes,
+                value_flow: lane.flow,
+                share: if total > 0.0 { lane.flow / total } else { 0.0 },
+                commodities: commodities
+                    .into_iter()
+                    .take(3)
+                    .map(|(name, _)| name)
+                    .collect(),
+                sample_terminals: lane.sample.map(|(_, ids)| ids),
+            }
+        })
+        .collect();
+    points.sort_by(|a, b| {
+        b.value_flow
+            .total_cmp(&a.value_flow)
+            .then_with(|| a.ends.cmp(&b.ends))
+    });
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn terminal(id: i32, system: &str, station: Option<&str>, body: &str) -> Terminal {
+        Terminal {
+            id,
+            name: format!("TDD {id}"),
+            code: None,
+            is_nqa: false,
+            system: Some(system.to_string()),
+            space_station_name: station.map(str::to_string),
+            city_name: station.is_none().then(|| format!("City {id}")),
+            outpost_name: None,
+            planet_name: Some(body.to_string()),
+            orbit_name: Some(body.to_string()),
+        }
+    }
+
+    fn route(
+        commodity: &str,
+        buy: &Terminal,
+        sell: &Terminal,
+        price: f64,
+        reports: i32,
+    ) -> TradeRoute {
+        TradeRoute {
+            commodity_id: commodity.to_lowercase(),
+            commodity_name: commodity.to_string(),
+            is_illegal: false,
+            buy_terminal_id: buy.id,
+            buy_terminal_name: buy.name.clone(),
+            buy_system: buy.system.clone(),
+            buy_price: price,
+            buy_stock: 5_000.0,
+            buy_user_rows: reports,
+            buy_is_planetary: buy.is_planetary(),
+            sell_terminal_id: sell.id,
+            sell_terminal_name: sell.name.clone(),
+            sell_system: sell.system.clone(),
+            sell_price: price * 1.2,
+            sell_demand: 1_000.0,
+            sell_user_rows: 0,
+            sell_is_planetary: sell.is_planetary(),
+            sell_is_nqa: false,
+            distance_gm: None,
+            profit_per_scu: price * 0.2,
+            roi_percent: 20.0,
+        }
+    }
+
+    #[test]
+    fn ranks_lanes_shared_by_valuable_routes() {
+        let lorville = terminal(1, "Stanton", None, "Hurston");
+        let lorville_cbd = terminal(2, "Stanton", None, "Hurston");
+        let baijini = terminal(3, "Stanton", Some("Baijini Point"), "ArcCorp");
+        let ruin = terminal(4, "Pyro", Some("Ruin Station"), "Pyro I");
+        let terminals = vec![
+            lorville.clone(),
+            lorville_cbd.clone(),
+            baijini.clone(),
+            ruin.clone(),
+        ];
+        let routes = vec![
+            route("Gold", &lorville, &baijini, 100.0, 2),
+            route("Agricium", &baijini, &lorville_cbd, 50.0, 1),
+            route("Laranite", &lorville, &ruin, 30.0, 1),
+            // Same marker on both ends: no quantum travel.
+            route("Scrap", &lorville, &lorville_cbd, 1_000.0, 9),
+        ];
+        let points = interdiction_points(&routes, &terminals, 10);
+
+        let top = &points[0];
+        assert_eq!(
+            top.ends,
+            ("Baijini Point".to_string(), "Hurston".to_string())
+        );
+        assert_eq!(top.routes, 2);
+        // 696 SCU × price × reports, both directions of the lane.
+        assert_eq!(top.value_flow, 696.0 * 100.0 * 2.0 + 696.0 * 50.0);
+        assert_eq!(
+            top.commodities,
+            vec!["Gold".to_string(), "Agricium".to_string()]
+        );
+        assert_eq!(top.sample_terminals, Some((1, 3)));
+
+        // The cross-system route flies to the jump point, then on from it.
+        let jump: Vec<_> = points
+            .iter()
+            .filter(|p| p.sample_terminals.is_none())
+            .collect();
+        assert_eq!(jump.len(), 2);
+        assert!(jump
+            .iter()
+            .any(|p| p.system == "Pyro" && p.ends.1 == "Ruin Station"));
+        let total: f64 = points
+            .iter()
+            .filter(|p| p.system == "Stanton")
+            .map(|p| p.share)
+            .sum();
+        assert!((total - 1.0).abs() < 1e-9, "{total}");
+    }
+}
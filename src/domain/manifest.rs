@@ -0,0 +1,297 @@
+//! Named cargo manifests — one per ship, hangar stash, or crew member's load.
+
+use serde::{Deserialize, Serialize};
+
+use super::entities::{CargoItem, Commodity};
+
+pub const DEFAULT_MANIFEST_ID: &str = "default";
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CargoManifest {
+    pub id: String,
+    pub name: String,
+    /// Ship (or location) this load belongs to, e.g. "Hull-C".
+    #[serde(default)]
+    pub ship: Option<String>,
+    #[serde(default)]
+    pub items: Vec<CargoItem>,
+}
+
+impl CargoManifest {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: name.into(),
+            ship: None,
+            items: Vec::new(),
+        }
+    }
+
+    /// The manifest every install starts with (and old single-list saves migrate into).
+    pub fn default_manifest() -> Self {
+        Self {
+            id: DEFAULT_MANIFEST_ID.to_string(),
+            name: "My Ship".to_string(),
+            ship: None,
+            items: Vec::new(),
+        }
+    }
+
+    pub fn total_scu(&self) -> u32 {
+        self.items.iter().map(|item| item.scu).sum()
+    }
+
+    pub fn label(&self) -> String {
+        match &self.ship {
+            Some(ship) if !ship.is_empty() => format!("{} ({ship})", self.name),
+            _ => self.name.clone(),
+        }
+    }
+
+    /// Add SCU to the matching line (same commodity and hot flag) or create one.
+    pub fn absorb(&mut self, mut item: CargoItem) {
+        if let Some(existing) = self
+            .items
+            .iter_mut()
+            .find(|line| line.commodity_id == item.commodity_id && line.is_hot == item.is_hot)
+        {
+            existing.scu += item.scu;
+        } else {
+            if self.items.iter().any(|line| line.id == item.id) {
+                item.id = format!("cargo-{}", uuid::Uuid::new_v4());
+            }
+            self.items.push(item);
+        }
+    }
+}
+
+/// What [`adjust_cargo`] did to a cargo list.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CargoAdjustResult {
+    /// A new line: its item id and commodity id.
+    Added(String, String),
+    Updated(String),
+    /// The line dropped to 0 SCU or below and was removed.
+    Removed(String),
+    /// SCU was taken away from a commodity that isn't on the list.
+    Error,
+}
+
+/// Add `delta` SCU of `commodity` (negative to take some away), keeping one
+/// row per commodity and hot flag, like [`CargoManifest::absorb`].
+pub fn adjust_cargo(
+    cargo_items: &mut Vec<CargoItem>,
+    commodity: &Commodity,
+    delta: i32,
+    is_hot: bool,
+) -> CargoAdjustResult {
+    if let Some(index) = cargo_items
+        .iter()
+        .position(|item| item.commodity_id == commodity.id && item.is_hot == is_hot)
+    {
+        let new_total = cargo_items[index].scu as i64 + delta as i64;
+        if new_total <= 0 {
+            CargoAdjustResult::Removed(cargo_items.remove(index).id)
+        } else {
+            cargo_items[index].scu = new_total.min(u32::MAX as i64) as u32;
+            CargoAdjustResult::Updated(cargo_items[index].id.clone())
+        }
+    } else if delta <= 0 {
+        CargoAdjustResult::Error
+    } else {
+        let item_id = format!("cargo-{}", uuid::Uuid::new_v4());
+        cargo_items.push(CargoItem {
+            id: item_id.clone(),
+            commodity_id: commodity.id.clone(),
+            commodity_name: commodity.name.clone(),
+            scu: delta as u32,
+            is_hot,
+        });
+        CargoAdjustResult::Added(item_id, commodity.id.clone())
+    }
+}
+
+/// Move `scu` of an item (or all of it when `None`) from one manifest to
+/// another. Returns `false` if either manifest or the item doesn't exist.
+pub fn move_item(
+    manifests: &mut [CargoManifest],
+    from_id: &str,
+    item_id: &str,
+    to_id: &str,
+    scu: Option<u32>,
+) -> bool {
+    if from_id == to_id || !manifests.iter().any(|m| m.id == to_id) {
+        return false;
+    }
+    let Some(from) = manifests.iter_mut().find(|m| m.id == from_id) else {
+        return false;
+    };
+    let Some(index) = from.items.iter().position(|item| item.id == item_id) else {
+        return false;
+    };
+
+    let available = from.items[index].scu;
+    let amount = scu.unwrap_or(available).min(available);
+    if amount == 0 {
+        return false;
+    }
+    let moved = if amount == available {
+        from.items.remove(index)
+    } else {
+        from.items[index].scu -= amount;
+        let mut part = from.items[index].clone();
+        part.scu = amount;
+        part.id = format!("cargo-{}", uuid::Uuid::new_v4());
+        part
+    };
+
+    if let Some(to) = manifests.iter_mut().find(|m| m.id == to_id) {
+        to.absorb(moved);
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(id: &str, commodity: &str, scu: u32, is_hot: bool) -> CargoItem {
+        CargoItem {
+            id: id.to_string(),
+            commodity_id: commodity.to_lowercase(),
+            commodity_name: commodity.to_string(),
+            scu,
+            is_hot,
+        }
+    }
+
+    fn manifest(id: &str, items: Vec<CargoItem>) -> CargoManifest {
+        CargoManifest {
+            id: id.to_string(),
+            name: id.to_string(),
+            ship: None,
+            items,
+        }
+    }
+
+    fn scu_of(manifest: &CargoManifest) -> Vec<(String, u32, bool)> {
+        manifest
+            .items
+            .iter()
+            .map(|item| (item.commodity_name.clone(), item.scu, item.is_hot))
+            .collect()
+    }
+
+    #[test]
+    fn moving_into_the_same_commodity_merges_lines() {
+        let mut manifests = vec![
+            manifest("ship", vec![item("a", "Gold", 10, false), item("b", "Gold", 4, true)]),
+            manifest("hangar", vec![item("a", "Gold", 6, false)]),
+        ];
+
+        assert!(move_item(&mut manifests, "ship", "a", "hangar", None));
+        assert_eq!(scu_of(&manifests[0]), vec![("Gold".to_string(), 4, true)]);
+        assert_eq!(scu_of(&manifests[1]), vec![("Gold".to_string(), 16, false)]);
+
+        // Hot cargo stays on its own line.
+        assert!(move_item(&mut manifests, "ship", "b", "hangar", None));
+        assert!(manifests[0].items.is_empty());
+        assert_eq!(
+            scu_of(&manifests[1]),
+            vec![("Gold".to_string(), 16, false), ("Gold".to_string(), 4, true)]
+        );
+        assert_ne!(manifests[1].items[0].id, manifests[1].items[1].id);
+    }
+
+    #[test]
+    fn partial_moves_split_the_line() {
+        let mut manifests = vec![
+            manifest("ship", vec![item("a", "Laranite", 32, false)]),
+            manifest("hangar", vec![item("a", "Gold", 1, false)]),
+        ];
+
+        assert!(move_item(&mut manifests, "ship", "a", "hangar", Some(12)));
+        assert_eq!(scu_of(&manifests[0]), vec![("Laranite".to_string(), 20, false)]);
+        assert_eq!(manifests[0].items[0].id, "a");
+        let moved = &manifests[1].items[1];
+        assert_eq!((moved.commodity_name.as_str(), moved.scu), ("Laranite", 12));
+        assert_ne!(moved.id, "a");
+
+        // Asking for more than there is moves what is left.
+        assert!(move_item(&mut manifests, "ship", "a", "hangar", Some(500)));
+        assert!(manifests[0].items.is_empty());
+        assert_eq!(manifests[1].total_scu(), 33);
+
+        let id = manifests[1].items[0].id.clone();
+        assert!(!move_item(&mut manifests, "hangar", &id, "ship", Some(0)));
+    }
+
+    #[test]
+    fn missing_manifests_or_items_change_nothing() {
+        let mut manifests = vec![
+            manifest("ship", vec![item("a", "Gold", 10, false)]),
+            manifest("hangar", Vec::new()),
+        ];
+        let before = manifests.clone();
+
+        assert!(!move_item(&mut manifests, "ship", "a", "nowhere", None));
+        assert!(!move_item(&mut manifests, "nowhere", "a", "hangar", None));
+        assert!(!move_item(&mut manifests, "ship", "zzz", "hangar", None));
+        assert!(!move_item(&mut manifests, "ship", "a", "ship", None));
+        assert_eq!(manifests, before);
+    }
+
+    #[test]
+    fn adjusting_cargo_keeps_one_line_per_commodity() {
+        let gold = gold();
+        let mut items = Vec::new();
+
+        assert_eq!(adjust_cargo(&mut items, &gold, -1, false), CargoAdjustResult::Error);
+        let CargoAdjustResult::Added(id, commodity_id) = adjust_cargo(&mut items, &gold, 8, false)
+        else {
+            panic!("expected a new line");
+        };
+        assert_eq!(commodity_id, "gold");
+        assert_eq!(
+            adjust_cargo(&mut items, &gold, 4, false),
+            CargoAdjustResult::Updated(id.clone())
+        );
+        assert_eq!(items[0].scu, 12);
+        assert_eq!(adjust_cargo(&mut items, &gold, -20, false), CargoAdjustResult::Removed(id));
+        assert!(items.is_empty());
+    }
+
+    #[test]
+    fn hot_and_legal_cargo_stay_on_separate_lines() {
+        let gold = gold();
+        let mut items = Vec::new();
+
+        let CargoAdjustResult::Added(legal, _) = adjust_cargo(&mut items, &gold, 20, false) else {
+            panic!("expected a legal line");
+        };
+        let CargoAdjustResult::Added(hot, _) = adjust_cargo(&mut items, &gold, 10, true) else {
+            panic!("expected a separate hot line");
+        };
+        assert_eq!(
+            adjust_cargo(&mut items, &gold, 5, true),
+            CargoAdjustResult::Updated(hot.clone())
+        );
+        assert_eq!(adjust_cargo(&mut items, &gold, -20, false), CargoAdjustResult::Removed(legal));
+        assert_eq!(items.len(), 1);
+        assert!(items[0].is_hot);
+        assert_eq!(items[0].scu, 15);
+        assert_eq!(adjust_cargo(&mut items, &gold, -1, false), CargoAdjustResult::Error);
+    }
+
+    fn gold() -> Commodity {
+        Commodity {
+            id: "gold".to_string(),
+            name: "Gold".to_string(),
+            category: "Metal".to_string(),
+            code: None,
+            weight_scu: None,
+            is_raw: false,
+            is_mineral: false,
+        }
+    }
+}
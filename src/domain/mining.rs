@@ -0,0 +1,923 @@
+//! Mining: raw ore loads and the refine-or-sell-raw decision.
+//!
+//! Refining keeps the ore content of a raw load, multiplied by the method's
+//! yield and the refinery's bonus for that ore. The calculator prices the
+//! refined output with the same [`PricePoint`] data the cargo pages use and
+//! compares every refinery/method pair against selling the load raw.
+
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    time::SystemTime,
+};
+
+use serde::{Deserialize, Serialize};
+
+use super::entities::{CargoItem, Commodity, CommodityId, PricePoint, SellLocation};
+use super::evaluation::{rank_best_prices_with, RankingParams};
+use crate::util::i18n::t;
+
+/// Refined names of the mineable ores, as UEX lists them.
+pub const ORES: [&str; 24] = [
+    "Agricium",
+    "Aluminum",
+    "Beryl",
+    "Bexalite",
+    "Borase",
+    "Copper",
+    "Corundum",
+    "Diamond",
+    "Gold",
+    "Hephaestanite",
+    "Ice",
+    "Iron",
+    "Laranite",
+    "Lindinium",
+    "Quantainium",
+    "Quartz",
+    "Riccite",
+    "Savrilium",
+    "Stileron",
+    "Taranite",
+    "Tin",
+    "Titanium",
+    "Torite",
+    "Tungsten",
+];
+
+/// A raw ore load waiting to be sold or refined.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct OreEntry {
+    pub id: String,
+    /// Refined name of the ore, e.g. `Laranite`.
+    pub ore: String,
+    /// Raw SCU in the hold.
+    pub scu: f64,
+    /// Share of the raw load that is ore, 0–100 %; the rest is inert and
+    /// refines to nothing.
+    pub quality: f64,
+}
+
+impl OreEntry {
+    /// SCU of actual ore in the load.
+    pub fn ore_scu(&self) -> f64 {
+        self.scu * (self.quality.clamp(0.0, 100.0) / 100.0)
+    }
+}
+
+/// A refining process offered by every refinery.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RefineryMethod {
+    pub name: &'static str,
+    /// Share of the ore content that comes out refined, 0–1.
+    pub yield_ratio: f64,
+    /// Processing time per raw SCU.
+    pub minutes_per_scu: f64,
+    /// Refinery fee per raw SCU in aUEC.
+    pub cost_per_scu: f64,
+}
+
+/// The game doesn't publish method figures; these are community estimates
+/// of the fast/slow, cheap/expensive and low/high yield ratings.
+pub const METHODS: [RefineryMethod; 9] = [
+    RefineryMethod {
+        name: "Dinyx Solventation",
+        yield_ratio: 0.90,
+        minutes_per_scu: 6.0,
+        cost_per_scu: 20.0,
+    },
+    RefineryMethod {
+        name: "Ferron Exchange",
+        yield_ratio: 0.90,
+        minutes_per_scu: 4.5,
+        cost_per_scu: 35.0,
+    },
+    RefineryMethod {
+        name: "Pyrometric Chromalysis",
+        yield_ratio: 0.90,
+        minutes_per_scu: 3.0,
+        cost_per_scu: 55.0,
+    },
+    RefineryMethod {
+        name: "Thermonatic Deposition",
+        yield_ratio: 0.80,
+        minutes_per_scu: 6.0,
+        cost_per_scu: 15.0,
+    },
+    RefineryMethod {
+        name: "Electrostarolysis",
+        yield_ratio: 0.80,
+        minutes_per_scu: 3.0,
+        cost_per_scu: 30.0,
+    },
+    RefineryMethod {
+        name: "Gaskin Process",
+        yield_ratio: 0.80,
+        minutes_per_scu: 1.5,
+        cost_per_scu: 50.0,
+    },
+    RefineryMethod {
+        name: "Kazen Winnowing",
+        yield_ratio: 0.65,
+        minutes_per_scu: 4.5,
+        cost_per_scu: 10.0,
+    },
+    RefineryMethod {
+        name: "Cormack Method",
+        yield_ratio: 0.65,
+        minutes_per_scu: 3.0,
+        cost_per_scu: 20.0,
+    },
+    RefineryMethod {
+        name: "XCR Reaction",
+        yield_ratio: 0.65,
+        minutes_per_scu: 1.5,
+        cost_per_scu: 30.0,
+    },
+];
+
+/// A station with a refinery deck.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Refinery {
+    pub name: &'static str,
+    pub system: &'static str,
+    /// Yield bonus in percentage points per ore; ores not listed get 0.
+    pub bonuses: &'static [(&'static str, f64)],
+}
+
+impl Refinery {
+    pub fn bonus(&self, ore: &str) -> f64 {
+        self.bonuses
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(ore))
+            .map_or(0.0, |(_, bonus)| *bonus)
+    }
+}
+
+/// Community estimates of the per-ore yield bonuses, like [`METHODS`]; the
+/// game publishes no table and they shift between patches. The Refinery page
+/// labels them as estimates.
+pub const REFINERIES: [Refinery; 12] = [
+    Refinery {
+        name: "ARC-L1 Wide Forest Station",
+        system: "Stanton",
+        bonuses: &[("Quantainium", 3.0), ("Laranite", 2.0), ("Agricium", -2.0)],
+    },
+    Refinery {
+        name: "ARC-L2 Lively Pathway Station",
+        system: "Stanton",
+        bonuses: &[("Bexalite", 2.0), ("Taranite", 1.0), ("Gold", -3.0)],
+    },
+    Refinery {
+        name: "ARC-L4 Faint Glen Station",
+        system: "Stanton",
+        bonuses: &[
+            ("Hephaestanite", 3.0),
+            ("Titanium", 2.0),
+            ("Quantainium", -1.0),
+        ],
+    },
+    Refinery {
+        name: "CRU-L1 Ambitious Dream Station",
+        system: "Stanton",
+        bonuses: &[("Gold", 4.0), ("Copper", 2.0), ("Laranite", -2.0)],
+    },
+    Refinery {
+        name: "HUR-L1 Green Glade Station",
+        system: "Stanton",
+        bonuses: &[("Agricium", 3.0), ("Tungsten", 2.0), ("Bexalite", -2.0)],
+    },
+    Refinery {
+        name: "HUR-L2 Faithful Dream Station",
+        system: "Stanton",
+        bonuses: &[("Laranite", 4.0), ("Borase", 2.0), ("Taranite", -2.0)],
+    },
+    Refinery {
+        name: "MIC-L1 Shallow Frontier Station",
+        system: "Stanton",
+        bonuses: &[("Taranite", 3.0), ("Beryl", 2.0), ("Hephaestanite", -1.0)],
+    },
+    Refinery {
+        name: "MIC-L2 Long Forest Station",
+        system: "Stanton",
+        bonuses: &[("Quantainium", 2.0), ("Diamond", 2.0), ("Copper", -2.0)],
+    },
+    Refinery {
+        name: "MIC-L5 Modern Icarus Station",
+        system: "Stanton",
+        bonuses: &[("Titanium", 3.0), ("Aluminum", 2.0), ("Gold", -1.0)],
+    },
+    Refinery {
+        name: "Checkmate Station",
+        system: "Pyro",
+        bonuses: &[("Stileron", 3.0), ("Savrilium", 2.0), ("Quantainium", -3.0)],
+    },
+    Refinery {
+        name: "Orbituary",
+        system: "Pyro",
+        bonuses: &[("Riccite", 3.0), ("Lindinium", 2.0), ("Laranite", -2.0)],
+    },
+    Refinery {
+        name: "Ruin Station",
+        system: "Pyro",
+        bonuses: &[("Torite", 3.0), ("Quantainium", 1.0), ("Bexalite", -2.0)],
+    },
+];
+
+/// Refined SCU a load yields with `method` at `refinery`.
+pub fn refined_scu(entry: &OreEntry, method: &RefineryMethod, refinery: &Refinery) -> f64 {
+    let ratio = (method.yield_ratio + refinery.bonus(&entry.ore) / 100.0).clamp(0.0, 1.0);
+    entry.ore_scu() * ratio
+}
+
+/// The refined commodity for an ore name.
+pub fn refined_commodity<'a>(commodities: &'a [Commodity], ore: &str) -> Option<&'a Commodity> {
+    commodities
+        .iter()
+        .find(|c| c.name.eq_ignore_ascii_case(ore))
+}
+
+/// The raw commodity for an ore name; UEX suffixes those with `(Ore)` or `(Raw)`.
+pub fn raw_commodity<'a>(commodities: &'a [Commodity], ore: &str) -> Option<&'a Commodity> {
+    let candidates = [format!("{ore} (Ore)"), format!("{ore} (Raw)")];
+    commodities.iter().find(|c| {
+        candidates
+            .iter()
+            .any(|name| c.name.eq_ignore_ascii_case(name))
+    })
+}
+
+/// Highest sell price, preferring terminals in `system` when any report one.
+pub fn best_sell_price(points: &[PricePoint], system: Option<&str>) -> Option<f64> {
+    let best = |in_system: bool| {
+        points
+            .iter()
+            .filter(|p| !in_system || p.system.as_deref() == system)
+            .filter_map(|p| p.price_sell.filter(|price| *price > 0.0))
+            .max_by(f64::total_cmp)
+    };
+    system.and_then(|_| best(true)).or_else(|| best(false))
+}
+
+/// Refining the whole hold at one refinery with one method.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RefineOption {
+    pub refinery: Refinery,
+    pub method: RefineryMethod,
+    pub refined_scu: f64,
+    /// Refined output at the best sell price in the refinery's system.
+    pub gross_value: f64,
+    pub cost: f64,
+    pub net_value: f64,
+    pub duration_secs: u64,
+    /// `net_value` minus the raw sale value.
+    pub gain_vs_raw: f64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MiningComparison {
+    /// Selling every load raw at its best price.
+    pub raw_value: f64,
+    /// Ores without a raw price; they count as 0 in `raw_value`.
+    pub raw_unpriced: Vec<String>,
+    /// Ores without a refined price; they count as 0 in every option.
+    pub refined_unpriced: Vec<String>,
+    /// Best net value first.
+    pub options: Vec<RefineOption>,
+}
+
+impl MiningComparison {
+    pub fn best(&self) -> Option<&RefineOption> {
+        self.options.first()
+    }
+}
+
+/// Compares selling `entries` raw with refining them at every refinery using
+/// every method.
+pub fn compare_refining(
+    entries: &[OreEntry],
+    commodities: &[Commodity],
+    prices: &HashMap<CommodityId, Vec<PricePoint>>,
+) -> MiningComparison {
+    let points_for = |commodity: Option<&Commodity>| -> &[PricePoint] {
+        commodity
+            .and_then(|c| prices.get(&c.id))
+            .map(Vec::as_slice)
+            .unwrap_or(&[])
+    };
+
+    let mut comparison = MiningComparison::default();
+    for entry in entries {
+        let raw_points = points_for(raw_commodity(commodities, &entry.ore));
+        match best_sell_price(raw_points, None) {
+            Some(price) => comparison.raw_value += price * entry.scu,
+            None => push_unique(&mut comparison.raw_unpriced, &entry.ore),
+        }
+        if best_sell_price(points_for(refined_commodity(commodities, &entry.ore)), None).is_none() {
+            push_unique(&mut comparison.refined_unpriced, &entry.ore);
+        }
+    }
+
+    let raw_scu: f64 = entries.iter().map(|e| e.scu).sum();
+    for refinery in REFINERIES {
+        for method in METHODS {
+            let mut refined = 0.0;
+            let mut gross = 0.0;
+            for entry in entries {
+                let scu = refined_scu(entry, &method, &refinery);
+                let points = points_for(refined_commodity(commodities, &entry.ore));
+                refined += scu;
+                gross += best_sell_price(points, Some(refinery.system)).unwrap_or(0.0) * scu;
+            }
+            let cost = method.cost_per_scu * raw_scu;
+            let net = gross - cost;
+            comparison.options.push(RefineOption {
+                refinery,
+                method,
+                refined_scu: refined,
+                gross_value: gross,
+                cost,
+                net_value: net,
+                duration_secs: (method.minutes_per_scu * raw_scu * 60.0).round() as u64,
+                gain_vs_raw: net - comparison.raw_value,
+            });
+        }
+    }
+    comparison.options.sort_by(|a, b| {
+        b.net_value
+            .total_cmp(&a.net_value)
+            .then(a.duration_secs.cmp(&b.duration_secs))
+    });
+    comparison
+}
+
+fn push_unique(list: &mut Vec<String>, ore: &str) {
+    if !list.iter().any(|existing| existing == ore) {
+        list.push(ore.to_string());
+    }
+}
+
+/// Whether UEX lists `commodity` as something mined: a mineral or metal, or
+/// the raw form of one.
+pub fn is_mineable(commodity: &Commodity) -> bool {
+    commodity.is_raw
+        || commodity.is_mineral
+        || matches!(commodity.category.as_str(), "Mineral" | "Metal")
+}
+
+/// Ore a mineable commodity belongs to: its name without the raw suffix.
+pub fn ore_name(commodity: &Commodity) -> &str {
+    let name = commodity.name.as_str();
+    name.strip_suffix(" (Ore)")
+        .or_else(|| name.strip_suffix(" (Raw)"))
+        .unwrap_or(name)
+}
+
+/// Best terminal to sell one form of an ore at within a system.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OreQuote {
+    pub commodity_id: CommodityId,
+    pub terminal: String,
+    pub price: f64,
+    /// Price after the ranking penalties, which picked this terminal.
+    pub adjusted_price: f64,
+    pub notes: Option<String>,
+    pub updated_at: SystemTime,
+}
+
+/// One ore in one system on the ore price board.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OreBoardRow {
+    pub ore: String,
+    pub system: String,
+    pub refined: Option<OreQuote>,
+    pub raw: Option<OreQuote>,
+    /// One of the known refineries is in `system`.
+    pub has_refinery: bool,
+}
+
+impl OreBoardRow {
+    /// What a raw SCU of this ore fetches here: the raw price, or the refined
+    /// price at the best method's yield when that pays more.
+    pub fn value_per_scu(&self) -> Option<f64> {
+        let best_yield = METHODS.iter().map(|m| m.yield_ratio).fold(0.0, f64::max);
+        let refined = self.refined.as_ref().map(|q| q.price * best_yield);
+        let raw = self.raw.as_ref().map(|q| q.price);
+        match (refined, raw) {
+            (Some(refined), Some(raw)) => Some(refined.max(raw)),
+            (refined, raw) => refined.or(raw),
+        }
+    }
+
+    /// Refined price as a multiple of the raw price.
+    pub fn refined_ratio(&self) -> Option<f64> {
+        let raw = self.raw.as_ref()?.price;
+        (raw > 0.0).then(|| self.refined.as_ref().map(|q| q.price / raw))?
+    }
+
+    /// The older of the two quotes; a row is only as fresh as its stalest price.
+    pub fn updated_at(&self) -> Option<SystemTime> {
+        [&self.refined, &self.raw]
+            .into_iter()
+            .flatten()
+            .map(|q| q.updated_at)
+            .min()
+    }
+}
+
+/// Best refined and raw sell terminal of every mineable ore in every system
+/// with prices, scored by [`rank_best_prices_with`]. Rows come per ore, then
+/// per system, alphabetically.
+pub fn ore_board(
+    commodities: &[Commodity],
+    prices: &HashMap<CommodityId, Vec<PricePoint>>,
+    locations: &HashMap<String, SellLocation>,
+    params: &RankingParams,
+) -> Vec<OreBoardRow> {
+    let mut ores: BTreeMap<&str, (Option<&Commodity>, Option<&Commodity>)> = BTreeMap::new();
+    for commodity in commodities.iter().filter(|c| is_mineable(c)) {
+        let ore = ore_name(commodity);
+        let forms = ores.entry(ore).or_default();
+        if ore == commodity.name {
+            forms.0 = Some(commodity);
+        } else {
+            forms.1 = Some(commodity);
+        }
+    }
+
+    let points_for = |commodity: Option<&Commodity>| -> &[PricePoint] {
+        commodity
+            .and_then(|c| prices.get(&c.id))
+            .map(Vec::as_slice)
+            .unwrap_or(&[])
+    };
+
+    let mut rows = Vec::new();
+    for (ore, (refined, raw)) in ores {
+        let systems: BTreeSet<&str> = points_for(refined)
+            .iter()
+            .chain(points_for(raw))
+            .filter_map(|p| p.system.as_deref())
+            .collect();
+        for system in systems {
+            let quote = |commodity: Option<&Commodity>| {
+                best_quote(commodity?, points_for(commodity), system, locations, params)
+            };
+            let (refined, raw) = (quote(refined), quote(raw));
+            if refined.is_none() && raw.is_none() {
+                continue;
+            }
+            rows.push(OreBoardRow {
+                ore: ore.to_string(),
+                system: system.to_string(),
+                refined,
+                raw,
+                has_refinery: REFINERIES.iter().any(|r| r.system == system),
+            });
+        }
+    }
+    rows
+}
+
+/// Sorting options for the ore price board.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OreBoardSort {
+    #[default]
+    ValuePerScu,
+    RefinedPrice,
+    RawPrice,
+    RefinedRatio,
+    Freshness,
+    Ore,
+}
+
+impl OreBoardSort {
+    pub const ALL: [OreBoardSort; 6] = [
+        Self::ValuePerScu,
+        Self::RefinedPrice,
+        Self::RawPrice,
+        Self::RefinedRatio,
+        Self::Freshness,
+        Self::Ore,
+    ];
+
+    pub fn label(&self) -> String {
+        t(match self {
+            Self::ValuePerScu => "ores.sort.value",
+            Self::RefinedPrice => "ores.sort.refined",
+            Self::RawPrice => "ores.sort.raw",
+            Self::RefinedRatio => "ores.sort.ratio",
+            Self::Freshness => "ores.sort.freshness",
+            Self::Ore => "ores.sort.ore",
+        })
+    }
+}
+
+/// Filter options for the ore price board.
+#[derive(Clone, Debug, Default)]
+pub struct OreBoardFilter {
+    pub system: Option<String>,
+    pub only_refinery_systems: bool,
+    /// Case-insensitive part of the ore name.
+    pub ore: String,
+}
+
+impl OreBoardFilter {
+    pub fn matches(&self, row: &OreBoardRow) -> bool {
+        let query = self.ore.trim().to_lowercase();
+        self.system
+            .as_ref()
+            .is_none_or(|system| *system == row.system)
+            && (!self.only_refinery_systems || row.has_refinery)
+            && (query.is_empty() || row.ore.to_lowercase().contains(&query))
+    }
+}
+
+/// Best first: highest value or price, newest data, or A–Z by ore. Rows
+/// without the sorted value go last.
+pub fn sort_ore_board(rows: &mut [OreBoardRow], sort: OreBoardSort) {
+    let key = |row: &OreBoardRow| -> Option<f64> {
+        match sort {
+            OreBoardSort::ValuePerScu => row.value_per_scu(),
+            OreBoardSort::RefinedPrice => row.refined.as_ref().map(|q| q.price),
+            OreBoardSort::RawPrice => row.raw.as_ref().map(|q| q.price),
+            OreBoardSort::RefinedRatio => row.refined_ratio(),
+            OreBoardSort::Freshness | OreBoardSort::Ore => None,
+        }
+    };
+    rows.sort_by(|a, b| {
+        let order = match sort {
+            OreBoardSort::Ore => a.ore.cmp(&b.ore),
+            OreBoardSort::Freshness => b.updated_at().cmp(&a.updated_at()),
+            _ => match (key(a), key(b)) {
+                (Some(a), Some(b)) => b.total_cmp(&a),
+                (a, b) => b.is_some().cmp(&a.is_some()),
+            },
+        };
+        order
+            .then_with(|| a.ore.cmp(&b.ore))
+            .then_with(|| a.system.cmp(&b.system))
+    });
+}
+
+/// Top-ranked terminal for `commodity` among its points in `system`. The
+/// system counts as home, so only armistice and hotspot penalties apply.
+fn best_quote(
+    commodity: &Commodity,
+    points: &[PricePoint],
+    system: &str,
+    locations: &HashMap<String, SellLocation>,
+    params: &RankingParams,
+) -> Option<OreQuote> {
+    let in_system: Vec<PricePoint> = points
+        .iter()
+        .filter(|p| p.system.as_deref() == Some(system))
+        .cloned()
+        .collect();
+    let item = CargoItem {
+        id: commodity.id.clone(),
+        commodity_id: commodity.id.clone(),
+        commodity_name: commodity.name.clone(),
+        scu: 1,
+        is_hot: false,
+    };
+    let params = RankingParams {
+        home_system: system.to_string(),
+        suggestions_per_item: 1,
+        ..params.clone()
+    };
+    let prices = HashMap::from([(commodity.id.clone(), in_system)]);
+    let top = rank_best_prices_with(&[item], &prices, locations, &params).best_overall?;
+    let price = top.sell_price.filter(|price| *price > 0.0)?;
+    let point = prices[&commodity.id].iter().find(|p| {
+        p.terminal_id.map(|id| id.to_string()) == top.location_id
+            || top.location_name.starts_with(&p.terminal_name)
+    })?;
+    Some(OreQuote {
+        commodity_id: commodity.id.clone(),
+        terminal: point.terminal_name.clone(),
+        price,
+        adjusted_price: top.adjusted_price,
+        notes: top.notes_label(),
+        updated_at: point.updated_at,
+    })
+}
+
+/// Refined output of a job, per ore.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct JobOutput {
+    pub ore: String,
+    pub scu: f64,
+}
+
+/// Ore handed to a refinery, waiting to be collected.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RefineryJob {
+    pub id: String,
+    pub refinery: String,
+    pub system: String,
+    pub method: String,
+    pub inputs: Vec<OreEntry>,
+    pub outputs: Vec<JobOutput>,
+    /// Unix seconds.
+    pub started_at: u64,
+    pub duration_secs: u64,
+    /// Set once the "job done" notification went out, so restarts don't repeat it.
+    #[serde(default)]
+    pub notified: bool,
+}
+
+impl RefineryJob {
+    /// A job refining `inputs` with the estimated output; `duration_secs`
+    /// overrides the estimate with the time the refinery kiosk shows.
+    pub fn start(
+        id: String,
+        inputs: Vec<OreEntry>,
+        refinery: &Refinery,
+        method: &RefineryMethod,
+        started_at: u64,
+        duration_secs: Option<u64>,
+    ) -> Self {
+        let raw_scu: f64 = inputs.iter().map(|e| e.scu).sum();
+        let mut outputs: Vec<JobOutput> = Vec::new();
+        for entry in &inputs {
+            let scu = refined_scu(entry, method, refinery);
+            match outputs.iter_mut().find(|o| o.ore == entry.ore) {
+                Some(output) => output.scu += scu,
+                None => outputs.push(JobOutput {
+                    ore: entry.ore.clone(),
+                    scu,
+                }),
+            }
+        }
+        RefineryJob {
+            id,
+            refinery: refinery.name.to_string(),
+            system: refinery.system.to_string(),
+            method: method.name.to_string(),
+            inputs,
+            outputs,
+            started_at,
+            duration_secs: duration_secs
+                .unwrap_or_else(|| (method.minutes_per_scu * raw_scu * 60.0).round() as u64),
+            notified: false,
+        }
+    }
+
+    pub fn finishes_at(&self) -> u64 {
+        self.started_at + self.duration_secs
+    }
+
+    pub fn remaining_secs(&self, now: u64) -> u64 {
+        self.finishes_at().saturating_sub(now)
+    }
+
+    pub fn is_done(&self, now: u64) -> bool {
+        now >= self.finishes_at()
+    }
+
+    /// Takes the refined output off the job as cargo lines, rounded to whole
+    /// SCU. Outputs that round to 0 SCU are dropped and returned by name;
+    /// ores without a matching commodity (e.g. before the commodity list has
+    /// loaded) stay on the job so they can still be collected later.
+    pub fn take_cargo(&mut self, commodities: &[Commodity]) -> (Vec<CargoItem>, Vec<String>) {
+        let mut items = Vec::new();
+        let mut dropped = Vec::new();
+        self.outputs.retain(|output| {
+            let scu = output.scu.round();
+            if scu < 1.0 {
+                dropped.push(output.ore.clone());
+                return false;
+            }
+            let Some(commodity) = refined_commodity(commodities, &output.ore) else {
+                return true;
+            };
+            items.push(CargoItem {
+                id: format!("cargo-{}", uuid::Uuid::new_v4()),
+                commodity_id: commodity.id.clone(),
+                commodity_name: commodity.name.clone(),
+                scu: scu as u32,
+                is_hot: false,
+            });
+            false
+        });
+        (items, dropped)
+    }
+
+    /// Everything refined has been taken as cargo.
+    pub fn is_collected(&self) -> bool {
+        self.outputs.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::SystemTime;
+
+    use super::*;
+
+    fn commodity(id: &str, name: &str) -> Commodity {
+        Commodity {
+            id: id.to_string(),
+            name: name.to_string(),
+            category: "Mineral".to_string(),
+            code: None,
+            weight_scu: None,
+            is_raw: name.ends_with("(Ore)"),
+            is_mineral: true,
+        }
+    }
+
+    fn point(system: &str, sell: f64) -> PricePoint {
+        PricePoint {
+            terminal_id: None,
+            terminal_name: format!("{system} terminal"),
+            system: Some(system.to_string()),
+            terminal_code: None,
+            price_sell_min: None,
+            price_sell: Some(sell),
+            price_sell_max: None,
+            price_buy_max: None,
+            price_buy: None,
+            price_buy_min: None,
+            price_average: None,
+            container_sizes: Vec::new(),
+            scu_buy: None,
+            scu_sell_stock: None,
+            status_sell: None,
+            status_buy: None,
+            city_name: None,
+            outpost_name: None,
+            space_station_name: None,
+            volatility_sell: None,
+            buy_user_rows: None,
+            sell_user_rows: None,
+            updated_at: SystemTime::UNIX_EPOCH,
+        }
+    }
+
+    fn laranite(scu: f64, quality: f64) -> OreEntry {
+        OreEntry {
+            id: "ore-1".into(),
+            ore: "Laranite".into(),
+            scu,
+            quality,
+        }
+    }
+
+    #[test]
+    fn yield_combines_quality_method_and_refinery_bonus() {
+        let dinyx = METHODS[0];
+        let hur_l2 = REFINERIES
+            .iter()
+            .find(|r| r.name.starts_with("HUR-L2"))
+            .unwrap();
+        // 100 SCU at 50 % ore, 90 % yield + 4 points bonus.
+        let scu = refined_scu(&laranite(100.0, 50.0), &dinyx, hur_l2);
+        assert!((scu - 47.0).abs() < 1e-9, "{scu}");
+    }
+
+    #[test]
+    fn compares_raw_sale_with_every_refinery_and_method() {
+        let commodities = vec![commodity("1", "Laranite"), commodity("2", "Laranite (Ore)")];
+        let prices = HashMap::from([
+            (
+                "1".to_string(),
+                vec![point("Stanton", 3_000.0), point("Pyro", 2_000.0)],
+            ),
+            ("2".to_string(), vec![point("Stanton", 1_000.0)]),
+        ]);
+        let result = compare_refining(&[laranite(100.0, 50.0)], &commodities, &prices);
+
+        assert_eq!(result.raw_value, 100_000.0);
+        assert!(result.raw_unpriced.is_empty() && result.refined_unpriced.is_empty());
+        assert_eq!(result.options.len(), REFINERIES.len() * METHODS.len());
+
+        let best = result.best().unwrap();
+        assert_eq!(best.refinery.system, "Stanton");
+        assert!(result
+            .options
+            .windows(2)
+            .all(|w| w[0].net_value >= w[1].net_value));
+        assert_eq!(best.gain_vs_raw, best.net_value - 100_000.0);
+
+        let pyro = result
+            .options
+            .iter()
+            .find(|o| o.refinery.system == "Pyro")
+            .unwrap();
+        assert!(pyro.gross_value / pyro.refined_scu <= 2_000.0 + 1e-9);
+    }
+
+    #[test]
+    fn jobs_count_down_and_turn_into_cargo() {
+        let method = METHODS[0];
+        let refinery = REFINERIES[5];
+        let inputs = vec![laranite(60.0, 50.0), laranite(40.0, 50.0)];
+        let job = RefineryJob::start("job-1".into(), inputs, &refinery, &method, 1_000, None);
+
+        assert_eq!(job.outputs.len(), 1);
+        assert!((job.outputs[0].scu - 47.0).abs() < 1e-9);
+        assert_eq!(job.duration_secs, 36_000);
+        assert_eq!(job.remaining_secs(1_000 + 600), 35_400);
+        assert!(!job.is_done(36_999) && job.is_done(37_000));
+
+        let mut pending = job.clone();
+        let (items, dropped) = pending.take_cargo(&[]);
+        assert!(items.is_empty() && dropped.is_empty());
+        assert!(
+            !pending.is_collected(),
+            "unmatched output must stay on the job"
+        );
+
+        let (items, dropped) = pending.take_cargo(&[commodity("1", "Laranite")]);
+        assert_eq!((items[0].commodity_id.as_str(), items[0].scu), ("1", 47));
+        assert!(dropped.is_empty());
+        assert!(pending.is_collected());
+
+        let mut partial = job.clone();
+        partial.outputs.push(JobOutput {
+            ore: "Gold".into(),
+            scu: 0.3,
+        });
+        partial.outputs.push(JobOutput {
+            ore: "Bexalite".into(),
+            scu: 5.0,
+        });
+        let (items, dropped) = partial.take_cargo(&[commodity("1", "Laranite")]);
+        assert_eq!(items.len(), 1);
+        assert_eq!(dropped, vec!["Gold".to_string()]);
+        let left: Vec<_> = partial.outputs.iter().map(|o| o.ore.as_str()).collect();
+        assert_eq!(left, vec!["Bexalite"]);
+
+        let kiosk = RefineryJob::start("job-2".into(), vec![], &refinery, &method, 0, Some(90));
+        assert_eq!(kiosk.finishes_at(), 90);
+    }
+
+    #[test]
+    fn reports_ores_without_prices() {
+        let commodities = vec![commodity("1", "Laranite")];
+        let result = compare_refining(&[laranite(10.0, 100.0)], &commodities, &HashMap::new());
+        assert_eq!(result.raw_unpriced, vec!["Laranite".to_string()]);
+        assert_eq!(result.refined_unpriced, vec!["Laranite".to_string()]);
+        assert_eq!(result.raw_value, 0.0);
+    }
+
+    #[test]
+    fn ore_board_quotes_best_terminal_per_system() {
+        let mut scrap = commodity("9", "Recycled Material Composite");
+        (scrap.category, scrap.is_mineral) = ("Waste".to_string(), false);
+        let commodities = vec![
+            commodity("1", "Laranite"),
+            commodity("2", "Laranite (Ore)"),
+            scrap,
+        ];
+        let grim_hex = PricePoint {
+            terminal_name: "Grim Hex".into(),
+            ..point("Stanton", 3_020.0)
+        };
+        let prices = HashMap::from([
+            ("1".to_string(), vec![point("Stanton", 3_000.0), grim_hex]),
+            (
+                "2".to_string(),
+                vec![point("Stanton", 1_000.0), point("Nyx", 1_200.0)],
+            ),
+            ("9".to_string(), vec![point("Stanton", 5.0)]),
+        ]);
+        let rows = ore_board(
+            &commodities,
+            &prices,
+            &HashMap::new(),
+            &RankingParams::default(),
+        );
+
+        let keys: Vec<_> = rows
+            .iter()
+            .map(|r| (r.ore.as_str(), r.system.as_str()))
+            .collect();
+        assert_eq!(keys, vec![("Laranite", "Nyx"), ("Laranite", "Stanton")]);
+
+        let nyx = &rows[0];
+        assert!(nyx.refined.is_none() && !nyx.has_refinery);
+        assert_eq!(nyx.value_per_scu(), Some(1_200.0));
+
+        // The hotspot penalty outweighs Grim Hex's slightly higher price.
+        let stanton = &rows[1];
+        let refined = stanton.refined.as_ref().unwrap();
+        assert_eq!(
+            (refined.terminal.as_str(), refined.price),
+            ("Stanton terminal", 3_000.0)
+        );
+        assert!(stanton.has_refinery);
+        assert_eq!(stanton.refined_ratio(), Some(3.0));
+        assert_eq!(stanton.updated_at(), Some(SystemTime::UNIX_EPOCH));
+
+        let mut sorted = rows.clone();
+        sort_ore_board(&mut sorted, OreBoardSort::RefinedPrice);
+        assert_eq!(sorted[0].system, "Stanton");
+        let filter = OreBoardFilter {
+            only_refinery_systems: true,
+            ..Default::default()
+        };
+        assert_eq!(rows.iter().filter(|r| filter.matches(r)).count(), 1);
+    }
+}
@@ -1,25 +1,83 @@
 //! Domain logic for cargo valuation lives here.
 
 pub mod app_state;
+pub mod crew_session;
 pub mod entities;
 pub mod evaluation;
+pub mod fence;
+pub mod game_log;
+pub mod history;
+pub mod import;
+pub mod interdiction;
+pub mod manifest;
+pub mod mining;
+pub mod report;
+pub mod sell_plan;
 pub mod trade_route;
+pub mod traffic;
+pub mod watchlist;
 
 #[allow(unused_imports)]
-pub use app_state::{AppState, CacheResource, CacheTimestamps, Profile};
+pub use app_state::{AppState, CacheResource, CacheTimestamps, DetectedPosition, Profile};
+#[allow(unused_imports)]
+pub use crew_session::{diff as session_diff, SessionOp, SessionSnapshot, HOST_MEMBER_ID};
 #[allow(unused_imports)]
 pub use entities::{
-    BestPrice, CargoEvaluation, CargoItem, Commodity, CommodityId, CrewMember, Location, PricePoint,
-    ProfitabilityParams, SellLocation, Terminal,
+    extract_locations, BestPrice, CargoEvaluation, CargoItem, Commodity, CommodityId, CrewMember,
+    Location, PricePoint, ProfitabilityParams, SellLocation, Terminal,
 };
 #[allow(unused_imports)]
 pub use evaluation::{
     evaluate_cargo_items, evaluate_item, price_summary, profitability_indicator, rank_best_prices,
-    BestPriceEntry, BestPriceSuggestion, BestPriceSummary, EvaluationSummary, ProfitIndicator,
-    ProfitIndicatorStatus,
+    rank_best_prices_with, BestPriceEntry, BestPriceSuggestion, BestPriceSummary, EvaluationSummary,
+    PriceNote, ProfitIndicator, ProfitIndicatorStatus, RankingParams,
+};
+#[allow(unused_imports)]
+pub use fence::{fence_outlets, fence_quotes, FenceOffer, FenceOutlet, FenceQuote, OutletItem};
+#[allow(unused_imports)]
+pub use game_log::{
+    parse_line, resolve_location, trade_patterns_for, LogEvent, LogTrade, PositionTracker,
+    TradeTracker,
+};
+#[allow(unused_imports)]
+pub use history::{
+    profit_timeline, summarize, HistorySummary, TradeHistory, TradeRecord, TradeSide,
+};
+#[allow(unused_imports)]
+pub use import::{
+    match_commodity, match_lines, parse_import, parse_number, ImportLine, ImportMatch, ImportParse,
+    MatchKind,
+};
+#[allow(unused_imports)]
+pub use interdiction::{interdiction_points, quantum_marker, route_flow, InterdictionPoint};
+#[allow(unused_imports)]
+pub use manifest::{adjust_cargo, move_item, CargoAdjustResult, CargoManifest, DEFAULT_MANIFEST_ID};
+#[allow(unused_imports)]
+pub use mining::{
+    best_sell_price, compare_refining, is_mineable, ore_board, ore_name, raw_commodity,
+    refined_commodity, refined_scu, sort_ore_board, JobOutput, MiningComparison, OreBoardFilter,
+    OreBoardRow, OreBoardSort, OreEntry, OreQuote, RefineOption, Refinery, RefineryJob,
+    RefineryMethod, METHODS, ORES, REFINERIES,
+};
+#[allow(unused_imports)]
+pub use report::{
+    presets as report_presets, render_report, report_scope, ReportScope, ReportSource,
+    ReportTemplate, TemplateError,
+};
+#[allow(unused_imports)]
+pub use sell_plan::{
+    add_distances_to_plan, calculate_best_value_plan, calculate_one_stop_plan,
+    sort_by_nearest_neighbor, SellItem, SellPlan, SellStop,
 };
 #[allow(unused_imports)]
 pub use trade_route::{
     calculate_routes_for_commodity, sort_routes, TradeRoute, TradeRouteFilter, TradeRouteSort,
     TradeRouteWithQuantity,
 };
+#[allow(unused_imports)]
+pub use traffic::{
+    traffic_board, LaneTraffic, SystemTraffic, TerminalTraffic, TrafficBoard, TrafficHistory,
+    TrafficSnapshot, Trend,
+};
+#[allow(unused_imports)]
+pub use watchlist::{WatchAlerts, WatchDirection, WatchHit, WatchRule};
@@ -0,0 +1,575 @@
+//! Text reports rendered from user-editable templates.
+//!
+//! Templates use a small Mustache-like syntax:
+//!
+//! - `{name}` inserts a value; unknown names are left as typed.
+//! - `{#name}…{/name}` repeats the block for every row of a list, or shows it
+//!   once when a value is non-empty. Inside, the row's own names shadow the
+//!   outer ones.
+//! - `{^name}…{/name}` shows the block when the list or value is empty.
+//! - `{{` and `}}` produce literal braces.
+//!
+//! A section tag alone on its line takes the line with it, so multi-line
+//! templates don't leave blank lines behind.
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::evaluation::{BestPriceSummary, EvaluationSummary};
+use super::history::{summarize, TradeHistory, TradeSide};
+use super::manifest::CargoManifest;
+use super::sell_plan::SellPlan;
+use crate::util::format;
+
+/// Id prefix of the built-in templates, which can be copied but not edited.
+pub const PRESET_PREFIX: &str = "preset:";
+
+/// Trades listed under `{#trades}`, newest first.
+const MAX_TRADES: usize = 20;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ReportTemplate {
+    pub id: String,
+    pub name: String,
+    pub body: String,
+    /// File extension used when exporting, without the dot.
+    #[serde(default = "default_extension")]
+    pub extension: String,
+}
+
+fn default_extension() -> String {
+    "txt".to_string()
+}
+
+impl ReportTemplate {
+    pub fn is_preset(&self) -> bool {
+        self.id.starts_with(PRESET_PREFIX)
+    }
+}
+
+/// Built-in templates: a one-line chat summary, Markdown and forum BBCode.
+pub fn presets() -> Vec<ReportTemplate> {
+    let preset = |id: &str, name: &str, extension: &str, body: &str| ReportTemplate {
+        id: format!("{PRESET_PREFIX}{id}"),
+        name: name.to_string(),
+        body: body.to_string(),
+        extension: extension.to_string(),
+    };
+    vec![
+        preset("chat", "Chat line", "txt", CHAT_PRESET),
+        preset("markdown", "Markdown", "md", MARKDOWN_PRESET),
+        preset("bbcode", "Forum BBCode", "txt", BBCODE_PRESET),
+    ]
+}
+
+const CHAT_PRESET: &str = "{manifest}: {scu} SCU ≈ {total_ev} aUEC\
+{#best_location} · best {best_location} @ {best_price}{/best_location}\
+{#plan} · plan {plan_total} aUEC over {stop_count} stop(s){/plan}";
+
+const MARKDOWN_PRESET: &str = "## Cargo report · {manifest}
+**{scu} SCU** · expected **{total_ev} aUEC** ({confidence}% confidence) · {date}
+
+| Commodity | SCU | Expected | Best sell |
+|---|---:|---:|---|
+{#items}
+| {commodity}{#hot} 🔥{/hot} | {scu} | {#ev}{ev} aUEC{/ev} | {#best_location}{best_location} @ {best_price}{/best_location} |
+{/items}
+{#plan}
+
+### Sell plan · {plan_total} aUEC
+{#stops}
+{n}. **{terminal}**{#system} ({system}){/system} — {value} aUEC
+{#stop_items}
+   - {commodity} × {scu} SCU @ {price}
+{/stop_items}
+{/stops}
+{/plan}
+{#session}
+
+### Session · {profit} aUEC profit
+{trade_count} trades · {revenue} aUEC revenue · {spent} aUEC spent
+{/session}
+";
+
+const BBCODE_PRESET: &str = "[b]Cargo report · {manifest}[/b]
+{scu} SCU · expected [b]{total_ev} aUEC[/b] · {date}
+[list]
+{#items}
+[*]{commodity}{#hot} [color=orange]hot[/color]{/hot} — {scu} SCU{#ev} · {ev} aUEC{/ev}{#best_location} · best {best_location} @ {best_price}{/best_location}
+{/items}
+[/list]
+{#plan}
+[b]Sell plan[/b] · {plan_total} aUEC
+[list=1]
+{#stops}
+[*]{terminal}{#system} ({system}){/system} — {value} aUEC
+{/stops}
+[/list]
+{/plan}
+";
+
+#[derive(Clone, Debug, PartialEq, Eq, Error)]
+pub enum TemplateError {
+    #[error("section {{#{0}}} is never closed")]
+    Unclosed(String),
+    #[error("{{/{found}}} closes {{#{expected}}}")]
+    Mismatched { expected: String, found: String },
+    #[error("{{/{0}}} has no matching opening tag")]
+    UnexpectedClose(String),
+}
+
+/// A value a template can refer to.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ReportValue {
+    Text(String),
+    List(Vec<ReportScope>),
+}
+
+impl ReportValue {
+    fn is_empty(&self) -> bool {
+        match self {
+            ReportValue::Text(text) => text.is_empty(),
+            ReportValue::List(rows) => rows.is_empty(),
+        }
+    }
+}
+
+/// Named values at one level: the report itself or one row of a list.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ReportScope(Vec<(&'static str, ReportValue)>);
+
+impl ReportScope {
+    pub fn text(mut self, name: &'static str, value: impl Into<String>) -> Self {
+        self.0.push((name, ReportValue::Text(value.into())));
+        self
+    }
+
+    fn list(mut self, name: &'static str, rows: Vec<ReportScope>) -> Self {
+        self.0.push((name, ReportValue::List(rows)));
+        self
+    }
+
+    fn get(&self, name: &str) -> Option<&ReportValue> {
+        self.0.iter().find(|(key, _)| *key == name).map(|(_, value)| value)
+    }
+}
+
+/// Everything a report can draw on.
+pub struct ReportSource<'a> {
+    pub manifest: &'a CargoManifest,
+    pub evaluation: &'a EvaluationSummary,
+    pub best_prices: &'a BestPriceSummary,
+    pub plan: Option<&'a SellPlan>,
+    pub history: &'a TradeHistory,
+    pub session_id: &'a str,
+    /// Unix seconds for `{date}`.
+    pub now: u64,
+}
+
+/// Placeholder reference for the editor: (name, description key), where a
+/// name ending in `[]` is a list and the following indented names its rows.
+pub const PLACEHOLDERS: &[(&str, &str)] = &[
+    ("manifest", "report.ph.manifest"),
+    ("ship", "report.ph.ship"),
+    ("scu", "report.ph.scu"),
+    ("item_count", "report.ph.item_count"),
+    ("total_ev", "report.ph.total_ev"),
+    ("confidence", "report.ph.confidence"),
+    ("date", "report.ph.date"),
+    ("best_location", "report.ph.best_location"),
+    ("best_price", "report.ph.best_price"),
+    ("items[]", "report.ph.items"),
+    ("  commodity, scu, hot, ev, min, max, best_location, best_price", "report.ph.item_fields"),
+    ("plan[]", "report.ph.plan"),
+    ("  plan_total, plan_distance, stop_count, stops[]", "report.ph.plan_fields"),
+    ("  stops: n, terminal, system, value, distance, stop_items[]", "report.ph.stop_fields"),
+    ("  stop_items: commodity, scu, price, value", "report.ph.stop_item_fields"),
+    ("session[]", "report.ph.session"),
+    ("  profit, revenue, spent, trade_count, per_hour", "report.ph.session_fields"),
+    ("trades[]", "report.ph.trades"),
+    ("  side, commodity, scu, price, total, terminal, time", "report.ph.trade_fields"),
+];
+
+/// Builds the values every template is rendered against.
+pub fn report_scope(source: &ReportSource) -> ReportScope {
+    let manifest = source.manifest;
+    let best_for = |item_id: &str| {
+        source
+            .best_prices
+            .suggestions
+            .iter()
+            .find(|s| s.item_id == item_id)
+            .and_then(|s| s.entries.first())
+            .map(|entry| (entry.location_name.clone(), price(entry.sell_price)))
+            .unwrap_or_default()
+    };
+
+    let items = manifest
+        .items
+        .iter()
+        .map(|item| {
+            let evaluation = source.evaluation.items.iter().find(|(id, _)| *id == item.id).map(|(_, e)| e);
+            let (location, best_price) = best_for(&item.id);
+            ReportScope::default()
+                .text("commodity", item.commodity_name.clone())
+                .text("scu", item.scu.to_string())
+                .text("hot", if item.is_hot { "hot" } else { "" })
+                .text("ev", evaluation.map(|e| format::integer(e.ev)).unwrap_or_default())
+                .text("min", price(evaluation.and_then(|e| e.min)))
+                .text("max", price(evaluation.and_then(|e| e.max)))
+                .text("best_location", location)
+                .text("best_price", best_price)
+        })
+        .collect();
+
+    let plan = source
+        .plan
+        .filter(|plan| !plan.stops.is_empty())
+        .map(|plan| {
+            let stops = plan
+                .stops
+                .iter()
+                .enumerate()
+                .map(|(index, stop)| {
+                    let stop_items = stop
+                        .items
+                        .iter()
+                        .map(|item| {
+                            ReportScope::default()
+                                .text("commodity", item.commodity_name.clone())
+                                .text("scu", item.scu.to_string())
+                                .text("price", format::number(item.price_per_unit, 2))
+                                .text("value", format::integer(item.total_value))
+                        })
+                        .collect();
+                    ReportScope::default()
+                        .text("n", (index + 1).to_string())
+                        .text("terminal", stop.terminal_name.clone())
+                        .text("system", stop.system.clone().unwrap_or_default())
+                        .text("value", format::integer(stop.stop_value))
+                        .text("distance", distance(stop.distance_from_prev))
+                        .list("stop_items", stop_items)
+                })
+                .collect();
+            ReportScope::default()
+                .text("plan_total", format::integer(plan.total_value))
+                .text("plan_distance", distance(plan.total_distance))
+                .text("stop_count", plan.stops.len().to_string())
+                .list("stops", stops)
+        });
+
+    let records = source.history.session_records(source.session_id);
+    let session = (!records.is_empty()).then(|| {
+        let summary = summarize(&records);
+        ReportScope::default()
+            .text("profit", format::integer(summary.profit))
+            .text("revenue", format::integer(summary.revenue))
+            .text("spent", format::integer(summary.spent))
+            .text("trade_count", summary.trade_count.to_string())
+            .text("per_hour", summary.profit_per_hour.map(format::integer).unwrap_or_default())
+    });
+    let trades = records
+        .iter()
+        .rev()
+        .take(MAX_TRADES)
+        .map(|record| {
+            ReportScope::default()
+                .text("side", match record.side {
+                    TradeSide::Buy => "buy",
+                    TradeSide::Sell => "sell",
+                })
+                .text("commodity", record.commodity_name.clone())
+                .text("scu", record.scu.to_string())
+                .text("price", format::number(record.price_per_scu, 2))
+                .text("total", format::integer(record.total()))
+                .text("terminal", record.terminal_name.clone())
+                .text("time", format::date_time(record.timestamp))
+        })
+        .collect();
+
+    let (best_location, best_price) = source
+        .best_prices
+        .best_overall
+        .as_ref()
+        .map(|best| (best.location_name.clone(), price(best.sell_price)))
+        .unwrap_or_default();
+
+    ReportScope::default()
+        .text("manifest", manifest.name.clone())
+        .text("ship", manifest.ship.clone().unwrap_or_default())
+        .text("scu", manifest.total_scu().to_string())
+        .text("item_count", manifest.items.len().to_string())
+        .text("total_ev", format::integer(source.evaluation.total_ev))
+        .text("confidence", format::integer(source.evaluation.average_confidence as f64 * 100.0))
+        .text("date", format::date_time(source.now))
+        .text("best_location", best_location)
+        .text("best_price", best_price)
+        .list("items", items)
+        .list("plan", plan.into_iter().collect())
+        .list("session", session.into_iter().collect())
+        .list("trades", trades)
+}
+
+fn price(value: Option<f64>) -> String {
+    value
+        .filter(|v| v.is_finite() && *v >= 0.0)
+        .map(|v| format::number(v, 2))
+        .unwrap_or_default()
+}
+
+fn distance(value: Option<f64>) -> String {
+    value.map(|gm| format!("{} Gm", format::number(gm, 1))).unwrap_or_default()
+}
+
+/// Renders `template` against `scope`.
+pub fn render_report(template: &str, scope: &ReportScope) -> Result<String, TemplateError> {
+    let nodes = parse(template)?;
+    let mut out = String::new();
+    render_nodes(&nodes, &mut vec![scope], &mut out);
+    Ok(out)
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Text(String),
+    Var(String),
+    Open { name: String, inverted: bool },
+    Close(String),
+}
+
+#[derive(Debug, PartialEq)]
+enum Node {
+    Text(String),
+    Var(String),
+    Section { name: String, inverted: bool, body: Vec<Node> },
+}
+
+fn tokenize(template: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut text = String::new();
+    let mut rest = template;
+    while let Some(c) = rest.chars().next() {
+        if let Some(after) = rest.strip_prefix("{{") {
+            text.push('{');
+            rest = after;
+            continue;
+        }
+        if let Some(after) = rest.strip_prefix("}}") {
+            text.push('}');
+            rest = after;
+            continue;
+        }
+        if c == '{' {
+            if let Some(end) = rest.find('}') {
+                let tag = &rest[1..end];
+                let (sigil, name) = match tag.chars().next() {
+                    Some(s @ ('#' | '^' | '/')) => (Some(s), tag[1..].trim()),
+                    _ => (None, tag.trim()),
+                };
+                if is_name(name) {
+                    if !text.is_empty() {
+                        tokens.push(Token::Text(std::mem::take(&mut text)));
+                    }
+                    let name = name.to_string();
+                    tokens.push(match sigil {
+                        Some('#') => Token::Open { name, inverted: false },
+                        Some('^') => Token::Open { name, inverted: true },
+                        Some(_) => Token::Close(name),
+                        None => Token::Var(name),
+                    });
+                    rest = &rest[end + 1..];
+                    continue;
+                }
+            }
+        }
+        text.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    if !text.is_empty() {
+        tokens.push(Token::Text(text));
+    }
+    tokens
+}
+
+fn is_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Drops the indentation and line break around section tags that stand
+/// alone on their line.
+fn strip_standalone(tokens: &mut [Token]) {
+    let standalone: Vec<bool> = (0..tokens.len())
+        .map(|index| {
+            if !matches!(tokens[index], Token::Open { .. } | Token::Close(_)) {
+                return false;
+            }
+            let before = match index.checked_sub(1).map(|i| &tokens[i]) {
+                None => true,
+                Some(Token::Text(text)) => {
+                    let line = text.rsplit('\n').next().unwrap_or_default();
+                    line.trim().is_empty() && (text.contains('\n') || index == 1)
+                }
+                Some(_) => false,
+            };
+            let after = match tokens.get(index + 1) {
+                None => true,
+                Some(Token::Text(text)) => text.split('\n').next().unwrap_or_default().trim().is_empty(),
+                Some(_) => false,
+            };
+            before && after
+        })
+        .collect();
+    for (index, _) in standalone.into_iter().enumerate().filter(|(_, alone)| *alone) {
+        if let Some(Token::Text(text)) = index.checked_sub(1).map(|i| &mut tokens[i]) {
+            let keep = text.rfind('\n').map(|at| at + 1).unwrap_or(0);
+            text.truncate(keep);
+        }
+        if let Some(Token::Text(text)) = tokens.get_mut(index + 1) {
+            let drop = text.find('\n').map(|at| at + 1).unwrap_or(text.len());
+            text.drain(..drop);
+        }
+    }
+}
+
+fn parse(template: &str) -> Result<Vec<Node>, TemplateError> {
+    let mut tokens = tokenize(template);
+    strip_standalone(&mut tokens);
+
+    // Open sections, innermost last, each with the nodes collected so far.
+    let mut stack: Vec<(String, bool, Vec<Node>)> = Vec::new();
+    let mut root = Vec::new();
+    for token in tokens {
+        let node = match token {
+            Token::Text(text) if text.is_empty() => continue,
+            Token::Text(text) => Node::Text(text),
+            Token::Var(name) => Node::Var(name),
+            Token::Open { name, inverted } => {
+                stack.push((name, inverted, Vec::new()));
+                continue;
+            }
+            Token::Close(found) => {
+                let Some((name, inverted, body)) = stack.pop() else {
+                    return Err(TemplateError::UnexpectedClose(found));
+                };
+                if name != found {
+                    return Err(TemplateError::Mismatched { expected: name, found });
+                }
+                Node::Section { name, inverted, body }
+            }
+        };
+        match stack.last_mut() {
+            Some((_, _, body)) => body.push(node),
+            None => root.push(node),
+        }
+    }
+    match stack.pop() {
+        Some((name, _, _)) => Err(TemplateError::Unclosed(name)),
+        None => Ok(root),
+    }
+}
+
+fn lookup<'a>(scopes: &[&'a ReportScope], name: &str) -> Option<&'a ReportValue> {
+    scopes.iter().rev().find_map(|scope| scope.get(name))
+}
+
+fn render_nodes<'a>(nodes: &'a [Node], scopes: &mut Vec<&'a ReportScope>, out: &mut String) {
+    for node in nodes {
+        match node {
+            Node::Text(text) => out.push_str(text),
+            Node::Var(name) => match lookup(scopes, name) {
+                Some(ReportValue::Text(text)) => out.push_str(text),
+                Some(ReportValue::List(rows)) => out.push_str(&rows.len().to_string()),
+                None => {
+                    out.push('{');
+                    out.push_str(name);
+                    out.push('}');
+                }
+            },
+            Node::Section { name, inverted, body } => {
+                let value = lookup(scopes, name);
+                let empty = value.is_none_or(ReportValue::is_empty);
+                if *inverted {
+                    if empty {
+                        render_nodes(body, scopes, out);
+                    }
+                    continue;
+                }
+                match value {
+                    Some(ReportValue::List(rows)) => {
+                        for row in rows {
+                            scopes.push(row);
+                            render_nodes(body, scopes, out);
+                            scopes.pop();
+                        }
+                    }
+                    Some(ReportValue::Text(_)) if !empty => render_nodes(body, scopes, out),
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scope() -> ReportScope {
+        let row = |name: &str, hot: bool| ReportScope::default().text("commodity", name).text("hot", if hot { "hot" } else { "" });
+        ReportScope::default()
+            .text("manifest", "Hold")
+            .text("ship", "")
+            .list("items", vec![row("Gold", true), row("Quartz", false)])
+            .list("plan", Vec::new())
+    }
+
+    #[test]
+    fn renders_sections_and_standalone_lines() {
+        let template = "{manifest}{#ship} ({ship}){/ship} {{literal}}\n{#items}\n- {commodity}{#hot} 🔥{/hot} in {manifest}\n{/items}\n{^plan}\nno plan\n{/plan}\n{unknown}";
+        assert_eq!(
+            render_report(template, &scope()).unwrap(),
+            "Hold {literal}\n- Gold 🔥 in Hold\n- Quartz in Hold\nno plan\n{unknown}"
+        );
+        assert_eq!(render_report("{items} items, { not a tag }", &scope()).unwrap(), "2 items, { not a tag }");
+    }
+
+    #[test]
+    fn reports_unbalanced_sections() {
+        assert_eq!(render_report("{#items}", &scope()), Err(TemplateError::Unclosed("items".to_string())));
+        assert_eq!(render_report("{/items}", &scope()), Err(TemplateError::UnexpectedClose("items".to_string())));
+        assert_eq!(
+            render_report("{#items}{#plan}{/items}", &scope()),
+            Err(TemplateError::Mismatched { expected: "plan".to_string(), found: "items".to_string() })
+        );
+    }
+
+    #[test]
+    fn presets_render_without_errors() {
+        let mut manifest = CargoManifest::default_manifest();
+        manifest.items = vec![crate::domain::CargoItem {
+            id: "a".to_string(),
+            commodity_id: "gold".to_string(),
+            commodity_name: "Gold".to_string(),
+            scu: 8,
+            is_hot: false,
+        }];
+        let evaluation = EvaluationSummary { total_ev: 4_000.0, average_confidence: 0.5, items: Vec::new() };
+        let best_prices = BestPriceSummary { suggestions: Vec::new(), best_overall: None };
+        let history = TradeHistory::default();
+        let scope = report_scope(&ReportSource {
+            manifest: &manifest,
+            evaluation: &evaluation,
+            best_prices: &best_prices,
+            plan: None,
+            history: &history,
+            session_id: "s",
+            now: 0,
+        });
+        for preset in presets() {
+            assert!(preset.is_preset());
+            let text = render_report(&preset.body, &scope).unwrap();
+            assert!(text.contains("8 SCU"), "{}: {text}", preset.name);
+            assert!(!text.contains("Sell plan"), "{}: {text}", preset.name);
+        }
+    }
+}
@@ -0,0 +1,246 @@
+//! Sell plans produced by the Sell Planner: which cargo to sell at which stop.
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use super::entities::{CargoItem, PricePoint};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SellPlan {
+    pub stops: Vec<SellStop>,
+    pub total_value: f64,
+    pub total_distance: Option<f64>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SellStop {
+    pub terminal_name: String,
+    pub terminal_id: Option<i32>,
+    pub system: Option<String>,
+    pub items: Vec<SellItem>,
+    pub stop_value: f64,
+    pub is_nqa: bool,
+    pub distance_from_prev: Option<f64>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SellItem {
+    pub commodity_name: String,
+    pub commodity_id: String,
+    pub scu: u32,
+    pub price_per_unit: f64,
+    pub total_value: f64,
+    pub available_stock: Option<f64>,
+}
+
+/// (terminal id, system, total value, items, is NQA) per terminal name.
+type TerminalTotals = (Option<i32>, Option<String>, f64, Vec<SellItem>, bool);
+
+/// Sell everything at the single terminal that pays the most in total.
+pub fn calculate_one_stop_plan(
+    items: &[CargoItem],
+    price_map: &HashMap<String, Vec<PricePoint>>,
+    nqa_terminal_ids: &HashSet<i32>,
+) -> SellPlan {
+    let mut terminal_values: HashMap<String, TerminalTotals> = HashMap::new();
+
+    for item in items {
+        let Some(prices) = price_map.get(&item.commodity_id) else { continue };
+
+        for point in prices {
+            if item.is_hot {
+                let is_nqa = point.terminal_id
+                    .map(|id| nqa_terminal_ids.contains(&id))
+                    .unwrap_or(false);
+                if !is_nqa { continue; }
+            }
+
+            let Some(price) = best_sell_price(point) else { continue };
+            let item_value = price * item.scu as f64;
+            let is_nqa = point.terminal_id
+                .map(|id| nqa_terminal_ids.contains(&id))
+                .unwrap_or(false);
+
+            let entry = terminal_values
+                .entry(point.terminal_name.clone())
+                .or_insert_with(|| (point.terminal_id, point.system.clone(), 0.0, Vec::new(), is_nqa));
+
+            entry.2 += item_value;
+            entry.3.push(SellItem {
+                commodity_name: item.commodity_name.clone(),
+                commodity_id: item.id.clone(),
+                scu: item.scu,
+                price_per_unit: price,
+                total_value: item_value,
+                available_stock: point.scu_sell_stock,
+            });
+        }
+    }
+
+    let best = terminal_values
+        .into_iter()
+        .max_by(|a, b| a.1.2.partial_cmp(&b.1.2).unwrap_or(std::cmp::Ordering::Equal));
+
+    match best {
+        Some((terminal_name, (terminal_id, system, total, sell_items, is_nqa))) => SellPlan {
+            stops: vec![SellStop {
+                terminal_name,
+                terminal_id,
+                system,
+                items: sell_items,
+                stop_value: total,
+                is_nqa,
+                distance_from_prev: None,
+            }],
+            total_value: total,
+            total_distance: None,
+        },
+        None => SellPlan {
+            stops: vec![],
+            total_value: 0.0,
+            total_distance: None,
+        },
+    }
+}
+
+/// Sell each item where it fetches the best price, grouped into stops.
+pub fn calculate_best_value_plan(
+    items: &[CargoItem],
+    price_map: &HashMap<String, Vec<PricePoint>>,
+    nqa_terminal_ids: &HashSet<i32>,
+) -> SellPlan {
+    let mut stops_map: HashMap<String, SellStop> = HashMap::new();
+    let mut total_value = 0.0;
+
+    for item in items {
+        let Some(prices) = price_map.get(&item.commodity_id) else { continue };
+
+        let best = prices
+            .iter()
+            .filter(|point| {
+                if item.is_hot {
+                    point.terminal_id
+                        .map(|id| nqa_terminal_ids.contains(&id))
+                        .unwrap_or(false)
+                } else {
+                    true
+                }
+            })
+            .filter_map(|point| {
+                let price = best_sell_price(point)?;
+                let is_nqa = point.terminal_id
+                    .map(|id| nqa_terminal_ids.contains(&id))
+                    .unwrap_or(false);
+                Some((point, price, is_nqa))
+            })
+            .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
+
+        if let Some((point, price, is_nqa)) = best {
+            let item_value = price * item.scu as f64;
+            total_value += item_value;
+
+            let stop = stops_map
+                .entry(point.terminal_name.clone())
+                .or_insert_with(|| SellStop {
+                    terminal_name: point.terminal_name.clone(),
+                    terminal_id: point.terminal_id,
+                    system: point.system.clone(),
+                    items: Vec::new(),
+                    stop_value: 0.0,
+                    is_nqa,
+                    distance_from_prev: None,
+                });
+
+            stop.stop_value += item_value;
+            stop.items.push(SellItem {
+                commodity_name: item.commodity_name.clone(),
+                commodity_id: item.id.clone(),
+                scu: item.scu,
+                price_per_unit: price,
+                total_value: item_value,
+                available_stock: point.scu_sell_stock,
+            });
+        }
+    }
+
+    let mut stops: Vec<SellStop> = stops_map.into_values().collect();
+    stops.sort_by(|a, b| b.stop_value.partial_cmp(&a.stop_value).unwrap_or(std::cmp::Ordering::Equal));
+
+    SellPlan {
+        stops,
+        total_value,
+        total_distance: None,
+    }
+}
+
+/// Reorder stops greedily by distance from the origin (`distances` are from the origin).
+pub fn sort_by_nearest_neighbor(
+    mut plan: SellPlan,
+    origin_id: i32,
+    distances: &HashMap<i32, f64>,
+) -> SellPlan {
+    if plan.stops.is_empty() { return plan; }
+
+    let mut sorted_stops: Vec<SellStop> = Vec::new();
+    let mut remaining: Vec<SellStop> = plan.stops;
+    let mut _current_pos = origin_id;
+    let mut total_distance = 0.0;
+
+    while !remaining.is_empty() {
+        let (nearest_idx, nearest_dist) = remaining
+            .iter()
+            .enumerate()
+            .map(|(idx, stop)| {
+                let dist = stop.terminal_id
+                    .and_then(|id| distances.get(&id).copied())
+                    .unwrap_or(f64::MAX);
+                (idx, dist)
+            })
+            .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+            .unwrap_or((0, 0.0));
+
+        let mut stop = remaining.remove(nearest_idx);
+        stop.distance_from_prev = Some(nearest_dist);
+        total_distance += nearest_dist;
+        
+        if let Some(id) = stop.terminal_id {
+            _current_pos = id;
+        }
+        
+        sorted_stops.push(stop);
+    }
+
+    plan.stops = sorted_stops;
+    plan.total_distance = Some(total_distance);
+    plan
+}
+
+/// Annotate stops with their distance from the origin without reordering.
+pub fn add_distances_to_plan(
+    mut plan: SellPlan,
+    _origin_id: i32,
+    distances: &HashMap<i32, f64>,
+) -> SellPlan {
+    let mut total_distance = 0.0;
+    
+    for stop in &mut plan.stops {
+        if let Some(id) = stop.terminal_id {
+            if let Some(&dist) = distances.get(&id) {
+                stop.distance_from_prev = Some(dist);
+                total_distance += dist;
+            }
+        }
+    }
+    
+    plan.total_distance = Some(total_distance);
+    plan
+}
+
+pub(super) fn best_sell_price(point: &PricePoint) -> Option<f64> {
+    point.price_sell_max
+        .or(point.price_sell)
+        .or(point.price_average)
+        .or(point.price_sell_min)
+        .filter(|p| p.is_finite() && *p > 0.0)
+}
@@ -6,6 +6,8 @@ use serde::{Deserialize, Serialize};
 
 use super::entities::PricePoint;
 
+use crate::util::i18n::t;
+
 /// A potential trade route: buy at A, sell at B.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct TradeRoute {
@@ -97,15 +99,15 @@ pub enum TradeRouteSort {
 }
 
 impl TradeRouteSort {
-    pub fn label(&self) -> &'static str {
-        match self {
-            Self::ProfitPerGm => "Profit/Gm",
-            Self::RoiPercent => "ROI %",
-            Self::ProfitTotal => "Profit",
-            Self::ActivityScore => "Traffic",
-            Self::Distance => "Distanz",
-            Self::CargoValue => "Warenwert",
-        }
+    pub fn label(&self) -> String {
+        t(match self {
+            Self::ProfitPerGm => "routes.sort.profit_per_gm",
+            Self::RoiPercent => "routes.sort.roi",
+            Self::ProfitTotal => "routes.sort.profit",
+            Self::ActivityScore => "routes.sort.traffic",
+            Self::Distance => "routes.sort.distance",
+            Self::CargoValue => "routes.sort.cargo_value",
+        })
     }
 }
 
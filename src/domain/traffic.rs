@@ -0,0 +1,440 @@
+//! Pirate traffic heatmap: where haulers show up, and with how much cargo.
+//!
+//! UEX counts the user price reports behind every listing (`buy_user_rows`,
+//! `sell_user_rows`). Players report prices where they trade, so the counts
+//! are a traffic proxy. [`traffic_board`] sums them per terminal, system and
+//! lane across all commodities and weighs them with the cargo value listed
+//! there; [`TrafficHistory`] keeps one snapshot per price refresh for trends.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::trade_route::TradeRoute;
+
+/// Share of the heat index that comes from report counts; the rest is cargo value.
+const REPORT_WEIGHT: f64 = 0.6;
+
+/// Snapshots kept in the traffic history.
+pub const MAX_SNAPSHOTS: usize = 60;
+
+/// How far back trends look.
+pub const TREND_WINDOW_SECS: u64 = 7 * 86_400;
+
+/// Traffic at one terminal, across all commodities.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TerminalTraffic {
+    pub terminal_id: i32,
+    pub name: String,
+    pub system: Option<String>,
+    pub is_planetary: bool,
+    pub is_nqa: bool,
+    /// Buy and sell price reports.
+    pub reports: u32,
+    /// Stock × buy price plus demand × sell price: cargo loaded or unloaded here.
+    pub cargo_value: f64,
+    pub commodities: usize,
+    /// Commodity with the most cargo value here.
+    pub top_commodity: Option<String>,
+    /// 0–100, relative to the busiest terminal.
+    pub index: f64,
+}
+
+/// Traffic summed over a system's terminals.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SystemTraffic {
+    pub name: String,
+    pub reports: u32,
+    pub cargo_value: f64,
+    pub terminals: usize,
+    /// 0–100, relative to the busiest system.
+    pub index: f64,
+}
+
+/// Traffic on one buy → sell terminal pair, across the commodities traded on it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LaneTraffic {
+    pub from_id: i32,
+    pub from: String,
+    pub from_system: Option<String>,
+    pub to_id: i32,
+    pub to: String,
+    pub to_system: Option<String>,
+    /// Sum of the routes' [`TradeRoute::activity_score`].
+    pub reports: u32,
+    /// Cargo a full hauler run carries: buy price × what both ends can trade.
+    pub cargo_value: f64,
+    pub commodities: usize,
+    /// 0–100, relative to the busiest lane.
+    pub index: f64,
+}
+
+/// Every ranking of the board, busiest first.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TrafficBoard {
+    pub terminals: Vec<TerminalTraffic>,
+    pub systems: Vec<SystemTraffic>,
+    pub lanes: Vec<LaneTraffic>,
+}
+
+/// One commodity listing at one terminal; routes repeat them per partner.
+struct Listing<'a> {
+    commodity: &'a str,
+    reports: u32,
+    value: f64,
+}
+
+/// Aggregates `routes` into terminal, system and lane traffic.
+pub fn traffic_board(routes: &[TradeRoute]) -> TrafficBoard {
+    let mut listings: HashMap<(i32, &str, bool), Listing> = HashMap::new();
+    let mut terminals: HashMap<i32, TerminalTraffic> = HashMap::new();
+    let mut lanes: HashMap<(i32, i32), LaneTraffic> = HashMap::new();
+
+    for route in routes {
+        let ends = [
+            (
+                route.buy_terminal_id,
+                &route.buy_terminal_name,
+                &route.buy_system,
+                route.buy_is_planetary,
+                false,
+                route.buy_user_rows,
+                route.buy_price * route.buy_stock,
+            ),
+            (
+                route.sell_terminal_id,
+                &route.sell_terminal_name,
+                &route.sell_system,
+                route.sell_is_planetary,
+                route.sell_is_nqa,
+                route.sell_user_rows,
+                route.sell_price * route.sell_demand,
+            ),
+        ];
+        for (is_buy, (id, name, system, planetary, nqa, reports, value)) in
+            [true, false].into_iter().zip(ends)
+        {
+            let terminal = terminals.entry(id).or_insert_with(|| TerminalTraffic {
+                terminal_id: id,
+                name: name.clone(),
+                system: system.clone(),
+                is_planetary: planetary,
+                is_nqa: false,
+                reports: 0,
+                cargo_value: 0.0,
+                commodities: 0,
+                top_commodity: None,
+                index: 0.0,
+            });
+            terminal.is_nqa |= nqa;
+            listings
+                .entry((id, route.commodity_name.as_str(), is_buy))
+                .or_insert(Listing {
+                    commodity: &route.commodity_name,
+                    reports: reports.max(0) as u32,
+                    value: value.max(0.0),
+                });
+        }
+
+        let lane = lanes
+            .entry((route.buy_terminal_id, route.sell_terminal_id))
+            .or_insert_with(|| LaneTraffic {
+                from_id: route.buy_terminal_id,
+                from: route.buy_terminal_name.clone(),
+                from_system: route.buy_system.clone(),
+                to_id: route.sell_terminal_id,
+                to: route.sell_terminal_name.clone(),
+                to_system: route.sell_system.clone(),
+                reports: 0,
+                cargo_value: 0.0,
+                commodities: 0,
+                index: 0.0,
+            });
+        lane.reports += route.activity_score().max(0) as u32;
+        lane.cargo_value += route.buy_price * route.buy_stock.min(route.sell_demand).max(0.0);
+        lane.commodities += 1;
+    }
+
+    let mut by_commodity: HashMap<i32, HashMap<&str, f64>> = HashMap::new();
+    for ((id, _, _), listing) in &listings {
+        let terminal = terminals.get_mut(id).expect("listed terminals are collected");
+        terminal.reports += listing.reports;
+        terminal.cargo_value += listing.value;
+        *by_commodity.entry(*id).or_default().entry(listing.commodity).or_default() +=
+            listing.value;
+    }
+    for (id, values) in by_commodity {
+        let terminal = terminals.get_mut(&id).expect("listed terminals are collected");
+        terminal.commodities = values.len();
+        terminal.top_commodity = values
+            .into_iter()
+            .filter(|(_, value)| *value > 0.0)
+            .max_by(|a, b| a.1.total_cmp(&b.1).then(b.0.cmp(a.0)))
+            .map(|(name, _)| name.to_string());
+    }
+
+    let mut systems: HashMap<String, SystemTraffic> = HashMap::new();
+    for terminal in terminals.values() {
+        let Some(name) = &terminal.system else {
+            continue;
+        };
+        let system = systems.entry(name.clone()).or_insert_with(|| SystemTraffic {
+            name: name.clone(),
+            reports: 0,
+            cargo_value: 0.0,
+            terminals: 0,
+            index: 0.0,
+        });
+        system.reports += terminal.reports;
+        system.cargo_value += terminal.cargo_value;
+        system.terminals += 1;
+    }
+
+    let mut board = TrafficBoard {
+        terminals: terminals.into_values().collect(),
+        systems: systems.into_values().collect(),
+        lanes: lanes.into_values().collect(),
+    };
+    rank(&mut board.terminals);
+    rank(&mut board.systems);
+    rank(&mut board.lanes);
+    board
+}
+
+/// What the heat index is computed from.
+trait Heat {
+    fn reports(&self) -> u32;
+    fn cargo_value(&self) -> f64;
+    fn index(&self) -> f64;
+    fn set_index(&mut self, index: f64);
+    fn label(&self) -> &str;
+}
+
+macro_rules! impl_heat {
+    ($type:ty, $label:ident) => {
+        impl Heat for $type {
+            fn reports(&self) -> u32 {
+                self.reports
+            }
+            fn cargo_value(&self) -> f64 {
+                self.cargo_value
+            }
+            fn index(&self) -> f64 {
+                self.index
+            }
+            fn set_index(&mut self, index: f64) {
+                self.index = index;
+            }
+            fn label(&self) -> &str {
+                &self.$label
+            }
+        }
+    };
+}
+
+impl_heat!(TerminalTraffic, name);
+impl_heat!(SystemTraffic, name);
+impl_heat!(LaneTraffic, from);
+
+/// Sets every entry's heat index and sorts the busiest first.
+fn rank<T: Heat>(entries: &mut [T]) {
+    let max_reports = entries.iter().map(Heat::reports).max().unwrap_or(0);
+    let max_value = entries.iter().map(Heat::cargo_value).fold(0.0, f64::max);
+    for entry in entries.iter_mut() {
+        let report_share = if max_reports > 0 {
+            entry.reports() as f64 / max_reports as f64
+        } else {
+            0.0
+        };
+        let value_share = if max_value > 0.0 { entry.cargo_value() / max_value } else { 0.0 };
+        entry.set_index(100.0 * (REPORT_WEIGHT * report_share + (1.0 - REPORT_WEIGHT) * value_share));
+    }
+    entries.sort_by(|a, b| b.index().total_cmp(&a.index()).then_with(|| a.label().cmp(b.label())));
+}
+
+/// Report counts of one board, for trends.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct TrafficSnapshot {
+    /// Unix seconds when the underlying prices were fetched.
+    pub taken_at: u64,
+    #[serde(default)]
+    pub terminals: HashMap<i32, u32>,
+    #[serde(default)]
+    pub systems: HashMap<String, u32>,
+}
+
+impl TrafficSnapshot {
+    pub fn of(board: &TrafficBoard, taken_at: u64) -> Self {
+        Self {
+            taken_at,
+            terminals: board.terminals.iter().map(|t| (t.terminal_id, t.reports)).collect(),
+            systems: board.systems.iter().map(|s| (s.name.clone(), s.reports)).collect(),
+        }
+    }
+}
+
+/// Traffic snapshots, oldest first; persisted next to the routes cache.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct TrafficHistory {
+    #[serde(default)]
+    pub snapshots: Vec<TrafficSnapshot>,
+}
+
+impl TrafficHistory {
+    pub fn has_snapshot(&self, taken_at: u64) -> bool {
+        self.snapshots.iter().any(|s| s.taken_at == taken_at)
+    }
+
+    /// Adds `snapshot` unless one from the same fetch exists, keeping the
+    /// newest [`MAX_SNAPSHOTS`]. Returns whether it was added.
+    pub fn record(&mut self, snapshot: TrafficSnapshot) -> bool {
+        if self.has_snapshot(snapshot.taken_at) {
+            return false;
+        }
+        self.snapshots.push(snapshot);
+        self.snapshots.sort_by_key(|s| s.taken_at);
+        let excess = self.snapshots.len().saturating_sub(MAX_SNAPSHOTS);
+        self.snapshots.drain(..excess);
+        true
+    }
+
+    pub fn terminal_trend(&self, terminal_id: i32, now: u64) -> Trend {
+        self.trend(now, |s| s.terminals.get(&terminal_id).copied())
+    }
+
+    pub fn system_trend(&self, system: &str, now: u64) -> Trend {
+        self.trend(now, |s| s.systems.get(system).copied())
+    }
+
+    /// Reports per snapshot within [`TREND_WINDOW_SECS`] of `now`; missing
+    /// from a snapshot counts as no reports.
+    fn trend(&self, now: u64, reports: impl Fn(&TrafficSnapshot) -> Option<u32>) -> Trend {
+        Trend {
+            series: self
+                .snapshots
+                .iter()
+                .filter(|s| s.taken_at + TREND_WINDOW_SECS >= now)
+                .map(|s| reports(s).unwrap_or(0))
+                .collect(),
+        }
+    }
+}
+
+/// Report counts over time, oldest first.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Trend {
+    pub series: Vec<u32>,
+}
+
+impl Trend {
+    /// Newest minus oldest count; `None` with fewer than two snapshots.
+    pub fn change(&self) -> Option<i64> {
+        match self.series.as_slice() {
+            [first, .., last] => Some(*last as i64 - *first as i64),
+            _ => None,
+        }
+    }
+
+    /// The series as block characters, scaled between its min and max.
+    pub fn sparkline(&self) -> String {
+        const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+        let min = self.series.iter().copied().min().unwrap_or(0);
+        let max = self.series.iter().copied().max().unwrap_or(0);
+        self.series
+            .iter()
+            .map(|&value| {
+                if max == min {
+                    BARS[3]
+                } else {
+                    BARS[((value - min) as usize * (BARS.len() - 1)) / (max - min) as usize]
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn route(commodity: &str, buy: (i32, &str), sell: (i32, &str), rows: (i32, i32)) -> TradeRoute {
+        TradeRoute {
+            commodity_id: commodity.to_lowercase(),
+            commodity_name: commodity.to_string(),
+            is_illegal: false,
+            buy_terminal_id: buy.0,
+            buy_terminal_name: buy.1.to_string(),
+            buy_system: Some("Stanton".to_string()),
+            buy_price: 100.0,
+            buy_stock: 50.0,
+            buy_user_rows: rows.0,
+            buy_is_planetary: false,
+            sell_terminal_id: sell.0,
+            sell_terminal_name: sell.1.to_string(),
+            sell_system: Some(if sell.0 > 10 { "Pyro" } else { "Stanton" }.to_string()),
+            sell_price: 150.0,
+            sell_demand: 20.0,
+            sell_user_rows: rows.1,
+            sell_is_planetary: true,
+            sell_is_nqa: sell.0 > 10,
+            distance_gm: None,
+            profit_per_scu: 50.0,
+            roi_percent: 50.0,
+        }
+    }
+
+    #[test]
+    fn aggregates_listings_once_per_terminal() {
+        let routes = vec![
+            route("Gold", (1, "Area18"), (2, "Lorville"), (10, 4)),
+            route("Gold", (1, "Area18"), (11, "Ruin Station"), (10, 2)),
+            route("Agricium", (3, "Everus"), (2, "Lorville"), (1, 1)),
+        ];
+        let board = traffic_board(&routes);
+
+        // Area18's Gold listing appears in two routes but counts once.
+        let area18 = board.terminals.iter().find(|t| t.terminal_id == 1).unwrap();
+        assert_eq!((area18.reports, area18.cargo_value, area18.commodities), (10, 5_000.0, 1));
+        let lorville = board.terminals.iter().find(|t| t.terminal_id == 2).unwrap();
+        assert_eq!((lorville.reports, lorville.commodities), (5, 2));
+        assert!(board.terminals.iter().find(|t| t.terminal_id == 11).unwrap().is_nqa);
+
+        // Most reports, but Lorville lists more cargo value.
+        assert_eq!(board.terminals[0].terminal_id, 1);
+        assert!((board.terminals[0].index - (60.0 + 40.0 * 5.0 / 6.0)).abs() < 1e-9);
+        assert!(board.terminals.windows(2).all(|w| w[0].index >= w[1].index));
+
+        let stanton = board.systems.iter().find(|s| s.name == "Stanton").unwrap();
+        assert_eq!((stanton.reports, stanton.terminals), (16, 3));
+
+        let lane = &board.lanes[0];
+        assert_eq!((lane.from_id, lane.to_id, lane.reports), (1, 2, 14));
+        assert_eq!(lane.cargo_value, 2_000.0);
+    }
+
+    #[test]
+    fn history_keeps_one_snapshot_per_fetch_and_trends_within_window() {
+        let mut history = TrafficHistory::default();
+        let snapshot = |at: u64, reports: u32| TrafficSnapshot {
+            taken_at: at,
+            terminals: HashMap::from([(1, reports)]),
+            systems: HashMap::new(),
+        };
+        assert!(history.record(snapshot(100, 4)));
+        assert!(!history.record(snapshot(100, 9)));
+        history.record(snapshot(TREND_WINDOW_SECS + 200, 6));
+        history.record(snapshot(TREND_WINDOW_SECS + 50, 2));
+
+        let trend = history.terminal_trend(1, TREND_WINDOW_SECS + 200);
+        assert_eq!(trend.series, vec![2, 6]);
+        assert_eq!(trend.change(), Some(4));
+        assert_eq!(trend.sparkline(), "▁█");
+        assert_eq!(history.terminal_trend(7, TREND_WINDOW_SECS + 200).change(), Some(0));
+
+        for at in 0..MAX_SNAPSHOTS as u64 {
+            history.record(snapshot(1_000_000 + at, 1));
+        }
+        assert_eq!(history.snapshots.len(), MAX_SNAPSHOTS);
+        assert_eq!(history.snapshots[0].taken_at, 1_000_000);
+    }
+}
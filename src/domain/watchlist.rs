@@ -0,0 +1,338 @@
+//! Price watchlist rules and alert deduplication.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::entities::{CommodityId, PricePoint};
+use super::history::TradeSide;
+use crate::util::{
+    format,
+    i18n::{t, tf},
+};
+
+/// Whether a rule fires when the price goes above or below its threshold.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WatchDirection {
+    #[default]
+    Above,
+    Below,
+}
+
+impl WatchDirection {
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            WatchDirection::Above => "≥",
+            WatchDirection::Below => "≤",
+        }
+    }
+
+    fn is_met(&self, price: f64, threshold: f64) -> bool {
+        match self {
+            WatchDirection::Above => price >= threshold,
+            WatchDirection::Below => price <= threshold,
+        }
+    }
+}
+
+/// A single watchlist entry, e.g. "Laranite sell price ≥ 3,000 at any Stanton terminal".
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct WatchRule {
+    pub id: String,
+    pub commodity_id: CommodityId,
+    pub commodity_name: String,
+    /// Restrict to one terminal; `None` watches every terminal.
+    #[serde(default)]
+    pub terminal_id: Option<i32>,
+    #[serde(default)]
+    pub terminal_name: Option<String>,
+    /// Restrict to one star system; ignored when a terminal is set.
+    #[serde(default)]
+    pub system: Option<String>,
+    /// `Sell` watches what terminals pay you, `Buy` what they charge you.
+    pub side: TradeSide,
+    pub threshold: f64,
+    pub direction: WatchDirection,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// A rule whose condition is currently met.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WatchHit {
+    pub rule_id: String,
+    pub terminal_name: String,
+    pub system: Option<String>,
+    pub price: f64,
+}
+
+impl WatchRule {
+    pub fn scope_label(&self) -> String {
+        if let Some(name) = &self.terminal_name {
+            name.clone()
+        } else if let Some(system) = &self.system {
+            tf("watch.scope.system", &[("system", system.clone())])
+        } else {
+            t("watch.scope.any")
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        let key = match self.side {
+            TradeSide::Sell => "watch.describe.sell",
+            TradeSide::Buy => "watch.describe.buy",
+        };
+        tf(
+            key,
+            &[
+                ("commodity", self.commodity_name.clone()),
+                ("direction", self.direction.symbol().to_string()),
+                ("threshold", format::integer(self.threshold)),
+                ("scope", self.scope_label()),
+            ],
+        )
+    }
+
+    fn applies_to(&self, point: &PricePoint) -> bool {
+        if let Some(terminal_id) = self.terminal_id {
+            return point.terminal_id == Some(terminal_id);
+        }
+        match &self.system {
+            Some(system) => point
+                .system
+                .as_deref()
+                .map(|s| s.eq_ignore_ascii_case(system))
+                .unwrap_or(false),
+            None => true,
+        }
+    }
+
+    fn price_of(&self, point: &PricePoint) -> Option<f64> {
+        let price = match self.side {
+            TradeSide::Sell => point
+                .price_sell_max
+                .or(point.price_sell)
+                .or(point.price_sell_min),
+            TradeSide::Buy => point
+                .price_buy_min
+                .or(point.price_buy)
+                .or(point.price_buy_max),
+        };
+        price.filter(|p| p.is_finite() && *p > 0.0)
+    }
+
+    /// Returns the most extreme matching terminal if the condition is met.
+    pub fn evaluate(&self, points: &[PricePoint]) -> Option<WatchHit> {
+        let candidates = points
+            .iter()
+            .filter(|point| self.applies_to(point))
+            .filter_map(|point| self.price_of(point).map(|price| (point, price)))
+            .filter(|(_, price)| self.direction.is_met(*price, self.threshold));
+
+        let best = match self.direction {
+            WatchDirection::Above => {
+                candidates.max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+            }
+            WatchDirection::Below => {
+                candidates.min_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+            }
+        };
+
+        best.map(|(point, price)| WatchHit {
+            rule_id: self.id.clone(),
+            terminal_name: point.terminal_name.clone(),
+            system: point.system.clone(),
+            price,
+        })
+    }
+}
+
+/// Tracks which rules are currently firing so an alert is raised only when a
+/// condition starts being true, not on every poll while it stays true.
+#[derive(Clone, Debug, Default)]
+pub struct WatchAlerts {
+    active: HashMap<String, WatchHit>,
+}
+
+impl WatchAlerts {
+    /// Re-evaluates all enabled rules and returns the hits that just started.
+    pub fn update(
+        &mut self,
+        rules: &[WatchRule],
+        prices: &HashMap<CommodityId, Vec<PricePoint>>,
+    ) -> Vec<WatchHit> {
+        let mut fresh = Vec::new();
+        let mut next = HashMap::new();
+
+        for rule in rules.iter().filter(|rule| rule.enabled) {
+            let Some(points) = prices.get(&rule.commodity_id) else {
+                // No data yet: keep whatever state we had.
+                if let Some(hit) = self.active.remove(&rule.id) {
+                    next.insert(rule.id.clone(), hit);
+                }
+                continue;
+            };
+            if let Some(hit) = rule.evaluate(points) {
+                if !self.active.contains_key(&rule.id) {
+                    fresh.push(hit.clone());
+                }
+                next.insert(rule.id.clone(), hit);
+            }
+        }
+
+        self.active = next;
+        fresh
+    }
+
+    /// Forget a rule's state, e.g. after it was edited, so it can fire again.
+    pub fn reset(&mut self, rule_id: &str) {
+        self.active.remove(rule_id);
+    }
+
+    pub fn hit(&self, rule_id: &str) -> Option<&WatchHit> {
+        self.active.get(rule_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::SystemTime;
+
+    use super::*;
+
+    fn point(terminal_id: i32, system: &str, sell: Option<f64>, buy: Option<f64>) -> PricePoint {
+        PricePoint {
+            terminal_id: Some(terminal_id),
+            terminal_name: format!("TDD {terminal_id}"),
+            system: Some(system.to_string()),
+            terminal_code: None,
+            price_sell_min: None,
+            price_sell: sell,
+            price_sell_max: None,
+            price_buy_max: None,
+            price_buy: buy,
+            price_buy_min: None,
+            price_average: None,
+            container_sizes: Vec::new(),
+            scu_buy: None,
+            scu_sell_stock: None,
+            status_sell: None,
+            status_buy: None,
+            city_name: None,
+            outpost_name: None,
+            space_station_name: None,
+            volatility_sell: None,
+            buy_user_rows: None,
+            sell_user_rows: None,
+            updated_at: SystemTime::now(),
+        }
+    }
+
+    fn rule(id: &str, side: TradeSide, direction: WatchDirection, threshold: f64) -> WatchRule {
+        WatchRule {
+            id: id.to_string(),
+            commodity_id: "lara".to_string(),
+            commodity_name: "Laranite".to_string(),
+            terminal_id: None,
+            terminal_name: None,
+            system: None,
+            side,
+            threshold,
+            direction,
+            enabled: true,
+        }
+    }
+
+    fn prices(points: Vec<PricePoint>) -> HashMap<CommodityId, Vec<PricePoint>> {
+        HashMap::from([("lara".to_string(), points)])
+    }
+
+    #[test]
+    fn evaluate_picks_the_most_extreme_matching_terminal() {
+        let points = vec![
+            point(1, "Stanton", Some(3_100.0), Some(2_500.0)),
+            point(2, "Stanton", Some(3_400.0), Some(2_300.0)),
+            point(3, "Stanton", Some(2_900.0), None),
+        ];
+
+        let above = rule("a", TradeSide::Sell, WatchDirection::Above, 3_000.0);
+        let hit = above.evaluate(&points).unwrap();
+        assert_eq!((hit.terminal_name.as_str(), hit.price), ("TDD 2", 3_400.0));
+
+        let below = rule("b", TradeSide::Buy, WatchDirection::Below, 2_400.0);
+        let hit = below.evaluate(&points).unwrap();
+        assert_eq!((hit.terminal_name.as_str(), hit.price), ("TDD 2", 2_300.0));
+
+        let unmet = rule("c", TradeSide::Sell, WatchDirection::Above, 3_500.0);
+        assert_eq!(unmet.evaluate(&points), None);
+    }
+
+    #[test]
+    fn evaluate_respects_the_terminal_and_system_scope() {
+        let points = vec![
+            point(1, "Stanton", Some(3_400.0), None),
+            point(2, "Pyro", Some(3_600.0), None),
+        ];
+
+        let mut by_system = rule("a", TradeSide::Sell, WatchDirection::Above, 3_000.0);
+        by_system.system = Some("stanton".to_string());
+        assert_eq!(by_system.evaluate(&points).unwrap().price, 3_400.0);
+
+        // A terminal wins over the system filter.
+        let mut by_terminal = by_system.clone();
+        by_terminal.terminal_id = Some(2);
+        assert_eq!(by_terminal.evaluate(&points).unwrap().price, 3_600.0);
+
+        by_terminal.terminal_id = Some(9);
+        assert_eq!(by_terminal.evaluate(&points), None);
+    }
+
+    #[test]
+    fn evaluate_ignores_missing_and_zero_prices() {
+        let points = vec![point(1, "Stanton", Some(0.0), None), point(2, "Stanton", None, None)];
+        let below = rule("a", TradeSide::Sell, WatchDirection::Below, 100.0);
+        assert_eq!(below.evaluate(&points), None);
+    }
+
+    #[test]
+    fn alerts_fire_once_until_the_condition_clears() {
+        let rules = vec![rule("a", TradeSide::Sell, WatchDirection::Above, 3_000.0)];
+        let high = prices(vec![point(1, "Stanton", Some(3_200.0), None)]);
+        let low = prices(vec![point(1, "Stanton", Some(2_800.0), None)]);
+        let mut alerts = WatchAlerts::default();
+
+        assert_eq!(alerts.update(&rules, &high).len(), 1);
+        assert!(alerts.update(&rules, &high).is_empty());
+        assert_eq!(alerts.hit("a").map(|hit| hit.price), Some(3_200.0));
+
+        // No data for the commodity keeps the rule's state.
+        assert!(alerts.update(&rules, &HashMap::new()).is_empty());
+        assert!(alerts.hit("a").is_some());
+        assert!(alerts.update(&rules, &high).is_empty());
+
+        assert!(alerts.update(&rules, &low).is_empty());
+        assert!(alerts.hit("a").is_none());
+        assert_eq!(alerts.update(&rules, &high).len(), 1);
+    }
+
+    #[test]
+    fn reset_and_disabled_rules() {
+        let mut rules = vec![rule("a", TradeSide::Sell, WatchDirection::Above, 3_000.0)];
+        let high = prices(vec![point(1, "Stanton", Some(3_200.0), None)]);
+        let mut alerts = WatchAlerts::default();
+
+        assert_eq!(alerts.update(&rules, &high).len(), 1);
+        alerts.reset("a");
+        assert_eq!(alerts.update(&rules, &high).len(), 1);
+
+        rules[0].enabled = false;
+        assert!(alerts.update(&rules, &high).is_empty());
+        assert!(alerts.hit("a").is_none());
+        rules[0].enabled = true;
+        assert_eq!(alerts.update(&rules, &high).len(), 1);
+    }
+}
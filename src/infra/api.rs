@@ -0,0 +1,534 @@
+//! Local HTTP/JSON API for overlays, stream-deck buttons and companion tools.
+//!
+//! The server only parses requests; it never touches UI state. Each request
+//! becomes an [`ApiCommand`] that is sent to the app together with a oneshot
+//! reply channel, so the app answers from `Signal<AppState>` on its own task.
+//!
+//! Bound to `127.0.0.1` only, and requests must name `localhost` or
+//! `127.0.0.1` as their `Host` so a DNS-rebound page can't reach it. `GET`
+//! answers allow any CORS origin so browser overlays can poll them; write
+//! endpoints require `Content-Type: application/json` (or are `DELETE`),
+//! which forces a CORS preflight that is never granted, so web pages can't
+//! change cargo behind the user's back.
+
+use std::{io, net::Ipv4Addr, time::Duration};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use thiserror::Error;
+use tracing::{debug, warn};
+use tokio::{
+    io::{AsyncRead, AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+    sync::{mpsc, oneshot},
+    time::{timeout_at, Instant},
+};
+
+/// Bumped when response shapes change incompatibly; part of every path.
+pub const API_VERSION: u32 = 1;
+
+const API_PREFIX: &str = "/api/v1";
+const MAX_HEADER_BYTES: usize = 16 * 1024;
+const MAX_BODY_BYTES: usize = 64 * 1024;
+/// Time a client gets to send its whole request.
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Error)]
+pub enum ApiError {
+    #[error("could not listen on 127.0.0.1:{port}: {source}")]
+    Bind { port: u16, source: io::Error },
+}
+
+/// What a request asks the app to do.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ApiCommand {
+    /// Items of the active manifest.
+    Cargo,
+    /// Expected value of the active manifest.
+    Summary,
+    /// Ranked sell suggestions per item.
+    BestPrices,
+    SellPlan(SellPlanMode),
+    AddCargo(AddCargo),
+    /// Remove `scu` (or everything) of the item matching `target`, which is
+    /// a cargo item id or a commodity name/code.
+    RemoveCargo { target: String, scu: Option<u32> },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SellPlanMode {
+    OneStop,
+    BestValue,
+}
+
+impl SellPlanMode {
+    pub fn key(self) -> &'static str {
+        match self {
+            SellPlanMode::OneStop => "one-stop",
+            SellPlanMode::BestValue => "best-value",
+        }
+    }
+}
+
+/// Body of `POST /api/v1/cargo`.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct AddCargo {
+    /// Commodity name, code or id; matched like the cargo import.
+    pub commodity: String,
+    pub scu: u32,
+    #[serde(default)]
+    pub hot: bool,
+}
+
+/// A parsed request waiting for the app's answer.
+pub struct ApiRequest {
+    pub command: ApiCommand,
+    pub reply: oneshot::Sender<ApiResponse>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ApiResponse {
+    pub status: u16,
+    pub body: Value,
+}
+
+impl ApiResponse {
+    pub fn ok(body: impl Serialize) -> Self {
+        Self {
+            status: 200,
+            body: serde_json::to_value(body).unwrap_or(Value::Null),
+        }
+    }
+
+    pub fn error(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            body: json!({ "error": message.into() }),
+        }
+    }
+}
+
+/// Either answered by the server itself or forwarded to the app.
+enum Routed {
+    Reply(ApiResponse),
+    Forward(ApiCommand),
+}
+
+/// Bind the listener up front so a busy port can be reported to the user.
+pub async fn bind(port: u16) -> Result<TcpListener, ApiError> {
+    TcpListener::bind((Ipv4Addr::LOCALHOST, port))
+        .await
+        .map_err(|source| ApiError::Bind { port, source })
+}
+
+/// Accept connections until the app drops its command receiver.
+pub async fn serve(listener: TcpListener, commands: mpsc::Sender<ApiRequest>) {
+    loop {
+        if commands.is_closed() {
+            return;
+        }
+        let (stream, _) = match listener.accept().await {
+            Ok(connection) => connection,
+            Err(err) => {
+                warn!(error = %err, "accept failed");
+                continue;
+            }
+        };
+        let commands = commands.clone();
+        tokio::spawn(async move {
+            if let Err(err) = handle_connection(stream, commands).await {
+                debug!(error = %err, "connection error");
+            }
+        });
+    }
+}
+
+/// One request per connection; overlays poll, so keep-alive isn't worth it.
+async fn handle_connection(
+    mut stream: TcpStream,
+    commands: mpsc::Sender<ApiRequest>,
+) -> io::Result<()> {
+    let (method, response) = match read_request(&mut stream).await? {
+        Some(request) => {
+            let response = match route(&request) {
+                Routed::Reply(response) => response,
+                Routed::Forward(command) => dispatch(&commands, command).await,
+            };
+            (request.method, response)
+        }
+        None => (String::new(), ApiResponse::error(400, "malformed request")),
+    };
+    stream.write_all(&encode_response(&method, &response)).await?;
+    stream.shutdown().await
+}
+
+async fn dispatch(commands: &mpsc::Sender<ApiRequest>, command: ApiCommand) -> ApiResponse {
+    let (reply, answer) = oneshot::channel();
+    if commands.send(ApiRequest { command, reply }).await.is_err() {
+        return ApiResponse::error(503, "app is shutting down");
+    }
+    answer
+        .await
+        .unwrap_or_else(|_| ApiResponse::error(503, "app did not answer"))
+}
+
+#[derive(Debug, Default)]
+struct HttpRequest {
+    method: String,
+    path: String,
+    query: String,
+    host: Option<String>,
+    content_type: Option<String>,
+    body: Vec<u8>,
+}
+
+/// Reads one request. A client that is slower than [`READ_TIMEOUT`] gets a
+/// `TimedOut` error.
+async fn read_request(stream: &mut (impl AsyncRead + Unpin)) -> io::Result<Option<HttpRequest>> {
+    let deadline = Instant::now() + READ_TIMEOUT;
+    let mut buffer = Vec::with_capacity(1024);
+    let mut chunk = [0_u8; 1024];
+    let header_end = loop {
+        if let Some(end) = find_header_end(&buffer) {
+            break end;
+        }
+        if buffer.len() > MAX_HEADER_BYTES {
+            return Ok(None);
+        }
+        let read = read_before(stream, &mut chunk, deadline).await?;
+        if read == 0 {
+            return Ok(None);
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    };
+
+    let Ok(head) = std::str::from_utf8(&buffer[..header_end]) else {
+        return Ok(None);
+    };
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next().unwrap_or_default().split_whitespace();
+    let (Some(method), Some(target)) = (request_line.next(), request_line.next()) else {
+        return Ok(None);
+    };
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+
+    let mut content_length = 0_usize;
+    let mut content_type = None;
+    let mut host = None;
+    for line in lines {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        match name.trim().to_ascii_lowercase().as_str() {
+            "content-length" => content_length = value.trim().parse().unwrap_or(usize::MAX),
+            "content-type" => content_type = Some(value.trim().to_ascii_lowercase()),
+            "host" => host = Some(value.trim().to_ascii_lowercase()),
+            _ => {}
+        }
+    }
+    if content_length > MAX_BODY_BYTES {
+        return Ok(None);
+    }
+
+    let mut body = buffer[header_end + 4..].to_vec();
+    while body.len() < content_length {
+        let read = read_before(stream, &mut chunk, deadline).await?;
+        if read == 0 {
+            return Ok(None);
+        }
+        body.extend_from_slice(&chunk[..read]);
+    }
+    body.truncate(content_length);
+
+    Ok(Some(HttpRequest {
+        method: method.to_ascii_uppercase(),
+        path: path.trim_end_matches('/').to_string(),
+        query: query.to_string(),
+        host,
+        content_type,
+        body,
+    }))
+}
+
+async fn read_before(
+    stream: &mut (impl AsyncRead + Unpin),
+    chunk: &mut [u8],
+    deadline: Instant,
+) -> io::Result<usize> {
+    timeout_at(deadline, stream.read(chunk))
+        .await
+        .map_err(|_| io::Error::new(io::ErrorKind::TimedOut, "request not received in time"))?
+}
+
+/// Whether `Host` names this machine; anything else is a rebound DNS name.
+fn is_local_host(host: Option<&str>) -> bool {
+    let Some(host) = host else {
+        return false;
+    };
+    let name = match host.rsplit_once(':') {
+        Some((name, port)) if port.parse::<u16>().is_ok() => name,
+        _ => host,
+    };
+    matches!(name, "localhost" | "127.0.0.1")
+}
+
+fn find_header_end(buffer: &[u8]) -> Option<usize> {
+    buffer.windows(4).position(|window| window == b"\r\n\r\n")
+}
+
+fn route(request: &HttpRequest) -> Routed {
+    if !is_local_host(request.host.as_deref()) {
+        return Routed::Reply(ApiResponse::error(403, "Host must be localhost or 127.0.0.1"));
+    }
+    let Some(resource) = request.path.strip_prefix(API_PREFIX) else {
+        return Routed::Reply(ApiResponse::error(404, "unknown endpoint; see /api/v1/health"));
+    };
+    let query_param = |key: &str| {
+        url::form_urlencoded::parse(request.query.as_bytes())
+            .find(|(name, _)| name == key)
+            .map(|(_, value)| value.into_owned())
+    };
+
+    match (request.method.as_str(), resource) {
+        ("GET", "/health") => Routed::Reply(ApiResponse::ok(json!({
+            "app": crate::util::version::APP_NAME,
+            "version": env!("CARGO_PKG_VERSION"),
+            "api_version": API_VERSION,
+        }))),
+        ("GET", "/cargo") => Routed::Forward(ApiCommand::Cargo),
+        ("GET", "/summary") => Routed::Forward(ApiCommand::Summary),
+        ("GET", "/best-prices") => Routed::Forward(ApiCommand::BestPrices),
+        ("GET", "/sell-plan") => match query_param("mode").as_deref() {
+            None | Some("best-value") => Routed::Forward(ApiCommand::SellPlan(SellPlanMode::BestValue)),
+            Some("one-stop") => Routed::Forward(ApiCommand::SellPlan(SellPlanMode::OneStop)),
+            Some(other) => Routed::Reply(ApiResponse::error(
+                400,
+                format!("unknown mode {other:?}; use one-stop or best-value"),
+            )),
+        },
+        ("POST", "/cargo") => {
+            let is_json = request
+                .content_type
+                .as_deref()
+                .is_some_and(|value| value.starts_with("application/json"));
+            if !is_json {
+                return Routed::Reply(ApiResponse::error(415, "expected Content-Type: application/json"));
+            }
+            match serde_json::from_slice::<AddCargo>(&request.body) {
+                Ok(add) if add.scu > 0 && !add.commodity.trim().is_empty() => {
+                    Routed::Forward(ApiCommand::AddCargo(add))
+                }
+                Ok(_) => Routed::Reply(ApiResponse::error(400, "commodity and scu > 0 are required")),
+                Err(err) => Routed::Reply(ApiResponse::error(400, format!("invalid body: {err}"))),
+            }
+        }
+        ("DELETE", resource) if resource.starts_with("/cargo/") => {
+            let target = decode_segment(&resource["/cargo/".len()..]);
+            let scu = match query_param("scu").map(|value| value.parse::<u32>()) {
+                None => None,
+                Some(Ok(scu)) if scu > 0 => Some(scu),
+                Some(_) => return Routed::Reply(ApiResponse::error(400, "scu must be a positive number")),
+            };
+            Routed::Forward(ApiCommand::RemoveCargo { target, scu })
+        }
+        ("OPTIONS", _) => Routed::Reply(ApiResponse {
+            status: 204,
+            body: Value::Null,
+        }),
+        (_, "/health" | "/cargo" | "/summary" | "/best-prices" | "/sell-plan") => {
+            Routed::Reply(ApiResponse::error(405, "method not allowed"))
+        }
+        _ => Routed::Reply(ApiResponse::error(404, "unknown endpoint; see /api/v1/health")),
+    }
+}
+
+/// Percent-decode one path segment ("Agricultural%20Supplies").
+fn decode_segment(segment: &str) -> String {
+    url::form_urlencoded::parse(format!("v={}", segment.replace('+', "%2B")).as_bytes())
+        .next()
+        .map(|(_, value)| value.into_owned())
+        .unwrap_or_default()
+}
+
+fn encode_response(method: &str, response: &ApiResponse) -> Vec<u8> {
+    let reason = match response.status {
+        200 => "OK",
+        204 => "No Content",
+        400 => "Bad Request",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        415 => "Unsupported Media Type",
+        503 => "Service Unavailable",
+        _ => "Error",
+    };
+    let body = if response.status == 204 {
+        String::new()
+    } else {
+        format!("{}\n", response.body)
+    };
+
+    let mut head = format!(
+        "HTTP/1.1 {} {reason}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n",
+        response.status,
+        body.len()
+    );
+    // Reads are open to browser overlays; everything else stays same-origin.
+    if method == "GET" {
+        head.push_str("Access-Control-Allow-Origin: *\r\n");
+    }
+    head.push_str("\r\n");
+
+    let mut bytes = head.into_bytes();
+    bytes.extend_from_slice(body.as_bytes());
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::io::duplex;
+
+    use super::*;
+
+    fn request(method: &str, path: &str) -> HttpRequest {
+        let (path, query) = path.split_once('?').unwrap_or((path, ""));
+        HttpRequest {
+            method: method.to_string(),
+            path: path.to_string(),
+            query: query.to_string(),
+            host: Some("127.0.0.1:8765".to_string()),
+            ..HttpRequest::default()
+        }
+    }
+
+    fn json_post(path: &str, body: &str) -> HttpRequest {
+        HttpRequest {
+            content_type: Some("application/json; charset=utf-8".to_string()),
+            body: body.as_bytes().to_vec(),
+            ..request("POST", path)
+        }
+    }
+
+    fn reply_status(routed: Routed) -> u16 {
+        match routed {
+            Routed::Reply(response) => response.status,
+            Routed::Forward(command) => panic!("expected a reply, got {command:?}"),
+        }
+    }
+
+    fn forwarded(routed: Routed) -> ApiCommand {
+        match routed {
+            Routed::Forward(command) => command,
+            Routed::Reply(response) => panic!("expected a command, got {response:?}"),
+        }
+    }
+
+    async fn parse(raw: &str) -> Option<HttpRequest> {
+        let (mut client, mut server) = duplex(MAX_HEADER_BYTES * 2);
+        client.write_all(raw.as_bytes()).await.unwrap();
+        drop(client);
+        read_request(&mut server).await.unwrap()
+    }
+
+    #[test]
+    fn routes_reads_and_writes() {
+        assert_eq!(reply_status(route(&request("GET", "/api/v1/health"))), 200);
+        assert_eq!(forwarded(route(&request("GET", "/api/v1/cargo"))), ApiCommand::Cargo);
+        assert_eq!(
+            forwarded(route(&request("GET", "/api/v1/sell-plan?mode=one-stop"))),
+            ApiCommand::SellPlan(SellPlanMode::OneStop)
+        );
+        assert_eq!(reply_status(route(&request("GET", "/api/v1/sell-plan?mode=fast"))), 400);
+        assert_eq!(reply_status(route(&request("PUT", "/api/v1/cargo"))), 405);
+        assert_eq!(reply_status(route(&request("GET", "/api/v2/cargo"))), 404);
+
+        assert_eq!(
+            forwarded(route(&json_post("/api/v1/cargo", r#"{"commodity":"Gold","scu":12}"#))),
+            ApiCommand::AddCargo(AddCargo {
+                commodity: "Gold".to_string(),
+                scu: 12,
+                hot: false,
+            })
+        );
+        assert_eq!(reply_status(route(&json_post("/api/v1/cargo", r#"{"commodity":"Gold","scu":0}"#))), 400);
+        assert_eq!(reply_status(route(&json_post("/api/v1/cargo", "not json"))), 400);
+        let form = HttpRequest {
+            content_type: Some("application/x-www-form-urlencoded".to_string()),
+            ..json_post("/api/v1/cargo", r#"{"commodity":"Gold","scu":1}"#)
+        };
+        assert_eq!(reply_status(route(&form)), 415);
+
+        assert_eq!(
+            forwarded(route(&request("DELETE", "/api/v1/cargo/Agricultural%20Supplies?scu=4"))),
+            ApiCommand::RemoveCargo {
+                target: "Agricultural Supplies".to_string(),
+                scu: Some(4),
+            }
+        );
+        assert_eq!(reply_status(route(&request("DELETE", "/api/v1/cargo/gold?scu=0"))), 400);
+    }
+
+    #[test]
+    fn only_local_host_names_are_served() {
+        for host in ["localhost", "localhost:8765", "127.0.0.1", "127.0.0.1:80"] {
+            let request = HttpRequest {
+                host: Some(host.to_string()),
+                ..request("GET", "/api/v1/health")
+            };
+            assert_eq!(reply_status(route(&request)), 200, "{host}");
+        }
+        for host in [None, Some("evil.example"), Some("evil.example:8765"), Some("127.0.0.1.nip.io"), Some("localhost:x")] {
+            let request = HttpRequest {
+                host: host.map(str::to_string),
+                ..request("GET", "/api/v1/cargo")
+            };
+            assert_eq!(reply_status(route(&request)), 403, "{host:?}");
+        }
+    }
+
+    #[test]
+    fn cors_is_only_granted_to_reads() {
+        let response = ApiResponse::ok(json!({}));
+        let has_cors = |method: &str| {
+            String::from_utf8(encode_response(method, &response))
+                .unwrap()
+                .contains("Access-Control-Allow-Origin: *")
+        };
+        assert!(has_cors("GET"));
+        assert!(!has_cors("POST"));
+        assert!(!has_cors("DELETE"));
+        assert!(!has_cors("OPTIONS"));
+    }
+
+    #[tokio::test]
+    async fn parses_requests_with_headers_and_body() {
+        let request = parse(
+            "post /api/v1/cargo/?x=1 HTTP/1.1\r\nHost: Localhost:8765\r\nContent-Type: Application/JSON\r\n\
+             Content-Length: 5\r\n\r\nhello and more",
+        )
+        .await
+        .unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/api/v1/cargo");
+        assert_eq!(request.query, "x=1");
+        assert_eq!(request.host.as_deref(), Some("localhost:8765"));
+        assert_eq!(request.content_type.as_deref(), Some("application/json"));
+        assert_eq!(request.body, b"hello");
+
+        // Truncated body, oversized body, garbage.
+        assert!(parse("POST / HTTP/1.1\r\nContent-Length: 10\r\n\r\nabc").await.is_none());
+        let oversized = format!("POST / HTTP/1.1\r\nContent-Length: {}\r\n\r\n", MAX_BODY_BYTES + 1);
+        assert!(parse(&oversized).await.is_none());
+        assert!(parse("\r\n\r\n").await.is_none());
+        assert!(parse(&"x".repeat(MAX_HEADER_BYTES + 2048)).await.is_none());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn slow_clients_time_out() {
+        let (mut client, mut server) = duplex(1024);
+        client.write_all(b"GET /api/v1/health HTTP/1.1\r\n").await.unwrap();
+        let err = read_request(&mut server).await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+        drop(client);
+    }
+}
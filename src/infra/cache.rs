@@ -9,14 +9,14 @@ use std::{
 };
 
 use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
 
-use crate::domain::{Terminal, TradeRoute};
+use crate::domain::{Terminal, TradeRoute, TrafficHistory};
+use crate::infra::config;
 
 const CACHE_FILENAME: &str = "terminal_cache.json";
 const ROUTES_CACHE_FILENAME: &str = "routes_cache.json";
-
-/// Cache TTL: 7 days. Terminals don't change often (only with major patches).
-pub const TERMINAL_CACHE_TTL: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+const TRAFFIC_HISTORY_FILENAME: &str = "traffic_history.json";
 
 /// Cached terminal data with TTL + version tracking.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -59,9 +59,9 @@ impl TerminalCache {
             .any(|t| t.id == terminal_id && t.is_nqa)
     }
 
-    /// Check if cache has expired (older than TTL).
+    /// Check if cache has expired (older than `cache.terminal_ttl_secs`).
     pub fn is_expired(&self) -> bool {
-        self.age() > TERMINAL_CACHE_TTL
+        self.age() > config::current().cache.terminal_ttl()
     }
 
     /// Get cache age as Duration.
@@ -109,23 +109,23 @@ pub fn load_terminal_cache() -> Option<TerminalCache> {
     let path = cache_path();
     
     if !path.exists() {
-        println!("[cache] No terminal cache found at {}", path.display());
+        debug!(path = %path.display(), "no terminal cache");
         return None;
     }
 
     match fs::read_to_string(&path) {
         Ok(content) => match serde_json::from_str(&content) {
             Ok(cache) => {
-                println!("[cache] Loaded terminal cache from {}", path.display());
+                debug!(path = %path.display(), "loaded terminal cache");
                 Some(cache)
             }
             Err(e) => {
-                println!("[cache] Failed to parse terminal cache: {e}");
+                warn!(error = %e, "failed to parse the terminal cache");
                 None
             }
         },
         Err(e) => {
-            println!("[cache] Failed to read terminal cache: {e}");
+            warn!(error = %e, "failed to read the terminal cache");
             None
         }
     }
@@ -136,11 +136,11 @@ pub fn save_terminal_cache(cache: &TerminalCache) -> Result<(), std::io::Error>
     let path = cache_path();
     let content = serde_json::to_string_pretty(cache)?;
     fs::write(&path, content)?;
-    println!(
-        "[cache] Saved terminal cache ({} terminals, version {}) to {}",
-        cache.terminals.len(),
-        cache.game_version,
-        path.display()
+    debug!(
+        count = cache.terminals.len(),
+        version = %cache.game_version,
+        path = %path.display(),
+        "saved terminal cache"
     );
     Ok(())
 }
@@ -151,12 +151,9 @@ pub fn cached_game_version() -> Option<String> {
 }
 
 // ============================================================================
-// Trade Routes Cache (24h TTL)
+// Trade Routes Cache (`cache.routes_ttl_secs`, 24h by default)
 // ============================================================================
 
-/// Cache TTL for routes: 24 hours.
-pub const ROUTES_CACHE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
-
 /// Cached trade routes with TTL.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RoutesCache {
@@ -176,9 +173,9 @@ impl RoutesCache {
         Self { cached_at, routes }
     }
 
-    /// Check if cache has expired (older than 24h).
+    /// Check if cache has expired (older than `cache.routes_ttl_secs`).
     pub fn is_expired(&self) -> bool {
-        self.age() > ROUTES_CACHE_TTL
+        self.age() > config::current().cache.routes_ttl()
     }
 
     /// Get cache age as Duration.
@@ -223,7 +220,7 @@ pub fn load_routes_cache() -> Option<RoutesCache> {
     let path = routes_cache_path();
     
     if !path.exists() {
-        println!("[routes-cache] No cache found");
+        debug!("no routes cache");
         return None;
     }
 
@@ -231,23 +228,19 @@ pub fn load_routes_cache() -> Option<RoutesCache> {
         Ok(content) => match serde_json::from_str::<RoutesCache>(&content) {
             Ok(cache) => {
                 if cache.is_expired() {
-                    println!("[routes-cache] Cache expired (age: {})", cache.age_string());
+                    debug!(age = %cache.age_string(), "routes cache expired");
                     return None;
                 }
-                println!(
-                    "[routes-cache] Loaded {} routes (age: {})",
-                    cache.routes.len(),
-                    cache.age_string()
-                );
+                debug!(count = cache.routes.len(), age = %cache.age_string(), "loaded routes cache");
                 Some(cache)
             }
             Err(e) => {
-                println!("[routes-cache] Failed to parse: {e}");
+                warn!(error = %e, "failed to parse the routes cache");
                 None
             }
         },
         Err(e) => {
-            println!("[routes-cache] Failed to read: {e}");
+            warn!(error = %e, "failed to read the routes cache");
             None
         }
     }
@@ -258,10 +251,48 @@ pub fn save_routes_cache(cache: &RoutesCache) -> Result<(), std::io::Error> {
     let path = routes_cache_path();
     let content = serde_json::to_string(cache)?; // compact, not pretty (can be large)
     fs::write(&path, content)?;
-    println!(
-        "[routes-cache] Saved {} routes to {}",
-        cache.routes.len(),
-        path.display()
-    );
+    debug!(count = cache.routes.len(), path = %path.display(), "saved routes cache");
     Ok(())
 }
+
+// ============================================================================
+// Traffic history (one snapshot per routes fetch)
+// ============================================================================
+
+/// Get the traffic history file path.
+fn traffic_history_path() -> PathBuf {
+    static PATH: OnceLock<PathBuf> = OnceLock::new();
+    PATH.get_or_init(|| {
+        let base = dirs::data_local_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("cargo-value-scanner");
+        let _ = fs::create_dir_all(&base);
+        base.join(TRAFFIC_HISTORY_FILENAME)
+    })
+    .clone()
+}
+
+/// Load the traffic history; empty if missing or unreadable.
+pub fn load_traffic_history() -> TrafficHistory {
+    let path = traffic_history_path();
+    if !path.exists() {
+        return TrafficHistory::default();
+    }
+    match fs::read_to_string(&path).map(|content| serde_json::from_str(&content)) {
+        Ok(Ok(history)) => history,
+        Ok(Err(e)) => {
+            warn!(error = %e, "failed to parse the traffic history");
+            TrafficHistory::default()
+        }
+        Err(e) => {
+            warn!(error = %e, "failed to read the traffic history");
+            TrafficHistory::default()
+        }
+    }
+}
+
+/// Save the traffic history to disk.
+pub fn save_traffic_history(history: &TrafficHistory) -> Result<(), std::io::Error> {
+    let content = serde_json::to_string(history)?;
+    fs::write(traffic_history_path(), content)
+}
@@ -1 +1,1008 @@
-//! Persistent configuration loading and storage will live here.
+//! Application configuration: a versioned `config.json` next to the user state.
+//!
+//! - Typed sections (network, cache, ranking, UI, profiles, local API, game log,
+//!   logging, webhooks, reports, watchlist) with serde defaults,
+//!   so a partial or older file still loads.
+//! - `validate()` rejects values that would break the app (zero TTLs, bad URLs).
+//! - `CVS_*` environment variables override file values at startup without
+//!   being written back to disk.
+
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        OnceLock, RwLock,
+    },
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+use tracing::level_filters::LevelFilter;
+
+use super::webhook::ShareKind;
+use crate::domain::{
+    render_report, Profile, ProfitabilityParams, RankingParams, ReportScope, ReportTemplate,
+    WatchRule,
+};
+use crate::util::{
+    i18n::Locale,
+    palette::{Palette, ThemeChoice},
+    persistence::{back_up, write_atomic, StoreFile},
+};
+
+/// Current on-disk config schema version.
+pub const CONFIG_VERSION: u32 = 1;
+
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    #[error("config directory unavailable")]
+    StorageUnavailable,
+    #[error("failed to read or write config: {0}")]
+    Io(#[from] io::Error),
+    #[error("config file is not valid JSON: {0}")]
+    Parse(#[from] serde_json::Error),
+    #[error("config version {0} is newer than this app supports ({CONFIG_VERSION})")]
+    UnsupportedVersion(u32),
+    #[error("invalid value for {field}: {reason}")]
+    Invalid { field: &'static str, reason: String },
+    #[error("environment variable {var} has invalid value {value:?}")]
+    Env { var: &'static str, value: String },
+    #[error("saving paused until the damaged config is recovered")]
+    RecoveryPending,
+}
+
+impl ConfigError {
+    /// The file itself can't be used and needs a recovery decision, as
+    /// opposed to storage that couldn't be reached.
+    pub fn is_damaged(&self) -> bool {
+        matches!(
+            self,
+            ConfigError::Parse(_) | ConfigError::UnsupportedVersion(_) | ConfigError::Invalid { .. }
+        )
+    }
+
+    fn invalid(field: &'static str, reason: impl Into<String>) -> Self {
+        Self::Invalid {
+            field,
+            reason: reason.into(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppConfig {
+    pub version: u32,
+    pub network: NetworkConfig,
+    pub cache: CacheConfig,
+    pub ranking: RankingConfig,
+    pub ui: UiConfig,
+    pub profiles: ProfilesConfig,
+    pub api: ApiConfig,
+    pub game_log: GameLogConfig,
+    pub logging: LoggingConfig,
+    pub webhooks: WebhookConfig,
+    pub reports: ReportConfig,
+    pub watchlist: WatchlistConfig,
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            version: CONFIG_VERSION,
+            network: NetworkConfig::default(),
+            cache: CacheConfig::default(),
+            ranking: RankingConfig::default(),
+            ui: UiConfig::default(),
+            profiles: ProfilesConfig::default(),
+            api: ApiConfig::default(),
+            game_log: GameLogConfig::default(),
+            logging: LoggingConfig::default(),
+            webhooks: WebhookConfig::default(),
+            reports: ReportConfig::default(),
+            watchlist: WatchlistConfig::default(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NetworkConfig {
+    /// UEX API v2 base URL (must end with a slash).
+    pub base_url: String,
+    pub request_timeout_secs: u64,
+    /// How often the watchlist poller wakes up.
+    pub watch_poll_secs: u64,
+}
+
+impl Default for NetworkConfig {
+    fn default() -> Self {
+        Self {
+            base_url: "https://api.uexcorp.uk/2.0/".to_string(),
+            request_timeout_secs: 30,
+            watch_poll_secs: 5 * 60,
+        }
+    }
+}
+
+impl NetworkConfig {
+    pub fn request_timeout(&self) -> Duration {
+        Duration::from_secs(self.request_timeout_secs)
+    }
+
+    pub fn watch_poll_interval(&self) -> Duration {
+        Duration::from_secs(self.watch_poll_secs)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CacheConfig {
+    /// Commodity and price data freshness.
+    pub price_ttl_secs: u64,
+    /// Terminal list on disk; terminals only change with major patches.
+    pub terminal_ttl_secs: u64,
+    /// Pre-computed trade routes on disk.
+    pub routes_ttl_secs: u64,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            price_ttl_secs: 60 * 60,
+            terminal_ttl_secs: 7 * 24 * 60 * 60,
+            routes_ttl_secs: 24 * 60 * 60,
+        }
+    }
+}
+
+impl CacheConfig {
+    pub fn price_ttl(&self) -> Duration {
+        Duration::from_secs(self.price_ttl_secs)
+    }
+
+    pub fn terminal_ttl(&self) -> Duration {
+        Duration::from_secs(self.terminal_ttl_secs)
+    }
+
+    pub fn routes_ttl(&self) -> Duration {
+        Duration::from_secs(self.routes_ttl_secs)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RankingConfig {
+    pub home_system: String,
+    pub cross_system_penalty: f64,
+    pub armistice_penalty: f64,
+    pub hotspot_penalty: f64,
+    /// Terminal name fragments treated as pirate hotspots.
+    pub hotspots: Vec<String>,
+    /// Sell suggestions kept per commodity on the Best Price page.
+    pub suggestions_per_item: usize,
+    /// Minimum buy price (aUEC/SCU) for the pirate "high value only" route filter.
+    pub pirate_min_buy_price: f64,
+}
+
+impl Default for RankingConfig {
+    fn default() -> Self {
+        let params = RankingParams::default();
+        Self {
+            home_system: params.home_system,
+            cross_system_penalty: params.cross_system_penalty,
+            armistice_penalty: params.armistice_penalty,
+            hotspot_penalty: params.hotspot_penalty,
+            hotspots: params.hotspots,
+            suggestions_per_item: params.suggestions_per_item,
+            pirate_min_buy_price: 5000.0,
+        }
+    }
+}
+
+impl RankingConfig {
+    pub fn params(&self) -> RankingParams {
+        RankingParams {
+            home_system: self.home_system.clone(),
+            cross_system_penalty: self.cross_system_penalty,
+            armistice_penalty: self.armistice_penalty,
+            hotspot_penalty: self.hotspot_penalty,
+            hotspots: self.hotspots.clone(),
+            suggestions_per_item: self.suggestions_per_item,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UiConfig {
+    /// Maximum rows shown on the routes page.
+    pub max_routes: usize,
+    pub toast_dismiss_secs: u64,
+    /// Language of the interface.
+    pub locale: Locale,
+    /// Colour theme; `auto` follows the gameplay profile.
+    pub theme: ThemeChoice,
+    /// Palette used when `theme` is `custom`.
+    pub custom_theme: Palette,
+}
+
+impl Default for UiConfig {
+    fn default() -> Self {
+        Self {
+            max_routes: 100,
+            toast_dismiss_secs: 6,
+            locale: Locale::default(),
+            theme: ThemeChoice::default(),
+            custom_theme: Palette::default(),
+        }
+    }
+}
+
+impl UiConfig {
+    pub fn toast_dismiss(&self) -> Duration {
+        Duration::from_secs(self.toast_dismiss_secs)
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProfilesConfig {
+    /// Profile preselected on first launch (`None` shows the picker).
+    pub default_profile: Profile,
+    /// Profitability parameters used on first launch and by "Reset".
+    pub default_profitability: ProfitabilityParams,
+}
+
+/// Embedded HTTP/JSON API for overlays and companion tools. Always bound to
+/// `127.0.0.1`; changes take effect on the next start.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ApiConfig {
+    pub enabled: bool,
+    pub port: u16,
+}
+
+impl Default for ApiConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: 47110,
+        }
+    }
+}
+
+/// Follow the game's `Game.log` to set the planner position and capture trades.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GameLogConfig {
+    pub enabled: bool,
+    /// Full path to `Game.log`; empty means look in the default install folders.
+    pub path: String,
+    pub poll_secs: u64,
+    /// Turn commodity kiosk purchases and sales into cargo changes and ledger entries.
+    pub capture_trades: bool,
+}
+
+impl Default for GameLogConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            path: String::new(),
+            poll_secs: 2,
+            capture_trades: true,
+        }
+    }
+}
+
+impl GameLogConfig {
+    pub fn poll_interval(&self) -> Duration {
+        Duration::from_secs(self.poll_secs)
+    }
+}
+
+/// Log levels and the rotating log file; read once at startup.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LoggingConfig {
+    /// `error`, `warn`, `info`, `debug`, `trace` or `off`.
+    pub level: String,
+    /// Per-module levels, e.g. `"infra::uex": "debug"`; paths are relative to the crate.
+    pub modules: BTreeMap<String, String>,
+    /// Also write daily log files to the app's data folder.
+    pub file: bool,
+    /// Daily log files kept before the oldest is deleted.
+    pub keep_files: usize,
+    /// Log large API payloads in full instead of only their size.
+    pub debug_payloads: bool,
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            level: "info".to_string(),
+            modules: BTreeMap::new(),
+            file: true,
+            keep_files: 7,
+            debug_payloads: false,
+        }
+    }
+}
+
+/// Discord webhooks that summaries and plans can be posted to.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WebhookConfig {
+    pub targets: Vec<WebhookTarget>,
+    /// Shortest gap between two posts to the same webhook.
+    pub min_interval_secs: u64,
+    pub templates: ShareTemplates,
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        Self {
+            targets: Vec::new(),
+            min_interval_secs: 5,
+            templates: ShareTemplates::default(),
+        }
+    }
+}
+
+impl WebhookConfig {
+    pub fn min_interval(&self) -> Duration {
+        Duration::from_secs(self.min_interval_secs)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WebhookTarget {
+    pub name: String,
+    pub url: String,
+    pub enabled: bool,
+    /// Poster name shown in the channel; empty keeps the webhook's own.
+    pub username: String,
+}
+
+impl Default for WebhookTarget {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            url: String::new(),
+            enabled: true,
+            username: String::new(),
+        }
+    }
+}
+
+/// Embed title and description per report, in the text report template
+/// syntax; placeholders are filled in when posting (see `infra::webhook`).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ShareTemplates {
+    pub best_prices: ShareTemplate,
+    pub sell_plan: ShareTemplate,
+    pub crew_payout: ShareTemplate,
+    pub loot: ShareTemplate,
+}
+
+impl Default for ShareTemplates {
+    fn default() -> Self {
+        let template = |title: &str, description: &str| ShareTemplate {
+            title: title.to_string(),
+            description: description.to_string(),
+        };
+        Self {
+            best_prices: template("Best prices · {manifest}", "{items} commodities · {scu} SCU · up to {total} aUEC"),
+            sell_plan: template("Sell plan · {manifest}", "{stops} stop(s) · {total} aUEC"),
+            crew_payout: template("Crew payout · {manifest}", "{total} aUEC split between {crew} crew"),
+            loot: template("Loot report · {manifest}", "{items} commodities · {scu} SCU · expected {total} aUEC"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ShareTemplate {
+    pub title: String,
+    pub description: String,
+}
+
+/// User-defined report templates; the built-in presets are not stored.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReportConfig {
+    pub templates: Vec<ReportTemplate>,
+}
+
+/// Price alert rules checked by the background poller.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WatchlistConfig {
+    pub rules: Vec<WatchRule>,
+}
+
+impl AppConfig {
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.version > CONFIG_VERSION {
+            return Err(ConfigError::UnsupportedVersion(self.version));
+        }
+
+        let url = url::Url::parse(&self.network.base_url)
+            .map_err(|err| ConfigError::invalid("network.base_url", err.to_string()))?;
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(ConfigError::invalid("network.base_url", "must be http or https"));
+        }
+        if !self.network.base_url.ends_with('/') {
+            return Err(ConfigError::invalid("network.base_url", "must end with '/'"));
+        }
+        if self.network.request_timeout_secs == 0 {
+            return Err(ConfigError::invalid("network.request_timeout_secs", "must be at least 1"));
+        }
+        if self.network.watch_poll_secs < 30 {
+            return Err(ConfigError::invalid("network.watch_poll_secs", "must be at least 30"));
+        }
+
+        for (field, value) in [
+            ("cache.price_ttl_secs", self.cache.price_ttl_secs),
+            ("cache.terminal_ttl_secs", self.cache.terminal_ttl_secs),
+            ("cache.routes_ttl_secs", self.cache.routes_ttl_secs),
+        ] {
+            if value == 0 {
+                return Err(ConfigError::invalid(field, "must be at least 1 second"));
+            }
+        }
+
+        for (field, value) in [
+            ("ranking.cross_system_penalty", self.ranking.cross_system_penalty),
+            ("ranking.armistice_penalty", self.ranking.armistice_penalty),
+            ("ranking.hotspot_penalty", self.ranking.hotspot_penalty),
+            ("ranking.pirate_min_buy_price", self.ranking.pirate_min_buy_price),
+        ] {
+            if !value.is_finite() || value < 0.0 {
+                return Err(ConfigError::invalid(field, "must be a non-negative number"));
+            }
+        }
+        if self.ranking.home_system.trim().is_empty() {
+            return Err(ConfigError::invalid("ranking.home_system", "must not be empty"));
+        }
+        if self.ranking.suggestions_per_item == 0 {
+            return Err(ConfigError::invalid("ranking.suggestions_per_item", "must be at least 1"));
+        }
+
+        if self.ui.max_routes == 0 {
+            return Err(ConfigError::invalid("ui.max_routes", "must be at least 1"));
+        }
+
+        if self.webhooks.min_interval_secs == 0 {
+            return Err(ConfigError::invalid("webhooks.min_interval_secs", "must be at least 1"));
+        }
+        for kind in ShareKind::ALL {
+            let template = kind.template(&self.webhooks.templates);
+            for text in [&template.title, &template.description] {
+                if let Err(err) = render_report(text, &ReportScope::default()) {
+                    return Err(ConfigError::invalid("webhooks.templates", format!("{}: {err}", kind.label())));
+                }
+            }
+        }
+        for target in &self.webhooks.targets {
+            let valid = url::Url::parse(&target.url)
+                .is_ok_and(|url| matches!(url.scheme(), "http" | "https"));
+            if !valid {
+                return Err(ConfigError::invalid("webhooks.targets", format!("'{}' is not an http(s) URL", target.url)));
+            }
+        }
+        if self.ui.toast_dismiss_secs == 0 {
+            return Err(ConfigError::invalid("ui.toast_dismiss_secs", "must be at least 1"));
+        }
+        if let Some((token, value)) = self.ui.custom_theme.invalid_token() {
+            return Err(ConfigError::invalid(
+                "ui.custom_theme",
+                format!("{token} must be a hex colour, got {value:?}"),
+            ));
+        }
+
+        if self.api.port < 1024 {
+            return Err(ConfigError::invalid("api.port", "must be between 1024 and 65535"));
+        }
+
+        if self.game_log.poll_secs == 0 {
+            return Err(ConfigError::invalid("game_log.poll_secs", "must be at least 1"));
+        }
+
+        let params = &self.profiles.default_profitability;
+        if !(0.0..=1.0).contains(&params.risk_pct) {
+            return Err(ConfigError::invalid(
+                "profiles.default_profitability.risk_pct",
+                "must be between 0 and 1",
+            ));
+        }
+        if params.crew_size == 0 {
+            return Err(ConfigError::invalid(
+                "profiles.default_profitability.crew_size",
+                "must be at least 1",
+            ));
+        }
+
+        if self.logging.level.parse::<LevelFilter>().is_err() {
+            return Err(ConfigError::invalid("logging.level", format!("unknown level {:?}", self.logging.level)));
+        }
+        for (module, level) in &self.logging.modules {
+            let valid_path = !module.is_empty()
+                && module.split("::").all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'));
+            if !valid_path {
+                return Err(ConfigError::invalid("logging.modules", format!("{module:?} is not a module path")));
+            }
+            if level.parse::<LevelFilter>().is_err() {
+                return Err(ConfigError::invalid("logging.modules", format!("unknown level {level:?} for {module}")));
+            }
+        }
+        if self.logging.keep_files == 0 {
+            return Err(ConfigError::invalid("logging.keep_files", "must be at least 1"));
+        }
+
+        for (index, template) in self.reports.templates.iter().enumerate() {
+            if template.name.trim().is_empty() {
+                return Err(ConfigError::invalid("reports.templates", "every template needs a name"));
+            }
+            if template.is_preset() || self.reports.templates[..index].iter().any(|t| t.id == template.id) {
+                return Err(ConfigError::invalid("reports.templates", format!("duplicate id {:?}", template.id)));
+            }
+            if let Err(err) = render_report(&template.body, &ReportScope::default()) {
+                return Err(ConfigError::invalid("reports.templates", format!("{}: {err}", template.name)));
+            }
+        }
+
+        for (index, rule) in self.watchlist.rules.iter().enumerate() {
+            if rule.commodity_id.trim().is_empty() {
+                return Err(ConfigError::invalid("watchlist.rules", "every rule needs a commodity"));
+            }
+            if !rule.threshold.is_finite() || rule.threshold <= 0.0 {
+                return Err(ConfigError::invalid(
+                    "watchlist.rules",
+                    format!("{}: threshold must be a positive number", rule.commodity_name),
+                ));
+            }
+            if self.watchlist.rules[..index].iter().any(|r| r.id == rule.id) {
+                return Err(ConfigError::invalid("watchlist.rules", format!("duplicate id {:?}", rule.id)));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Apply `CVS_*` overrides, looking variables up through `lookup` so the
+    /// source can be swapped out (e.g. `std::env::var`).
+    pub fn apply_env_overrides(
+        &mut self,
+        lookup: impl Fn(&str) -> Option<String>,
+    ) -> Result<(), ConfigError> {
+        fn parse<T: std::str::FromStr>(var: &'static str, value: String) -> Result<T, ConfigError> {
+            value
+                .trim()
+                .parse()
+                .map_err(|_| ConfigError::Env { var, value })
+        }
+
+        if let Some(value) = lookup("CVS_BASE_URL") {
+            self.network.base_url = value;
+        }
+        if let Some(value) = lookup("CVS_REQUEST_TIMEOUT_SECS") {
+            self.network.request_timeout_secs = parse("CVS_REQUEST_TIMEOUT_SECS", value)?;
+        }
+        if let Some(value) = lookup("CVS_WATCH_POLL_SECS") {
+            self.network.watch_poll_secs = parse("CVS_WATCH_POLL_SECS", value)?;
+        }
+        if let Some(value) = lookup("CVS_PRICE_TTL_SECS") {
+            self.cache.price_ttl_secs = parse("CVS_PRICE_TTL_SECS", value)?;
+        }
+        if let Some(value) = lookup("CVS_TERMINAL_TTL_SECS") {
+            self.cache.terminal_ttl_secs = parse("CVS_TERMINAL_TTL_SECS", value)?;
+        }
+        if let Some(value) = lookup("CVS_ROUTES_TTL_SECS") {
+            self.cache.routes_ttl_secs = parse("CVS_ROUTES_TTL_SECS", value)?;
+        }
+        if let Some(value) = lookup("CVS_HOME_SYSTEM") {
+            self.ranking.home_system = value;
+        }
+        if let Some(value) = lookup("CVS_PIRATE_MIN_BUY_PRICE") {
+            self.ranking.pirate_min_buy_price = parse("CVS_PIRATE_MIN_BUY_PRICE", value)?;
+        }
+        if let Some(value) = lookup("CVS_API_ENABLED") {
+            self.api.enabled = parse("CVS_API_ENABLED", value)?;
+        }
+        if let Some(value) = lookup("CVS_API_PORT") {
+            self.api.port = parse("CVS_API_PORT", value)?;
+        }
+        if let Some(value) = lookup("CVS_GAME_LOG") {
+            self.game_log.path = value;
+        }
+        if let Some(value) = lookup("CVS_LOCALE") {
+            self.ui.locale = parse("CVS_LOCALE", value)?;
+        }
+        if let Some(value) = lookup("CVS_THEME") {
+            self.ui.theme = parse("CVS_THEME", value)?;
+        }
+        if let Some(value) = lookup("CVS_LOG") {
+            self.logging.level = value;
+        }
+        Ok(())
+    }
+}
+
+/// Names of the environment variables that can override config values.
+pub const ENV_OVERRIDES: &[&str] = &[
+    "CVS_BASE_URL",
+    "CVS_REQUEST_TIMEOUT_SECS",
+    "CVS_WATCH_POLL_SECS",
+    "CVS_PRICE_TTL_SECS",
+    "CVS_TERMINAL_TTL_SECS",
+    "CVS_ROUTES_TTL_SECS",
+    "CVS_HOME_SYSTEM",
+    "CVS_PIRATE_MIN_BUY_PRICE",
+    "CVS_API_ENABLED",
+    "CVS_API_PORT",
+    "CVS_GAME_LOG",
+    "CVS_LOCALE",
+    "CVS_THEME",
+    "CVS_LOG",
+];
+
+/// Upgrades a raw `config.json` document by one version. Index `n` migrates
+/// version `n` to `n + 1`.
+type Migration = fn(&mut Value);
+
+const CONFIG_MIGRATIONS: &[Migration] = &[migrate_config_v0_to_v1];
+
+/// v0 is a file without a `version` field. Its sections already match v1;
+/// anything missing is filled in by the serde defaults.
+fn migrate_config_v0_to_v1(_doc: &mut Value) {}
+
+fn migrate_config(mut doc: Value) -> Result<Value, ConfigError> {
+    let mut version = doc.get("version").and_then(Value::as_u64).unwrap_or(0) as u32;
+    if version > CONFIG_VERSION {
+        return Err(ConfigError::UnsupportedVersion(version));
+    }
+    if version < CONFIG_VERSION {
+        tracing::info!(from = version, to = CONFIG_VERSION, "migrating config.json");
+    }
+    while version < CONFIG_VERSION {
+        CONFIG_MIGRATIONS[version as usize](&mut doc);
+        version += 1;
+    }
+    if let Some(object) = doc.as_object_mut() {
+        object.insert("version".into(), Value::from(version));
+    }
+    Ok(doc)
+}
+
+/// Parse, migrate and validate the contents of a `config.json`.
+fn parse_config(data: &str) -> Result<AppConfig, ConfigError> {
+    let doc = migrate_config(serde_json::from_str(data)?)?;
+    let config: AppConfig = serde_json::from_value(doc)?;
+    config.validate()?;
+    Ok(config)
+}
+
+fn config_path() -> Result<PathBuf, ConfigError> {
+    StoreFile::Config.path().ok_or(ConfigError::StorageUnavailable)
+}
+
+/// Read `config.json`. A missing file yields the defaults.
+pub fn load_config_file() -> Result<AppConfig, ConfigError> {
+    load_config_at(&config_path()?, StoreFile::Config.save_lock())
+}
+
+/// Read the `.bak` copy kept by the last successful save.
+pub fn load_config_backup() -> Result<Option<AppConfig>, ConfigError> {
+    let path = StoreFile::Config.backup_path().ok_or(ConfigError::StorageUnavailable)?;
+    match fs::read_to_string(path) {
+        Ok(data) => parse_config(&data).map(Some),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err.into()),
+    }
+}
+
+pub fn save_config_file(config: &AppConfig) -> Result<(), ConfigError> {
+    save_config_at(&config_path()?, StoreFile::Config.save_lock(), config)
+}
+
+/// Locks saving when the file itself is bad, so the defaults used in its
+/// place are never written over the user's settings.
+fn load_config_at(path: &Path, lock: &AtomicBool) -> Result<AppConfig, ConfigError> {
+    let result = match fs::read_to_string(path) {
+        Ok(data) => parse_config(&data),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(AppConfig::default()),
+        Err(err) => Err(err.into()),
+    };
+    if result.as_ref().is_err_and(ConfigError::is_damaged) {
+        lock.store(true, Ordering::SeqCst);
+    }
+    result
+}
+
+fn save_config_at(path: &Path, lock: &AtomicBool, config: &AppConfig) -> Result<(), ConfigError> {
+    if lock.load(Ordering::SeqCst) {
+        return Err(ConfigError::RecoveryPending);
+    }
+    config.validate()?;
+    back_up(path)?;
+    write_atomic(path, serde_json::to_string_pretty(config)?.as_bytes())?;
+    Ok(())
+}
+
+struct ConfigStore {
+    /// What is (or will be) written to disk.
+    file: AppConfig,
+    /// `file` plus environment overrides; what the app actually uses.
+    effective: AppConfig,
+}
+
+static STORE: OnceLock<RwLock<ConfigStore>> = OnceLock::new();
+
+fn store() -> &'static RwLock<ConfigStore> {
+    STORE.get_or_init(|| {
+        RwLock::new(ConfigStore {
+            file: AppConfig::default(),
+            effective: AppConfig::default(),
+        })
+    })
+}
+
+/// Load the config file and environment overrides into the global store.
+/// On error the defaults stay in effect and the problems are returned so the
+/// UI can report them.
+pub fn init() -> Vec<ConfigError> {
+    activate_loaded(load_config_file())
+}
+
+fn activate_loaded(loaded: Result<AppConfig, ConfigError>) -> Vec<ConfigError> {
+    let mut problems = Vec::new();
+    let file = loaded.unwrap_or_else(|err| {
+        problems.push(err);
+        AppConfig::default()
+    });
+
+    let mut effective = file.clone();
+    if let Err(err) = effective.apply_env_overrides(|var| std::env::var(var).ok()) {
+        problems.push(err);
+        effective = file.clone();
+    } else if let Err(err) = effective.validate() {
+        problems.push(err);
+        effective = file.clone();
+    }
+
+    if let Ok(mut guard) = store().write() {
+        *guard = ConfigStore { file, effective };
+    }
+    problems
+}
+
+/// The configuration currently in effect.
+pub fn current() -> AppConfig {
+    store()
+        .read()
+        .map(|guard| guard.effective.clone())
+        .unwrap_or_default()
+}
+
+/// The configuration as stored on disk (without environment overrides).
+pub fn file_config() -> AppConfig {
+    store()
+        .read()
+        .map(|guard| guard.file.clone())
+        .unwrap_or_default()
+}
+
+/// Validate, persist and activate a new file configuration. Refused while a
+/// damaged `config.json` awaits a recovery decision.
+pub fn update(config: AppConfig) -> Result<(), ConfigError> {
+    update_with(config, save_config_file)
+}
+
+fn update_with(
+    config: AppConfig,
+    save: impl FnOnce(&AppConfig) -> Result<(), ConfigError>,
+) -> Result<(), ConfigError> {
+    let mut effective = config.clone();
+    effective.apply_env_overrides(|var| std::env::var(var).ok())?;
+    effective.validate()?;
+    save(&config)?;
+    if let Ok(mut guard) = store().write() {
+        *guard = ConfigStore {
+            file: config,
+            effective,
+        };
+    }
+    Ok(())
+}
+
+/// Environment overrides that are currently set.
+pub fn active_env_overrides() -> Vec<&'static str> {
+    ENV_OVERRIDES
+        .iter()
+        .copied()
+        .filter(|var| std::env::var(var).is_ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::util::persistence::quarantine_at;
+
+    fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+        move |var| vars.get(var).cloned()
+    }
+
+    fn invalid_field(result: Result<(), ConfigError>) -> &'static str {
+        match result {
+            Err(ConfigError::Invalid { field, .. }) => field,
+            other => panic!("expected an invalid field, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn every_version_has_a_migration() {
+        assert_eq!(CONFIG_MIGRATIONS.len(), CONFIG_VERSION as usize);
+    }
+
+    #[test]
+    fn unversioned_file_is_migrated_and_keeps_its_values() {
+        let config = parse_config(r#"{ "cache": { "price_ttl_secs": 120 } }"#).unwrap();
+        assert_eq!(config.version, CONFIG_VERSION);
+        assert_eq!(config.cache.price_ttl_secs, 120);
+        assert_eq!(config.cache.routes_ttl_secs, CacheConfig::default().routes_ttl_secs);
+        assert_eq!(config.network, NetworkConfig::default());
+    }
+
+    #[test]
+    fn newer_version_is_rejected() {
+        let data = format!(r#"{{ "version": {} }}"#, CONFIG_VERSION + 1);
+        assert!(matches!(
+            parse_config(&data),
+            Err(ConfigError::UnsupportedVersion(v)) if v == CONFIG_VERSION + 1
+        ));
+    }
+
+    #[test]
+    fn invalid_file_values_are_reported_by_field() {
+        let err = parse_config(r#"{ "version": 1, "api": { "port": 80 } }"#).unwrap_err();
+        assert!(matches!(err, ConfigError::Invalid { field: "api.port", .. }));
+
+        let mut config = AppConfig::default();
+        config.network.base_url = "ftp://example.com/".into();
+        assert_eq!(invalid_field(config.validate()), "network.base_url");
+
+        let mut config = AppConfig::default();
+        config.network.base_url = "https://example.com/api".into();
+        assert_eq!(invalid_field(config.validate()), "network.base_url");
+
+        let mut config = AppConfig::default();
+        config.cache.terminal_ttl_secs = 0;
+        assert_eq!(invalid_field(config.validate()), "cache.terminal_ttl_secs");
+
+        let mut config = AppConfig::default();
+        config.ranking.hotspot_penalty = f64::NAN;
+        assert_eq!(invalid_field(config.validate()), "ranking.hotspot_penalty");
+
+        let mut config = AppConfig::default();
+        config.webhooks.templates.loot.title = "{#items}{manifest}".into();
+        assert_eq!(invalid_field(config.validate()), "webhooks.templates");
+
+        let mut config = AppConfig::default();
+        config.logging.level = "loud".into();
+        assert_eq!(invalid_field(config.validate()), "logging.level");
+
+        let mut config = AppConfig::default();
+        config.logging.modules.insert("cvs::".into(), "debug".into());
+        assert_eq!(invalid_field(config.validate()), "logging.modules");
+
+        assert!(AppConfig::default().validate().is_ok());
+    }
+
+    #[test]
+    fn watchlist_rules_round_trip_and_are_validated() {
+        let data = r#"{
+            "version": 1,
+            "watchlist": { "rules": [{
+                "id": "r1", "commodity_id": "lara", "commodity_name": "Laranite",
+                "side": "Sell", "threshold": 3000.0, "direction": "Above"
+            }] }
+        }"#;
+        let config = parse_config(data).unwrap();
+        let rule = &config.watchlist.rules[0];
+        assert!(rule.enabled);
+        assert_eq!(rule.terminal_id, None);
+        let saved = serde_json::to_string(&config).unwrap();
+        assert_eq!(parse_config(&saved).unwrap(), config);
+
+        let mut config = config;
+        config.watchlist.rules.push(config.watchlist.rules[0].clone());
+        assert_eq!(invalid_field(config.validate()), "watchlist.rules");
+
+        config.watchlist.rules.pop();
+        config.watchlist.rules[0].threshold = 0.0;
+        assert_eq!(invalid_field(config.validate()), "watchlist.rules");
+    }
+
+    #[test]
+    fn environment_overrides_file_values() {
+        let file = parse_config(
+            r#"{ "version": 1, "network": { "request_timeout_secs": 10 }, "api": { "port": 9000 } }"#,
+        )
+        .unwrap();
+
+        let mut effective = file.clone();
+        effective
+            .apply_env_overrides(env(&[
+                ("CVS_API_PORT", " 9100 "),
+                ("CVS_HOME_SYSTEM", "Pyro"),
+                ("CVS_API_ENABLED", "true"),
+            ]))
+            .unwrap();
+        effective.validate().unwrap();
+
+        assert_eq!(effective.api.port, 9100);
+        assert!(effective.api.enabled);
+        assert_eq!(effective.ranking.home_system, "Pyro");
+        // Unset variables leave the file values alone.
+        assert_eq!(effective.network.request_timeout_secs, 10);
+        // The file config itself is untouched.
+        assert_eq!(file.api.port, 9000);
+    }
+
+    #[test]
+    fn unparsable_environment_value_is_an_error() {
+        let mut config = AppConfig::default();
+        let err = config
+            .apply_env_overrides(env(&[("CVS_PRICE_TTL_SECS", "an hour")]))
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            ConfigError::Env { var: "CVS_PRICE_TTL_SECS", ref value } if value == "an hour"
+        ));
+
+        // Values that parse but break the config are caught by validate.
+        let mut config = AppConfig::default();
+        config
+            .apply_env_overrides(env(&[("CVS_WATCH_POLL_SECS", "5")]))
+            .unwrap();
+        assert_eq!(invalid_field(config.validate()), "network.watch_poll_secs");
+    }
+
+    #[test]
+    fn damaged_file_survives_init_and_update() {
+        let dir = std::env::temp_dir().join(format!("cvs-config-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("config.json");
+        let original = r#"{ "version": 1, "api": { "port": 80 }, "webhooks": { "targets": [] } }"#;
+        fs::write(&path, original).unwrap();
+        let lock = AtomicBool::new(false);
+        let save = |config: &AppConfig| save_config_at(&path, &lock, config);
+
+        let problems = activate_loaded(load_config_at(&path, &lock));
+        assert!(problems.first().is_some_and(ConfigError::is_damaged));
+        let err = update_with(AppConfig::default(), save).unwrap_err();
+        assert!(matches!(err, ConfigError::RecoveryPending));
+        assert_eq!(fs::read_to_string(&path).unwrap(), original);
+
+        let moved = quarantine_at(&path, &lock).unwrap().unwrap();
+        assert_eq!(fs::read_to_string(moved).unwrap(), original);
+        update_with(AppConfig::default(), save).unwrap();
+        assert!(load_config_at(&path, &lock).is_ok());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,977 @@
+//! Peer-to-peer crew sessions over QUIC, without a central server.
+//!
+//! A host opens a [`SessionHost`] and reads its address and short join code
+//! out to the crew; members connect with [`SessionMember::join`]. QUIC runs
+//! on a throwaway self-signed certificate and only provides the transport:
+//! each member and the host then run CPace, a password-authenticated key
+//! exchange on ristretto255, with the join code as the password and the
+//! connection's TLS exporter as channel binding. Both sides confirm the keys
+//! before anything else is sent, and every message then travels sealed with
+//! ChaCha20-Poly1305.
+//!
+//! Someone listening in, or sitting in the middle with their own certificate,
+//! learns nothing they could test code guesses against offline; each attempt
+//! costs a connection. The host refuses an address after a few failed joins,
+//! and new members altogether after too many.
+//!
+//! Wire format: one bidirectional stream per member. Each side first sends
+//! its 32-byte CPace share, then a 32-byte key confirmation tag, then
+//! length-prefixed sealed frames holding a JSON [`Message`]. The host answers
+//! a `Join` with a full snapshot and then streams diffs as it publishes
+//! changes.
+
+use std::{
+    collections::HashMap,
+    io,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use curve25519_dalek::{
+    ristretto::{CompressedRistretto, RistrettoPoint},
+    traits::IsIdentity,
+    Scalar,
+};
+
+use quinn::{
+    crypto::rustls::QuicClientConfig,
+    rustls::{
+        self,
+        client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier},
+        crypto::{verify_tls12_signature, verify_tls13_signature, CryptoProvider},
+        pki_types::{CertificateDer, PrivatePkcs8KeyDer, ServerName, UnixTime},
+        DigitallySignedStruct, SignatureScheme,
+    },
+    ClientConfig, Connection, Endpoint, RecvStream, SendStream, ServerConfig, TransportConfig,
+    VarInt,
+};
+use ring::{
+    aead::{self, Aad, LessSafeKey, Nonce, UnboundKey, CHACHA20_POLY1305},
+    digest, hkdf, hmac,
+    rand::{SecureRandom, SystemRandom},
+};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::sync::{broadcast, watch};
+use tracing::{info, warn};
+
+use crate::domain::{
+    crew_session::{diff, SessionOp, SessionSnapshot, ROLE_MEMBER},
+    manifest::CargoManifest,
+    sell_plan::SellPlan,
+    CrewMember,
+};
+
+pub const DEFAULT_PORT: u16 = 47120;
+
+/// Name the host's certificate is issued for; members connect to it by address.
+const SERVER_NAME: &str = "cvs-crew";
+const KEY_INFO: &[u8] = b"cvs crew session v2";
+/// CPace domain separation tag for the generator.
+const CPACE_DSI: &[u8] = b"CPaceRistretto255";
+const EXPORTER_LABEL: &[u8] = b"EXPORTER-cvs-crew-session";
+const SHARE_LEN: usize = 32;
+const TAG_LEN: usize = 32;
+/// Largest frame a member accepts from the (authenticated) host.
+const MAX_FRAME_BYTES: usize = 4 * 1024 * 1024;
+/// Largest frame the host accepts from a member; members only ever send `Join`.
+const MAX_JOIN_FRAME_BYTES: usize = 4 * 1024;
+/// Time a peer gets from connecting to having joined.
+const JOIN_TIMEOUT: Duration = Duration::from_secs(10);
+/// Joins one address may have in flight at once.
+const MAX_PENDING_PER_ADDR: usize = 2;
+/// Failed joins after which an address is refused for the session.
+const MAX_FAILED_JOINS: u32 = 5;
+/// Failed joins after which the host stops accepting anyone new.
+const MAX_FAILED_JOINS_TOTAL: u32 = 50;
+const MAX_MEMBERS: usize = 16;
+/// Updates buffered per member before it's sent a fresh snapshot instead.
+const UPDATE_BUFFER: usize = 64;
+/// QUIC close code for a peer that failed the join code check.
+const CLOSE_REJECTED: u32 = 1;
+const CLOSE_LEFT: u32 = 2;
+
+#[derive(Debug, Error)]
+pub enum SessionError {
+    #[error("could not open a session endpoint on {addr}: {source}")]
+    Bind { addr: SocketAddr, source: io::Error },
+    #[error("could not reach the session host at {addr}: {reason}")]
+    Connect { addr: SocketAddr, reason: String },
+    #[error("the host did not accept the join code")]
+    Rejected,
+    #[error("the session connection was lost: {0}")]
+    Connection(String),
+    #[error("malformed session message: {0}")]
+    Protocol(String),
+    #[error("could not set up session encryption: {0}")]
+    Crypto(String),
+    #[error("not a host address: {0}")]
+    Address(String),
+}
+
+/// Short code the crew types in to join, e.g. `K7QM-2XWD`. Each character
+/// takes 5 bits of a fresh random byte; 256 is a multiple of the 32-letter
+/// alphabet, so every letter is equally likely.
+pub fn new_join_code() -> Result<String, SessionError> {
+    const ALPHABET: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+    let mut bytes = [0u8; 8];
+    SystemRandom::new()
+        .fill(&mut bytes)
+        .map_err(|_| SessionError::Crypto("no randomness".to_string()))?;
+    let chars: String = bytes
+        .iter()
+        .map(|b| ALPHABET[*b as usize % ALPHABET.len()] as char)
+        .collect();
+    Ok(format!("{}-{}", &chars[..4], &chars[4..]))
+}
+
+/// Resolves `host` or `host:port` (default [`DEFAULT_PORT`]) as typed by a member.
+pub async fn resolve_host(input: &str) -> Result<SocketAddr, SessionError> {
+    let input = input.trim();
+    let has_port = input.parse::<SocketAddr>().is_ok()
+        || input.rsplit_once(':').is_some_and(|(_, port)| port.parse::<u16>().is_ok());
+    let with_port = if has_port {
+        input.to_string()
+    } else {
+        format!("{input}:{DEFAULT_PORT}")
+    };
+    tokio::net::lookup_host(&with_port)
+        .await
+        .ok()
+        .and_then(|mut addrs| addrs.next())
+        .ok_or_else(|| SessionError::Address(input.to_string()))
+}
+
+/// Best guess at the address crew on the same network reach this machine
+/// at. Connecting a UDP socket only picks a route; nothing is sent.
+pub fn lan_ip() -> Option<IpAddr> {
+    let socket = std::net::UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0)).ok()?;
+    socket.connect((Ipv4Addr::new(192, 0, 2, 1), 9)).ok()?;
+    Some(socket.local_addr().ok()?.ip()).filter(|ip| !ip.is_unspecified())
+}
+
+/// Join codes compare case-insensitively and ignore dashes and spaces.
+fn normalize_code(code: &str) -> String {
+    code.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Message {
+    Join { name: String },
+    Welcome { member_id: String, version: u64, snapshot: SessionSnapshot },
+    Update { version: u64, ops: Vec<SessionOp> },
+}
+
+// --- Host -------------------------------------------------------------------
+
+/// A connected crew member as the host sees it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SessionPeer {
+    pub member_id: String,
+    pub name: String,
+    pub addr: SocketAddr,
+}
+
+#[derive(Clone, Debug)]
+struct Update {
+    version: u64,
+    ops: Vec<SessionOp>,
+}
+
+struct HostState {
+    version: u64,
+    snapshot: SessionSnapshot,
+    peers: Vec<SessionPeer>,
+}
+
+/// Joins in flight and failed joins per address, to bound guessing.
+#[derive(Default)]
+struct JoinGate {
+    pending: HashMap<IpAddr, usize>,
+    failures: HashMap<IpAddr, u32>,
+    total_failures: u32,
+}
+
+struct HostShared {
+    code: String,
+    state: Mutex<HostState>,
+    updates: broadcast::Sender<Update>,
+    gate: Mutex<JoinGate>,
+}
+
+impl HostShared {
+    /// Whether a new connection from `ip` may try to join.
+    fn admit(&self, ip: IpAddr) -> bool {
+        let members = self.state.lock().unwrap_or_else(|e| e.into_inner()).peers.len();
+        let mut gate = self.gate.lock().unwrap_or_else(|e| e.into_inner());
+        let pending = gate.pending.get(&ip).copied().unwrap_or(0);
+        let allowed = members < MAX_MEMBERS
+            && pending < MAX_PENDING_PER_ADDR
+            && gate.total_failures < MAX_FAILED_JOINS_TOTAL
+            && gate.failures.get(&ip).copied().unwrap_or(0) < MAX_FAILED_JOINS;
+        if allowed {
+            gate.pending.insert(ip, pending + 1);
+        }
+        allowed
+    }
+
+    /// Ends a join attempt from `ip` that [`Self::admit`] let in. Any attempt
+    /// that doesn't end in a join counts as failed: a peer can learn whether
+    /// its guess was right and hang up before the host sees its answer.
+    fn finish_attempt(&self, ip: IpAddr, failed: bool) {
+        let mut gate = self.gate.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(pending) = gate.pending.get_mut(&ip) {
+            *pending -= 1;
+            if *pending == 0 {
+                gate.pending.remove(&ip);
+            }
+        }
+        if failed {
+            *gate.failures.entry(ip).or_default() += 1;
+            gate.total_failures += 1;
+            if gate.total_failures == MAX_FAILED_JOINS_TOTAL {
+                warn!("too many failed joins; no longer accepting new members");
+            }
+        }
+    }
+
+    /// Moves to `next` and sends the diff to every member. Holding the lock
+    /// while sending keeps joins from missing or doubling an update.
+    fn publish_with(&self, change: impl FnOnce(&mut SessionSnapshot)) -> u64 {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let mut next = state.snapshot.clone();
+        change(&mut next);
+        let ops = diff(&state.snapshot, &next);
+        if ops.is_empty() {
+            return state.version;
+        }
+        state.version += 1;
+        state.snapshot = next;
+        let _ = self.updates.send(Update { version: state.version, ops });
+        state.version
+    }
+}
+
+/// Hosts a crew session until dropped or [`SessionHost::close`]d.
+pub struct SessionHost {
+    endpoint: Endpoint,
+    shared: Arc<HostShared>,
+}
+
+impl SessionHost {
+    /// Listens on `addr` (port 0 picks a free one) and shares `snapshot`.
+    /// Must run inside a Tokio runtime.
+    pub fn start(addr: SocketAddr, snapshot: SessionSnapshot) -> Result<Self, SessionError> {
+        let endpoint = Endpoint::server(server_config()?, addr)
+            .map_err(|source| SessionError::Bind { addr, source })?;
+        let (updates, _) = broadcast::channel(UPDATE_BUFFER);
+        let shared = Arc::new(HostShared {
+            code: new_join_code()?,
+            state: Mutex::new(HostState {
+                version: 0,
+                snapshot,
+                peers: Vec::new(),
+            }),
+            updates,
+            gate: Mutex::default(),
+        });
+        tokio::spawn(accept_members(endpoint.clone(), shared.clone()));
+        Ok(Self { endpoint, shared })
+    }
+
+    pub fn code(&self) -> &str {
+        &self.shared.code
+    }
+
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.endpoint.local_addr().ok()
+    }
+
+    pub fn peers(&self) -> Vec<SessionPeer> {
+        self.with_state(|state| state.peers.clone())
+    }
+
+    pub fn snapshot(&self) -> SessionSnapshot {
+        self.with_state(|state| state.snapshot.clone())
+    }
+
+    /// Shares the host's current manifest and plan; returns the session version.
+    pub fn publish(&self, manifest: CargoManifest, plan: Option<SellPlan>) -> u64 {
+        self.shared.publish_with(|snapshot| {
+            snapshot.manifest = manifest;
+            snapshot.plan = plan;
+        })
+    }
+
+    /// Sets a crew member's weight in the split.
+    pub fn set_weight(&self, member_id: &str, weight: f32) -> u64 {
+        self.shared.publish_with(|snapshot| {
+            if let Some(member) = snapshot.crew.iter_mut().find(|m| m.id == member_id) {
+                member.weight = weight.max(0.0);
+            }
+        })
+    }
+
+    pub fn close(&self) {
+        self.endpoint.close(VarInt::from_u32(CLOSE_LEFT), b"host closed the session");
+    }
+
+    fn with_state<R>(&self, read: impl FnOnce(&HostState) -> R) -> R {
+        read(&self.shared.state.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+}
+
+impl Drop for SessionHost {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+async fn accept_members(endpoint: Endpoint, shared: Arc<HostShared>) {
+    while let Some(incoming) = endpoint.accept().await {
+        let ip = incoming.remote_address().ip();
+        if !shared.admit(ip) {
+            info!(%ip, "refused a join attempt");
+            incoming.refuse();
+            continue;
+        }
+        let shared = shared.clone();
+        tokio::spawn(async move {
+            let Ok(connection) = incoming.await else {
+                shared.finish_attempt(ip, false);
+                return;
+            };
+            if let Err(err) = serve_member(&connection, &shared).await {
+                info!(member = %connection.remote_address(), error = %err, "member dropped");
+            }
+        });
+    }
+}
+
+/// Waits for the member's handshake and `Join`; returns its name.
+async fn authenticate(
+    connection: &Connection,
+    shared: &HostShared,
+) -> Result<(SealedChannel, SendStream, RecvStream, String), SessionError> {
+    let (mut send, mut recv) = connection.accept_bi().await.map_err(connection_error)?;
+    let mut channel = handshake(connection, &mut send, &mut recv, &shared.code, Side::Host).await?;
+    match channel.receive(&mut recv).await? {
+        Message::Join { name } => Ok((channel, send, recv, name.trim().chars().take(32).collect())),
+        _ => Err(SessionError::Protocol("expected a join".to_string())),
+    }
+}
+
+async fn serve_member(connection: &Connection, shared: &HostShared) -> Result<(), SessionError> {
+    let ip = connection.remote_address().ip();
+    let joined = tokio::time::timeout(JOIN_TIMEOUT, authenticate(connection, shared))
+        .await
+        .unwrap_or_else(|_| Err(SessionError::Protocol("join timed out".to_string())));
+    shared.finish_attempt(ip, joined.is_err());
+    let (mut channel, mut send, _recv, name) = match joined {
+        Ok(joined) => joined,
+        Err(err) => {
+            connection.close(VarInt::from_u32(CLOSE_REJECTED), b"join failed");
+            return Err(err);
+        }
+    };
+
+    let peer = SessionPeer {
+        member_id: uuid::Uuid::new_v4().to_string(),
+        name: if name.is_empty() { connection.remote_address().to_string() } else { name },
+        addr: connection.remote_address(),
+    };
+    let member_id = peer.member_id.clone();
+    let mut updates = shared.updates.subscribe();
+    shared.publish_with(|snapshot| {
+        snapshot.crew.push(CrewMember {
+            id: peer.member_id.clone(),
+            name: peer.name.clone(),
+            role: ROLE_MEMBER.to_string(),
+            weight: 1.0,
+        })
+    });
+    shared.state.lock().unwrap_or_else(|e| e.into_inner()).peers.push(peer);
+
+    let result = stream_updates(connection, shared, &member_id, &mut channel, &mut send, &mut updates).await;
+
+    shared.state.lock().unwrap_or_else(|e| e.into_inner()).peers.retain(|p| p.member_id != member_id);
+    shared.publish_with(|snapshot| snapshot.crew.retain(|m| m.id != member_id));
+    result
+}
+
+async fn stream_updates(
+    connection: &Connection,
+    shared: &HostShared,
+    member_id: &str,
+    channel: &mut SealedChannel,
+    send: &mut SendStream,
+    updates: &mut broadcast::Receiver<Update>,
+) -> Result<(), SessionError> {
+    let mut sent = send_welcome(shared, member_id, channel, send).await?;
+    loop {
+        tokio::select! {
+            update = updates.recv() => match update {
+                Ok(update) if update.version <= sent => {}
+                Ok(update) => {
+                    let message = Message::Update { version: update.version, ops: update.ops };
+                    channel.send(send, &message).await?;
+                    sent = update.version;
+                }
+                Err(broadcast::error::RecvError::Lagged(_)) => {
+                    sent = send_welcome(shared, member_id, channel, send).await?;
+                }
+                Err(broadcast::error::RecvError::Closed) => return Ok(()),
+            },
+            _ = connection.closed() => return Ok(()),
+        }
+    }
+}
+
+/// Sends the full current snapshot; returns its version.
+async fn send_welcome(
+    shared: &HostShared,
+    member_id: &str,
+    channel: &mut SealedChannel,
+    send: &mut SendStream,
+) -> Result<u64, SessionError> {
+    let (version, snapshot) = {
+        let state = shared.state.lock().unwrap_or_else(|e| e.into_inner());
+        (state.version, state.snapshot.clone())
+    };
+    let message = Message::Welcome {
+        member_id: member_id.to_string(),
+        version,
+        snapshot,
+    };
+    channel.send(send, &message).await?;
+    Ok(version)
+}
+
+// --- Member -----------------------------------------------------------------
+
+/// The host's session as last received.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SessionView {
+    pub version: u64,
+    pub snapshot: SessionSnapshot,
+    /// The host closed the session or the connection dropped.
+    pub ended: bool,
+}
+
+/// A crew member's live copy of a host's session.
+pub struct SessionMember {
+    endpoint: Endpoint,
+    connection: Connection,
+    member_id: String,
+    view: watch::Receiver<SessionView>,
+}
+
+impl SessionMember {
+    /// Connects to the host at `host` and joins with `code`. Must run inside
+    /// a Tokio runtime.
+    pub async fn join(host: SocketAddr, code: &str, name: &str) -> Result<Self, SessionError> {
+        let local: SocketAddr = if host.is_ipv4() {
+            (Ipv4Addr::UNSPECIFIED, 0).into()
+        } else {
+            (Ipv6Addr::UNSPECIFIED, 0).into()
+        };
+        let mut endpoint =
+            Endpoint::client(local).map_err(|source| SessionError::Bind { addr: local, source })?;
+        endpoint.set_default_client_config(client_config()?);
+
+        let connect_error = |reason: String| SessionError::Connect { addr: host, reason };
+        let connection = endpoint
+            .connect(host, SERVER_NAME)
+            .map_err(|err| connect_error(err.to_string()))?
+            .await
+            .map_err(|err| connect_error(err.to_string()))?;
+        let (mut send, mut recv) = connection.open_bi().await.map_err(connection_error)?;
+        let mut channel = handshake(&connection, &mut send, &mut recv, code, Side::Member)
+            .await
+            .map_err(|err| match err {
+                SessionError::Connection(_) => SessionError::Rejected,
+                err => err,
+            })?;
+        channel.send(&mut send, &Message::Join { name: name.to_string() }).await?;
+
+        let (member_id, version, snapshot) = match channel.receive(&mut recv).await {
+            Ok(Message::Welcome { member_id, version, snapshot }) => (member_id, version, snapshot),
+            Ok(_) => return Err(SessionError::Protocol("expected a welcome".to_string())),
+            Err(SessionError::Connection(_)) => return Err(SessionError::Rejected),
+            Err(err) => return Err(err),
+        };
+        let (view_tx, view) = watch::channel(SessionView { version, snapshot, ended: false });
+        tokio::spawn(follow_host(connection.clone(), channel, recv, view_tx));
+        Ok(Self { endpoint, connection, member_id, view })
+    }
+
+    pub fn member_id(&self) -> &str {
+        &self.member_id
+    }
+
+    pub fn view(&self) -> SessionView {
+        self.view.borrow().clone()
+    }
+
+    /// Waits for the next change; returns the new view, or `None` once the
+    /// session has ended and no further changes will come.
+    pub async fn changed(&mut self) -> Option<SessionView> {
+        if self.view.borrow().ended {
+            return None;
+        }
+        self.view.changed().await.ok()?;
+        Some(self.view.borrow_and_update().clone())
+    }
+
+    pub fn leave(&self) {
+        self.connection.close(VarInt::from_u32(CLOSE_LEFT), b"left the session");
+        self.endpoint.close(VarInt::from_u32(CLOSE_LEFT), b"left the session");
+    }
+}
+
+impl Drop for SessionMember {
+    fn drop(&mut self) {
+        self.leave();
+    }
+}
+
+async fn follow_host(
+    connection: Connection,
+    mut channel: SealedChannel,
+    mut recv: RecvStream,
+    view: watch::Sender<SessionView>,
+) {
+    loop {
+        let message = tokio::select! {
+            message = channel.receive(&mut recv) => message,
+            _ = connection.closed() => break,
+        };
+        match message {
+            Ok(Message::Welcome { version, snapshot, .. }) => {
+                view.send_modify(|v| {
+                    v.version = version;
+                    v.snapshot = snapshot;
+                });
+            }
+            Ok(Message::Update { version, ops }) => {
+                let in_order = view.borrow().version + 1 == version;
+                if !in_order {
+                    warn!(version, "update out of order; leaving the session");
+                    break;
+                }
+                view.send_modify(|v| {
+                    v.version = version;
+                    v.snapshot.apply(&ops);
+                });
+            }
+            Ok(Message::Join { .. }) => {}
+            Err(err) => {
+                info!(error = %err, "session ended");
+                break;
+            }
+        }
+    }
+    view.send_modify(|v| v.ended = true);
+}
+
+// --- Encryption -------------------------------------------------------------
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Side {
+    Host,
+    Member,
+}
+
+/// Sealed frames in both directions of one stream. Nonces are per-direction
+/// frame counters, which QUIC's in-order delivery keeps in step.
+struct SealedChannel {
+    seal: LessSafeKey,
+    open: LessSafeKey,
+    sent: u64,
+    received: u64,
+    max_frame: usize,
+}
+
+impl SealedChannel {
+    async fn send(&mut self, stream: &mut SendStream, message: &Message) -> Result<(), SessionError> {
+        let mut frame = serde_json::to_vec(message).map_err(|err| SessionError::Protocol(err.to_string()))?;
+        self.seal
+            .seal_in_place_append_tag(nonce(self.sent), Aad::empty(), &mut frame)
+            .map_err(|_| SessionError::Crypto("seal failed".to_string()))?;
+        self.sent += 1;
+        let len = u32::try_from(frame.len()).map_err(|_| SessionError::Protocol("frame too large".to_string()))?;
+        stream.write_all(&len.to_be_bytes()).await.map_err(connection_error)?;
+        stream.write_all(&frame).await.map_err(connection_error)
+    }
+
+    async fn receive(&mut self, stream: &mut RecvStream) -> Result<Message, SessionError> {
+        let mut len = [0u8; 4];
+        stream.read_exact(&mut len).await.map_err(connection_error)?;
+        let len = u32::from_be_bytes(len) as usize;
+        if len > self.max_frame {
+            return Err(SessionError::Protocol(format!("frame of {len} bytes")));
+        }
+        let mut frame = vec![0u8; len];
+        stream.read_exact(&mut frame).await.map_err(connection_error)?;
+        let plain = self
+            .open
+            .open_in_place(nonce(self.received), Aad::empty(), &mut frame)
+            .map_err(|_| SessionError::Rejected)?;
+        self.received += 1;
+        serde_json::from_slice(plain).map_err(|err| SessionError::Protocol(err.to_string()))
+    }
+}
+
+fn nonce(counter: u64) -> Nonce {
+    let mut bytes = [0u8; aead::NONCE_LEN];
+    bytes[4..].copy_from_slice(&counter.to_be_bytes());
+    Nonce::assume_unique_for_key(bytes)
+}
+
+/// Runs CPace over the stream and checks the peer's key confirmation. A
+/// peer with another join code, or on the far side of someone relaying the
+/// connection, fails with [`SessionError::Rejected`].
+async fn handshake(
+    connection: &Connection,
+    send: &mut SendStream,
+    recv: &mut RecvStream,
+    code: &str,
+    side: Side,
+) -> Result<SealedChannel, SessionError> {
+    let mut binding = [0u8; 32];
+    connection
+        .export_keying_material(&mut binding, EXPORTER_LABEL, &[])
+        .map_err(|_| SessionError::Crypto("no TLS keys to bind to".to_string()))?;
+    let pake = Pake::start(code, &binding)?;
+    send.write_all(&pake.share).await.map_err(connection_error)?;
+    let mut peer = [0u8; SHARE_LEN];
+    recv.read_exact(&mut peer).await.map_err(connection_error)?;
+
+    let agreed = pake.finish(&peer, side)?;
+    send.write_all(agreed.tag.as_ref()).await.map_err(connection_error)?;
+    let mut tag = [0u8; TAG_LEN];
+    recv.read_exact(&mut tag).await.map_err(connection_error)?;
+    agreed.confirm(&tag)?;
+    Ok(agreed.channel)
+}
+
+/// One side of a CPace exchange: the generator is derived from the join
+/// code and channel binding, so only peers with the same code on the same
+/// TLS connection end up with the same key.
+struct Pake {
+    secret: Scalar,
+    share: [u8; SHARE_LEN],
+    binding: Vec<u8>,
+}
+
+impl Pake {
+    fn start(code: &str, binding: &[u8]) -> Result<Self, SessionError> {
+        let mut hash = digest::Context::new(&digest::SHA512);
+        for part in [CPACE_DSI, normalize_code(code).as_bytes(), binding] {
+            hash.update(&(part.len() as u64).to_be_bytes());
+            hash.update(part);
+        }
+        let mut uniform = [0u8; 64];
+        uniform.copy_from_slice(hash.finish().as_ref());
+        let generator = RistrettoPoint::from_uniform_bytes(&uniform);
+
+        let mut wide = [0u8; 64];
+        SystemRandom::new()
+            .fill(&mut wide)
+            .map_err(|_| SessionError::Crypto("no randomness".to_string()))?;
+        let secret = Scalar::from_bytes_mod_order_wide(&wide);
+        Ok(Self {
+            secret,
+            share: (secret * generator).compress().to_bytes(),
+            binding: binding.to_vec(),
+        })
+    }
+
+    /// Derives the session keys from the peer's share.
+    fn finish(self, peer: &[u8; SHARE_LEN], side: Side) -> Result<Agreed, SessionError> {
+        let shared = CompressedRistretto(*peer)
+            .decompress()
+            .map(|point| self.secret * point)
+            .filter(|point| !point.is_identity())
+            .ok_or(SessionError::Rejected)?;
+
+        let (host_share, member_share) = match side {
+            Side::Host => (&self.share, peer),
+            Side::Member => (peer, &self.share),
+        };
+        let salt = hkdf::Salt::new(hkdf::HKDF_SHA256, &self.binding);
+        let prk = salt.extract(shared.compress().as_bytes());
+        let crypto = || SessionError::Crypto("key derivation failed".to_string());
+        let info = |direction: &'static [u8]| [KEY_INFO, direction, &host_share[..], &member_share[..]];
+        let aead_key = |direction| -> Result<LessSafeKey, SessionError> {
+            let info = info(direction);
+            let okm = prk.expand(&info, &CHACHA20_POLY1305).map_err(|_| crypto())?;
+            Ok(LessSafeKey::new(UnboundKey::from(okm)))
+        };
+        let confirm_key = |direction| -> Result<hmac::Key, SessionError> {
+            let info = info(direction);
+            Ok(prk.expand(&info, hmac::HMAC_SHA256).map_err(|_| crypto())?.into())
+        };
+        let (to_member, to_host) = (aead_key(b"host->member")?, aead_key(b"member->host")?);
+        let (host_confirm, member_confirm) = (confirm_key(b"host confirm")?, confirm_key(b"member confirm")?);
+
+        let (seal, open, ours, theirs, max_frame) = match side {
+            Side::Host => (to_member, to_host, host_confirm, member_confirm, MAX_JOIN_FRAME_BYTES),
+            Side::Member => (to_host, to_member, member_confirm, host_confirm, MAX_FRAME_BYTES),
+        };
+        Ok(Agreed {
+            channel: SealedChannel { seal, open, sent: 0, received: 0, max_frame },
+            tag: hmac::sign(&ours, KEY_INFO),
+            peer_confirm: theirs,
+        })
+    }
+}
+
+/// Keys from a finished exchange, pending the peer's confirmation.
+struct Agreed {
+    channel: SealedChannel,
+    tag: hmac::Tag,
+    peer_confirm: hmac::Key,
+}
+
+impl Agreed {
+    fn confirm(&self, tag: &[u8]) -> Result<(), SessionError> {
+        hmac::verify(&self.peer_confirm, KEY_INFO, tag).map_err(|_| SessionError::Rejected)
+    }
+}
+
+// --- QUIC setup -------------------------------------------------------------
+
+fn server_config() -> Result<ServerConfig, SessionError> {
+    let certified = rcgen::generate_simple_self_signed(vec![SERVER_NAME.to_string()])
+        .map_err(|err| SessionError::Crypto(err.to_string()))?;
+    let key = PrivatePkcs8KeyDer::from(certified.key_pair.serialize_der());
+    let mut config = ServerConfig::with_single_cert(vec![certified.cert.der().clone()], key.into())
+        .map_err(|err| SessionError::Crypto(err.to_string()))?;
+    // Members only ever open the one session stream.
+    let mut transport = TransportConfig::default();
+    transport
+        .max_concurrent_bidi_streams(VarInt::from_u32(1))
+        .max_concurrent_uni_streams(VarInt::from_u32(0));
+    config.transport_config(Arc::new(transport));
+    Ok(config)
+}
+
+fn client_config() -> Result<ClientConfig, SessionError> {
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let tls = rustls::ClientConfig::builder_with_provider(provider.clone())
+        .with_protocol_versions(&[&rustls::version::TLS13])
+        .map_err(|err| SessionError::Crypto(err.to_string()))?
+        .dangerous()
+        .with_custom_certificate_verifier(Arc::new(AnyHostCertificate(provider)))
+        .with_no_client_auth();
+    let quic = QuicClientConfig::try_from(tls).map_err(|err| SessionError::Crypto(err.to_string()))?;
+    Ok(ClientConfig::new(Arc::new(quic)))
+}
+
+/// Hosts use throwaway self-signed certificates, so there is nothing to
+/// verify them against. The host is authenticated by the join-code handshake
+/// instead, which is bound to this TLS session: a relay presenting its own
+/// certificate has different session keys on each side and fails it.
+#[derive(Debug)]
+struct AnyHostCertificate(Arc<CryptoProvider>);
+
+impl ServerCertVerifier for AnyHostCertificate {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls12_signature(message, cert, dss, &self.0.signature_verification_algorithms)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls13_signature(message, cert, dss, &self.0.signature_verification_algorithms)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.0.signature_verification_algorithms.supported_schemes()
+    }
+}
+
+fn connection_error(err: impl std::fmt::Display) -> SessionError {
+    SessionError::Connection(err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::domain::{crew_session::HOST_MEMBER_ID, CargoItem};
+
+    fn manifest(items: &[(&str, u32)]) -> CargoManifest {
+        let mut manifest = CargoManifest::default_manifest();
+        manifest.items = items
+            .iter()
+            .map(|(name, scu)| CargoItem {
+                id: format!("cargo-{name}"),
+                commodity_id: name.to_lowercase(),
+                commodity_name: name.to_string(),
+                scu: *scu,
+                is_hot: false,
+            })
+            .collect();
+        manifest
+    }
+
+    fn loopback() -> SocketAddr {
+        (Ipv4Addr::LOCALHOST, 0).into()
+    }
+
+    async fn next(member: &mut SessionMember) -> SessionView {
+        tokio::time::timeout(Duration::from_secs(5), member.changed())
+            .await
+            .expect("no update from the host")
+            .expect("session ended")
+    }
+
+    #[tokio::test]
+    async fn members_follow_the_host_on_loopback() {
+        let snapshot = SessionSnapshot::new(manifest(&[("Gold", 10)]), None, "Captain");
+        let host = SessionHost::start(loopback(), snapshot).unwrap();
+        let addr = host.local_addr().unwrap();
+        let code = host.code().to_lowercase().replace('-', " ");
+
+        let mut member = SessionMember::join(addr, &code, "Gunner").await.unwrap();
+        let view = member.view();
+        assert_eq!(view.snapshot.manifest.items.len(), 1);
+        let names: Vec<_> = view.snapshot.crew.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, vec!["Captain", "Gunner"]);
+        assert_eq!(host.peers()[0].member_id, member.member_id());
+
+        let plan = SellPlan { stops: Vec::new(), total_value: 4_000.0, total_distance: None };
+        let version = host.publish(manifest(&[("Gold", 6), ("Laranite", 4)]), Some(plan));
+        host.set_weight(HOST_MEMBER_ID, 3.0);
+        let mut view = next(&mut member).await;
+        while view.version < version + 1 {
+            view = next(&mut member).await;
+        }
+        assert_eq!(view.snapshot, host.snapshot());
+        let cuts: Vec<f64> = view.snapshot.split().into_iter().map(|(_, cut)| cut).collect();
+        assert_eq!(cuts, vec![3_000.0, 1_000.0]);
+
+        member.leave();
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while !host.peers().is_empty() {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+            }
+        })
+        .await
+        .expect("host never noticed the member leaving");
+        assert_eq!(host.snapshot().crew.len(), 1);
+    }
+
+    fn agree(member_code: &str, member_binding: &[u8]) -> (Agreed, Agreed) {
+        let host = Pake::start("K7QM-2XWD", &[7; 32]).unwrap();
+        let member = Pake::start(member_code, member_binding).unwrap();
+        let (host_share, member_share) = (host.share, member.share);
+        (
+            host.finish(&member_share, Side::Host).unwrap(),
+            member.finish(&host_share, Side::Member).unwrap(),
+        )
+    }
+
+    #[test]
+    fn pake_agrees_only_on_the_same_code_and_channel() {
+        let (host, member) = agree("k7qm 2xwd", &[7; 32]);
+        assert!(host.confirm(member.tag.as_ref()).is_ok());
+        assert!(member.confirm(host.tag.as_ref()).is_ok());
+        assert_ne!(host.tag.as_ref(), member.tag.as_ref());
+
+        for (code, binding) in [("K7QM-2XWE", [7; 32]), ("K7QM-2XWD", [8; 32])] {
+            let (host, member) = agree(code, &binding);
+            assert!(matches!(host.confirm(member.tag.as_ref()), Err(SessionError::Rejected)));
+            assert!(matches!(member.confirm(host.tag.as_ref()), Err(SessionError::Rejected)));
+        }
+
+        let identity = RistrettoPoint::default().compress().to_bytes();
+        let host = Pake::start("K7QM-2XWD", &[7; 32]).unwrap();
+        assert!(matches!(host.finish(&identity, Side::Host), Err(SessionError::Rejected)));
+    }
+
+    #[test]
+    fn join_codes_draw_every_character_from_the_full_alphabet() {
+        let codes: Vec<String> = (0..64).map(|_| new_join_code().unwrap()).collect();
+        for code in &codes {
+            assert_eq!(code.len(), 9);
+            assert_eq!(&code[4..5], "-");
+            assert_eq!(normalize_code(code), code.replace('-', ""));
+        }
+        // With 32 letters per position, 64 codes leave well over 16 distinct
+        // characters in every position; a 16-value position would not.
+        for position in (0..9).filter(|at| *at != 4) {
+            let seen: std::collections::HashSet<u8> =
+                codes.iter().map(|code| code.as_bytes()[position]).collect();
+            assert!(seen.len() > 16, "position {position} only saw {}", seen.len());
+        }
+    }
+
+    #[tokio::test]
+    async fn wrong_join_code_is_rejected() {
+        let host = SessionHost::start(loopback(), SessionSnapshot::new(manifest(&[]), None, "Captain")).unwrap();
+        let result = SessionMember::join(host.local_addr().unwrap(), "AAAA-AAAA", "Stowaway").await;
+        assert!(matches!(result, Err(SessionError::Rejected)), "{:?}", result.err());
+        assert!(host.peers().is_empty());
+    }
+
+    #[tokio::test]
+    async fn repeated_wrong_codes_lock_the_address_out() {
+        let host = SessionHost::start(loopback(), SessionSnapshot::new(manifest(&[]), None, "Captain")).unwrap();
+        let addr = host.local_addr().unwrap();
+        for _ in 0..MAX_FAILED_JOINS {
+            let result = SessionMember::join(addr, "AAAA-AAAA", "Stowaway").await;
+            assert!(matches!(result, Err(SessionError::Rejected)), "{:?}", result.err());
+        }
+        // The member gives up on the host's tag before the host sees its own.
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while host.shared.gate.lock().unwrap().total_failures < MAX_FAILED_JOINS {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+            }
+        })
+        .await
+        .expect("host never counted the wrong codes");
+        let result = SessionMember::join(addr, host.code(), "Gunner").await;
+        assert!(matches!(result, Err(SessionError::Connect { .. })), "{:?}", result.err());
+        assert!(host.peers().is_empty());
+    }
+
+    #[tokio::test]
+    async fn oversized_join_is_dropped() {
+        let host = SessionHost::start(loopback(), SessionSnapshot::new(manifest(&[]), None, "Captain")).unwrap();
+        let addr = host.local_addr().unwrap();
+        let name = "x".repeat(MAX_JOIN_FRAME_BYTES);
+        assert!(SessionMember::join(addr, host.code(), &name).await.is_err());
+        assert!(host.peers().is_empty());
+
+        let member = SessionMember::join(addr, host.code(), "Gunner").await.unwrap();
+        assert_eq!(host.peers()[0].member_id, member.member_id());
+    }
+}
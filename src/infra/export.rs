@@ -0,0 +1,757 @@
+//! CSV and JSON export of cargo evaluations, best prices, sell plans and routes.
+//!
+//! Every export kind has a fixed column list. Columns are only ever appended,
+//! so spreadsheets and scripts built on an export keep working after updates.
+
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::domain::{
+    BestPriceSummary, CargoItem, EvaluationSummary, SellPlan, TradeRoute,
+};
+use crate::util::persistence::write_atomic;
+
+/// Bumped when a column is renamed or removed (appending does not count).
+pub const EXPORT_VERSION: u32 = 1;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "CSV",
+            ExportFormat::Json => "JSON",
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ExportError {
+    #[error("no export location available")]
+    NoDestination,
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Serde(#[from] serde_json::Error),
+}
+
+/// A flat export record with a stable column layout.
+pub trait ExportRow: Serialize {
+    const COLUMNS: &'static [&'static str];
+
+    /// Cell values in `COLUMNS` order.
+    fn cells(&self) -> Vec<String>;
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct EvaluationRow {
+    pub item_id: String,
+    pub commodity_id: String,
+    pub commodity_name: String,
+    pub scu: u32,
+    pub is_hot: bool,
+    pub expected_value: f64,
+    pub min_value: Option<f64>,
+    pub max_value: Option<f64>,
+    pub confidence: f32,
+}
+
+impl ExportRow for EvaluationRow {
+    const COLUMNS: &'static [&'static str] = &[
+        "item_id",
+        "commodity_id",
+        "commodity_name",
+        "scu",
+        "is_hot",
+        "expected_value",
+        "min_value",
+        "max_value",
+        "confidence",
+    ];
+
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.item_id.clone(),
+            self.commodity_id.clone(),
+            self.commodity_name.clone(),
+            self.scu.to_string(),
+            self.is_hot.to_string(),
+            money(self.expected_value),
+            opt_money(self.min_value),
+            opt_money(self.max_value),
+            format!("{:.2}", self.confidence),
+        ]
+    }
+}
+
+pub fn evaluation_rows(items: &[CargoItem], summary: &EvaluationSummary) -> Vec<EvaluationRow> {
+    items
+        .iter()
+        .filter_map(|item| {
+            let (_, evaluation) = summary.items.iter().find(|(id, _)| id == &item.id)?;
+            Some(EvaluationRow {
+                item_id: item.id.clone(),
+                commodity_id: item.commodity_id.clone(),
+                commodity_name: item.commodity_name.clone(),
+                scu: item.scu,
+                is_hot: item.is_hot,
+                expected_value: evaluation.ev,
+                min_value: evaluation.min,
+                max_value: evaluation.max,
+                confidence: evaluation.confidence,
+            })
+        })
+        .collect()
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct BestPriceRow {
+    pub item_id: String,
+    pub commodity_name: String,
+    pub rank: usize,
+    pub location_id: Option<String>,
+    pub location_name: String,
+    pub sell_price: Option<f64>,
+    pub buy_price: Option<f64>,
+    pub adjusted_price: f64,
+    pub stock: Option<f64>,
+    /// Stable note codes (see `PriceNote::code`), so exports don't depend on
+    /// the UI language.
+    pub notes: Vec<String>,
+}
+
+impl ExportRow for BestPriceRow {
+    const COLUMNS: &'static [&'static str] = &[
+        "item_id",
+        "commodity_name",
+        "rank",
+        "location_id",
+        "location_name",
+        "sell_price",
+        "buy_price",
+        "adjusted_price",
+        "stock",
+        "notes",
+    ];
+
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.item_id.clone(),
+            self.commodity_name.clone(),
+            self.rank.to_string(),
+            self.location_id.clone().unwrap_or_default(),
+            self.location_name.clone(),
+            opt_money(self.sell_price),
+            opt_money(self.buy_price),
+            money(self.adjusted_price),
+            opt_money(self.stock),
+            self.notes.join(";"),
+        ]
+    }
+}
+
+pub fn best_price_rows(summary: &BestPriceSummary) -> Vec<BestPriceRow> {
+    summary
+        .suggestions
+        .iter()
+        .flat_map(|suggestion| {
+            suggestion
+                .entries
+                .iter()
+                .enumerate()
+                .map(|(index, entry)| BestPriceRow {
+                    item_id: suggestion.item_id.clone(),
+                    commodity_name: suggestion.commodity_name.clone(),
+                    rank: index + 1,
+                    location_id: entry.location_id.clone(),
+                    location_name: entry.location_name.clone(),
+                    sell_price: entry.sell_price,
+                    buy_price: entry.buy_price,
+                    adjusted_price: entry.adjusted_price,
+                    stock: entry.stock,
+                    notes: entry.notes.iter().map(|note| note.code()).collect(),
+                })
+        })
+        .collect()
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct SellPlanRow {
+    pub stop: usize,
+    pub terminal_id: Option<i32>,
+    pub terminal_name: String,
+    pub system: Option<String>,
+    pub is_nqa: bool,
+    pub distance_gm: Option<f64>,
+    pub commodity_id: String,
+    pub commodity_name: String,
+    pub scu: u32,
+    pub price_per_scu: f64,
+    pub total_value: f64,
+}
+
+impl ExportRow for SellPlanRow {
+    const COLUMNS: &'static [&'static str] = &[
+        "stop",
+        "terminal_id",
+        "terminal_name",
+        "system",
+        "is_nqa",
+        "distance_gm",
+        "commodity_id",
+        "commodity_name",
+        "scu",
+        "price_per_scu",
+        "total_value",
+    ];
+
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.stop.to_string(),
+            self.terminal_id.map(|id| id.to_string()).unwrap_or_default(),
+            self.terminal_name.clone(),
+            self.system.clone().unwrap_or_default(),
+            self.is_nqa.to_string(),
+            opt_money(self.distance_gm),
+            self.commodity_id.clone(),
+            self.commodity_name.clone(),
+            self.scu.to_string(),
+            money(self.price_per_scu),
+            money(self.total_value),
+        ]
+    }
+}
+
+pub fn sell_plan_rows(plan: &SellPlan) -> Vec<SellPlanRow> {
+    plan.stops
+        .iter()
+        .enumerate()
+        .flat_map(|(index, stop)| {
+            stop.items.iter().map(move |item| SellPlanRow {
+                stop: index + 1,
+                terminal_id: stop.terminal_id,
+                terminal_name: stop.terminal_name.clone(),
+                system: stop.system.clone(),
+                is_nqa: stop.is_nqa,
+                distance_gm: stop.distance_from_prev,
+                commodity_id: item.commodity_id.clone(),
+                commodity_name: item.commodity_name.clone(),
+                scu: item.scu,
+                price_per_scu: item.price_per_unit,
+                total_value: item.total_value,
+            })
+        })
+        .collect()
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct RouteRow {
+    pub commodity_id: String,
+    pub commodity_name: String,
+    pub is_illegal: bool,
+    pub buy_terminal: String,
+    pub buy_system: Option<String>,
+    pub buy_price: f64,
+    pub buy_stock: f64,
+    pub sell_terminal: String,
+    pub sell_system: Option<String>,
+    pub sell_price: f64,
+    pub sell_demand: f64,
+    pub sell_is_nqa: bool,
+    pub distance_gm: Option<f64>,
+    pub profit_per_scu: f64,
+    pub roi_percent: f64,
+    pub quantity: u32,
+    pub invest: f64,
+    pub profit_total: f64,
+}
+
+impl ExportRow for RouteRow {
+    const COLUMNS: &'static [&'static str] = &[
+        "commodity_id",
+        "commodity_name",
+        "is_illegal",
+        "buy_terminal",
+        "buy_system",
+        "buy_price",
+        "buy_stock",
+        "sell_terminal",
+        "sell_system",
+        "sell_price",
+        "sell_demand",
+        "sell_is_nqa",
+        "distance_gm",
+        "profit_per_scu",
+        "roi_percent",
+        "quantity",
+        "invest",
+        "profit_total",
+    ];
+
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.commodity_id.clone(),
+            self.commodity_name.clone(),
+            self.is_illegal.to_string(),
+            self.buy_terminal.clone(),
+            self.buy_system.clone().unwrap_or_default(),
+            money(self.buy_price),
+            money(self.buy_stock),
+            self.sell_terminal.clone(),
+            self.sell_system.clone().unwrap_or_default(),
+            money(self.sell_price),
+            money(self.sell_demand),
+            self.sell_is_nqa.to_string(),
+            opt_money(self.distance_gm),
+            money(self.profit_per_scu),
+            money(self.roi_percent),
+            self.quantity.to_string(),
+            money(self.invest),
+            money(self.profit_total),
+        ]
+    }
+}
+
+/// Routes as currently filtered and sorted, evaluated for `scu` of cargo.
+pub fn route_rows(routes: &[TradeRoute], scu: u32) -> Vec<RouteRow> {
+    routes
+        .iter()
+        .map(|route| {
+            let sized = route.for_quantity(scu);
+            RouteRow {
+                commodity_id: route.commodity_id.clone(),
+                commodity_name: route.commodity_name.clone(),
+                is_illegal: route.is_illegal,
+                buy_terminal: route.buy_terminal_name.clone(),
+                buy_system: route.buy_system.clone(),
+                buy_price: route.buy_price,
+                buy_stock: route.buy_stock,
+                sell_terminal: route.sell_terminal_name.clone(),
+                sell_system: route.sell_system.clone(),
+                sell_price: route.sell_price,
+                sell_demand: route.sell_demand,
+                sell_is_nqa: route.sell_is_nqa,
+                distance_gm: route.distance_gm,
+                profit_per_scu: route.profit_per_scu,
+                roi_percent: route.roi_percent,
+                quantity: sized.quantity,
+                invest: sized.invest,
+                profit_total: sized.profit_total,
+            }
+        })
+        .collect()
+}
+
+/// Rows ready to export, tagged with what they are.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ExportPayload {
+    Evaluation(Vec<EvaluationRow>),
+    BestPrices(Vec<BestPriceRow>),
+    SellPlan(Vec<SellPlanRow>),
+    Routes(Vec<RouteRow>),
+}
+
+impl ExportPayload {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ExportPayload::Evaluation(_) => "evaluation",
+            ExportPayload::BestPrices(_) => "best_prices",
+            ExportPayload::SellPlan(_) => "sell_plan",
+            ExportPayload::Routes(_) => "routes",
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        match self {
+            ExportPayload::Evaluation(rows) => rows.is_empty(),
+            ExportPayload::BestPrices(rows) => rows.is_empty(),
+            ExportPayload::SellPlan(rows) => rows.is_empty(),
+            ExportPayload::Routes(rows) => rows.is_empty(),
+        }
+    }
+
+    pub fn render(&self, format: ExportFormat) -> Result<String, ExportError> {
+        let kind = self.kind();
+        match self {
+            ExportPayload::Evaluation(rows) => render(kind, rows, format),
+            ExportPayload::BestPrices(rows) => render(kind, rows, format),
+            ExportPayload::SellPlan(rows) => render(kind, rows, format),
+            ExportPayload::Routes(rows) => render(kind, rows, format),
+        }
+    }
+
+    pub fn default_file_name(&self, format: ExportFormat) -> String {
+        format!("cvs-{}.{}", self.kind(), format.extension())
+    }
+}
+
+pub fn render<R: ExportRow>(
+    kind: &str,
+    rows: &[R],
+    format: ExportFormat,
+) -> Result<String, ExportError> {
+    match format {
+        ExportFormat::Csv => Ok(to_csv(rows)),
+        ExportFormat::Json => to_json(kind, rows),
+    }
+}
+
+/// RFC 4180 CSV with a header row and `\n` line endings.
+pub fn to_csv<R: ExportRow>(rows: &[R]) -> String {
+    let mut out = String::new();
+    push_csv_line(&mut out, R::COLUMNS.iter().map(|c| c.to_string()));
+    for row in rows {
+        push_csv_line(&mut out, row.cells());
+    }
+    out
+}
+
+#[derive(Serialize)]
+struct JsonDocument<'a, R> {
+    kind: &'a str,
+    version: u32,
+    columns: &'a [&'a str],
+    rows: &'a [R],
+}
+
+pub fn to_json<R: ExportRow>(kind: &str, rows: &[R]) -> Result<String, ExportError> {
+    let document = JsonDocument {
+        kind,
+        version: EXPORT_VERSION,
+        columns: R::COLUMNS,
+        rows,
+    };
+    let mut json = serde_json::to_string_pretty(&document)?;
+    json.push('\n');
+    Ok(json)
+}
+
+pub fn write_export(path: &Path, contents: &str) -> Result<(), ExportError> {
+    write_atomic(path, contents.as_bytes())?;
+    Ok(())
+}
+
+/// Ask where to save, then write the export. `Ok(None)` means the user cancelled.
+///
+/// Builds without the native dialog write to the downloads folder instead.
+pub async fn export_with_dialog(
+    payload: &ExportPayload,
+    format: ExportFormat,
+) -> Result<Option<PathBuf>, ExportError> {
+    let contents = payload.render(format)?;
+    let file_name = payload.default_file_name(format);
+    let Some(path) = pick_save_path(&file_name, format.label(), format.extension()).await? else {
+        return Ok(None);
+    };
+    write_export(&path, &contents)?;
+    Ok(Some(path))
+}
+
+/// Like [`export_with_dialog`] for ready-made text such as a rendered report.
+pub async fn save_text_with_dialog(
+    contents: &str,
+    file_name: &str,
+    extension: &str,
+) -> Result<Option<PathBuf>, ExportError> {
+    let Some(path) = pick_save_path(file_name, &extension.to_uppercase(), extension).await? else {
+        return Ok(None);
+    };
+    write_export(&path, contents)?;
+    Ok(Some(path))
+}
+
+#[cfg(feature = "desktop")]
+async fn pick_save_path(
+    file_name: &str,
+    filter: &str,
+    extension: &str,
+) -> Result<Option<PathBuf>, ExportError> {
+    let mut dialog = rfd::AsyncFileDialog::new()
+        .set_file_name(file_name)
+        .add_filter(filter, &[extension]);
+    if let Some(dir) = dirs::download_dir() {
+        dialog = dialog.set_directory(dir);
+    }
+    Ok(dialog.save_file().await.map(|handle| handle.path().to_path_buf()))
+}
+
+#[cfg(not(feature = "desktop"))]
+async fn pick_save_path(
+    file_name: &str,
+    _filter: &str,
+    _extension: &str,
+) -> Result<Option<PathBuf>, ExportError> {
+    let dir = dirs::download_dir()
+        .or_else(dirs::home_dir)
+        .ok_or(ExportError::NoDestination)?;
+    Ok(Some(unused_path(&dir, file_name)))
+}
+
+/// `dir/file_name`, or `dir/<stem> (2).<ext>` and so on when that is taken,
+/// so an export without a save dialog never replaces an earlier file.
+#[cfg_attr(feature = "desktop", allow(dead_code))]
+fn unused_path(dir: &Path, file_name: &str) -> PathBuf {
+    let candidate = dir.join(file_name);
+    if !candidate.exists() {
+        return candidate;
+    }
+    let (stem, extension) = match file_name.rsplit_once('.') {
+        Some((stem, extension)) if !stem.is_empty() => (stem, format!(".{extension}")),
+        _ => (file_name, String::new()),
+    };
+    (2..)
+        .map(|n| dir.join(format!("{stem} ({n}){extension}")))
+        .find(|path| !path.exists())
+        .expect("some numbered name is free")
+}
+
+fn push_csv_line(out: &mut String, cells: impl IntoIterator<Item = String>) {
+    let line = cells
+        .into_iter()
+        .map(|cell| csv_escape(&cell))
+        .collect::<Vec<_>>()
+        .join(",");
+    out.push_str(&line);
+    out.push('\n');
+}
+
+fn csv_escape(cell: &str) -> String {
+    if cell.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_string()
+    }
+}
+
+fn money(value: f64) -> String {
+    format!("{value:.2}")
+}
+
+fn opt_money(value: Option<f64>) -> String {
+    value.map(money).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{
+        BestPriceEntry, BestPriceSuggestion, CargoEvaluation, PriceNote, SellItem, SellStop,
+    };
+
+    /// Compare against `tests/golden/<name>`. Run with `BLESS_GOLDEN=1` to
+    /// rewrite the files after an intentional layout change.
+    fn assert_golden(name: &str, actual: &str) {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/golden")
+            .join(name);
+        if std::env::var_os("BLESS_GOLDEN").is_some() {
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, actual).unwrap();
+            return;
+        }
+        let expected = std::fs::read_to_string(&path)
+            .unwrap_or_else(|err| panic!("missing golden file {}: {err}", path.display()));
+        assert_eq!(actual, expected, "export differs from {}", path.display());
+    }
+
+    fn cargo_fixture() -> (Vec<CargoItem>, EvaluationSummary) {
+        let items = vec![
+            CargoItem {
+                id: "cargo-1".into(),
+                commodity_id: "LARA".into(),
+                commodity_name: "Laranite".into(),
+                scu: 32,
+                is_hot: false,
+            },
+            CargoItem {
+                id: "cargo-2".into(),
+                commodity_id: "WIDO".into(),
+                commodity_name: "WiDoW, \"pure\"".into(),
+                scu: 4,
+                is_hot: true,
+            },
+        ];
+        let summary = EvaluationSummary {
+            total_ev: 100_800.0,
+            average_confidence: 0.6,
+            items: vec![
+                (
+                    "cargo-1".into(),
+                    CargoEvaluation {
+                        ev: 96_000.0,
+                        min: Some(92_160.0),
+                        max: Some(99_200.0),
+                        confidence: 0.8,
+                    },
+                ),
+                (
+                    "cargo-2".into(),
+                    CargoEvaluation {
+                        ev: 4_800.0,
+                        min: None,
+                        max: None,
+                        confidence: 0.4,
+                    },
+                ),
+            ],
+        };
+        (items, summary)
+    }
+
+    fn best_price_fixture() -> BestPriceSummary {
+        let entry = |name: &str, sell: f64, adjusted: f64| BestPriceEntry {
+            location_id: Some(name.to_lowercase().replace(' ', "-")),
+            location_name: name.into(),
+            sell_price: Some(sell),
+            buy_price: None,
+            adjusted_price: adjusted,
+            stock: Some(1_200.0),
+            status_sell: None,
+            status_buy: None,
+            container_sizes: vec![1.0, 2.0],
+            notes: Vec::new(),
+        };
+        let mut risky = entry("Ruin Station", 3_200.0, 2_720.0);
+        risky.notes = vec![PriceNote::CrossSystem, PriceNote::Hotspot, PriceNote::Sell("low")];
+        BestPriceSummary {
+            suggestions: vec![BestPriceSuggestion {
+                item_id: "cargo-1".into(),
+                commodity_name: "Laranite".into(),
+                entries: vec![entry("Area18 TDD", 3_000.0, 3_000.0), risky],
+            }],
+            best_overall: None,
+        }
+    }
+
+    fn sell_plan_fixture() -> SellPlan {
+        SellPlan {
+            stops: vec![
+                SellStop {
+                    terminal_name: "Area18 TDD".into(),
+                    terminal_id: Some(42),
+                    system: Some("Stanton".into()),
+                    items: vec![SellItem {
+                        commodity_name: "Laranite".into(),
+                        commodity_id: "LARA".into(),
+                        scu: 32,
+                        price_per_unit: 3_000.0,
+                        total_value: 96_000.0,
+                        available_stock: None,
+                    }],
+                    stop_value: 96_000.0,
+                    is_nqa: false,
+                    distance_from_prev: None,
+                },
+                SellStop {
+                    terminal_name: "GrimHEX".into(),
+                    terminal_id: Some(7),
+                    system: Some("Stanton".into()),
+                    items: vec![SellItem {
+                        commodity_name: "WiDoW".into(),
+                        commodity_id: "WIDO".into(),
+                        scu: 4,
+                        price_per_unit: 1_200.5,
+                        total_value: 4_802.0,
+                        available_stock: Some(10.0),
+                    }],
+                    stop_value: 4_802.0,
+                    is_nqa: true,
+                    distance_from_prev: Some(12.75),
+                },
+            ],
+            total_value: 100_802.0,
+            total_distance: Some(12.75),
+        }
+    }
+
+    fn route_fixture() -> Vec<TradeRoute> {
+        vec![TradeRoute {
+            commodity_id: "LARA".into(),
+            commodity_name: "Laranite".into(),
+            is_illegal: false,
+            buy_terminal_id: 1,
+            buy_terminal_name: "Shubin SAL-5".into(),
+            buy_system: Some("Stanton".into()),
+            buy_price: 2_500.0,
+            buy_stock: 500.0,
+            buy_user_rows: 3,
+            buy_is_planetary: true,
+            sell_terminal_id: 2,
+            sell_terminal_name: "Area18 TDD".into(),
+            sell_system: Some("Stanton".into()),
+            sell_price: 3_000.0,
+            sell_demand: 80.0,
+            sell_user_rows: 5,
+            sell_is_planetary: true,
+            sell_is_nqa: false,
+            distance_gm: Some(40.0),
+            profit_per_scu: 500.0,
+            roi_percent: 20.0,
+        }]
+    }
+
+    #[test]
+    fn evaluation_golden() {
+        let (items, summary) = cargo_fixture();
+        let payload = ExportPayload::Evaluation(evaluation_rows(&items, &summary));
+        assert_golden("evaluation.csv", &payload.render(ExportFormat::Csv).unwrap());
+        assert_golden("evaluation.json", &payload.render(ExportFormat::Json).unwrap());
+    }
+
+    #[test]
+    fn best_prices_golden() {
+        let payload = ExportPayload::BestPrices(best_price_rows(&best_price_fixture()));
+        assert_golden("best_prices.csv", &payload.render(ExportFormat::Csv).unwrap());
+        assert_golden("best_prices.json", &payload.render(ExportFormat::Json).unwrap());
+    }
+
+    #[test]
+    fn sell_plan_golden() {
+        let payload = ExportPayload::SellPlan(sell_plan_rows(&sell_plan_fixture()));
+        assert_golden("sell_plan.csv", &payload.render(ExportFormat::Csv).unwrap());
+        assert_golden("sell_plan.json", &payload.render(ExportFormat::Json).unwrap());
+    }
+
+    #[test]
+    fn routes_golden() {
+        let payload = ExportPayload::Routes(route_rows(&route_fixture(), 100));
+        assert_golden("routes.csv", &payload.render(ExportFormat::Csv).unwrap());
+        assert_golden("routes.json", &payload.render(ExportFormat::Json).unwrap());
+    }
+
+    #[test]
+    fn csv_header_matches_columns() {
+        let csv = to_csv::<RouteRow>(&[]);
+        assert_eq!(csv.trim_end(), RouteRow::COLUMNS.join(","));
+    }
+
+    #[test]
+    fn numbered_names_keep_earlier_exports() {
+        let dir = std::env::temp_dir().join(format!("cvs-export-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        assert_eq!(unused_path(&dir, "cargo.csv"), dir.join("cargo.csv"));
+        std::fs::write(dir.join("cargo.csv"), "").unwrap();
+        assert_eq!(unused_path(&dir, "cargo.csv"), dir.join("cargo (2).csv"));
+        std::fs::write(dir.join("cargo (2).csv"), "").unwrap();
+        assert_eq!(unused_path(&dir, "cargo.csv"), dir.join("cargo (3).csv"));
+
+        std::fs::write(dir.join("notes"), "").unwrap();
+        assert_eq!(unused_path(&dir, "notes"), dir.join("notes (2)"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
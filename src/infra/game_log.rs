@@ -0,0 +1,128 @@
+//! Tail the Star Citizen `Game.log` for new lines.
+//!
+//! The game rewrites the log at every launch, so a file that shrinks is read
+//! again from the start.
+
+use std::{
+    fs::File,
+    io::{self, Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+};
+
+/// Where launchers install the LIVE channel by default.
+const DEFAULT_LOCATIONS: &[&str] = &[
+    r"C:\Program Files\Roberts Space Industries\StarCitizen\LIVE\Game.log",
+    r"D:\Roberts Space Industries\StarCitizen\LIVE\Game.log",
+    // Wine prefixes created by the LUG helper and Lutris.
+    "Games/star-citizen/drive_c/Program Files/Roberts Space Industries/StarCitizen/LIVE/Game.log",
+    "Games/star-citizen/drive_c/Roberts Space Industries/StarCitizen/LIVE/Game.log",
+];
+
+/// The configured path, or the first default location that exists.
+pub fn resolve_game_log_path(configured: &str) -> Option<PathBuf> {
+    let configured = configured.trim();
+    if !configured.is_empty() {
+        return Some(PathBuf::from(configured));
+    }
+    let home = dirs::home_dir();
+    DEFAULT_LOCATIONS
+        .iter()
+        .map(|location| {
+            let path = Path::new(location);
+            match &home {
+                Some(home) if path.is_relative() => home.join(path),
+                _ => path.to_path_buf(),
+            }
+        })
+        .find(|path| path.is_file())
+}
+
+/// Reads lines appended since the last poll.
+#[derive(Debug)]
+pub struct LogTail {
+    path: PathBuf,
+    offset: u64,
+    /// Trailing text without a newline yet; completed on the next poll.
+    partial: String,
+}
+
+impl LogTail {
+    /// Start at the beginning so the current session's position is picked up.
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            offset: 0,
+            partial: String::new(),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Complete lines written since the previous call. A missing file is not
+    /// an error: the game may simply not have been started yet.
+    pub fn poll(&mut self) -> io::Result<Vec<String>> {
+        let mut file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err),
+        };
+        let len = file.metadata()?.len();
+        if len < self.offset {
+            self.offset = 0;
+            self.partial.clear();
+        }
+        if len == self.offset {
+            return Ok(Vec::new());
+        }
+
+        file.seek(SeekFrom::Start(self.offset))?;
+        let mut bytes = Vec::with_capacity((len - self.offset) as usize);
+        file.take(len - self.offset).read_to_end(&mut bytes)?;
+        self.offset += bytes.len() as u64;
+
+        self.partial.push_str(&String::from_utf8_lossy(&bytes));
+        let Some(last_newline) = self.partial.rfind('\n') else {
+            return Ok(Vec::new());
+        };
+        let rest = self.partial.split_off(last_newline + 1);
+        let complete = std::mem::replace(&mut self.partial, rest);
+        Ok(complete
+            .lines()
+            .map(|line| line.trim_end_matches('\r').to_string())
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, io::Write};
+
+    use super::*;
+
+    #[test]
+    fn returns_complete_lines_and_restarts_after_rewrite() {
+        let path = std::env::temp_dir().join(format!("cvs-game-log-{}.log", std::process::id()));
+        fs::write(&path, "first\r\nsecond\npart").unwrap();
+        let mut tail = LogTail::new(path.clone());
+
+        assert_eq!(tail.poll().unwrap(), ["first", "second"]);
+        assert!(tail.poll().unwrap().is_empty());
+
+        fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(b"ial\nthird\n")
+            .unwrap();
+        assert_eq!(tail.poll().unwrap(), ["partial", "third"]);
+
+        // A new game session truncates and rewrites the file.
+        fs::write(&path, "new session\n").unwrap();
+        assert_eq!(tail.poll().unwrap(), ["new session"]);
+
+        fs::remove_file(&path).unwrap();
+        assert!(tail.poll().unwrap().is_empty());
+    }
+}
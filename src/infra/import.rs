@@ -0,0 +1,27 @@
+//! Reading cargo lists from disk for the import dialog.
+
+use std::io;
+
+/// Let the user pick a CSV/JSON/text file and return its contents.
+/// `Ok(None)` means the dialog was cancelled.
+#[cfg(feature = "desktop")]
+pub async fn pick_import_file() -> io::Result<Option<String>> {
+    let mut dialog = rfd::AsyncFileDialog::new()
+        .add_filter("Cargo list", &["csv", "json", "txt"]);
+    if let Some(dir) = dirs::download_dir() {
+        dialog = dialog.set_directory(dir);
+    }
+    match dialog.pick_file().await {
+        Some(handle) => std::fs::read_to_string(handle.path()).map(Some),
+        None => Ok(None),
+    }
+}
+
+/// Builds without a native file dialog can only import pasted text.
+#[cfg(not(feature = "desktop"))]
+pub async fn pick_import_file() -> io::Result<Option<String>> {
+    Ok(None)
+}
+
+/// Whether [`pick_import_file`] can show a dialog in this build.
+pub const FILE_DIALOG_AVAILABLE: bool = cfg!(feature = "desktop");
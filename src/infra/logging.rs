@@ -0,0 +1,175 @@
+//! Structured logging: `tracing` events to stderr and a daily rotating file.
+//!
+//! Levels come from `logging.level` plus per-module overrides in
+//! `logging.modules` (`CVS_LOG` overrides the base level for one run). Files
+//! go to `logs/` next to the caches and only the newest `logging.keep_files`
+//! are kept.
+//!
+//! Large payloads (API responses and the like) are logged through
+//! [`redact`], which only shows them in full when debug output is on.
+
+use std::{
+    fmt,
+    path::PathBuf,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use tracing::level_filters::LevelFilter;
+use tracing_appender::{non_blocking::WorkerGuard, rolling};
+use tracing_subscriber::{
+    fmt as layer_fmt, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter, Layer,
+};
+
+use super::config::LoggingConfig;
+
+/// Crate the relative module paths in `logging.modules` belong to.
+const CRATE: &str = "cargo_value_scanner";
+
+/// Payloads up to this many bytes are always logged as they are.
+const PAYLOAD_LIMIT: usize = 512;
+
+static FULL_PAYLOADS: AtomicBool = AtomicBool::new(false);
+
+/// Where log files are written.
+pub fn log_dir() -> Option<PathBuf> {
+    dirs::data_local_dir().map(|dir| dir.join("cargo-value-scanner").join("logs"))
+}
+
+/// `EnvFilter` directives for `config`, e.g. `info,cargo_value_scanner::infra::uex=debug`.
+pub fn filter_directives(config: &LoggingConfig) -> String {
+    let mut directives = vec![config.level.trim().to_lowercase()];
+    for (module, level) in &config.modules {
+        let target = if module == CRATE || module.starts_with(&format!("{CRATE}::")) {
+            module.clone()
+        } else {
+            format!("{CRATE}::{module}")
+        };
+        directives.push(format!("{target}={}", level.trim().to_lowercase()));
+    }
+    directives.join(",")
+}
+
+/// Installs the global subscriber. Log files are named `<file_prefix>.<date>.log`;
+/// `console` also echoes events to stderr. Keep the returned guard alive so
+/// buffered lines are flushed on exit.
+pub fn init(config: &LoggingConfig, file_prefix: &str, console: bool) -> Option<WorkerGuard> {
+    let debug = config
+        .level
+        .parse::<LevelFilter>()
+        .is_ok_and(|level| level >= LevelFilter::DEBUG);
+    FULL_PAYLOADS.store(config.debug_payloads || debug, Ordering::Relaxed);
+
+    let filter =
+        || EnvFilter::try_new(filter_directives(config)).unwrap_or_else(|_| EnvFilter::new("info"));
+    let stderr = console.then(|| {
+        layer_fmt::layer()
+            .with_writer(std::io::stderr)
+            .with_filter(filter())
+    });
+
+    // The appender scans the directory for old files while building and
+    // complains on stderr if it does not exist yet.
+    let (file, guard) = match log_dir().filter(|_| config.file).and_then(|dir| {
+        std::fs::create_dir_all(&dir).ok()?;
+        rolling::Builder::new()
+            .rotation(rolling::Rotation::DAILY)
+            .filename_prefix(file_prefix)
+            .filename_suffix("log")
+            .max_log_files(config.keep_files.max(1))
+            .build(dir)
+            .ok()
+    }) {
+        Some(appender) => {
+            let (writer, guard) = tracing_appender::non_blocking(appender);
+            let layer = layer_fmt::layer()
+                .with_ansi(false)
+                .with_writer(writer)
+                .with_filter(filter());
+            (Some(layer), Some(guard))
+        }
+        None => (None, None),
+    };
+
+    // Ignore a subscriber that is already installed (e.g. by a test harness).
+    let _ = tracing_subscriber::registry()
+        .with(stderr)
+        .with(file)
+        .try_init();
+    guard
+}
+
+/// A payload for a log field: in full when small or when debug output is
+/// on, otherwise just its size.
+pub fn redact(payload: &str) -> Redacted<'_> {
+    Redacted {
+        payload,
+        full: FULL_PAYLOADS.load(Ordering::Relaxed),
+    }
+}
+
+pub struct Redacted<'a> {
+    payload: &'a str,
+    full: bool,
+}
+
+impl fmt::Display for Redacted<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.full || self.payload.len() <= PAYLOAD_LIMIT {
+            f.write_str(self.payload)
+        } else {
+            write!(
+                f,
+                "<{} bytes redacted; enable logging.debug_payloads to see them>",
+                self.payload.len()
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_directives_and_redacts_large_payloads() {
+        let mut config = LoggingConfig {
+            level: "Warn".to_string(),
+            ..LoggingConfig::default()
+        };
+        config
+            .modules
+            .insert("infra::uex".to_string(), "debug".to_string());
+        config
+            .modules
+            .insert("cargo_value_scanner::app".to_string(), "trace".to_string());
+        assert_eq!(
+            filter_directives(&config),
+            "warn,cargo_value_scanner::app=trace,cargo_value_scanner::infra::uex=debug"
+        );
+        assert!(EnvFilter::try_new(filter_directives(&config)).is_ok());
+
+        let large = "x".repeat(PAYLOAD_LIMIT + 1);
+        let redacted = Redacted {
+            payload: &large,
+            full: false,
+        }
+        .to_string();
+        assert!(redacted.starts_with("<513 bytes redacted"));
+        assert_eq!(
+            Redacted {
+                payload: &large,
+                full: true
+            }
+            .to_string(),
+            large
+        );
+        assert_eq!(
+            Redacted {
+                payload: "{}",
+                full: false
+            }
+            .to_string(),
+            "{}"
+        );
+    }
+}
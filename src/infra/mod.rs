@@ -1,3 +1,11 @@
+pub mod api;
 pub mod cache;
 pub mod config;
+pub mod crew_session;
+pub mod export;
+pub mod game_log;
+pub mod import;
+pub mod logging;
+pub mod routes;
 pub mod uex;
+pub mod webhook;
@@ -0,0 +1,93 @@
+//! Building the full trade route list from UEX prices (shared by the Routes page and CLI).
+
+use std::collections::HashSet;
+
+use tracing::{debug_span, info, instrument, warn};
+
+use crate::domain::{calculate_routes_for_commodity, traffic_board, TradeRoute, TrafficSnapshot};
+use crate::infra::cache::{
+    load_routes_cache, load_traffic_history, save_routes_cache, save_traffic_history, RoutesCache,
+};
+use crate::infra::uex::{UexClient, UexClientError};
+
+/// Commodity categories that are bought and sold at terminals.
+pub const TRADE_CATEGORIES: &[&str] = &[
+    "Agricultural",
+    "Food",
+    "Gas",
+    "Medical",
+    "Metal",
+    "Mineral",
+    "Scrap",
+    "Vice",
+    "Drug",
+    "Hallucinogen",
+];
+
+/// Routes from the on-disk cache, or freshly calculated (and cached) when the
+/// cache is missing, expired, or `force_refresh` is set.
+#[instrument(skip(client, nqa_terminal_ids))]
+pub async fn load_routes(
+    client: &UexClient,
+    nqa_terminal_ids: &HashSet<i32>,
+    force_refresh: bool,
+) -> Result<Vec<TradeRoute>, UexClientError> {
+    if !force_refresh {
+        if let Some(cache) = load_routes_cache() {
+            info!(count = cache.routes.len(), age = %cache.age_string(), "using cached routes");
+            record_traffic(&cache.routes, cache.cached_at);
+            return Ok(cache.routes);
+        }
+    }
+
+    info!("fetching fresh route data");
+    let routes = fetch_all_routes(client, nqa_terminal_ids).await?;
+    let cache = RoutesCache::new(routes.clone());
+    let _ = save_routes_cache(&cache);
+    record_traffic(&routes, cache.cached_at);
+    Ok(routes)
+}
+
+/// Adds a traffic snapshot for routes fetched at `fetched_at`, once per fetch.
+fn record_traffic(routes: &[TradeRoute], fetched_at: u64) {
+    let mut history = load_traffic_history();
+    if history.has_snapshot(fetched_at) {
+        return;
+    }
+    history.record(TrafficSnapshot::of(&traffic_board(routes), fetched_at));
+    if let Err(e) = save_traffic_history(&history) {
+        warn!(error = %e, "failed to save the traffic history");
+    }
+}
+
+/// Fetch prices for every trade commodity and calculate all buy→sell pairs.
+#[instrument(skip_all)]
+pub async fn fetch_all_routes(
+    client: &UexClient,
+    nqa_terminal_ids: &HashSet<i32>,
+) -> Result<Vec<TradeRoute>, UexClientError> {
+    let commodities = client.get_commodities().await?.data;
+    let trade_commodities: Vec<_> = commodities
+        .iter()
+        .filter(|c| TRADE_CATEGORIES.contains(&c.category.as_str()))
+        .collect();
+
+    info!(commodities = trade_commodities.len(), "loading prices for routes");
+
+    let mut all_routes = Vec::new();
+    for commodity in trade_commodities {
+        if let Ok(prices) = client.get_prices(&commodity.id, Some(&commodity.name)).await {
+            let _span = debug_span!("calculate_routes", commodity = %commodity.id).entered();
+            all_routes.extend(calculate_routes_for_commodity(
+                &commodity.id,
+                &commodity.name,
+                false, // TODO: get is_illegal from commodity
+                &prices.data,
+                nqa_terminal_ids,
+            ));
+        }
+    }
+
+    info!(count = all_routes.len(), "calculated routes");
+    Ok(all_routes)
+}
@@ -17,12 +17,13 @@ use thiserror::Error;
 use time::format_description::well_known::Rfc3339;
 use time::OffsetDateTime;
 use tokio::sync::Mutex;
+use tracing::{debug, info, instrument, warn};
 
 use crate::domain::{Commodity, CommodityId, PricePoint, Terminal};
 use crate::infra::cache::{load_terminal_cache, save_terminal_cache, TerminalCache};
+use crate::infra::config;
+use crate::infra::logging::redact;
 
-const DEFAULT_BASE_URL: &str = "https://api.uexcorp.uk/2.0/";
-const DEFAULT_TTL: Duration = Duration::from_secs(60 * 60);
 const USER_AGENT: &str = "cargo-value-scanner/0.1.0";
 
 #[derive(Debug, Error)]
@@ -93,18 +94,24 @@ pub struct UexClient {
 }
 
 impl UexClient {
+    /// Client configured from the current `AppConfig` (base URL, timeout, TTL).
     pub fn new() -> Result<Self, UexClientError> {
-        Self::with_base_url(DEFAULT_BASE_URL)
+        let config = config::current();
+        Self::with_base_url(&config.network.base_url)
     }
 
     pub fn with_base_url(base: &str) -> Result<Self, UexClientError> {
+        let config = config::current();
         let base_url = Url::parse(base)?;
-        let http = Client::builder().user_agent(USER_AGENT).build()?;
+        let http = Client::builder()
+            .user_agent(USER_AGENT)
+            .timeout(config.network.request_timeout())
+            .build()?;
         Ok(Self {
             http,
             base_url,
             cache: Arc::new(Mutex::new(UexCache::default())),
-            ttl: DEFAULT_TTL,
+            ttl: config.cache.price_ttl(),
         })
     }
 
@@ -113,6 +120,7 @@ impl UexClient {
         self
     }
 
+    #[instrument(skip(self))]
     pub async fn get_commodities(&self) -> Result<CachedPayload<Vec<Commodity>>, UexClientError> {
         if let Some(payload) = self.cached_commodities().await {
             return Ok(payload);
@@ -139,6 +147,7 @@ impl UexClient {
         }
     }
 
+    #[instrument(skip(self))]
     pub async fn get_prices(
         &self,
         commodity_id: &str,
@@ -159,23 +168,16 @@ impl UexClient {
             let mut url = self.url("commodities_prices")?;
             url.query_pairs_mut().append_pair(&key, &value);
 
-            println!("Requesting UEX prices from {url}");
+            debug!(%url, "requesting prices");
 
             match self
                 .fetch_data::<serde_json::Value>(self.http.get(url.clone()))
                 .await
             {
                 Ok(raw) => {
-                    println!(
-                        "UEX price payload ({key}={value}): {}",
-                        serde_json::to_string_pretty(&raw).unwrap_or_else(|_| raw.to_string())
-                    );
+                    debug!(%key, %value, payload = %redact(&raw.to_string()), "price payload");
                     let data = parse_price_points(raw);
-                    println!(
-                        "UEX parsed {} entries for commodity {commodity_id} (key: {key}). Sample: {:?}",
-                        data.len(),
-                        data.first()
-                    );
+                    info!(commodity_id, %key, entries = data.len(), "loaded prices");
                     let status = if data.is_empty() {
                         CacheStatus::Cached
                     } else {
@@ -184,9 +186,7 @@ impl UexClient {
                     return Ok(self.store_prices(commodity_id, data, status).await);
                 }
                 Err(error) => {
-                    println!(
-                        "UEX price request failed for {url}: {error}; retrying with next identifier if available."
-                    );
+                    warn!(%url, %error, "price request failed; trying the next identifier");
                     last_error = Some(error);
                 }
             }
@@ -205,17 +205,18 @@ impl UexClient {
     }
 
     /// Load terminals with TTL + version check.
-    /// Refreshes if: cache expired (>7 days) OR game version changed.
+    /// Refreshes if: cache expired (older than `cache.terminal_ttl_secs`) OR game version changed.
+    #[instrument(skip(self))]
     pub async fn get_terminals(&self) -> Result<TerminalCache, UexClientError> {
         // Check in-memory cache first (always valid within session)
         {
             let cache = self.cache.lock().await;
             if let Some(ref terminals) = cache.terminals {
-                println!(
-                    "[terminals] Using in-memory cache ({} terminals, version {}, age: {})",
-                    terminals.terminals.len(),
-                    terminals.game_version,
-                    terminals.age_string()
+                debug!(
+                    count = terminals.terminals.len(),
+                    version = %terminals.game_version,
+                    age = %terminals.age_string(),
+                    "terminals from memory"
                 );
                 return Ok(terminals.clone());
             }
@@ -227,9 +228,10 @@ impl UexClient {
             
             // Check TTL first
             if disk_cache.is_expired() {
-                println!(
-                    "[terminals] Cache expired (age: {}, TTL: 7d), refreshing...",
-                    age
+                info!(
+                    %age,
+                    ttl_hours = config::current().cache.terminal_ttl_secs / 3600,
+                    "terminal cache expired; refreshing"
                 );
                 return self.refresh_terminals().await;
             }
@@ -238,17 +240,15 @@ impl UexClient {
             let current_version = self.fetch_current_game_version().await?;
             
             if disk_cache.game_version == current_version {
-                println!(
-                    "[terminals] Disk cache valid (age: {}, version: {})",
-                    age, current_version
-                );
+                info!(%age, version = %current_version, "terminals from disk cache");
                 // Store in memory cache
                 self.cache.lock().await.terminals = Some(disk_cache.clone());
                 return Ok(disk_cache);
             } else {
-                println!(
-                    "[terminals] Version changed: {} -> {}, refreshing...",
-                    disk_cache.game_version, current_version
+                info!(
+                    from = %disk_cache.game_version,
+                    to = %current_version,
+                    "game version changed; refreshing terminals"
                 );
             }
         }
@@ -258,8 +258,9 @@ impl UexClient {
     }
 
     /// Force refresh terminals from API.
+    #[instrument(skip(self))]
     pub async fn refresh_terminals(&self) -> Result<TerminalCache, UexClientError> {
-        println!("[terminals] Fetching all terminals from API...");
+        info!("fetching terminals");
         
         // Get current game version first
         let game_version = self.fetch_current_game_version().await?;
@@ -270,18 +271,13 @@ impl UexClient {
         let terminals: Vec<Terminal> = terminals_dto.into_iter().map(Terminal::from).collect();
         let nqa_count = terminals.iter().filter(|t| t.is_nqa).count();
 
-        println!(
-            "[terminals] Loaded {} terminals ({} NQA) for version {}",
-            terminals.len(),
-            nqa_count,
-            game_version
-        );
+        info!(count = terminals.len(), nqa = nqa_count, version = %game_version, "loaded terminals");
 
         let cache = TerminalCache::new(game_version, terminals);
 
         // Save to disk
         if let Err(e) = save_terminal_cache(&cache) {
-            println!("[terminals] Warning: failed to save cache: {e}");
+            warn!(error = %e, "failed to save the terminal cache");
         }
 
         // Store in memory
@@ -326,6 +322,7 @@ impl UexClient {
 
     /// Get distances from one origin to multiple destinations.
     /// Returns a map of destination_id -> distance in Gm.
+    #[instrument(skip(self, destination_ids), fields(destinations = destination_ids.len()))]
     pub async fn get_terminal_distances(
         &self,
         origin_id: i32,
@@ -373,7 +370,7 @@ impl UexClient {
             .get(commodity_id)
             .and_then(|entry| entry.if_fresh(self.ttl));
         if result.is_some() {
-            println!("Serving cached UEX prices for commodity {commodity_id}");
+            debug!(commodity_id, "prices from memory");
         }
         result
     }
@@ -481,6 +478,10 @@ struct CommodityDto {
     code: Option<String>,
     #[serde(default)]
     weight_scu: Option<f64>,
+    #[serde(default)]
+    is_raw: Option<i32>,
+    #[serde(default)]
+    is_mineral: Option<i32>,
     #[serde(alias = "date_modified", alias = "dateModified", default)]
     date_modified: Option<i64>,
 }
@@ -493,6 +494,8 @@ impl From<CommodityDto> for Commodity {
             category: value.kind.unwrap_or_else(|| "Unknown".to_string()),
             code: value.code,
             weight_scu: value.weight_scu,
+            is_raw: value.is_raw.unwrap_or(0) == 1,
+            is_mineral: value.is_mineral.unwrap_or(0) == 1,
         }
     }
 }
@@ -0,0 +1,535 @@
+//! Sharing summaries and plans to Discord webhooks as embeds.
+//!
+//! Each report builds a [`ShareReport`]: placeholder values for the
+//! configurable title and description templates plus the embed's fields.
+//! Templates are rendered with [`render_report`], the engine behind text
+//! reports, so both share one syntax.
+//! The UI renders [`ShareReport::to_message`] as a preview and posts the
+//! same message, so what is previewed is what lands in the channel.
+//!
+//! Discord allows about five posts per webhook every two seconds; the client
+//! additionally spaces posts to each webhook by `webhooks.min_interval_secs`
+//! and honours the `retry_after` of a 429 answer.
+
+use std::{
+    collections::HashMap,
+    sync::{Mutex, OnceLock},
+    time::{Duration, Instant},
+};
+
+use reqwest::{Client, StatusCode};
+use serde::Serialize;
+use thiserror::Error;
+
+use super::config::{ShareTemplate, ShareTemplates};
+use crate::domain::{
+    render_report, BestPriceSummary, CargoManifest, EvaluationSummary, ReportScope, SellPlan,
+    SessionSnapshot,
+};
+use crate::util::{format, i18n::t};
+
+const TITLE_LIMIT: usize = 256;
+const DESCRIPTION_LIMIT: usize = 4096;
+const FIELD_LIMIT: usize = 25;
+const FIELD_NAME_LIMIT: usize = 256;
+const FIELD_VALUE_LIMIT: usize = 1024;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ShareKind {
+    BestPrices,
+    SellPlan,
+    CrewPayout,
+    Loot,
+}
+
+impl ShareKind {
+    pub const ALL: [ShareKind; 4] = [
+        ShareKind::BestPrices,
+        ShareKind::SellPlan,
+        ShareKind::CrewPayout,
+        ShareKind::Loot,
+    ];
+
+    pub fn label(self) -> String {
+        t(match self {
+            ShareKind::BestPrices => "share.kind.best_prices",
+            ShareKind::SellPlan => "share.kind.sell_plan",
+            ShareKind::CrewPayout => "share.kind.crew_payout",
+            ShareKind::Loot => "share.kind.loot",
+        })
+    }
+
+    pub fn template(self, templates: &ShareTemplates) -> &ShareTemplate {
+        match self {
+            ShareKind::BestPrices => &templates.best_prices,
+            ShareKind::SellPlan => &templates.sell_plan,
+            ShareKind::CrewPayout => &templates.crew_payout,
+            ShareKind::Loot => &templates.loot,
+        }
+    }
+
+    pub fn template_mut(self, templates: &mut ShareTemplates) -> &mut ShareTemplate {
+        match self {
+            ShareKind::BestPrices => &mut templates.best_prices,
+            ShareKind::SellPlan => &mut templates.sell_plan,
+            ShareKind::CrewPayout => &mut templates.crew_payout,
+            ShareKind::Loot => &mut templates.loot,
+        }
+    }
+
+    /// Placeholders the report fills, for the template editor.
+    pub fn placeholders(self) -> &'static [&'static str] {
+        match self {
+            ShareKind::BestPrices | ShareKind::Loot => &["manifest", "items", "scu", "total", "date"],
+            ShareKind::SellPlan => &["manifest", "stops", "scu", "total", "date"],
+            ShareKind::CrewPayout => &["manifest", "crew", "total", "date"],
+        }
+    }
+
+    /// Embed accent colour.
+    fn color(self) -> u32 {
+        match self {
+            ShareKind::BestPrices => 0x38bdf8,
+            ShareKind::SellPlan => 0x34d399,
+            ShareKind::CrewPayout => 0xfbbf24,
+            ShareKind::Loot => 0xf43f5e,
+        }
+    }
+}
+
+/// Body of a webhook post (the subset of Discord's format used here).
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct WebhookMessage {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    pub embeds: Vec<Embed>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Embed {
+    pub title: String,
+    pub description: String,
+    pub color: u32,
+    pub fields: Vec<EmbedField>,
+    pub footer: EmbedFooter,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct EmbedField {
+    pub name: String,
+    pub value: String,
+    pub inline: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct EmbedFooter {
+    pub text: String,
+}
+
+/// A report ready to be rendered with its template.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ShareReport {
+    pub kind: ShareKind,
+    /// Values for `{name}` placeholders in the template.
+    pub values: ReportScope,
+    pub fields: Vec<EmbedField>,
+}
+
+impl ShareReport {
+    /// The embed as it will be posted, within Discord's size limits.
+    pub fn to_message(&self, template: &ShareTemplate, username: &str) -> WebhookMessage {
+        let fields = self
+            .fields
+            .iter()
+            .take(FIELD_LIMIT)
+            .map(|field| EmbedField {
+                name: truncate(&field.name, FIELD_NAME_LIMIT),
+                value: truncate(&field.value, FIELD_VALUE_LIMIT),
+                inline: field.inline,
+            })
+            .collect();
+        WebhookMessage {
+            username: Some(username.trim().to_string()).filter(|name| !name.is_empty()),
+            embeds: vec![Embed {
+                title: truncate(&self.render(&template.title), TITLE_LIMIT),
+                description: truncate(&self.render(&template.description), DESCRIPTION_LIMIT),
+                color: self.kind.color(),
+                fields,
+                footer: EmbedFooter {
+                    text: format!("Cargo Value Scanner · {}", self.kind.label()),
+                },
+            }],
+        }
+    }
+
+    /// Saved templates are checked by `AppConfig::validate`; a broken one is
+    /// posted as typed rather than not at all.
+    fn render(&self, template: &str) -> String {
+        render_report(template, &self.values).unwrap_or_else(|_| template.to_string())
+    }
+}
+
+/// Small message for checking a webhook from Settings.
+pub fn test_message(username: &str) -> WebhookMessage {
+    WebhookMessage {
+        username: Some(username.trim().to_string()).filter(|name| !name.is_empty()),
+        embeds: vec![Embed {
+            title: "Cargo Value Scanner".to_string(),
+            description: t("share.test_message"),
+            color: ShareKind::BestPrices.color(),
+            fields: Vec::new(),
+            footer: EmbedFooter { text: date_value() },
+        }],
+    }
+}
+
+fn truncate(text: &str, limit: usize) -> String {
+    if text.chars().count() <= limit {
+        return text.to_string();
+    }
+    let mut cut: String = text.chars().take(limit - 1).collect();
+    cut.push('…');
+    cut
+}
+
+fn field(name: impl Into<String>, value: impl Into<String>, inline: bool) -> EmbedField {
+    EmbedField {
+        name: name.into(),
+        value: value.into(),
+        inline,
+    }
+}
+
+fn date_value() -> String {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|since| since.as_secs())
+        .unwrap_or_default();
+    format::date_time(now)
+}
+
+/// Best sell spot per item of `manifest`.
+pub fn best_prices_report(manifest: &CargoManifest, summary: &BestPriceSummary) -> ShareReport {
+    let mut total = 0.0;
+    let mut fields = Vec::new();
+    for suggestion in &summary.suggestions {
+        let Some(entry) = suggestion.entries.first() else {
+            continue;
+        };
+        let scu = manifest
+            .items
+            .iter()
+            .find(|item| item.id == suggestion.item_id)
+            .map(|item| item.scu)
+            .unwrap_or(0);
+        let price = entry.sell_price.unwrap_or(entry.adjusted_price);
+        total += price * scu as f64;
+        fields.push(field(
+            format!("{} · {scu} SCU", suggestion.commodity_name),
+            format!("{} @ {} aUEC", entry.location_name, format::number(price, 2)),
+            true,
+        ));
+    }
+    ShareReport {
+        kind: ShareKind::BestPrices,
+        values: ReportScope::default()
+            .text("manifest", manifest.label())
+            .text("items", fields.len().to_string())
+            .text("scu", manifest.total_scu().to_string())
+            .text("total", format::integer(total))
+            .text("date", date_value()),
+        fields,
+    }
+}
+
+/// Stops of `plan` in order with what to sell at each.
+pub fn sell_plan_report(manifest: &CargoManifest, plan: &SellPlan) -> ShareReport {
+    let fields = plan
+        .stops
+        .iter()
+        .enumerate()
+        .map(|(index, stop)| {
+            let mut name = format!("{}. {}", index + 1, stop.terminal_name);
+            if let Some(system) = &stop.system {
+                name.push_str(&format!(" · {system}"));
+            }
+            let mut lines: Vec<String> = stop
+                .items
+                .iter()
+                .map(|item| {
+                    format!(
+                        "{} × {} SCU @ {}",
+                        item.commodity_name,
+                        item.scu,
+                        format::number(item.price_per_unit, 2)
+                    )
+                })
+                .collect();
+            lines.push(format!("**{} aUEC**", format::integer(stop.stop_value)));
+            field(name, lines.join("\n"), false)
+        })
+        .collect();
+    ShareReport {
+        kind: ShareKind::SellPlan,
+        values: ReportScope::default()
+            .text("manifest", manifest.label())
+            .text("stops", plan.stops.len().to_string())
+            .text("scu", manifest.total_scu().to_string())
+            .text("total", format::integer(plan.total_value))
+            .text("date", date_value()),
+        fields,
+    }
+}
+
+/// Each crew member's cut of a crew session's plan.
+pub fn crew_payout_report(snapshot: &SessionSnapshot) -> ShareReport {
+    let total = snapshot.plan.as_ref().map(|plan| plan.total_value).unwrap_or(0.0);
+    let fields = snapshot
+        .split()
+        .into_iter()
+        .map(|(member, cut)| {
+            field(
+                member.name.clone(),
+                format!("{} aUEC (×{})", format::integer(cut), format::number(member.weight as f64, 1)),
+                true,
+            )
+        })
+        .collect();
+    ShareReport {
+        kind: ShareKind::CrewPayout,
+        values: ReportScope::default()
+            .text("manifest", snapshot.manifest.label())
+            .text("crew", snapshot.crew.len().to_string())
+            .text("total", format::integer(total))
+            .text("date", date_value()),
+        fields,
+    }
+}
+
+/// What's in the hold and what it's expected to fetch.
+pub fn loot_report(manifest: &CargoManifest, evaluation: &EvaluationSummary) -> ShareReport {
+    let fields = manifest
+        .items
+        .iter()
+        .map(|item| {
+            let ev = evaluation
+                .items
+                .iter()
+                .find(|(id, _)| *id == item.id)
+                .map(|(_, e)| format!("≈ {} aUEC", format::integer(e.ev)))
+                .unwrap_or_else(|| "—".to_string());
+            let hot = if item.is_hot { " 🔥" } else { "" };
+            field(format!("{}{hot}", item.commodity_name), format!("{} SCU · {ev}", item.scu), true)
+        })
+        .collect();
+    ShareReport {
+        kind: ShareKind::Loot,
+        values: ReportScope::default()
+            .text("manifest", manifest.label())
+            .text("items", manifest.items.len().to_string())
+            .text("scu", manifest.total_scu().to_string())
+            .text("total", format::integer(evaluation.total_ev))
+            .text("date", date_value()),
+        fields,
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum WebhookError {
+    #[error("posting too fast; try again in {} s", .0.as_secs().max(1))]
+    RateLimited(Duration),
+    #[error("webhook answered {status}: {body}")]
+    Status { status: u16, body: String },
+    #[error(transparent)]
+    Http(#[from] reqwest::Error),
+}
+
+/// Posts webhook messages, spacing posts per webhook URL.
+pub struct WebhookClient {
+    http: Client,
+    /// Earliest next post per webhook URL.
+    next_post: Mutex<HashMap<String, Instant>>,
+}
+
+impl WebhookClient {
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            http: Client::builder().timeout(timeout).build().unwrap_or_default(),
+            next_post: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Posts `message`, then blocks that webhook for `min_interval`.
+    pub async fn post(
+        &self,
+        url: &str,
+        message: &WebhookMessage,
+        min_interval: Duration,
+    ) -> Result<(), WebhookError> {
+        let now = Instant::now();
+        {
+            let mut next_post = self.next_post.lock().unwrap_or_else(|e| e.into_inner());
+            if let Some(wait) = next_post.get(url).and_then(|at| at.checked_duration_since(now)) {
+                return Err(WebhookError::RateLimited(wait));
+            }
+            next_post.insert(url.to_string(), now + min_interval);
+        }
+
+        let response = self.http.post(url).query(&[("wait", "true")]).json(message).send().await?;
+        let status = response.status();
+        if status.is_success() {
+            return Ok(());
+        }
+        let header_wait = response
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse::<f64>().ok());
+        let body = response.text().await.unwrap_or_default();
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            let retry_after = serde_json::from_str::<serde_json::Value>(&body)
+                .ok()
+                .and_then(|json| json.get("retry_after").and_then(|v| v.as_f64()))
+                .or(header_wait)
+                .filter(|secs| secs.is_finite() && *secs >= 0.0)
+                .map(Duration::from_secs_f64)
+                .unwrap_or(min_interval);
+            let mut next_post = self.next_post.lock().unwrap_or_else(|e| e.into_inner());
+            next_post.insert(url.to_string(), Instant::now() + retry_after);
+            return Err(WebhookError::RateLimited(retry_after));
+        }
+        Err(WebhookError::Status {
+            status: status.as_u16(),
+            body: truncate(&body, 200),
+        })
+    }
+}
+
+/// Client shared by every page, so rate limits hold across them.
+pub fn client() -> &'static WebhookClient {
+    static CLIENT: OnceLock<WebhookClient> = OnceLock::new();
+    CLIENT.get_or_init(|| WebhookClient::new(super::config::current().network.request_timeout()))
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    use super::*;
+    use crate::domain::{CargoItem, CargoEvaluation};
+
+    /// Local stand-in for Discord: answers each request with the next
+    /// `(status, body)` and hands back the request bodies it received.
+    async fn stand_in(replies: Vec<(u16, &'static str)>) -> (String, tokio::task::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/api/webhooks/1/token", listener.local_addr().unwrap());
+        let handle = tokio::spawn(async move {
+            let mut bodies = Vec::new();
+            for (status, reply) in replies {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut request = Vec::new();
+                let mut buffer = [0u8; 4096];
+                let body = loop {
+                    let read = stream.read(&mut buffer).await.unwrap();
+                    request.extend_from_slice(&buffer[..read]);
+                    let text = String::from_utf8_lossy(&request).to_string();
+                    let Some((head, body)) = text.split_once("\r\n\r\n") else {
+                        continue;
+                    };
+                    let length = head
+                        .lines()
+                        .find_map(|line| line.to_lowercase().strip_prefix("content-length:").map(|v| v.trim().to_string()))
+                        .and_then(|v| v.parse::<usize>().ok())
+                        .unwrap_or(0);
+                    if body.len() >= length {
+                        break body.to_string();
+                    }
+                };
+                bodies.push(body);
+                let response = format!(
+                    "HTTP/1.1 {status} X\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{reply}",
+                    reply.len()
+                );
+                stream.write_all(response.as_bytes()).await.unwrap();
+            }
+            bodies
+        });
+        (url, handle)
+    }
+
+    fn loot() -> ShareReport {
+        let mut manifest = CargoManifest::default_manifest();
+        manifest.items = vec![CargoItem {
+            id: "a".to_string(),
+            commodity_id: "gold".to_string(),
+            commodity_name: "Gold".to_string(),
+            scu: 12,
+            is_hot: true,
+        }];
+        let evaluation = EvaluationSummary {
+            total_ev: 1_234.0,
+            average_confidence: 1.0,
+            items: vec![("a".to_string(), CargoEvaluation { ev: 1_234.0, min: None, max: None, confidence: 1.0 })],
+        };
+        loot_report(&manifest, &evaluation)
+    }
+
+    #[test]
+    fn templates_fill_placeholders_within_limits() {
+        let template = ShareTemplate {
+            title: format!("{{manifest}} {}", "x".repeat(300)),
+            description: "{scu} SCU · {total} aUEC · {unknown}".to_string(),
+        };
+        let message = loot().to_message(&template, " ");
+        let embed = &message.embeds[0];
+        assert_eq!(message.username, None);
+        assert_eq!(embed.title.chars().count(), TITLE_LIMIT);
+        assert!(embed.title.starts_with("My Ship x"));
+        assert_eq!(embed.description, "12 SCU · 1,234 aUEC · {unknown}");
+        assert_eq!(embed.fields[0].name, "Gold 🔥");
+    }
+
+    #[test]
+    fn values_are_not_substituted_twice() {
+        let mut report = loot();
+        report.values = ReportScope::default()
+            .text("manifest", "{total} & {{co}}")
+            .text("total", "1,234");
+        let template = ShareTemplate {
+            title: "{manifest}".to_string(),
+            description: "{{total}} = {total}{#missing} never{/missing}".to_string(),
+        };
+        let embed = &report.to_message(&template, "").embeds[0];
+        assert_eq!(embed.title, "{total} & {{co}}");
+        assert_eq!(embed.description, "{total} = 1,234");
+    }
+
+    #[tokio::test]
+    async fn posts_embeds_and_respects_rate_limits() {
+        let (url, server) = stand_in(vec![(204, ""), (429, r#"{"message":"slow down","retry_after":30.0}"#)]).await;
+        let client = WebhookClient::new(Duration::from_secs(5));
+        let message = loot().to_message(&ShareTemplates::default().loot, "Cargo Bot");
+
+        client.post(&url, &message, Duration::ZERO).await.unwrap();
+        assert!(matches!(
+            client.post(&url, &message, Duration::ZERO).await,
+            Err(WebhookError::RateLimited(wait)) if wait > Duration::from_secs(29)
+        ));
+        // Blocked locally: the stand-in never sees a third request.
+        assert!(matches!(client.post(&url, &message, Duration::ZERO).await, Err(WebhookError::RateLimited(_))));
+
+        let bodies = server.await.unwrap();
+        let posted: serde_json::Value = serde_json::from_str(&bodies[0]).unwrap();
+        assert_eq!(posted["username"], "Cargo Bot");
+        assert_eq!(posted["embeds"][0]["title"], "Loot report · My Ship");
+        assert_eq!(posted["embeds"][0]["fields"][0]["value"], "12 SCU · ≈ 1,234 aUEC");
+
+        let spaced = WebhookClient::new(Duration::from_secs(5));
+        let (url, _server) = stand_in(vec![(204, "")]).await;
+        spaced.post(&url, &message, Duration::from_secs(60)).await.unwrap();
+        assert!(matches!(spaced.post(&url, &message, Duration::from_secs(60)).await, Err(WebhookError::RateLimited(_))));
+    }
+}
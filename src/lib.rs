@@ -0,0 +1,7 @@
+//! Core of Cargo Value Scanner, shared by the desktop app and the `cvs` CLI.
+//!
+//! Nothing in here depends on the UI framework.
+
+pub mod domain;
+pub mod infra;
+pub mod util;
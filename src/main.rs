@@ -1,10 +1,9 @@
 #![cfg_attr(target_os = "windows", windows_subsystem = "windows")]
 
 mod app;
-mod domain;
-mod infra;
 mod ui;
-mod util;
+
+use cargo_value_scanner::{domain, infra, util};
 
 use dioxus::prelude::*;
 
@@ -29,6 +28,14 @@ fn main() {
         std::env::set_var("WEBKIT_DISABLE_DMABUF_RENDERER", "1");
     }
 
+    // Problems are reported by the app once it loads the config again.
+    infra::config::init();
+    let _log_guard = infra::logging::init(
+        &infra::config::current().logging,
+        "cargo-value-scanner",
+        true,
+    );
+
     let builder = LaunchBuilder::new();
 
     #[cfg(feature = "desktop")]
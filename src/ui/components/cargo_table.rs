@@ -1,8 +1,8 @@
 use dioxus::prelude::*;
 
 use super::confidence_badge::ConfidenceBadge;
-use crate::domain::Profile;
-use crate::ui::theme;
+use crate::ui::{i18n::tr, keyboard, theme};
+use crate::util::format;
 
 #[derive(Clone, PartialEq)]
 pub struct CargoRow {
@@ -21,12 +21,49 @@ pub struct CargoRow {
 pub fn CargoTable(
     rows: Vec<CargoRow>,
     selected_id: Option<String>,
-    profile: Profile,
     on_select: EventHandler<String>,
     on_remove: EventHandler<String>,
     on_toggle_hot: EventHandler<String>,
+    /// Enter on the selected row.
+    on_activate: EventHandler<String>,
 ) -> Element {
     let is_empty = rows.is_empty();
+    let ids: Vec<String> = rows.iter().map(|row| row.id.clone()).collect();
+    let position = selected_id.as_ref().and_then(|id| ids.iter().position(|row_id| row_id == id));
+
+    // Arrow keys move the selection, Enter activates it, Delete removes it.
+    let on_keydown = move |evt: KeyboardEvent| {
+        let step = |target: usize| {
+            if let Some(id) = ids.get(target) {
+                on_select.call(id.clone());
+                keyboard::scroll_into_view(&row_element_id(id));
+            }
+        };
+        match evt.key() {
+            Key::ArrowDown => {
+                evt.prevent_default();
+                step(position.map_or(0, |p| (p + 1).min(ids.len().saturating_sub(1))));
+            }
+            Key::ArrowUp => {
+                evt.prevent_default();
+                step(position.map_or(0, |p| p.saturating_sub(1)));
+            }
+            Key::Enter => {
+                if let Some(p) = position {
+                    on_activate.call(ids[p].clone());
+                }
+            }
+            Key::Delete => {
+                if let Some(p) = position {
+                    on_remove.call(ids[p].clone());
+                    if let Some(next) = ids.get(p + 1).or_else(|| p.checked_sub(1).and_then(|q| ids.get(q))) {
+                        on_select.call(next.clone());
+                    }
+                }
+            }
+            _ => {}
+        }
+    };
     let rendered_rows = rows
         .into_iter()
         .map(|row| {
@@ -36,28 +73,30 @@ pub fn CargoTable(
         .collect::<Vec<_>>();
     rsx! {
         div {
-            class: "{theme::table_container(profile)}",
+            class: "{theme::table_container()}",
+            tabindex: 0,
+            title: tr!("cargo.table.keyboard_hint"),
+            onkeydown: on_keydown,
             table {
-                class: "min-w-full {theme::table_divider(profile)} text-sm",
+                class: "min-w-full {theme::table_divider()} text-sm",
                 thead {
-                    class: "{theme::table_header(profile)} text-left tracking-wide",
+                    class: "{theme::table_header()} text-left tracking-wide",
                     tr {
-                        th { class: "px-4 py-3 font-medium", "Commodity" }
+                        th { class: "px-4 py-3 font-medium", {tr!("table.commodity")} }
                         th { class: "px-4 py-3 font-medium", "SCU" }
-                        th { class: "px-4 py-3 font-medium", "EV (aUEC)" }
-                        th { class: "px-4 py-3 font-medium", "Best Sell" }
-                        th { class: "px-4 py-3 font-medium", "Confidence" }
-                        th { class: "px-4 py-3 font-medium text-center", "Hot" }
+                        th { class: "px-4 py-3 font-medium", {tr!("table.ev_auec")} }
+                        th { class: "px-4 py-3 font-medium", {tr!("table.best_sell")} }
+                        th { class: "px-4 py-3 font-medium", {tr!("table.confidence")} }
+                        th { class: "px-4 py-3 font-medium text-center", {tr!("table.hot")} }
                         th { class: "px-4 py-3" }
                     }
                 }
                 tbody {
-                    class: "{theme::table_divider(profile)}",
+                    class: "{theme::table_divider()}",
                     for (row, selected) in rendered_rows {
                         CargoRowView {
                             row,
                             selected,
-                            profile: profile,
                             on_select: on_select.clone(),
                             on_remove: on_remove.clone(),
                             on_toggle_hot: on_toggle_hot.clone(),
@@ -66,9 +105,9 @@ pub fn CargoTable(
                     if is_empty {
                         tr {
                             td {
-                                class: "px-4 py-6 text-center text-sm {theme::text_muted(profile)}",
+                                class: "px-4 py-6 text-center text-sm {theme::text_muted()}",
                                 colspan: "7",
-                                "Add cargo items to begin calculating expected value."
+                                {tr!("cargo.table.empty")}
                             }
                         }
                     }
@@ -78,18 +117,21 @@ pub fn CargoTable(
     }
 }
 
+fn row_element_id(id: &str) -> String {
+    format!("cargo-row-{id}")
+}
+
 fn best_location_text(row: &CargoRow) -> String {
     row.best_sell_location
         .as_ref()
         .cloned()
-        .unwrap_or_else(|| "n/a".to_string())
+        .unwrap_or_else(|| tr!("common.not_available"))
 }
 
 #[derive(Props, Clone, PartialEq)]
 struct CargoRowViewProps {
     row: CargoRow,
     selected: bool,
-    profile: Profile,
     on_select: EventHandler<String>,
     on_remove: EventHandler<String>,
     on_toggle_hot: EventHandler<String>,
@@ -98,34 +140,22 @@ struct CargoRowViewProps {
 #[component]
 fn CargoRowView(props: CargoRowViewProps) -> Element {
     let row = props.row;
-    let profile = props.profile;
-    let selected_bg = match profile {
-        Profile::Pirate => "bg-[#3b1712]/30",
-        _ => "bg-indigo-500/10",
-    };
-    let hover_bg = match profile {
-        Profile::Pirate => "hover:bg-[#3b1712]/20",
-        _ => "hover:bg-slate-800/40",
-    };
-    let row_class = format!(
-        "cursor-pointer transition-colors {}",
-        if props.selected { selected_bg } else { hover_bg }
-    );
     let select_id = row.id.clone();
     let remove_id = row.id.clone();
     let toggle_id = row.id.clone();
     let is_hot = row.is_hot;
     rsx! {
         tr {
-            class: row_class,
+            id: row_element_id(&row.id),
+            class: theme::table_row(props.selected),
             onclick: move |_| props.on_select.call(select_id.clone()),
             td {
-                class: "px-4 py-3 font-medium {theme::text_secondary(profile)}",
+                class: "px-4 py-3 font-medium {theme::text_secondary()}",
                 "{row.commodity_name}"
             }
-            td { class: "px-4 py-3 {theme::text_secondary(profile)}", "{row.scu}" }
-            td { class: "px-4 py-3 {theme::text_secondary(profile)}", {format!("{:.0}", row.expected_value)} }
-            td { class: "px-4 py-3 {theme::text_muted(profile)}", {best_location_text(&row)} }
+            td { class: "px-4 py-3 {theme::text_secondary()}", "{row.scu}" }
+            td { class: "px-4 py-3 {theme::text_secondary()}", {format::integer(row.expected_value)} }
+            td { class: "px-4 py-3 {theme::text_muted()}", {best_location_text(&row)} }
             td {
                 class: "px-4 py-3",
                 ConfidenceBadge { value: row.confidence }
@@ -150,7 +180,7 @@ fn CargoRowView(props: CargoRowViewProps) -> Element {
                         evt.stop_propagation();
                         props.on_remove.call(remove_id.clone());
                     },
-                    "Remove"
+                    {tr!("common.remove")}
                 }
             }
         }
@@ -0,0 +1,220 @@
+//! Ctrl+K command palette: fuzzy search over pages, actions, commodities and
+//! terminals.
+
+use dioxus::prelude::*;
+
+use crate::app::Route;
+use crate::domain::{extract_locations, AppState, Location, Profile};
+use crate::infra::uex::UexClient;
+use crate::ui::keyboard::{self, COMMODITY_FOCUS, PLAN_FROM, REFRESH_SELECTED};
+use crate::ui::{i18n::tr, theme};
+use crate::util::fuzzy;
+
+const MAX_RESULTS: usize = 12;
+const INPUT_ID: &str = "command-palette-input";
+
+#[derive(Clone, PartialEq)]
+pub enum PaletteAction {
+    Navigate(Route),
+    /// Open Cargo with the commodity typed into the add form.
+    AddCommodity(String),
+    FocusCommodity,
+    RefreshSelected,
+    PlanFrom(Location),
+    SwitchProfile(Profile),
+}
+
+#[derive(Clone, PartialEq)]
+pub struct PaletteEntry {
+    pub label: String,
+    /// Secondary text: entry kind, location or shortcut.
+    pub hint: String,
+    pub action: PaletteAction,
+}
+
+impl PaletteEntry {
+    pub fn new(label: String, hint: String, action: PaletteAction) -> Self {
+        PaletteEntry { label, hint, action }
+    }
+}
+
+/// `pages` are the navigation entries of the current profile; commodities,
+/// terminals and the fixed actions are added here.
+#[component]
+pub fn CommandPalette(pages: Vec<PaletteEntry>, on_close: EventHandler<()>) -> Element {
+    let state = use_context::<Signal<AppState>>();
+    let mut query = use_signal(String::new);
+    let mut highlighted = use_signal(|| 0usize);
+
+    let terminals = use_resource(|| async {
+        let client = UexClient::new().ok()?;
+        Some(client.get_terminals().await.ok()?.terminals)
+    });
+
+    use_effect(|| keyboard::focus_element(INPUT_ID));
+
+    let mut entries = pages;
+    entries.extend(fixed_actions(state.with(|st| st.profile)));
+    entries.extend(state.with(|st| {
+        st.commodities
+            .iter()
+            .map(|c| {
+                PaletteEntry::new(
+                    c.name.clone(),
+                    tr!("palette.kind.commodity"),
+                    PaletteAction::AddCommodity(c.name.clone()),
+                )
+            })
+            .collect::<Vec<_>>()
+    }));
+    if let Some(Some(terminals)) = terminals.read().as_ref() {
+        let locations = extract_locations(terminals);
+        // Terminals plan from their location's representative terminal, the
+        // one the planner's own location picker uses.
+        for terminal in terminals {
+            let name = terminal.location_name();
+            if let Some(location) = locations.iter().find(|l| l.name == name) {
+                entries.push(PaletteEntry::new(
+                    terminal.name.clone(),
+                    tr!("palette.kind.terminal", location = name),
+                    PaletteAction::PlanFrom(location.clone()),
+                ));
+            }
+        }
+        for location in locations {
+            entries.push(PaletteEntry::new(
+                tr!("palette.plan_from", location = location.name),
+                location.system.clone().unwrap_or_default(),
+                PaletteAction::PlanFrom(location),
+            ));
+        }
+    }
+
+    let current_query = query();
+    let results: Vec<PaletteEntry> = fuzzy::rank(
+        &current_query,
+        entries.iter().map(|entry| entry.label.as_str()),
+        MAX_RESULTS,
+    )
+    .into_iter()
+    .map(|index| entries[index].clone())
+    .collect();
+    let active = highlighted().min(results.len().saturating_sub(1));
+
+    let nav = use_navigator();
+    let mut state_mut = state;
+    let mut run = move |action: PaletteAction| {
+        match action {
+            PaletteAction::Navigate(route) => {
+                nav.push(route);
+            }
+            PaletteAction::AddCommodity(name) => {
+                *COMMODITY_FOCUS.write() = Some(name);
+                nav.push(Route::Cargo {});
+            }
+            PaletteAction::FocusCommodity => {
+                *COMMODITY_FOCUS.write() = Some(String::new());
+                nav.push(Route::Cargo {});
+            }
+            PaletteAction::RefreshSelected => {
+                *REFRESH_SELECTED.write() += 1;
+            }
+            PaletteAction::PlanFrom(location) => {
+                *PLAN_FROM.write() = Some(location);
+                nav.push(Route::Planner {});
+            }
+            PaletteAction::SwitchProfile(profile) => {
+                state_mut.with_mut(|st| st.profile = profile);
+            }
+        }
+        on_close.call(());
+    };
+
+    let keyed_results = results.clone();
+    let on_keydown = move |evt: KeyboardEvent| match evt.key() {
+        Key::ArrowDown => {
+            evt.prevent_default();
+            highlighted.set((active + 1).min(keyed_results.len().saturating_sub(1)));
+        }
+        Key::ArrowUp => {
+            evt.prevent_default();
+            highlighted.set(active.saturating_sub(1));
+        }
+        Key::Enter => {
+            evt.prevent_default();
+            if let Some(entry) = keyed_results.get(active) {
+                run(entry.action.clone());
+            }
+        }
+        Key::Escape => on_close.call(()),
+        _ => {}
+    };
+
+    rsx! {
+        div {
+            class: "fixed inset-0 z-50 flex items-start justify-center bg-slate-950/80 pt-[15vh] backdrop-blur",
+            onclick: move |_| on_close.call(()),
+            div {
+                class: "w-full max-w-xl {theme::panel_solid()} p-3 shadow-xl",
+                onclick: move |evt| evt.stop_propagation(),
+                input {
+                    id: INPUT_ID,
+                    class: "w-full {theme::input_class()}",
+                    value: current_query,
+                    placeholder: tr!("palette.placeholder"),
+                    autocomplete: "off",
+                    spellcheck: false,
+                    oninput: move |evt| {
+                        query.set(evt.value());
+                        highlighted.set(0);
+                    },
+                    onkeydown: on_keydown,
+                }
+                ul { class: "mt-2 max-h-[50vh] overflow-y-auto",
+                    for (index, entry) in results.into_iter().enumerate() {
+                        li {
+                            key: "{index}-{entry.label}",
+                            class: "flex items-center justify-between gap-4 rounded-lg px-3 py-2 text-sm {theme::table_row(index == active)}",
+                            onmouseenter: move |_| highlighted.set(index),
+                            onclick: {
+                                let action = entry.action.clone();
+                                move |_| run(action.clone())
+                            },
+                            span { class: "truncate {theme::text_secondary()}", "{entry.label}" }
+                            span { class: "shrink-0 text-xs {theme::text_muted()}", "{entry.hint}" }
+                        }
+                    }
+                    if terminals.read().is_none() {
+                        li { class: "px-3 py-2 text-xs {theme::text_muted()}", {tr!("palette.loading_terminals")} }
+                    }
+                }
+                p { class: "mt-2 px-1 text-[11px] {theme::text_muted()}", {tr!("palette.footer")} }
+            }
+        }
+    }
+}
+
+fn fixed_actions(profile: Profile) -> Vec<PaletteEntry> {
+    let mut actions = vec![
+        PaletteEntry::new(
+            tr!("palette.action.focus_commodity"),
+            "/".to_string(),
+            PaletteAction::FocusCommodity,
+        ),
+        PaletteEntry::new(
+            tr!("palette.action.refresh_selected"),
+            "Ctrl+R".to_string(),
+            PaletteAction::RefreshSelected,
+        ),
+    ];
+    for other in [Profile::Pirate, Profile::Trader, Profile::Miner] {
+        if other != profile {
+            actions.push(PaletteEntry::new(
+                tr!("palette.action.switch_profile", profile = other.name()),
+                tr!("palette.kind.profile"),
+                PaletteAction::SwitchProfile(other),
+            ));
+        }
+    }
+    actions
+}
@@ -1,18 +1,20 @@
 use dioxus::prelude::*;
 
+use crate::ui::i18n::tr;
+
 #[component]
 pub fn ConfidenceBadge(value: f32) -> Element {
     let (label, color) = match value {
         v if v >= 0.75 => (
-            "High",
+            tr!("confidence.high"),
             "bg-emerald-500/10 text-emerald-300 border-emerald-500/40",
         ),
         v if v >= 0.45 => (
-            "Medium",
+            tr!("confidence.medium"),
             "bg-amber-500/10 text-amber-300 border-amber-500/40",
         ),
-        v if v > 0.0 => ("Low", "bg-rose-500/10 text-rose-300 border-rose-500/40"),
-        _ => ("N/A", "bg-slate-700/40 text-slate-300 border-slate-600/60"),
+        v if v > 0.0 => (tr!("confidence.low"), "bg-rose-500/10 text-rose-300 border-rose-500/40"),
+        _ => (tr!("common.not_available"), "bg-slate-700/40 text-slate-300 border-slate-600/60"),
     };
 
     rsx! {
@@ -0,0 +1,57 @@
+use dioxus::prelude::*;
+
+use crate::{
+    infra::export::{export_with_dialog, ExportFormat, ExportPayload},
+    ui::components::toast::{push_toast, ToastKind, ToastMessage},
+    ui::{i18n::tr, theme},
+};
+
+/// "Export CSV / JSON" buttons that write `payload` to a user-chosen file.
+#[component]
+pub fn ExportButtons(payload: ExportPayload) -> Element {
+    let toasts = use_context::<Signal<Vec<ToastMessage>>>();
+    let mut busy = use_signal(|| false);
+    let disabled = busy() || payload.is_empty();
+
+    let export = move |format: ExportFormat| {
+        let payload = payload.clone();
+        move |_| {
+            if busy() {
+                return;
+            }
+            busy.set(true);
+            let payload = payload.clone();
+            spawn(async move {
+                match export_with_dialog(&payload, format).await {
+                    Ok(Some(path)) => push_toast(
+                        toasts,
+                        ToastKind::Success,
+                        tr!("export.toast.done", format = format.label(), path = path.display()),
+                    ),
+                    Ok(None) => {}
+                    Err(err) => push_toast(toasts, ToastKind::Error, tr!("export.toast.failed", error = err)),
+                }
+                busy.set(false);
+            });
+        }
+    };
+
+    rsx! {
+        div { class: "flex gap-2",
+            button {
+                class: "{theme::btn_small_inactive()}",
+                disabled,
+                title: tr!("export.as_csv"),
+                onclick: export(ExportFormat::Csv),
+                "⬇ CSV"
+            }
+            button {
+                class: "{theme::btn_small_inactive()}",
+                disabled,
+                title: tr!("export.as_json"),
+                onclick: export(ExportFormat::Json),
+                "⬇ JSON"
+            }
+        }
+    }
+}
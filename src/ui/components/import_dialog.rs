@@ -0,0 +1,203 @@
+//! Review dialog for importing cargo lists from files or pasted text.
+
+use dioxus::{document, prelude::*};
+
+use crate::{
+    domain::{match_lines, parse_import, Commodity, ImportLine, ImportMatch, MatchKind},
+    infra::import::{pick_import_file, FILE_DIALOG_AVAILABLE},
+    ui::components::toast::{push_toast, ToastKind, ToastMessage},
+    ui::{i18n::tr, theme},
+};
+
+/// A reviewed line ready to merge into the cargo list.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImportSelection {
+    pub commodity: Commodity,
+    pub line: ImportLine,
+}
+
+#[component]
+pub fn ImportDialog(
+    open: Signal<bool>,
+    commodities: Vec<Commodity>,
+    on_import: EventHandler<Vec<ImportSelection>>,
+) -> Element {
+    let toasts = use_context::<Signal<Vec<ToastMessage>>>();
+    let mut open = open;
+    let mut input = use_signal(String::new);
+    let mut matches = use_signal(Vec::<ImportMatch>::new);
+    let mut rejected = use_signal(Vec::<String>::new);
+    let mut reviewing = use_signal(|| false);
+
+    if !open() {
+        return rsx! { Fragment {} };
+    }
+
+    let mut close = move || {
+        open.set(false);
+        reviewing.set(false);
+        input.set(String::new());
+        matches.set(Vec::new());
+        rejected.set(Vec::new());
+    };
+
+    let on_review = {
+        let commodities = commodities.clone();
+        move |_| {
+            let parsed = parse_import(&input());
+            if parsed.lines.is_empty() {
+                push_toast(toasts, ToastKind::Warning, tr!("import.toast.no_lines"));
+                return;
+            }
+            matches.set(match_lines(&parsed.lines, &commodities));
+            rejected.set(parsed.rejected);
+            reviewing.set(true);
+        }
+    };
+
+    let on_paste = move |_| {
+        spawn(async move {
+            let result = document::eval(
+                "try { return await navigator.clipboard.readText(); } catch (_err) { return null; }",
+            )
+            .await;
+            match result.ok().and_then(|value| value.as_str().map(str::to_string)) {
+                Some(text) if !text.trim().is_empty() => input.set(text),
+                _ => push_toast(toasts, ToastKind::Warning, tr!("import.toast.clipboard_empty")),
+            }
+        });
+    };
+
+    let on_file = move |_| {
+        spawn(async move {
+            match pick_import_file().await {
+                Ok(Some(text)) => input.set(text),
+                Ok(None) => {}
+                Err(err) => push_toast(toasts, ToastKind::Error, tr!("import.toast.read_failed", error = err)),
+            }
+        });
+    };
+
+    let on_confirm = {
+        let commodities = commodities.clone();
+        move |_| {
+            let selections: Vec<ImportSelection> = matches()
+                .into_iter()
+                .filter_map(|m| {
+                    let id = m.commodity_id?;
+                    let commodity = commodities.iter().find(|c| c.id == id)?.clone();
+                    Some(ImportSelection { commodity, line: m.line })
+                })
+                .collect();
+            if selections.is_empty() {
+                push_toast(toasts, ToastKind::Warning, tr!("import.toast.nothing"));
+                return;
+            }
+            on_import.call(selections);
+            close();
+        }
+    };
+
+    let rows = matches();
+    let matched_count = rows.iter().filter(|m| m.commodity_id.is_some()).count();
+    let unmatched_count = rows.len() - matched_count;
+    let rejected_lines = rejected();
+
+    rsx! {
+        div { class: "fixed inset-0 z-40 flex items-center justify-center bg-slate-950/80 backdrop-blur",
+            div { class: "flex max-h-[85vh] w-full max-w-2xl flex-col {theme::panel_solid()} p-6 shadow-xl",
+                div { class: "flex items-center justify-between",
+                    h2 { class: "text-lg font-semibold {theme::text_secondary()}", {tr!("import.title")} }
+                    button { class: "{theme::link_class()}", onclick: move |_| close(), {tr!("common.close")} }
+                }
+
+                if !reviewing() {
+                    p { class: "mt-2 text-sm {theme::text_muted()}",
+                        {tr!("import.hint")}
+                    }
+                    textarea {
+                        class: "mt-3 h-48 w-full {theme::input_class()} font-mono",
+                        value: input(),
+                        oninput: move |evt| input.set(evt.value()),
+                        placeholder: "120 Laranite\n32 Quantanium\nAgricium x16",
+                    }
+                    div { class: "mt-4 flex flex-wrap justify-end gap-2",
+                        button { class: "{theme::btn_small_inactive()}", onclick: on_paste, {format!("📋 {}", tr!("import.paste"))} }
+                        if FILE_DIALOG_AVAILABLE {
+                            button { class: "{theme::btn_small_inactive()}", onclick: on_file, {format!("📂 {}", tr!("import.open_file"))} }
+                        }
+                        button { class: "{theme::btn_primary()}", onclick: on_review, {tr!("import.review")} }
+                    }
+                } else {
+                    p { class: "mt-2 text-sm {theme::text_muted()}",
+                        {tr!("import.summary", matched = matched_count, unmatched = unmatched_count)}
+                    }
+                    div { class: "mt-3 flex-1 overflow-y-auto {theme::table_container()}",
+                        div { class: "{theme::table_divider()}",
+                            for (index, row) in rows.iter().enumerate() {
+                                ImportReviewRow {
+                                    key: "{index}",
+                                    row: row.clone(),
+                                    commodities: commodities.clone(),
+                                    on_change: move |commodity_id: Option<String>| {
+                                        matches.with_mut(|list| {
+                                            if let Some(entry) = list.get_mut(index) {
+                                                entry.kind = commodity_id.as_ref().map(|_| MatchKind::Exact);
+                                                entry.commodity_id = commodity_id;
+                                            }
+                                        });
+                                    },
+                                }
+                            }
+                        }
+                    }
+                    if !rejected_lines.is_empty() {
+                        p { class: "mt-3 text-xs text-amber-300",
+                            {tr!("import.ignored", lines = rejected_lines.join(" · "))}
+                        }
+                    }
+                    div { class: "mt-4 flex justify-end gap-2",
+                        button { class: "{theme::btn_small_inactive()}", onclick: move |_| reviewing.set(false), {tr!("common.back")} }
+                        button { class: "{theme::btn_primary()}", onclick: on_confirm, {tr!("import.confirm", count = matched_count)} }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn ImportReviewRow(
+    row: ImportMatch,
+    commodities: Vec<Commodity>,
+    on_change: EventHandler<Option<String>>,
+) -> Element {
+    let status = match row.kind {
+        Some(MatchKind::Exact) => ("✓", "text-emerald-300"),
+        Some(MatchKind::Fuzzy) => ("≈", "text-amber-300"),
+        None => ("✕", "text-rose-300"),
+    };
+    let selected = row.commodity_id.clone().unwrap_or_default();
+    let hot = if row.line.is_hot { " 🔥" } else { "" };
+
+    rsx! {
+        div { class: "flex items-center gap-3 px-4 py-2 text-sm",
+            span { class: "w-4 {status.1}", "{status.0}" }
+            div { class: "flex-1 min-w-0",
+                p { class: "truncate {theme::text_secondary()}", "{row.line.scu} SCU · {row.line.name}{hot}" }
+                p { class: "truncate text-xs {theme::text_muted()}", "{row.line.raw}" }
+            }
+            select {
+                class: "w-56 {theme::input_small()}",
+                onchange: move |evt| {
+                    let value = evt.value();
+                    on_change.call(Some(value).filter(|v| !v.is_empty()));
+                },
+                option { value: "", selected: selected.is_empty(), {tr!("import.skip")} }
+                for commodity in commodities.iter() {
+                    option { value: commodity.id.clone(), selected: commodity.id == selected, "{commodity.name}" }
+                }
+            }
+        }
+    }
+}
@@ -1,6 +1,11 @@
 pub mod cargo_table;
+pub mod command_palette;
 pub mod confidence_badge;
+pub mod export_buttons;
+pub mod import_dialog;
 pub mod kpi_card;
 pub mod price_table;
 pub mod profit_indicator;
+pub mod recovery_prompt;
+pub mod share_panel;
 pub mod toast;
@@ -2,8 +2,8 @@ use std::cmp::Ordering;
 
 use dioxus::prelude::*;
 
-use crate::domain::Profile;
-use crate::ui::theme;
+use crate::ui::{i18n::tr, theme};
+use crate::util::format;
 
 #[derive(Clone, PartialEq)]
 pub struct PriceRow {
@@ -22,7 +22,7 @@ pub struct PriceRow {
 }
 
 #[component]
-pub fn PriceTable(rows: Vec<PriceRow>, profile: Profile) -> Element {
+pub fn PriceTable(rows: Vec<PriceRow>) -> Element {
     let sort_mode = use_signal(|| SortMode::SellRange);
     let count = rows.len();
     let is_empty = rows.is_empty();
@@ -39,23 +39,23 @@ pub fn PriceTable(rows: Vec<PriceRow>, profile: Profile) -> Element {
     let best_sell_value = highlights
         .best_sell
         .as_ref()
-        .map(|(_, price)| format!("{price:.0} aUEC"))
+        .map(|(_, price)| format::auec(*price))
         .unwrap_or_else(|| "—".to_string());
     let best_sell_caption = highlights
         .best_sell
         .as_ref()
         .map(|(location, _)| location.clone())
-        .unwrap_or_else(|| "No sell data".to_string());
+        .unwrap_or_else(|| tr!("price_table.no_sell_data"));
     let best_buy_value = highlights
         .best_buy
         .as_ref()
-        .map(|(_, price)| format!("{price:.0} aUEC"))
+        .map(|(_, price)| format::auec(*price))
         .unwrap_or_else(|| "—".to_string());
     let best_buy_caption = highlights
         .best_buy
         .as_ref()
         .map(|(location, _)| location.clone())
-        .unwrap_or_else(|| "No buy data".to_string());
+        .unwrap_or_else(|| tr!("price_table.no_buy_data"));
     let sell_range_summary = format_summary_range(highlights.sell_range);
     let buy_range_summary = format_summary_range(highlights.buy_range);
 
@@ -79,139 +79,136 @@ pub fn PriceTable(rows: Vec<PriceRow>, profile: Profile) -> Element {
 
     rsx! {
         div {
-            class: "{theme::panel_border(profile)}",
+            class: "{theme::panel_border()}",
             if highlights.has_data() {
                 div {
-                    class: "grid gap-4 {theme::table_header(profile)} px-4 py-3 text-sm sm:grid-cols-3",
+                    class: "grid gap-4 {theme::table_header()} px-4 py-3 text-sm sm:grid-cols-3",
                     SummaryStat {
-                        title: "Best Sell (max)",
+                        title: tr!("price_table.best_sell_max"),
                         value: best_sell_value,
                         caption: best_sell_caption,
-                        profile: profile,
                     }
                     SummaryStat {
-                        title: "Best Buy (min)",
+                        title: tr!("price_table.best_buy_min"),
                         value: best_buy_value,
                         caption: best_buy_caption,
-                        profile: profile,
                     }
                     div {
-                        class: "{theme::panel_solid(profile)} p-3",
-                        p { class: "text-[10px] font-semibold uppercase tracking-wide {theme::text_muted(profile)}", "Price Ranges" }
-                        p { class: "text-xs {theme::text_muted(profile)}", "Sell: {sell_range_summary}" }
-                        p { class: "text-xs {theme::text_muted(profile)}", "Buy: {buy_range_summary}" }
+                        class: "{theme::panel_solid()} p-3",
+                        p { class: "text-[10px] font-semibold uppercase tracking-wide {theme::text_muted()}", {tr!("price_table.ranges")} }
+                        p { class: "text-xs {theme::text_muted()}", {tr!("price_table.range_sell", range = sell_range_summary)} }
+                        p { class: "text-xs {theme::text_muted()}", {tr!("price_table.range_buy", range = buy_range_summary)} }
                     }
                 }
             }
             header {
-                class: "flex flex-wrap items-center justify-between gap-2 {theme::table_header(profile)} px-4 py-3",
-                h3 { class: "text-sm font-semibold {theme::text_secondary(profile)}", "Price Points" }
-                span { class: "text-xs {theme::text_muted(profile)}", "{count} sources" }
+                class: "flex flex-wrap items-center justify-between gap-2 {theme::table_header()} px-4 py-3",
+                h3 { class: "text-sm font-semibold {theme::text_secondary()}", {tr!("price_table.title")} }
+                span { class: "text-xs {theme::text_muted()}", {tr!("price_table.sources", count = count)} }
             }
             if !is_empty {
                 div {
-                    class: "flex flex-wrap items-center gap-2 {theme::table_header(profile)} px-4 py-2 text-xs uppercase tracking-wide",
-                    span { "Sort:" }
+                    class: "flex flex-wrap items-center gap-2 {theme::table_header()} px-4 py-2 text-xs uppercase tracking-wide",
+                    span { {tr!("price_table.sort")} }
                     button {
-                        class: sort_button_class(current_sort == SortMode::SellRange, profile),
+                        class: theme::btn_small_toggle(current_sort == SortMode::SellRange),
                         onclick: {
                             let mut sort_mode = sort_mode.clone();
                             move |_| sort_mode.set(SortMode::SellRange)
                         },
-                        "Sell Range"
+                        {tr!("price_table.sell_range")}
                     }
                     button {
-                        class: sort_button_class(current_sort == SortMode::BuyRange, profile),
+                        class: theme::btn_small_toggle(current_sort == SortMode::BuyRange),
                         onclick: {
                             let mut sort_mode = sort_mode.clone();
                             move |_| sort_mode.set(SortMode::BuyRange)
                         },
-                        "Buy Range"
+                        {tr!("price_table.buy_range")}
                     }
                     button {
-                        class: sort_button_class(current_sort == SortMode::Stock, profile),
+                        class: theme::btn_small_toggle(current_sort == SortMode::Stock),
                         onclick: {
                             let mut sort_mode = sort_mode.clone();
                             move |_| sort_mode.set(SortMode::Stock)
                         },
-                        "Stock"
+                        {tr!("price_table.stock")}
                     }
                     button {
-                        class: sort_button_class(current_sort == SortMode::Demand, profile),
+                        class: theme::btn_small_toggle(current_sort == SortMode::Demand),
                         onclick: {
                             let mut sort_mode = sort_mode.clone();
                             move |_| sort_mode.set(SortMode::Demand)
                         },
-                        "Demand"
+                        {tr!("table.demand")}
                     }
                 }
             }
             if is_empty {
-                p { class: "px-4 py-6 text-sm {theme::text_muted(profile)}", "No price data available yet." }
+                p { class: "px-4 py-6 text-sm {theme::text_muted()}", {tr!("price_table.empty")} }
             } else {
                 table {
-                        class: "min-w-full {theme::table_divider(profile)} text-sm",
+                        class: "min-w-full {theme::table_divider()} text-sm",
                         thead {
-                            class: "sticky top-0 z-10 {theme::table_header(profile)} text-left tracking-wide",
+                            class: "sticky top-0 z-10 {theme::table_header()} text-left tracking-wide",
                             tr {
-                                th { class: "px-4 py-3 font-medium", "Terminal" }
-                                th { class: "px-4 py-3 font-medium text-right", "Sell Range (aUEC)" }
-                                th { class: "px-4 py-3 font-medium text-right", "Buy Range (aUEC)" }
-                            th { class: "px-4 py-3 font-medium text-right", "Stock (SCU)" }
-                            th { class: "px-4 py-3 font-medium text-right", "Demand" }
-                            th { class: "px-4 py-3 font-medium text-right min-w-[150px]", "Containers (SCU)" }
-                                th { class: "px-4 py-3 font-medium", "Updated" }
+                                th { class: "px-4 py-3 font-medium", {tr!("table.terminal")} }
+                                th { class: "px-4 py-3 font-medium text-right", {tr!("price_table.sell_range_auec")} }
+                                th { class: "px-4 py-3 font-medium text-right", {tr!("price_table.buy_range_auec")} }
+                            th { class: "px-4 py-3 font-medium text-right", {tr!("table.stock_scu")} }
+                            th { class: "px-4 py-3 font-medium text-right", {tr!("table.demand")} }
+                            th { class: "px-4 py-3 font-medium text-right min-w-[150px]", {tr!("price_table.containers_scu")} }
+                                th { class: "px-4 py-3 font-medium", {tr!("price_table.updated")} }
                             }
                         }
                     tbody {
-                        class: "{theme::table_divider(profile)}",
+                        class: "{theme::table_divider()}",
                         for (row, is_best_sell, is_best_buy) in rendered_rows {
                             tr {
-                                class: row_hover_class(profile),
-                                td { class: "px-4 py-3 font-medium {theme::text_secondary(profile)}",
+                                class: "cvs-row",
+                                td { class: "px-4 py-3 font-medium {theme::text_secondary()}",
                                     if row.is_nqa {
                                         span {
-                                            class: "mr-1.5 inline-flex items-center rounded bg-[#3b1712] px-1.5 py-0.5 text-[10px] font-semibold {theme::text_primary(profile)}",
+                                            class: "mr-1.5 inline-flex items-center rounded bg-[#3b1712] px-1.5 py-0.5 text-[10px] font-semibold {theme::text_primary()}",
                                             "🏴‍☠️"
                                         }
                                     }
                                     "{row.location}"
                                 }
                                 td {
-                                    class: "px-4 py-3 text-right {theme::text_secondary(profile)}",
+                                    class: "px-4 py-3 text-right {theme::text_secondary()}",
                                     div { class: "flex flex-col items-end gap-1 text-xs",
                                         span { class: "text-sm font-medium", "{format_price_range(row.sell_price_min, row.sell_price_max)}" }
                                         if is_best_sell {
                                             span {
-                                                class: "rounded-full border border-[#5c2a1f] px-2 py-0.5 text-[10px] font-semibold uppercase tracking-wide {theme::text_primary(profile)}",
-                                                "Best Sell"
+                                                class: "rounded-full border border-[#5c2a1f] px-2 py-0.5 text-[10px] font-semibold uppercase tracking-wide {theme::text_primary()}",
+                                                {tr!("table.best_sell")}
                                             }
                                         }
                                     }
                                 }
                                 td {
-                                    class: "px-4 py-3 text-right {theme::text_secondary(profile)}",
+                                    class: "px-4 py-3 text-right {theme::text_secondary()}",
                                     div { class: "flex flex-col items-end gap-1 text-xs",
                                         span { class: "text-sm font-medium", "{format_price_range(row.buy_price_min, row.buy_price_max)}" }
                                         if is_best_buy {
                                             span {
-                                                class: "rounded-full border border-[#5c2a1f] px-2 py-0.5 text-[10px] font-semibold uppercase tracking-wide {theme::text_primary(profile)}",
-                                                "Best Buy"
+                                                class: "rounded-full border border-[#5c2a1f] px-2 py-0.5 text-[10px] font-semibold uppercase tracking-wide {theme::text_primary()}",
+                                                {tr!("table.best_buy")}
                                             }
                                         }
                                     }
                                 }
-                                td { class: "px-4 py-3 text-right {theme::text_secondary(profile)}", "{format_stock(row.stock)}" }
+                                td { class: "px-4 py-3 text-right {theme::text_secondary()}", "{format_stock(row.stock)}" }
                                 td {
-                                    class: "px-4 py-3 text-right {theme::text_secondary(profile)}",
+                                    class: "px-4 py-3 text-right {theme::text_secondary()}",
                                     DemandCell {
                                         sell: row.status_sell,
                                         buy: row.status_buy,
-                                        profile: profile,
                                     }
                                 }
-                                td { class: "px-4 py-3 text-right {theme::text_secondary(profile)} whitespace-nowrap min-w-[150px]", "{format_containers(&row.container_sizes)}" }
-                                td { class: "px-4 py-3 {theme::text_muted(profile)}", "{row.updated_label}" }
+                                td { class: "px-4 py-3 text-right {theme::text_secondary()} whitespace-nowrap min-w-[150px]", "{format_containers(&row.container_sizes)}" }
+                                td { class: "px-4 py-3 {theme::text_muted()}", "{row.updated_label}" }
                             }
                         }
                     }
@@ -221,32 +218,21 @@ pub fn PriceTable(rows: Vec<PriceRow>, profile: Profile) -> Element {
     }
 }
 
-fn row_hover_class(profile: Profile) -> &'static str {
-    match profile {
-        Profile::Pirate => "hover:bg-[#3b1712]/30",
-        _ => "hover:bg-slate-800/40",
-    }
-}
-
 fn format_stock(value: Option<f64>) -> String {
     match value {
         Some(v) if v.is_finite() && v > 0.0 => {
-            if v >= 1000.0 {
-                format!("{:.1}k", v / 1000.0)
-            } else {
-                format!("{:.0}", v)
-            }
+            format::compact(v)
         }
         _ => "—".to_string(),
     }
 }
 
-fn status_label(value: Option<i32>) -> Option<&'static str> {
+fn status_label(value: Option<i32>) -> Option<String> {
     match value {
-        Some(3) => Some("High"),
-        Some(2) => Some("Normal"),
-        Some(1) => Some("Low"),
-        Some(0) => Some("Unavailable"),
+        Some(3) => Some(tr!("status.high")),
+        Some(2) => Some(tr!("status.normal")),
+        Some(1) => Some(tr!("status.low")),
+        Some(0) => Some(tr!("status.unavailable")),
         _ => None,
     }
 }
@@ -259,7 +245,7 @@ fn format_containers(sizes: &[f64]) -> String {
     let formatted: Vec<String> = sizes
         .iter()
         .filter(|size| size.is_finite() && **size > 0.0)
-        .map(|size| format!("{size:.0}"))
+        .map(|size| format::integer(*size))
         .collect();
 
     if formatted.is_empty() {
@@ -274,10 +260,10 @@ fn format_price_range(min: Option<f64>, max: Option<f64>) -> String {
     let max = usable(max);
 
     match (min, max) {
-        (Some(min), Some(max)) if (max - min).abs() < f64::EPSILON => format!("{min:.0}"),
-        (Some(min), Some(max)) => format!("{min:.0} - {max:.0}"),
-        (Some(min), None) => format!("≥ {min:.0}"),
-        (None, Some(max)) => format!("≤ {max:.0}"),
+        (Some(min), Some(max)) if (max - min).abs() < f64::EPSILON => format::integer(min),
+        (Some(min), Some(max)) => format!("{} - {}", format::integer(min), format::integer(max)),
+        (Some(min), None) => format!("≥ {}", format::integer(min)),
+        (None, Some(max)) => format!("≤ {}", format::integer(max)),
         _ => "—".to_string(),
     }
 }
@@ -350,8 +336,8 @@ fn extend_range(range: &mut Option<(f64, f64)>, candidate: Option<f64>) {
 
 fn format_summary_range(range: Option<(f64, f64)>) -> String {
     match range {
-        Some((min, max)) if (max - min).abs() < f64::EPSILON => format!("{min:.0} aUEC"),
-        Some((min, max)) => format!("{min:.0} - {max:.0} aUEC"),
+        Some((min, max)) if (max - min).abs() < f64::EPSILON => format::auec(min),
+        Some((min, max)) => format!("{} - {}", format::integer(min), format::auec(max)),
         None => "—".to_string(),
     }
 }
@@ -360,17 +346,16 @@ fn format_summary_range(range: Option<(f64, f64)>) -> String {
 struct DemandCellProps {
     sell: Option<i32>,
     buy: Option<i32>,
-    profile: Profile,
 }
 
 #[component]
 fn DemandCell(props: DemandCellProps) -> Element {
-    let sell = status_label(props.sell).unwrap_or("—");
-    let buy = status_label(props.buy).unwrap_or("—");
+    let sell = status_label(props.sell).unwrap_or_else(|| "—".to_string());
+    let buy = status_label(props.buy).unwrap_or_else(|| "—".to_string());
     rsx! {
         div { class: "flex flex-col items-end gap-0.5 text-xs",
-            span { class: "{theme::text_muted(props.profile)}", "Sell: {sell}" }
-            span { class: "{theme::text_muted(props.profile)}", "Buy: {buy}" }
+            span { class: "{theme::text_muted()}", {tr!("status.sell", status = sell)} }
+            span { class: "{theme::text_muted()}", {tr!("status.buy", status = buy)} }
         }
     }
 }
@@ -403,17 +388,16 @@ struct SummaryStatProps {
     title: String,
     value: String,
     caption: String,
-    profile: Profile,
 }
 
 #[component]
 fn SummaryStat(props: SummaryStatProps) -> Element {
     rsx! {
         div {
-            class: "{theme::panel_solid(props.profile)} p-3",
-            p { class: "text-[10px] font-semibold uppercase tracking-wide {theme::text_muted(props.profile)}", "{props.title}" }
-            p { class: "text-lg font-semibold {theme::text_secondary(props.profile)}", "{props.value}" }
-            p { class: "text-xs {theme::text_muted(props.profile)}", "{props.caption}" }
+            class: "{theme::panel_solid()} p-3",
+            p { class: "text-[10px] font-semibold uppercase tracking-wide {theme::text_muted()}", "{props.title}" }
+            p { class: "text-lg font-semibold {theme::text_secondary()}", "{props.value}" }
+            p { class: "text-xs {theme::text_muted()}", "{props.caption}" }
         }
     }
 }
@@ -426,14 +410,6 @@ enum SortMode {
     Demand,
 }
 
-fn sort_button_class(active: bool, profile: Profile) -> &'static str {
-    if active {
-        theme::btn_small_active(profile)
-    } else {
-        theme::btn_small_inactive(profile)
-    }
-}
-
 fn sort_rows(rows: &mut Vec<(PriceRow, bool, bool)>, mode: SortMode) {
     match mode {
         SortMode::SellRange => {
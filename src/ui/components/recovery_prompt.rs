@@ -0,0 +1,107 @@
+//! Shown when a saved file can't be read, instead of silently starting empty.
+
+use dioxus::prelude::*;
+
+use crate::{
+    app::{persist_history, persist_user_state},
+    domain::AppState,
+    infra::config,
+    ui::{
+        components::toast::{push_toast, ToastKind, ToastMessage},
+        i18n::tr,
+    },
+    util::persistence::{load_history_backup, load_state_backup, quarantine, StoreFile},
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecoveryIssue {
+    pub file: StoreFile,
+    pub reason: String,
+}
+
+#[component]
+pub fn RecoveryPrompt(issues: Signal<Vec<RecoveryIssue>>) -> Element {
+    let mut state = use_context::<Signal<AppState>>();
+    let toasts = use_context::<Signal<Vec<ToastMessage>>>();
+    let mut issues = issues;
+
+    let Some(issue) = issues.with(|list| list.first().cloned()) else {
+        return rsx! { Fragment {} };
+    };
+    let file = issue.file;
+    let label = file.label();
+
+    let restore_label = label.clone();
+    let on_restore = move |_| {
+        let mut restored_config = None;
+        let restored = match file {
+            StoreFile::State => load_state_backup()
+                .map(|backup| backup.map(|saved| state.with_mut(|st| st.apply_persisted(saved))))
+                .map_err(|err| err.to_string()),
+            StoreFile::History => load_history_backup()
+                .map(|backup| backup.map(|history| state.with_mut(|st| st.history = history)))
+                .map_err(|err| err.to_string()),
+            StoreFile::Config => config::load_config_backup()
+                .map(|backup| backup.map(|saved| restored_config = Some(saved)))
+                .map_err(|err| err.to_string()),
+        };
+        match restored {
+            Ok(Some(())) => {
+                if let Err(err) = quarantine(file) {
+                    push_toast(toasts, ToastKind::Error, tr!("recovery.toast.move_failed", error = err));
+                    return;
+                }
+                match file {
+                    StoreFile::State => persist_user_state(&state),
+                    StoreFile::History => persist_history(&state),
+                    StoreFile::Config => {
+                        if let Err(err) = restored_config.take().map_or(Ok(()), config::update) {
+                            push_toast(toasts, ToastKind::Error, tr!("recovery.toast.backup_unusable", error = err));
+                            return;
+                        }
+                    }
+                }
+                issues.with_mut(|list| list.retain(|i| i.file != file));
+                push_toast(toasts, ToastKind::Success, tr!("recovery.toast.restored", file = restore_label));
+            }
+            Ok(None) => push_toast(toasts, ToastKind::Error, tr!("recovery.toast.no_backup")),
+            Err(err) => push_toast(toasts, ToastKind::Error, tr!("recovery.toast.backup_unusable", error = err)),
+        }
+    };
+
+    let fresh_label = label.clone();
+    let on_fresh = move |_| match quarantine(file) {
+        Ok(moved) => {
+            issues.with_mut(|list| list.retain(|i| i.file != file));
+            let note = moved
+                .map(|path| format!(" {}", tr!("recovery.toast.kept_at", path = path.display())))
+                .unwrap_or_default();
+            push_toast(toasts, ToastKind::Info, format!("{}{note}", tr!("recovery.toast.fresh", file = fresh_label)));
+        }
+        Err(err) => push_toast(toasts, ToastKind::Error, tr!("recovery.toast.move_failed", error = err)),
+    };
+
+    rsx! {
+        div { class: "fixed inset-0 z-50 flex items-center justify-center bg-slate-950/80 backdrop-blur",
+            div { class: "w-full max-w-lg rounded-xl border border-amber-500/40 cvs-surface p-6 shadow-xl",
+                h2 { class: "text-lg font-semibold text-amber-200", {tr!("recovery.title", file = label)} }
+                p { class: "mt-2 text-sm cvs-text", "{issue.reason}" }
+                p { class: "mt-2 text-sm cvs-text-muted",
+                    {tr!("recovery.body")}
+                }
+                div { class: "mt-6 flex justify-end gap-3",
+                    button {
+                        class: "rounded-lg px-4 py-2 text-xs font-semibold uppercase tracking-wide cvs-btn",
+                        onclick: on_fresh,
+                        {tr!("recovery.start_fresh")}
+                    }
+                    button {
+                        class: "rounded-lg px-4 py-2 text-xs font-semibold uppercase tracking-wide cvs-btn-primary",
+                        onclick: on_restore,
+                        {tr!("recovery.restore_backup")}
+                    }
+                }
+            }
+        }
+    }
+}
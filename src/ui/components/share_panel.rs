@@ -0,0 +1,126 @@
+use dioxus::prelude::*;
+
+use crate::{
+    infra::{
+        config,
+        webhook::{self, ShareReport, WebhookError, WebhookMessage},
+    },
+    ui::components::toast::{push_toast, ToastKind, ToastMessage},
+    ui::{i18n::tr, theme},
+};
+
+/// "Share to Discord": pick a webhook, preview the embed and post it.
+#[component]
+pub fn SharePanel(report: ShareReport) -> Element {
+    let toasts = use_context::<Signal<Vec<ToastMessage>>>();
+    let mut selected = use_signal(String::new);
+    let mut busy = use_signal(|| false);
+
+    let settings = config::current().webhooks;
+    let targets: Vec<_> = settings.targets.iter().filter(|t| t.enabled).cloned().collect();
+    let target = targets
+        .iter()
+        .find(|t| t.name == selected())
+        .or(targets.first())
+        .cloned();
+    let message = report.to_message(
+        report.kind.template(&settings.templates),
+        target.as_ref().map(|t| t.username.as_str()).unwrap_or_default(),
+    );
+
+    let on_send = {
+        let message = message.clone();
+        let target = target.clone();
+        move |_| {
+            let Some(target) = target.clone() else {
+                return;
+            };
+            if busy() {
+                return;
+            }
+            busy.set(true);
+            let message = message.clone();
+            let min_interval = settings.min_interval();
+            spawn(async move {
+                match webhook::client().post(&target.url, &message, min_interval).await {
+                    Ok(()) => push_toast(toasts, ToastKind::Success, tr!("share.toast.sent", target = target.name)),
+                    Err(err @ WebhookError::RateLimited(_)) => {
+                        push_toast(toasts, ToastKind::Warning, tr!("share.toast.failed", error = err))
+                    }
+                    Err(err) => push_toast(toasts, ToastKind::Error, tr!("share.toast.failed", error = err)),
+                }
+                busy.set(false);
+            });
+        }
+    };
+
+    rsx! {
+        details { class: "{theme::panel_border()} p-4",
+            summary { class: "cursor-pointer text-sm font-semibold uppercase tracking-wide {theme::text_muted()}",
+                {tr!("share.title", kind = report.kind.label())}
+            }
+            div { class: "mt-3 space-y-3",
+                if targets.is_empty() {
+                    p { class: "text-sm {theme::text_muted()}", {tr!("share.no_targets")} }
+                } else {
+                    div { class: "flex flex-wrap items-end gap-3",
+                        div { class: "w-64",
+                            label { class: "{theme::label_class()}", {tr!("share.target")} }
+                            select {
+                                class: "mt-1 w-full {theme::input_small()}",
+                                onchange: move |evt| selected.set(evt.value()),
+                                for t in targets.iter() {
+                                    option {
+                                        value: "{t.name}",
+                                        selected: target.as_ref().is_some_and(|current| current.name == t.name),
+                                        "{t.name}"
+                                    }
+                                }
+                            }
+                        }
+                        button {
+                            class: "{theme::btn_small_active()}",
+                            disabled: busy(),
+                            onclick: on_send,
+                            if busy() {
+                                {tr!("share.sending")}
+                            } else {
+                                {tr!("share.send")}
+                            }
+                        }
+                    }
+                }
+                EmbedPreview { message }
+            }
+        }
+    }
+}
+
+/// Rough rendition of how Discord shows the embed.
+#[component]
+pub fn EmbedPreview(message: WebhookMessage) -> Element {
+    rsx! {
+        for embed in message.embeds {
+            div {
+                class: "rounded-md bg-[#2b2d31] p-3 text-sm text-slate-200",
+                style: "border-left: 4px solid #{embed.color:06x}",
+                if let Some(username) = &message.username {
+                    p { class: "mb-1 text-xs font-semibold text-slate-400", "{username}" }
+                }
+                p { class: "font-semibold text-white", "{embed.title}" }
+                p { class: "mt-1 whitespace-pre-line", "{embed.description}" }
+                if !embed.fields.is_empty() {
+                    div { class: "mt-2 grid grid-cols-3 gap-2",
+                        for field in embed.fields.iter() {
+                            div { class: if field.inline { "" } else { "col-span-3" },
+                                p { class: "text-xs font-semibold text-white", "{field.name}" }
+                                p { class: "whitespace-pre-line text-xs", {field.value.replace("**", "")} }
+                            }
+                        }
+                    }
+                }
+                p { class: "mt-2 text-[10px] text-slate-400", "{embed.footer.text}" }
+            }
+        }
+    }
+}
@@ -1,11 +1,9 @@
-use std::time::Duration;
-
 use dioxus::prelude::*;
 
+use crate::infra::config;
+use crate::ui::i18n::tr;
 use crate::util::generate_id;
 
-const TOAST_AUTO_DISMISS: Duration = Duration::from_secs(6);
-
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ToastKind {
     Info,
@@ -78,7 +76,7 @@ fn ToastCard(view: ToastView, toasts: Signal<Vec<ToastMessage>>) -> Element {
         let mut toasts = toasts_for_timer.clone();
         let id = toast_id.clone();
         async move {
-            tokio::time::sleep(TOAST_AUTO_DISMISS).await;
+            tokio::time::sleep(config::current().ui.toast_dismiss()).await;
             toasts.with_mut(|items| items.retain(|toast| toast.id != id));
         }
     });
@@ -98,7 +96,7 @@ fn ToastCard(view: ToastView, toasts: Signal<Vec<ToastMessage>>) -> Element {
                     let target = view.id.clone();
                     toasts.with_mut(|items| items.retain(|toast| toast.id != target));
                 },
-                "Dismiss"
+                {tr!("toast.dismiss")}
             }
         }
     }
@@ -0,0 +1,37 @@
+//! Reactive access to the message catalogs. Translating through these helpers
+//! subscribes the calling component to the locale, so switching languages in
+//! Settings re-renders everything that shows text.
+
+use dioxus::prelude::*;
+
+use crate::util::i18n::{self, Locale};
+
+pub static LOCALE: GlobalSignal<Locale> = Signal::global(i18n::current_locale);
+
+pub fn t(key: &str) -> String {
+    let _subscribe = LOCALE.read();
+    i18n::t(key)
+}
+
+pub fn tf(key: &str, args: &[(&str, String)]) -> String {
+    let _subscribe = LOCALE.read();
+    i18n::tf(key, args)
+}
+
+pub fn set_locale(locale: Locale) {
+    i18n::set_locale(locale);
+    *LOCALE.write() = locale;
+}
+
+/// `tr!("nav.cargo")` or `tr!("settings.cache.prices", commodity = name)`; named
+/// arguments fill the matching `{name}` placeholders.
+macro_rules! tr {
+    ($key:literal) => {
+        $crate::ui::i18n::t($key)
+    };
+    ($key:literal, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::ui::i18n::tf($key, &[$((stringify!($name), ($value).to_string())),+])
+    };
+}
+
+pub(crate) use tr;
@@ -0,0 +1,119 @@
+//! Global keyboard shortcuts and the cross-page requests they trigger.
+//!
+//! A single `keydown` listener on the window recognises the shortcuts and
+//! forwards them by name; pages pick up requests such as "focus the commodity
+//! input" from the global signals below, whichever of them is mounted.
+
+use std::str::FromStr;
+
+use dioxus::{document, prelude::*};
+
+use crate::domain::Location;
+
+/// Commodity the Cargo page should type into its input before focusing it;
+/// an empty string only focuses.
+pub static COMMODITY_FOCUS: GlobalSignal<Option<String>> = Signal::global(|| None);
+
+/// Bumped to ask the Cargo page to refresh prices of its selected row.
+pub static REFRESH_SELECTED: GlobalSignal<u64> = Signal::global(|| 0);
+
+/// Location the Sell planner should plan from.
+pub static PLAN_FROM: GlobalSignal<Option<Location>> = Signal::global(|| None);
+
+/// Element id of the Cargo page's commodity input.
+pub const COMMODITY_INPUT_ID: &str = "commodity-input";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Shortcut {
+    /// Ctrl+K
+    Palette,
+    /// Alt+1 … Alt+9, zero-based.
+    Tab(usize),
+    /// Ctrl+Tab
+    NextTab,
+    /// Ctrl+Shift+Tab
+    PreviousTab,
+    /// `/` outside text fields
+    FocusCommodity,
+    /// Ctrl+R
+    RefreshSelected,
+}
+
+impl FromStr for Shortcut {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "palette" => Ok(Shortcut::Palette),
+            "next_tab" => Ok(Shortcut::NextTab),
+            "previous_tab" => Ok(Shortcut::PreviousTab),
+            "focus_commodity" => Ok(Shortcut::FocusCommodity),
+            "refresh_selected" => Ok(Shortcut::RefreshSelected),
+            other => other
+                .strip_prefix("tab:")
+                .and_then(|n| n.parse::<usize>().ok())
+                .filter(|n| *n >= 1)
+                .map(|n| Shortcut::Tab(n - 1))
+                .ok_or_else(|| format!("unknown shortcut {other:?}")),
+        }
+    }
+}
+
+/// Replaces any listener left by a previous mount so shortcuts fire once.
+const LISTENER: &str = r#"
+if (window.__cvsShortcuts) {
+    window.removeEventListener('keydown', window.__cvsShortcuts);
+}
+window.__cvsShortcuts = (event) => {
+    const tag = event.target && event.target.tagName;
+    const typing = tag === 'INPUT' || tag === 'TEXTAREA' || tag === 'SELECT';
+    const ctrl = event.ctrlKey || event.metaKey;
+    const key = event.key.toLowerCase();
+    let name = null;
+    if (ctrl && key === 'k') {
+        name = 'palette';
+    } else if (ctrl && key === 'r') {
+        name = 'refresh_selected';
+    } else if (ctrl && key === 'tab') {
+        name = event.shiftKey ? 'previous_tab' : 'next_tab';
+    } else if (event.altKey && !ctrl && /^Digit[1-9]$/.test(event.code)) {
+        name = 'tab:' + event.code.slice(5);
+    } else if (!typing && !ctrl && !event.altKey && key === '/') {
+        name = 'focus_commodity';
+    }
+    if (name) {
+        event.preventDefault();
+        dioxus.send(name);
+    }
+};
+window.addEventListener('keydown', window.__cvsShortcuts);
+"#;
+
+/// Installs the window listener and calls `on_shortcut` for every shortcut.
+#[component]
+pub fn GlobalShortcuts(on_shortcut: EventHandler<Shortcut>) -> Element {
+    use_future(move || async move {
+        let mut listener = document::eval(LISTENER);
+        while let Ok(name) = listener.recv::<String>().await {
+            if let Ok(shortcut) = name.parse() {
+                on_shortcut.call(shortcut);
+            }
+        }
+    });
+
+    rsx! {}
+}
+
+/// Focuses the element with `id` once it's rendered.
+pub fn focus_element(id: &str) {
+    document::eval(&format!(
+        "requestAnimationFrame(() => document.getElementById('{id}')?.focus());"
+    ));
+}
+
+/// Scrolls the element with `id` into view if it's outside the visible area.
+pub fn scroll_into_view(id: &str) {
+    document::eval(&format!(
+        "document.getElementById('{id}')?.scrollIntoView({{ block: 'nearest' }});"
+    ));
+}
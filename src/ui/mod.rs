@@ -1,4 +1,6 @@
 pub mod components;
+pub mod i18n;
+pub mod keyboard;
 pub mod pages;
 pub mod shell;
 pub mod theme;
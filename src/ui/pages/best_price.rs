@@ -7,9 +7,19 @@ use dioxus::{document, prelude::*};
 use tokio::time::sleep;
 
 use crate::{
-    domain::{rank_best_prices, AppState, BestPriceSummary},
-    ui::components::toast::{push_toast, ToastKind, ToastMessage},
-    ui::pages::cargo::request_price_fetch,
+    domain::{rank_best_prices_with, AppState, BestPriceSummary, CargoManifest},
+    infra::{
+        config,
+        export::{best_price_rows, ExportPayload},
+        webhook::best_prices_report,
+    },
+    ui::components::{
+        export_buttons::ExportButtons,
+        share_panel::SharePanel,
+        toast::{push_toast, ToastKind, ToastMessage},
+    },
+    ui::{i18n::tr, pages::cargo::request_price_fetch, theme},
+    util::format,
 };
 
 #[component]
@@ -18,14 +28,14 @@ pub fn BestPricePage() -> Element {
     let toasts = use_context::<Signal<Vec<ToastMessage>>>();
     let price_request = use_context::<Signal<Option<String>>>();
 
-    let items = state.with(|st| st.cargo_items.clone());
+    let items = state.with(|st| st.cargo_items().to_vec());
     let price_map = state.with(|st| st.price_points.clone());
     let locations = state.with(|st| st.sell_locations.clone());
 
     if items.is_empty() {
         return rsx! {
-            div { class: "rounded-xl border border-slate-800 bg-slate-900/40 p-6 text-sm text-slate-400",
-                "Add cargo items first to generate best-price suggestions." }
+            div { class: "rounded-xl border cvs-border cvs-surface p-6 text-sm cvs-text-muted",
+                {tr!("best_price.empty")} }
         };
     }
 
@@ -45,9 +55,12 @@ pub fn BestPricePage() -> Element {
         .map(|item| (item.id.clone(), item.commodity_id.clone()))
         .collect();
 
-    let summary = rank_best_prices(&items, &price_map, &locations);
+    let summary = rank_best_prices_with(&items, &price_map, &locations, &config::current().ranking.params());
     let suggestion_views = build_views(&summary, &item_to_commodity);
     let quick_copy = build_summary_text(&summary);
+    let export_payload = ExportPayload::BestPrices(best_price_rows(&summary));
+    let manifest = state.with(|st| st.active_manifest().cloned().unwrap_or_else(CargoManifest::default_manifest));
+    let share_report = best_prices_report(&manifest, &summary);
     let summary_copied = use_signal(|| false);
     let on_copy_summary = {
         let quick_copy = quick_copy.clone();
@@ -78,13 +91,13 @@ pub fn BestPricePage() -> Element {
                 push_toast(
                     toasts.clone(),
                     ToastKind::Info,
-                    format!("Refreshing price data for {} commodities...", missing.len()),
+                    tr!("best_price.toast.refreshing_many", count = missing.len()),
                 );
             } else {
                 push_toast(
                     toasts.clone(),
                     ToastKind::Info,
-                    "All commodities already have price data.",
+                    tr!("best_price.toast.all_loaded"),
                 );
             }
         }
@@ -95,34 +108,34 @@ pub fn BestPricePage() -> Element {
             header {
                 class: "flex flex-wrap items-start justify-between gap-4",
                 div {
-                    h1 { class: "text-2xl font-semibold text-slate-100", "Best-Price Finder" }
+                    h1 { class: "text-2xl font-semibold cvs-text", {tr!("best_price.title")} }
                     p {
-                        class: "text-sm text-slate-400",
-                        "Ranks sell locations by adjusted price considering travel risk and volatility."
+                        class: "text-sm cvs-text-muted",
+                        {tr!("best_price.subtitle")}
                     }
                 }
                 button {
-                    class: "rounded-md border border-indigo-500/40 px-3 py-2 text-xs font-semibold uppercase tracking-wide text-indigo-200 hover:bg-indigo-500/10",
+                    class: "rounded-md px-3 py-2 text-xs font-semibold uppercase tracking-wide cvs-btn-accent",
                     onclick: on_refresh_all,
-                    "Refresh Missing Data"
+                    {tr!("best_price.refresh_missing")}
                 }
             }
 
             if !missing.is_empty() {
                 div {
                     class: "rounded-lg border border-amber-500/30 bg-amber-500/10 px-3 py-2 text-xs text-amber-200",
-                    {format!("{} commodity price set(s) missing or stale. Refresh to improve accuracy.", missing.len())}
+                    {tr!("best_price.missing_warning", count = missing.len())}
                 }
             }
 
             if let Some(best) = summary.best_overall.as_ref() {
                 div {
                     class: "rounded-xl border border-emerald-500/40 bg-emerald-500/10 p-4 text-emerald-100",
-                    h2 { class: "text-sm font-semibold uppercase tracking-wide", "Best Overall" }
+                    h2 { class: "text-sm font-semibold uppercase tracking-wide", {tr!("best_price.best_overall")} }
                     p { class: "mt-1 text-lg font-semibold", "{format_price_display(best.sell_price)} aUEC" }
                     p { class: "text-sm opacity-90", "{best.location_name}" }
-                    if let Some(notes) = &best.notes {
-                        p { class: "mt-1 text-xs opacity-80", "Notes: {notes}" }
+                    if let Some(notes) = best.notes_label() {
+                        p { class: "mt-1 text-xs opacity-80", {tr!("best_price.notes", notes = notes)} }
                     }
                 }
             }
@@ -140,26 +153,31 @@ pub fn BestPricePage() -> Element {
             }
 
             section {
-                class: "rounded-xl border border-slate-800 bg-slate-900/40 p-4",
+                class: "rounded-xl border cvs-border cvs-surface p-4",
                 div { class: "flex items-center justify-between gap-3",
-                    h2 { class: "text-sm font-semibold uppercase tracking-wide text-slate-500", "Quick Summary" }
+                    h2 { class: "text-sm font-semibold uppercase tracking-wide cvs-text-muted", {tr!("best_price.quick_summary")} }
+                    div { class: "ml-auto",
+                        ExportButtons { payload: export_payload }
+                    }
                     button {
-                        class: "rounded-md border border-slate-700 px-3 py-1 text-xs font-semibold uppercase tracking-wide text-slate-200 hover:border-indigo-500 hover:text-indigo-200",
+                        class: "rounded-md px-3 py-1 text-xs font-semibold uppercase tracking-wide cvs-btn",
                         onclick: on_copy_summary,
                         if summary_copied() {
-                            "Copied!"
+                            {tr!("common.copied")}
                         } else {
-                            "Copy"
+                            {tr!("common.copy")}
                         }
                     }
                 }
                 textarea {
-                    class: "mt-3 h-32 w-full rounded-lg border border-slate-800 bg-slate-950 p-3 text-sm text-slate-200",
+                    class: "mt-3 h-32 w-full rounded-lg p-3 text-sm cvs-input",
                     value: quick_copy.clone(),
                     readonly: true,
                 }
-                p { class: "mt-2 text-xs text-slate-500", "Copy and share these recommendations with your crew." }
+                p { class: "mt-2 text-xs cvs-text-muted", {tr!("best_price.share_hint")} }
             }
+
+            SharePanel { report: share_report }
         }
     }
 }
@@ -176,40 +194,40 @@ fn SuggestionCard(
     let entries = view.entries.clone();
     rsx! {
         div {
-            class: "w-full rounded-xl border border-slate-800 bg-slate-900/40 p-4",
+            class: "w-full rounded-xl border cvs-border cvs-surface p-4",
             div { class: "flex items-center justify-between",
-                h3 { class: "text-sm font-semibold text-slate-100", "{commodity_name}" }
+                h3 { class: "text-sm font-semibold cvs-text", "{commodity_name}" }
                 button {
-                    class: "text-xs font-semibold uppercase tracking-wide text-indigo-300 hover:text-indigo-100",
+                    class: "{theme::link_class()}",
                     onclick: move |_| {
                         if let Some(ref id) = commodity_id {
                             request_price_fetch(state.clone(), price_request.clone(), id);
-                            push_toast(toasts.clone(), ToastKind::Info, "Refreshing price data...");
+                            push_toast(toasts.clone(), ToastKind::Info, tr!("cargo.toast.refreshing"));
                         } else {
-                            push_toast(toasts.clone(), ToastKind::Warning, "Commodity lookup missing for this item.");
+                            push_toast(toasts.clone(), ToastKind::Warning, tr!("best_price.toast.lookup_missing"));
                         }
                     },
-                    "Refresh"
+                    {tr!("common.refresh")}
                 }
             }
             table {
-                class: "mt-3 w-full divide-y divide-slate-800 text-sm",
+                class: "mt-3 w-full divide-y cvs-divide text-sm",
                 thead {
-                    class: "text-xs uppercase tracking-wide text-slate-500",
+                    class: "text-xs uppercase tracking-wide cvs-text-muted",
                     tr {
-                        th { class: "py-2 text-left", "Location" }
-                        th { class: "py-2 text-right", "Sell Max (aUEC)" }
-                        th { class: "py-2 text-right", "Buy Min (aUEC)" }
-                        th { class: "py-2 text-right", "Stock (SCU)" }
-                        th { class: "py-2 text-right", "Demand" }
-                        th { class: "py-2 text-right", "Containers" }
-                        th { class: "py-2 text-right", "Adjusted (aUEC)" }
+                        th { class: "py-2 text-left", {tr!("table.location")} }
+                        th { class: "py-2 text-right", {tr!("table.sell_max_auec")} }
+                        th { class: "py-2 text-right", {tr!("table.buy_min_auec")} }
+                        th { class: "py-2 text-right", {tr!("table.stock_scu")} }
+                        th { class: "py-2 text-right", {tr!("table.demand")} }
+                        th { class: "py-2 text-right", {tr!("table.containers")} }
+                        th { class: "py-2 text-right", {tr!("table.adjusted_auec")} }
                     }
                 }
                 tbody {
                     for entry in entries {
                         tr {
-                            class: "border-t border-slate-900/60 text-slate-200",
+                            class: "border-t cvs-border cvs-text",
                             td { class: "py-2 text-left", "{entry.location}" }
                             td { class: "py-2 text-right", "{entry.sell_display}" }
                             td { class: "py-2 text-right", "{entry.buy_display}" }
@@ -220,7 +238,7 @@ fn SuggestionCard(
                         }
                         if let Some(notes) = entry.notes {
                             tr {
-                                td { class: "pb-2 text-left text-xs text-slate-500", colspan: "7", "Notes: {notes}" }
+                                td { class: "pb-2 text-left text-xs cvs-text-muted", colspan: "7", {tr!("best_price.notes", notes = notes)} }
                             }
                         }
                     }
@@ -266,8 +284,8 @@ fn build_views(summary: &BestPriceSummary, map: &HashMap<String, String>) -> Vec
                     stock_display: format_stock_display(entry.stock),
                     demand_display: format_status_display(entry.status_sell, entry.status_buy),
                     containers_display: format_containers_display(&entry.container_sizes),
-                    adjusted_display: format!("{:.0}", entry.adjusted_price.max(0.0)),
-                    notes: entry.notes.clone(),
+                    adjusted_display: format::integer(entry.adjusted_price.max(0.0)),
+                    notes: entry.notes_label(),
                 })
                 .collect(),
         })
@@ -288,20 +306,20 @@ fn build_summary_text(summary: &BestPriceSummary) -> String {
             let mut details = Vec::new();
             let stock = format_stock_display(entry.stock);
             if stock != "—" {
-                details.push(format!("stock {stock}"));
+                details.push(tr!("best_price.summary.stock", stock = stock));
             }
             let demand = format_status_display(entry.status_sell, entry.status_buy);
             if demand != "—" {
-                details.push(format!("demand {demand}"));
+                details.push(tr!("best_price.summary.demand", demand = demand));
             }
             let containers = format_containers_display(&entry.container_sizes);
             if containers != "—" {
-                details.push(format!("containers {containers}"));
+                details.push(tr!("best_price.summary.containers", containers = containers));
             }
             if !details.is_empty() {
                 line.push_str(&format!(" [{}]", details.join(", ")));
             }
-            if let Some(notes) = entry.notes.as_deref() {
+            if let Some(notes) = entry.notes_label() {
                 line.push_str(&format!(" ({notes})"));
             }
             lines.push(line);
@@ -309,7 +327,8 @@ fn build_summary_text(summary: &BestPriceSummary) -> String {
     }
     if let Some(best) = summary.best_overall.as_ref() {
         let mut line = format!(
-            "Overall best: {} @ {} aUEC",
+            "{}: {} @ {} aUEC",
+            tr!("best_price.summary.overall_best"),
             best.location_name,
             format_price_display(best.sell_price)
         );
@@ -317,21 +336,21 @@ fn build_summary_text(summary: &BestPriceSummary) -> String {
         let mut details = Vec::new();
         let stock = format_stock_display(best.stock);
         if stock != "—" {
-            details.push(format!("stock {stock}"));
+            details.push(tr!("best_price.summary.stock", stock = stock));
         }
         let demand = format_status_display(best.status_sell, best.status_buy);
         if demand != "—" {
-            details.push(format!("demand {demand}"));
+            details.push(tr!("best_price.summary.demand", demand = demand));
         }
         let containers = format_containers_display(&best.container_sizes);
         if containers != "—" {
-            details.push(format!("containers {containers}"));
+            details.push(tr!("best_price.summary.containers", containers = containers));
         }
 
         if !details.is_empty() {
             line.push_str(&format!(" [{}]", details.join(", ")));
         }
-        if let Some(notes) = best.notes.as_deref() {
+        if let Some(notes) = best.notes_label() {
             line.push_str(&format!(" ({notes})"));
         }
         lines.push(line);
@@ -342,13 +361,13 @@ fn build_summary_text(summary: &BestPriceSummary) -> String {
 fn format_price_display(value: Option<f64>) -> String {
     value
         .filter(|v| v.is_finite() && *v >= 0.0)
-        .map(|v| format!("{:.0}", v))
-        .unwrap_or_else(|| "n/a".to_string())
+        .map(format::integer)
+        .unwrap_or_else(|| tr!("common.not_available"))
 }
 
 fn format_stock_display(value: Option<f64>) -> String {
     match value {
-        Some(v) if v.is_finite() && v > 0.0 => format!("{:.0} SCU", v),
+        Some(v) if v.is_finite() && v > 0.0 => format::scu(v),
         _ => "—".to_string(),
     }
 }
@@ -358,19 +377,23 @@ fn format_status_display(sell: Option<i32>, buy: Option<i32>) -> String {
     let buy_text = status_label(buy);
 
     match (sell_text, buy_text) {
-        (Some(sell), Some(buy)) => format!("Sell: {sell} / Buy: {buy}"),
-        (Some(sell), None) => format!("Sell: {sell}"),
-        (None, Some(buy)) => format!("Buy: {buy}"),
+        (Some(sell), Some(buy)) => format!(
+            "{} / {}",
+            tr!("status.sell", status = sell),
+            tr!("status.buy", status = buy)
+        ),
+        (Some(sell), None) => tr!("status.sell", status = sell),
+        (None, Some(buy)) => tr!("status.buy", status = buy),
         _ => "—".to_string(),
     }
 }
 
-fn status_label(code: Option<i32>) -> Option<&'static str> {
+fn status_label(code: Option<i32>) -> Option<String> {
     match code {
-        Some(3) => Some("High"),
-        Some(2) => Some("Normal"),
-        Some(1) => Some("Low"),
-        Some(0) => Some("Unavailable"),
+        Some(3) => Some(tr!("status.high")),
+        Some(2) => Some(tr!("status.normal")),
+        Some(1) => Some(tr!("status.low")),
+        Some(0) => Some(tr!("status.unavailable")),
         _ => None,
     }
 }
@@ -383,7 +406,7 @@ fn format_containers_display(sizes: &[f64]) -> String {
     let values: Vec<String> = sizes
         .iter()
         .filter(|size| size.is_finite() && **size > 0.0)
-        .map(|size| format!("{size:.0}"))
+        .map(|size| format::integer(*size))
         .collect();
 
     if values.is_empty() {
@@ -393,7 +416,7 @@ fn format_containers_display(sizes: &[f64]) -> String {
     }
 }
 
-fn copy_text_to_clipboard(text: &str) -> bool {
+pub(crate) fn copy_text_to_clipboard(text: &str) -> bool {
     if text.trim().is_empty() {
         return false;
     }
@@ -5,10 +5,11 @@ use dioxus::prelude::*;
 use std::collections::HashSet;
 
 use crate::{
-    app::{persist_user_state, CACHE_TTL},
+    app::{persist_history, persist_user_state},
     domain::{
-        evaluate_cargo_items, profitability_indicator, AppState,
-        CacheResource, CargoItem, Commodity, PricePoint,
+        adjust_cargo, evaluate_cargo_items, move_item, profitability_indicator, AppState,
+        CacheResource, CargoAdjustResult, CargoItem, CargoManifest, Commodity, PricePoint,
+        TradeRecord, TradeSide,
     },
     ui::components::{
         cargo_table::{CargoRow, CargoTable},
@@ -17,8 +18,22 @@ use crate::{
         profit_indicator::ProfitIndicator,
         toast::{push_toast, ToastKind, ToastMessage},
     },
-    ui::theme,
-    util::generate_id,
+    infra::{
+        config,
+        export::{evaluation_rows, ExportPayload},
+        webhook::loot_report,
+    },
+    ui::components::{
+        export_buttons::ExportButtons,
+        share_panel::SharePanel,
+        import_dialog::{ImportDialog, ImportSelection},
+    },
+    ui::{
+        i18n::tr,
+        keyboard::{self, COMMODITY_FOCUS, COMMODITY_INPUT_ID, REFRESH_SELECTED},
+        theme,
+    },
+    util::format,
 };
 
 #[component]
@@ -27,20 +42,26 @@ pub fn CargoPage() -> Element {
     let toasts = use_context::<Signal<Vec<ToastMessage>>>();
     let price_request = use_context::<Signal<Option<String>>>();
     
-    let profile = state.with(|s| s.profile);
 
     let mut commodity_query = use_signal(String::new);
     let mut scu_input = use_signal(String::new);
+    let mut paid_input = use_signal(String::new);
     let selected_item = use_signal(|| None::<String>);
+    let mut import_open = use_signal(|| false);
 
     let commodities = state.with(|st| st.commodities.clone());
-    let items = state.with(|st| st.cargo_items.clone());
+    let items = state.with(|st| st.cargo_items().to_vec());
     let price_map = state.with(|st| st.price_points.clone());
     let profitability = state.with(|st| st.profitability.clone());
     let nqa_terminal_ids = state.with(|st| st.nqa_terminal_ids.clone());
 
     let summary = evaluate_cargo_items(&items, &price_map);
+    let all_items: Vec<CargoItem> = state.with(|st| st.all_cargo_items().cloned().collect());
+    let fleet_ev = evaluate_cargo_items(&all_items, &price_map).total_ev;
+    let manifest_count = state.with(|st| st.own_manifests().count());
     let indicator = profitability_indicator(summary.total_ev, &profitability);
+    let evaluation_export = ExportPayload::Evaluation(evaluation_rows(&items, &summary));
+    let loot_share = state.with(|st| st.active_manifest().filter(|m| !m.items.is_empty()).map(|m| loot_report(m, &summary)));
 
     let evaluation_lookup: HashMap<_, _> = summary
         .items
@@ -77,7 +98,7 @@ pub fn CargoPage() -> Element {
         })
         .collect();
 
-    let total_ev_display = format!("{:.0}", summary.total_ev);
+    let total_ev_display = format::integer(summary.total_ev);
     let average_confidence = summary.average_confidence;
 
     let selected_id = selected_item();
@@ -122,7 +143,7 @@ pub fn CargoPage() -> Element {
                     status_sell: point.status_sell,
                     status_buy: point.status_buy,
                     container_sizes: point.container_sizes.clone(),
-                    updated_label: humanize_age(point.updated_at),
+                    updated_label: format::relative_time(point.updated_at),
                     is_nqa,
                 }
             })
@@ -136,6 +157,7 @@ pub fn CargoPage() -> Element {
         let price_request = price_request.clone();
         let mut commodity_query = commodity_query.clone();
         let mut scu_input = scu_input.clone();
+        let mut paid_input = paid_input;
         let mut selected_item = selected_item.clone();
         move |evt: FormEvent| {
             evt.prevent_default();
@@ -144,7 +166,7 @@ pub fn CargoPage() -> Element {
                 push_toast(
                     toasts.clone(),
                     ToastKind::Warning,
-                    "Pick a commodity first.",
+                    tr!("cargo.toast.pick_commodity"),
                 );
                 return;
             }
@@ -162,7 +184,7 @@ pub fn CargoPage() -> Element {
                 push_toast(
                     toasts.clone(),
                     ToastKind::Error,
-                    "Commodity not found. Use the autocomplete list.",
+                    tr!("cargo.toast.commodity_not_found"),
                 );
                 return;
             };
@@ -173,19 +195,57 @@ pub fn CargoPage() -> Element {
                     push_toast(
                         toasts.clone(),
                         ToastKind::Error,
-                        "Enter a non-zero SCU adjustment (positive to add, negative to subtract).",
+                        tr!("cargo.toast.nonzero_scu"),
                     );
                     return;
                 }
             };
 
-            match adjust_cargo_item(
+            // Optional purchase price: logs the addition as a buy in the trade history.
+            let paid_per_scu = match paid_input().trim() {
+                "" => None,
+                raw => match raw.replace(',', ".").parse::<f64>() {
+                    Ok(value) if value.is_finite() && value > 0.0 && delta > 0 => Some(value),
+                    Ok(_) if delta < 0 => None,
+                    _ => {
+                        push_toast(
+                            toasts,
+                            ToastKind::Error,
+                            tr!("cargo.toast.paid_invalid"),
+                        );
+                        return;
+                    }
+                },
+            };
+
+            let result = adjust_cargo_item(
                 state.clone(),
                 &commodity,
                 delta,
                 selected_item.clone(),
                 toasts.clone(),
-            ) {
+            );
+
+            if let Some(price) = paid_per_scu {
+                if matches!(result, CargoAdjustResult::Added(..) | CargoAdjustResult::Updated(_)) {
+                    let mut state = state;
+                    state.with_mut(|st| {
+                        let record = TradeRecord::new(
+                            &st.session_id,
+                            TradeSide::Buy,
+                            commodity.id.clone(),
+                            commodity.name.clone(),
+                            delta as u32,
+                            price,
+                        );
+                        st.history.record(record);
+                    });
+                    persist_history(&state);
+                    paid_input.set(String::new());
+                }
+            }
+
+            match result {
                 CargoAdjustResult::Added(new_id, commodity_id) => {
                     commodity_query.set(String::new());
                     scu_input.set(String::new());
@@ -197,7 +257,7 @@ pub fn CargoPage() -> Element {
                     scu_input.set(String::new());
                     selected_item.set(Some(item_id));
                 }
-                CargoAdjustResult::Removed => {
+                CargoAdjustResult::Removed(_) => {
                     commodity_query.set(String::new());
                     scu_input.set(String::new());
                 }
@@ -211,12 +271,12 @@ pub fn CargoPage() -> Element {
         let toasts = toasts.clone();
         let mut selected_item = selected_item.clone();
         move |id: String| {
-            state.with_mut(|st| st.cargo_items.retain(|item| item.id != id));
+            state.with_mut(|st| st.cargo_items_mut().retain(|item| item.id != id));
             if selected_item().as_ref() == Some(&id) {
                 selected_item.set(None);
             }
             persist_user_state(&state);
-            push_toast(toasts.clone(), ToastKind::Info, "Cargo item removed.");
+            push_toast(toasts.clone(), ToastKind::Info, tr!("cargo.toast.removed"));
         }
     };
 
@@ -231,7 +291,7 @@ pub fn CargoPage() -> Element {
         let mut state = state.clone();
         move |id: String| {
             state.with_mut(|st| {
-                if let Some(item) = st.cargo_items.iter_mut().find(|item| item.id == id) {
+                if let Some(item) = st.cargo_items_mut().iter_mut().find(|item| item.id == id) {
                     item.is_hot = !item.is_hot;
                 }
             });
@@ -239,71 +299,98 @@ pub fn CargoPage() -> Element {
         }
     };
 
-    let on_refresh_prices = {
-        let state = state.clone();
-        let price_request = price_request.clone();
-        let toasts = toasts.clone();
-        let selected_item = selected_item.clone();
-        move |_| {
-            if let Some(selected) = selected_item() {
-                if let Some(commodity_id) = state.with(|st| {
-                    st.cargo_items
-                        .iter()
-                        .find(|item| item.id == selected)
-                        .map(|item| item.commodity_id.clone())
-                }) {
-                    request_price_fetch(state.clone(), price_request.clone(), &commodity_id);
-                    push_toast(toasts.clone(), ToastKind::Info, "Refreshing price data...");
-                }
-            } else {
-                push_toast(
-                    toasts.clone(),
-                    ToastKind::Warning,
-                    "Select a cargo row first.",
-                );
-            }
+    let refresh_item = move |id: &str| {
+        if let Some(commodity_id) = state.with(|st| {
+            st.cargo_items()
+                .iter()
+                .find(|item| item.id == id)
+                .map(|item| item.commodity_id.clone())
+        }) {
+            request_price_fetch(state, price_request, &commodity_id);
+            push_toast(toasts, ToastKind::Info, tr!("cargo.toast.refreshing"));
         }
     };
 
+    let refresh_selected = move || match selected_item() {
+        Some(selected) => refresh_item(&selected),
+        None => push_toast(toasts, ToastKind::Warning, tr!("cargo.toast.select_row")),
+    };
+
+    let on_activate = move |id: String| refresh_item(&id);
+
+    // Ctrl+R and the command palette ask for a refresh of the selected row.
+    let mut seen_refresh = use_signal(|| *REFRESH_SELECTED.peek());
+    use_effect(move || {
+        let requested = REFRESH_SELECTED();
+        if requested != *seen_refresh.peek() {
+            seen_refresh.set(requested);
+            refresh_selected();
+        }
+    });
+
+    // "/" and the command palette focus the commodity input, optionally prefilled.
+    use_effect(move || {
+        let Some(name) = COMMODITY_FOCUS() else {
+            return;
+        };
+        *COMMODITY_FOCUS.write() = None;
+        if !name.is_empty() {
+            commodity_query.set(name);
+        }
+        keyboard::focus_element(COMMODITY_INPUT_ID);
+    });
+
     let (price_rows, selected_commodity_id, selected_is_hot) = match price_breakdown {
         Some((rows, id, is_hot)) => (rows, Some(id), is_hot),
         None => (Vec::new(), None, false),
     };
 
     rsx! {
+        ImportDialog {
+            open: import_open,
+            commodities: commodities.clone(),
+            on_import: move |selections: Vec<ImportSelection>| {
+                import_cargo_lines(state, price_request, toasts, selections);
+            },
+        }
         div { class: "space-y-8",
             section {
-                class: "grid gap-4 sm:grid-cols-3",
+                class: "grid gap-4 sm:grid-cols-4",
                 KpiCard {
-                    title: "Total Expected Value".to_string(),
+                    title: tr!("cargo.kpi.total_ev"),
                     value: total_ev_display,
-                    description: Some("Sum of all cargo EV (aUEC)".to_string()),
-                    profile: profile,
+                    description: Some(tr!("cargo.kpi.total_ev_hint")),
                 }
                 KpiCard {
-                    title: "Average Confidence".to_string(),
-                    value: format!("{:.0}%", average_confidence * 100.0),
-                    description: Some("Weighted by evaluated items".to_string()),
-                    profile: profile,
+                    title: tr!("cargo.kpi.all_manifests"),
+                    value: format::integer(fleet_ev),
+                    description: Some(tr!("cargo.kpi.all_manifests_hint", count = manifest_count)),
                 }
-                ProfitIndicator { indicator: indicator, profile: profile }
+                KpiCard {
+                    title: tr!("cargo.kpi.avg_confidence"),
+                    value: format!("{}%", format::integer(average_confidence as f64 * 100.0)),
+                    description: Some(tr!("cargo.kpi.avg_confidence_hint")),
+                }
+                ProfitIndicator { indicator: indicator }
             }
 
             section {
                 class: "grid gap-6 lg:grid-cols-[2fr,1fr]",
                 div {
                     class: "space-y-4",
+                    ManifestBar { selected_item }
                     form {
-                        class: "{theme::panel_border(profile)} flex flex-wrap items-end gap-4 px-4 py-4",
+                        class: "{theme::panel_border()} flex flex-wrap items-end gap-4 px-4 py-4",
                         onsubmit: on_submit,
                         div { class: "flex-1 min-w-[200px]",
-                            label { class: "{theme::label_class(profile)}", "Commodity" }
+                            label { class: "{theme::label_class()}", {tr!("cargo.field.commodity")} }
                             input {
-                                class: "mt-1 w-full {theme::input_small(profile)}",
+                                id: COMMODITY_INPUT_ID,
+                                class: "mt-1 w-full {theme::input_small()}",
                                 value: commodity_query(),
                                 oninput: move |evt| commodity_query.set(evt.value().to_string()),
                                 list: "commodity-list",
-                                placeholder: "e.g. Agricultural Supplies",
+                                placeholder: tr!("cargo.field.commodity_placeholder"),
                             }
                             datalist {
                                 id: "commodity-list",
@@ -313,53 +400,279 @@ pub fn CargoPage() -> Element {
                             }
                         }
                         div { class: "w-32",
-                            label { class: "{theme::label_class(profile)}", "SCU" }
+                            label { class: "{theme::label_class()}", "SCU" }
                             input {
-                                class: "mt-1 w-full {theme::input_small(profile)}",
+                                class: "mt-1 w-full {theme::input_small()}",
                                 inputmode: "decimal",
                                 value: scu_input(),
                                 oninput: move |evt| scu_input.set(evt.value().to_string()),
                                 placeholder: "32",
                             }
                         }
+                        div { class: "w-32",
+                            label { class: "{theme::label_class()}", {tr!("cargo.field.paid")} }
+                            input {
+                                class: "mt-1 w-full {theme::input_small()}",
+                                inputmode: "decimal",
+                                value: paid_input(),
+                                oninput: move |evt| paid_input.set(evt.value().to_string()),
+                                placeholder: tr!("common.optional"),
+                                title: tr!("cargo.field.paid_hint"),
+                            }
+                        }
                         button {
-                            class: "{theme::btn_primary(profile)}",
+                            class: "{theme::btn_primary()}",
                             r#type: "submit",
-                            "Add Cargo"
+                            {tr!("cargo.add")}
                         }
                     }
 
+                    div { class: "flex justify-end gap-2",
+                        button {
+                            class: "{theme::btn_small_inactive()}",
+                            title: tr!("cargo.import_hint"),
+                            onclick: move |_| import_open.set(true),
+                            {format!("⬆ {}", tr!("cargo.import"))}
+                        }
+                        ExportButtons { payload: evaluation_export }
+                    }
                     CargoTable {
                         rows,
                         selected_id: selected_id.clone(),
-                        profile: profile,
                         on_select,
                         on_remove,
+                        on_activate,
                         on_toggle_hot,
                     }
+                    if let Some(report) = loot_share {
+                        SharePanel { report }
+                    }
                 }
 
                 div {
                     class: "space-y-4",
                     div { class: "flex items-center justify-between",
-                        h2 { class: "text-sm font-semibold {theme::text_secondary(profile)}", "Price Breakdown" }
+                        h2 { class: "text-sm font-semibold {theme::text_secondary()}", {tr!("cargo.price_breakdown")} }
                         button {
-                            class: "{theme::link_class(profile)}",
-                            onclick: on_refresh_prices,
-                            "Refresh"
+                            class: "{theme::link_class()}",
+                            onclick: move |_| refresh_selected(),
+                            {tr!("common.refresh")}
                         }
                     }
                     if let Some(ref commodity_id) = selected_commodity_id {
-                        p { class: "text-xs {theme::text_muted(profile)}", "Commodity ID: {commodity_id}" }
+                        p { class: "text-xs {theme::text_muted()}", {tr!("cargo.commodity_id", id = commodity_id)} }
                     }
                     if selected_is_hot {
                         p { 
                             class: "text-xs text-orange-400 flex items-center gap-1",
                             span { "🔥" }
-                            "Showing only no-questions-asked terminals (hot cargo)"
+                            {tr!("cargo.hot_nqa_only")}
                         }
                     }
-                    PriceTable { rows: price_rows, profile: profile }
+                    PriceTable { rows: price_rows }
+                }
+            }
+        }
+    }
+}
+
+/// Manifest switcher plus rename, ship assignment and moving the selected item.
+#[component]
+fn ManifestBar(selected_item: Signal<Option<String>>) -> Element {
+    let mut state = use_context::<Signal<AppState>>();
+    let toasts = use_context::<Signal<Vec<ToastMessage>>>();
+    let mut selected_item = selected_item;
+
+    let mut new_name = use_signal(String::new);
+    let mut move_target = use_signal(String::new);
+    let mut move_scu = use_signal(String::new);
+
+    let (manifests, active_id) = state.with(|st| {
+        let active = st.active_manifest().map(|m| m.id.clone()).unwrap_or_default();
+        (st.manifests.clone(), active)
+    });
+    let price_map = state.with(|st| st.price_points.clone());
+    let tabs: Vec<(CargoManifest, f64)> = manifests
+        .iter()
+        .map(|m| (m.clone(), evaluate_cargo_items(&m.items, &price_map).total_ev))
+        .collect();
+    let Some(active) = manifests.iter().find(|m| m.id == active_id).cloned() else {
+        return rsx! { Fragment {} };
+    };
+    let others: Vec<CargoManifest> = manifests.iter().filter(|m| m.id != active_id).cloned().collect();
+    let selected = selected_item().and_then(|id| active.items.iter().find(|item| item.id == id).cloned());
+
+    let on_create = move |evt: FormEvent| {
+        evt.prevent_default();
+        let name = new_name().trim().to_string();
+        if name.is_empty() {
+            push_toast(toasts, ToastKind::Warning, tr!("manifest.toast.name_required"));
+            return;
+        }
+        let manifest = CargoManifest::new(name.clone());
+        state.with_mut(|st| {
+            st.active_manifest_id = manifest.id.clone();
+            st.manifests.push(manifest);
+        });
+        selected_item.set(None);
+        new_name.set(String::new());
+        persist_user_state(&state);
+        push_toast(toasts, ToastKind::Success, tr!("manifest.toast.created", name = name));
+    };
+
+    let on_delete = {
+        let active = active.clone();
+        move |_| {
+            if manifests.len() <= 1 {
+                push_toast(toasts, ToastKind::Warning, tr!("manifest.toast.keep_one"));
+                return;
+            }
+            if !active.items.is_empty() {
+                push_toast(toasts, ToastKind::Warning, tr!("manifest.toast.not_empty"));
+                return;
+            }
+            state.with_mut(|st| {
+                st.manifests.retain(|m| m.id != active.id);
+                st.active_manifest_mut();
+            });
+            selected_item.set(None);
+            persist_user_state(&state);
+            push_toast(toasts, ToastKind::Info, tr!("manifest.toast.deleted", name = active.name));
+        }
+    };
+
+    let on_move = {
+        let selected = selected.clone();
+        let active_id = active_id.clone();
+        let others = others.clone();
+        move |_| {
+            let Some(item) = selected.clone() else {
+                return;
+            };
+            let target = Some(move_target())
+                .filter(|id| others.iter().any(|m| &m.id == id))
+                .or_else(|| others.first().map(|m| m.id.clone()));
+            let Some(target) = target else {
+                push_toast(toasts, ToastKind::Warning, tr!("manifest.toast.need_second"));
+                return;
+            };
+            let scu = match move_scu().trim() {
+                "" => None,
+                raw => match raw.parse::<u32>() {
+                    Ok(value) if value > 0 => Some(value),
+                    _ => {
+                        push_toast(toasts, ToastKind::Error, tr!("manifest.toast.move_scu_invalid"));
+                        return;
+                    }
+                },
+            };
+            let moved = state.with_mut(|st| move_item(&mut st.manifests, &active_id, &item.id, &target, scu));
+            if moved {
+                let amount = scu.unwrap_or(item.scu).min(item.scu);
+                let target_name = others
+                    .iter()
+                    .find(|m| m.id == target)
+                    .map(|m| m.name.clone())
+                    .unwrap_or_default();
+                if amount == item.scu {
+                    selected_item.set(None);
+                }
+                move_scu.set(String::new());
+                persist_user_state(&state);
+                push_toast(
+                    toasts,
+                    ToastKind::Success,
+                    tr!("manifest.toast.moved", scu = amount, commodity = item.commodity_name, target = target_name),
+                );
+            }
+        }
+    };
+
+    rsx! {
+        div { class: "{theme::panel_border()} space-y-3 px-4 py-4",
+            div { class: "flex flex-wrap items-center gap-2",
+                for (manifest, ev) in tabs {
+                    button {
+                        key: "{manifest.id}",
+                        class: if manifest.id == active_id { theme::btn_small_active() } else { theme::btn_small_inactive() },
+                        title: "{manifest.total_scu()} SCU · {format::auec(ev)}",
+                        onclick: {
+                            let id = manifest.id.clone();
+                            move |_| {
+                                state.with_mut(|st| st.active_manifest_id = id.clone());
+                                selected_item.set(None);
+                                persist_user_state(&state);
+                            }
+                        },
+                        "{manifest.label()} · {manifest.total_scu()} SCU"
+                    }
+                }
+                form { class: "ml-auto flex gap-2", onsubmit: on_create,
+                    input {
+                        class: "w-36 {theme::input_small()}",
+                        value: new_name(),
+                        oninput: move |evt| new_name.set(evt.value()),
+                        placeholder: tr!("manifest.new_placeholder"),
+                    }
+                    button { class: "{theme::btn_small_inactive()}", r#type: "submit", {format!("+ {}", tr!("common.add"))} }
+                }
+            }
+            div { class: "flex flex-wrap items-end gap-3",
+                div { class: "flex-1 min-w-[140px]",
+                    label { class: "{theme::label_class()}", {tr!("manifest.name")} }
+                    input {
+                        class: "mt-1 w-full {theme::input_small()}",
+                        value: active.name.clone(),
+                        onchange: move |evt| {
+                            let name = evt.value().trim().to_string();
+                            if !name.is_empty() {
+                                state.with_mut(|st| st.active_manifest_mut().name = name);
+                                persist_user_state(&state);
+                            }
+                        },
+                    }
+                }
+                div { class: "flex-1 min-w-[140px]",
+                    label { class: "{theme::label_class()}", {tr!("manifest.ship")} }
+                    input {
+                        class: "mt-1 w-full {theme::input_small()}",
+                        value: active.ship.clone().unwrap_or_default(),
+                        placeholder: tr!("manifest.ship_placeholder"),
+                        onchange: move |evt| {
+                            let ship = Some(evt.value().trim().to_string()).filter(|s| !s.is_empty());
+                            state.with_mut(|st| st.active_manifest_mut().ship = ship);
+                            persist_user_state(&state);
+                        },
+                    }
+                }
+                button {
+                    class: "{theme::btn_small_inactive()}",
+                    title: tr!("manifest.delete_hint"),
+                    onclick: on_delete,
+                    {tr!("common.delete")}
+                }
+            }
+            if let Some(item) = selected {
+                div { class: "flex flex-wrap items-end gap-3",
+                    p { class: "text-xs {theme::text_muted()}",
+                        {tr!("manifest.move_to", commodity = item.commodity_name, scu = item.scu)}
+                    }
+                    select {
+                        class: "{theme::input_small()}",
+                        disabled: others.is_empty(),
+                        onchange: move |evt| move_target.set(evt.value()),
+                        for manifest in others.iter() {
+                            option { value: manifest.id.clone(), selected: move_target() == manifest.id, "{manifest.label()}" }
+                        }
+                    }
+                    input {
+                        class: "w-24 {theme::input_small()}",
+                        inputmode: "numeric",
+                        value: move_scu(),
+                        oninput: move |evt| move_scu.set(evt.value()),
+                        placeholder: tr!("manifest.move_all"),
+                    }
+                    button { class: "{theme::btn_small_inactive()}", onclick: on_move, {tr!("manifest.move")} }
                 }
             }
         }
@@ -373,7 +686,7 @@ pub fn request_price_fetch(
 ) {
     let resource = CacheResource::Prices(commodity_id.to_string());
     let needs_fetch = state.with(|st| {
-        let stale = st.is_stale(&resource, CACHE_TTL);
+        let stale = st.is_stale(&resource, config::current().cache.price_ttl());
         let missing = st
             .price_points
             .get(commodity_id)
@@ -383,26 +696,10 @@ pub fn request_price_fetch(
     });
 
     if needs_fetch {
-        println!("Queueing price fetch for {commodity_id} (stale: {needs_fetch})");
+        tracing::debug!(%commodity_id, stale = needs_fetch, "queueing price fetch");
         price_request.set(Some(commodity_id.to_string()));
     } else {
-        println!("Skipping price fetch for {commodity_id}; cache still fresh.");
-    }
-}
-
-pub fn humanize_age(updated_at: std::time::SystemTime) -> String {
-    use std::time::SystemTime;
-
-    let now = SystemTime::now();
-    let age = now.duration_since(updated_at).unwrap_or_default().as_secs();
-    if age < 60 {
-        format!("{age}s ago")
-    } else if age < 3_600 {
-        format!("{}m ago", age / 60)
-    } else if age < 86_400 {
-        format!("{}h ago", age / 3_600)
-    } else {
-        format!("{}d ago", age / 86_400)
+        tracing::debug!(%commodity_id, "prices still fresh; skipping fetch");
     }
 }
 
@@ -443,13 +740,6 @@ fn best_sell_info(points: &[PricePoint], is_hot: bool, nqa_terminal_ids: &HashSe
         .max_by(|a, b| a.price.partial_cmp(&b.price).unwrap())
 }
 
-enum CargoAdjustResult {
-    Added(String, String),
-    Updated(String),
-    Removed,
-    Error,
-}
-
 fn adjust_cargo_item(
     mut state: Signal<AppState>,
     commodity: &Commodity,
@@ -457,73 +747,90 @@ fn adjust_cargo_item(
     mut selected_item: Signal<Option<String>>,
     toasts: Signal<Vec<ToastMessage>>,
 ) -> CargoAdjustResult {
-    let mut result = CargoAdjustResult::Error;
-    let mut toast: Option<(ToastKind, String)> = None;
-
-    state.with_mut(|st| {
-        if let Some(index) = st
-            .cargo_items
-            .iter()
-            .position(|item| item.commodity_id == commodity.id)
-        {
-            let current = st.cargo_items[index].scu as i32;
-            let new_total = current + delta;
-            if new_total <= 0 {
-                let removed = st.cargo_items.remove(index);
-                if selected_item().as_ref() == Some(&removed.id) {
-                    selected_item.set(None);
-                }
-                result = CargoAdjustResult::Removed;
-                toast = Some((
-                    ToastKind::Info,
-                    format!(
-                        "Removed {} after adjustment (new total would be {new_total}).",
-                        commodity.name
-                    ),
-                ));
-            } else {
-                st.cargo_items[index].scu = new_total as u32;
-                let id = st.cargo_items[index].id.clone();
-                result = CargoAdjustResult::Updated(id.clone());
-                toast = Some((
-                    ToastKind::Success,
-                    format!("Updated {} to {new_total} SCU.", commodity.name),
-                ));
-            }
-        } else if delta < 0 {
-            result = CargoAdjustResult::Error;
-            toast = Some((
-                ToastKind::Error,
-                format!(
-                    "Cannot subtract {delta} SCU because {name} is not in your cargo list.",
-                    name = commodity.name
-                ),
-            ));
-        } else {
-            let item_id = generate_id("cargo");
-            let new_item = CargoItem {
-                id: item_id.clone(),
-                commodity_id: commodity.id.clone(),
-                commodity_name: commodity.name.clone(),
-                scu: delta as u32,
-                is_hot: false,
-            };
-            st.cargo_items.push(new_item);
-            result = CargoAdjustResult::Added(item_id.clone(), commodity.id.clone());
-            toast = Some((
-                ToastKind::Success,
-                format!("Tracked {} with {delta} SCU.", commodity.name),
-            ));
-        }
+    let (result, toast) = state.with_mut(|st| {
+        apply_cargo_adjustment(st.cargo_items_mut(), commodity, delta, false)
     });
 
+    if let CargoAdjustResult::Removed(removed_id) = &result {
+        if selected_item().as_ref() == Some(removed_id) {
+            selected_item.set(None);
+        }
+    }
     if !matches!(result, CargoAdjustResult::Error) {
         persist_user_state(&state);
     }
 
-    if let Some((kind, message)) = toast {
-        push_toast(toasts, kind, message);
-    }
+    let (kind, message) = toast;
+    push_toast(toasts, kind, message);
 
     result
 }
+
+/// [`adjust_cargo`] plus the toast describing what happened.
+fn apply_cargo_adjustment(
+    cargo_items: &mut Vec<CargoItem>,
+    commodity: &Commodity,
+    delta: i32,
+    is_hot: bool,
+) -> (CargoAdjustResult, (ToastKind, String)) {
+    let before = cargo_items
+        .iter()
+        .find(|item| item.commodity_id == commodity.id && item.is_hot == is_hot)
+        .map_or(0, |item| item.scu as i64);
+    let total = before + delta as i64;
+    let result = adjust_cargo(cargo_items, commodity, delta, is_hot);
+    let toast = match &result {
+        CargoAdjustResult::Added(..) => (
+            ToastKind::Success,
+            tr!("cargo.toast.adjust_added", commodity = commodity.name, scu = delta),
+        ),
+        CargoAdjustResult::Updated(_) => (
+            ToastKind::Success,
+            tr!("cargo.toast.adjust_updated", commodity = commodity.name, total = total),
+        ),
+        CargoAdjustResult::Removed(_) => (
+            ToastKind::Info,
+            tr!("cargo.toast.adjust_removed", commodity = commodity.name, total = total),
+        ),
+        CargoAdjustResult::Error => (
+            ToastKind::Error,
+            tr!("cargo.toast.adjust_missing", scu = delta, commodity = commodity.name),
+        ),
+    };
+    (result, toast)
+}
+
+/// Merge reviewed import lines into the active manifest.
+fn import_cargo_lines(
+    mut state: Signal<AppState>,
+    price_request: Signal<Option<String>>,
+    toasts: Signal<Vec<ToastMessage>>,
+    selections: Vec<ImportSelection>,
+) {
+    let mut total_scu = 0_u32;
+    let mut first_added: Option<String> = None;
+    state.with_mut(|st| {
+        let cargo_items = st.cargo_items_mut();
+        for selection in &selections {
+            let delta = selection.line.scu.min(i32::MAX as u32) as i32;
+            let hot = selection.line.is_hot;
+            match adjust_cargo(cargo_items, &selection.commodity, delta, hot) {
+                CargoAdjustResult::Added(_, commodity_id) => {
+                    first_added.get_or_insert(commodity_id);
+                    total_scu += selection.line.scu;
+                }
+                CargoAdjustResult::Updated(_) => total_scu += selection.line.scu,
+                _ => {}
+            }
+        }
+    });
+    persist_user_state(&state);
+    if let Some(commodity_id) = first_added {
+        request_price_fetch(state, price_request, &commodity_id);
+    }
+    push_toast(
+        toasts,
+        ToastKind::Success,
+        tr!("cargo.toast.imported", lines = selections.len(), scu = total_scu),
+    );
+}
@@ -0,0 +1,413 @@
+//! Crew — host or join a peer-to-peer session sharing the manifest, sell
+//! plan and crew split.
+
+use std::{
+    net::{Ipv4Addr, SocketAddr},
+    rc::Rc,
+    sync::Arc,
+    time::Duration,
+};
+
+use dioxus::{core::spawn_forever, prelude::*};
+use tokio::sync::Notify;
+
+use crate::domain::{
+    calculate_best_value_plan, crew_session::ROLE_HOST, AppState, CargoManifest, CrewMember,
+    SellPlan, SessionSnapshot,
+};
+use crate::infra::crew_session::{
+    lan_ip, resolve_host, SessionHost, SessionMember, SessionView, DEFAULT_PORT,
+};
+use crate::infra::webhook::crew_payout_report;
+use crate::ui::components::share_panel::SharePanel;
+use crate::ui::{i18n::tr, theme};
+use crate::util::format;
+
+/// The app's part in a crew session, shared through context.
+#[derive(Clone, Default)]
+pub enum CrewSession {
+    #[default]
+    Off,
+    /// Connecting to a host.
+    Joining,
+    /// Joining failed; the page shows why.
+    Failed(String),
+    Hosting(Rc<SessionHost>),
+    Joined {
+        host: SocketAddr,
+        view: SessionView,
+        leave: Arc<Notify>,
+    },
+}
+
+/// Provides the crew session to every page. While hosting, the active
+/// manifest and its best-value plan are published on every state change.
+pub fn use_crew_session_provider(state: Signal<AppState>) {
+    let session = use_context_provider(|| Signal::new(CrewSession::Off));
+    use_effect(move || {
+        let CrewSession::Hosting(host) = &*session.read() else {
+            return;
+        };
+        let (manifest, plan) = state.with(host_share);
+        host.publish(manifest, plan);
+    });
+}
+
+/// What a host shares: the active manifest and, once priced, its plan.
+fn host_share(st: &AppState) -> (CargoManifest, Option<SellPlan>) {
+    let manifest = st.active_manifest().cloned().unwrap_or_else(CargoManifest::default_manifest);
+    let plan = calculate_best_value_plan(&manifest.items, &st.price_points, &st.nqa_terminal_ids);
+    (manifest, Some(plan).filter(|plan| !plan.stops.is_empty()))
+}
+
+/// Mirrors the host's manifest into the crew manifest.
+fn apply_view(st: &mut AppState, view: &SessionView) {
+    let shared = &view.snapshot.manifest;
+    st.mirror_crew_manifest(shared, tr!("crew.manifest_name", name = shared.name));
+}
+
+#[component]
+pub fn CrewPage() -> Element {
+    let mut state = use_context::<Signal<AppState>>();
+    let mut session = use_context::<Signal<CrewSession>>();
+
+    let mut name = use_signal(String::new);
+    let mut port = use_signal(|| DEFAULT_PORT.to_string());
+    let mut host_input = use_signal(String::new);
+    let mut code_input = use_signal(String::new);
+    let mut error = use_signal(|| None::<String>);
+
+    // The host's crew changes from the network; redraw while hosting.
+    let mut tick = use_signal(|| 0u64);
+    use_future(move || async move {
+        loop {
+            tokio::time::sleep(Duration::from_secs(1)).await;
+            if matches!(*session.peek(), CrewSession::Hosting(_)) {
+                tick += 1;
+            }
+        }
+    });
+    let _ = tick();
+
+    let start_hosting = move |_| {
+        error.set(None);
+        let Ok(port) = port().trim().parse::<u16>() else {
+            error.set(Some(tr!("crew.error.port")));
+            return;
+        };
+        let host_name = Some(name().trim().to_string())
+            .filter(|n| !n.is_empty())
+            .unwrap_or_else(|| tr!("crew.role.host"));
+        let snapshot = state.with(|st| {
+            let (manifest, plan) = host_share(st);
+            SessionSnapshot::new(manifest, plan, &host_name)
+        });
+        // Inside a task so the endpoint finds the Tokio runtime.
+        spawn(async move {
+            match SessionHost::start((Ipv4Addr::UNSPECIFIED, port).into(), snapshot) {
+                Ok(host) => session.set(CrewSession::Hosting(Rc::new(host))),
+                Err(err) => error.set(Some(err.to_string())),
+            }
+        });
+    };
+
+    let join = move |_| {
+        error.set(None);
+        session.set(CrewSession::Joining);
+        let (address, code, member_name) = (host_input(), code_input(), name());
+        // Outlives the page: members stay in the session across navigation.
+        spawn_forever(async move {
+            let member = match resolve_host(&address).await {
+                Ok(addr) => SessionMember::join(addr, &code, member_name.trim()).await.map(|m| (addr, m)),
+                Err(err) => Err(err),
+            };
+            let (host, mut member) = match member {
+                Ok(joined) => joined,
+                Err(err) => {
+                    session.set(CrewSession::Failed(err.to_string()));
+                    return;
+                }
+            };
+            let leave = Arc::new(Notify::new());
+            let view = member.view();
+            state.with_mut(|st| apply_view(st, &view));
+            session.set(CrewSession::Joined { host, view, leave: leave.clone() });
+            loop {
+                let view = tokio::select! {
+                    view = member.changed() => view,
+                    _ = leave.notified() => break,
+                };
+                let Some(view) = view else {
+                    if let CrewSession::Joined { view, .. } = &mut *session.write() {
+                        view.ended = true;
+                    }
+                    break;
+                };
+                state.with_mut(|st| apply_view(st, &view));
+                if let CrewSession::Joined { view: current, .. } = &mut *session.write() {
+                    *current = view;
+                }
+            }
+        });
+    };
+
+    let current = session();
+    let joining = matches!(current, CrewSession::Joining);
+    let message = match &current {
+        CrewSession::Failed(message) => Some(message.clone()),
+        _ => error(),
+    };
+    rsx! {
+        div { class: "space-y-6",
+            section {
+                h2 { class: "text-xl font-semibold {theme::text_secondary()}", {tr!("crew.title")} }
+                p { class: "text-sm {theme::text_muted()}", {tr!("crew.subtitle")} }
+            }
+
+            if let Some(message) = message {
+                div { class: "rounded border border-rose-500/40 bg-rose-500/10 px-4 py-3 text-sm text-rose-200", "{message}" }
+            }
+
+            match current {
+                CrewSession::Off | CrewSession::Joining | CrewSession::Failed(_) => rsx! {
+                    div { class: "grid gap-4 md:grid-cols-2",
+                        div { class: "{theme::panel_border()} space-y-3 px-4 py-4",
+                            h3 { class: "font-semibold {theme::text_secondary()}", {tr!("crew.host.title")} }
+                            p { class: "text-sm {theme::text_muted()}", {tr!("crew.host.hint")} }
+                            div {
+                                label { class: "{theme::label_class()}", {tr!("crew.name")} }
+                                input {
+                                    class: "mt-1 w-full {theme::input_small()}",
+                                    value: name(),
+                                    oninput: move |evt| name.set(evt.value()),
+                                }
+                            }
+                            div {
+                                label { class: "{theme::label_class()}", {tr!("crew.port")} }
+                                input {
+                                    class: "mt-1 w-32 {theme::input_small()}",
+                                    value: port(),
+                                    oninput: move |evt| port.set(evt.value()),
+                                }
+                            }
+                            button { class: "{theme::btn_primary()}", onclick: start_hosting, {tr!("crew.host.start")} }
+                        }
+                        div { class: "{theme::panel_border()} space-y-3 px-4 py-4",
+                            h3 { class: "font-semibold {theme::text_secondary()}", {tr!("crew.join.title")} }
+                            p { class: "text-sm {theme::text_muted()}", {tr!("crew.join.hint")} }
+                            div {
+                                label { class: "{theme::label_class()}", {tr!("crew.name")} }
+                                input {
+                                    class: "mt-1 w-full {theme::input_small()}",
+                                    value: name(),
+                                    oninput: move |evt| name.set(evt.value()),
+                                }
+                            }
+                            div {
+                                label { class: "{theme::label_class()}", {tr!("crew.join.address")} }
+                                input {
+                                    class: "mt-1 w-full {theme::input_small()}",
+                                    value: host_input(),
+                                    placeholder: "192.168.1.20:{DEFAULT_PORT}",
+                                    oninput: move |evt| host_input.set(evt.value()),
+                                }
+                            }
+                            div {
+                                label { class: "{theme::label_class()}", {tr!("crew.join.code")} }
+                                input {
+                                    class: "mt-1 w-40 font-mono uppercase {theme::input_small()}",
+                                    value: code_input(),
+                                    placeholder: "K7QM-2XWD",
+                                    oninput: move |evt| code_input.set(evt.value()),
+                                }
+                            }
+                            button {
+                                class: "{theme::btn_primary()}",
+                                disabled: joining || host_input().trim().is_empty() || code_input().trim().is_empty(),
+                                onclick: join,
+                                if joining { {tr!("crew.join.connecting")} } else { {tr!("crew.join.start")} }
+                            }
+                        }
+                    }
+                },
+                CrewSession::Hosting(host) => {
+                    let snapshot = host.snapshot();
+                    let port = host.local_addr().map(|a| a.port()).unwrap_or(DEFAULT_PORT);
+                    let address = lan_ip()
+                        .map(|ip| SocketAddr::new(ip, port).to_string())
+                        .unwrap_or_else(|| format!("<IP>:{port}"));
+                    let peers = host.peers().len();
+                    let weights = host.clone();
+                    rsx! {
+                        div { class: "{theme::panel_border()} flex flex-wrap items-center justify-between gap-4 px-4 py-4",
+                            div {
+                                p { class: "{theme::label_class()}", {tr!("crew.join.code")} }
+                                p { class: "font-mono text-3xl font-semibold tracking-widest {theme::text_primary()}", "{host.code()}" }
+                            }
+                            div {
+                                p { class: "{theme::label_class()}", {tr!("crew.host.address")} }
+                                p { class: "font-mono {theme::text_secondary()}", "{address}" }
+                                p { class: "text-xs {theme::text_muted()}", {tr!("crew.host.members", count = peers)} }
+                            }
+                            button {
+                                class: "{theme::btn_small_inactive()}",
+                                onclick: move |_| {
+                                    if let CrewSession::Hosting(host) = &*session.peek() {
+                                        host.close();
+                                    }
+                                    session.set(CrewSession::Off);
+                                },
+                                {tr!("crew.host.stop")}
+                            }
+                        }
+                        SplitTable {
+                            snapshot: snapshot.clone(),
+                            on_weight: move |(id, weight): (String, f32)| {
+                                weights.set_weight(&id, weight);
+                            },
+                        }
+                        PlanSummary { plan: snapshot.plan.clone() }
+                        SharePanel { report: crew_payout_report(&snapshot) }
+                    }
+                }
+                CrewSession::Joined { host, view, leave } => rsx! {
+                    div { class: "{theme::panel_border()} flex flex-wrap items-center justify-between gap-4 px-4 py-4",
+                        div {
+                            p { class: "{theme::label_class()}", {tr!("crew.joined.host")} }
+                            p { class: "font-mono {theme::text_secondary()}", "{host}" }
+                            if view.ended {
+                                p { class: "text-xs text-amber-300", {tr!("crew.joined.ended")} }
+                            } else {
+                                p { class: "text-xs {theme::text_muted()}", {tr!("crew.joined.synced", version = view.version)} }
+                            }
+                        }
+                        button {
+                            class: "{theme::btn_small_inactive()}",
+                            onclick: move |_| {
+                                leave.notify_one();
+                                session.set(CrewSession::Off);
+                            },
+                            if view.ended { {tr!("crew.joined.close")} } else { {tr!("crew.joined.leave")} }
+                        }
+                    }
+                    ManifestTable { manifest: view.snapshot.manifest.clone() }
+                    SplitTable { snapshot: view.snapshot.clone() }
+                    PlanSummary { plan: view.snapshot.plan.clone() }
+                    SharePanel { report: crew_payout_report(&view.snapshot) }
+                },
+            }
+        }
+    }
+}
+
+#[component]
+fn ManifestTable(manifest: CargoManifest) -> Element {
+    rsx! {
+        section { class: "space-y-2",
+            h3 { class: "font-semibold {theme::text_secondary()}",
+                {tr!("crew.manifest", name = manifest.label(), scu = manifest.total_scu())}
+            }
+            div { class: "{theme::table_container()}",
+                table { class: "min-w-full {theme::table_divider()} text-sm",
+                    tbody { class: "{theme::table_divider()}",
+                        for item in manifest.items {
+                            tr { key: "{item.id}", class: "cvs-row",
+                                td { class: "px-4 py-2 {theme::text_secondary()}",
+                                    "{item.commodity_name}"
+                                    if item.is_hot { span { class: "ml-1", "🔥" } }
+                                }
+                                td { class: "px-4 py-2 text-right {theme::text_muted()}", "{item.scu} SCU" }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Crew and their cut of the plan; weights are editable when `on_weight` is set.
+#[component]
+fn SplitTable(snapshot: SessionSnapshot, on_weight: Option<EventHandler<(String, f32)>>) -> Element {
+    let rows: Vec<(CrewMember, f64)> = snapshot
+        .split()
+        .into_iter()
+        .map(|(member, cut)| (member.clone(), cut))
+        .collect();
+    rsx! {
+        section { class: "space-y-2",
+            h3 { class: "font-semibold {theme::text_secondary()}", {tr!("crew.split")} }
+            div { class: "{theme::table_container()}",
+                table { class: "min-w-full {theme::table_divider()} text-sm",
+                    thead { class: "{theme::table_header()} text-left tracking-wide",
+                        tr {
+                            th { class: "px-4 py-3 font-medium", {tr!("crew.col.name")} }
+                            th { class: "px-4 py-3 font-medium", {tr!("crew.col.role")} }
+                            th { class: "px-4 py-3 font-medium text-right", {tr!("crew.col.weight")} }
+                            th { class: "px-4 py-3 font-medium text-right", {tr!("crew.col.cut")} }
+                        }
+                    }
+                    tbody { class: "{theme::table_divider()}",
+                        for (member, cut) in rows {
+                            tr { key: "{member.id}", class: "cvs-row",
+                                td { class: "px-4 py-3 font-medium {theme::text_secondary()}", "{member.name}" }
+                                td { class: "px-4 py-3 {theme::text_muted()}",
+                                    if member.role == ROLE_HOST { {tr!("crew.role.host")} } else { {tr!("crew.role.member")} }
+                                }
+                                td { class: "px-4 py-3 text-right",
+                                    if let Some(on_weight) = on_weight {
+                                        input {
+                                            class: "w-20 text-right {theme::input_small()}",
+                                            r#type: "number",
+                                            min: "0",
+                                            step: "0.5",
+                                            value: "{member.weight}",
+                                            onchange: {
+                                                let id = member.id.clone();
+                                                move |evt: Event<FormData>| {
+                                                    if let Ok(weight) = evt.value().parse::<f32>() {
+                                                        on_weight.call((id.clone(), weight));
+                                                    }
+                                                }
+                                            },
+                                        }
+                                    } else {
+                                        span { class: "{theme::text_muted()}", "{member.weight}" }
+                                    }
+                                }
+                                td { class: "px-4 py-3 text-right font-semibold {theme::text_primary()}", {format::auec(cut)} }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn PlanSummary(plan: Option<SellPlan>) -> Element {
+    let Some(plan) = plan else {
+        return rsx! {
+            p { class: "text-sm {theme::text_muted()}", {tr!("crew.plan.none")} }
+        };
+    };
+    rsx! {
+        section { class: "space-y-2",
+            h3 { class: "font-semibold {theme::text_secondary()}",
+                {tr!("crew.plan", value = format::auec(plan.total_value))}
+            }
+            ol { class: "space-y-1 text-sm",
+                for (index, stop) in plan.stops.iter().enumerate() {
+                    li { key: "{index}", class: "{theme::text_muted()}",
+                        span { class: "font-medium {theme::text_secondary()}", "{index + 1}. {stop.terminal_name}" }
+                        if let Some(system) = &stop.system {
+                            span { class: "ml-1", "· {system}" }
+                        }
+                        span { class: "ml-2", "{stop.items.len()} × · {format::auec(stop.stop_value)}" }
+                    }
+                }
+            }
+        }
+    }
+}
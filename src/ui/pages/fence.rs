@@ -0,0 +1,304 @@
+//! Fence — what hot cargo fetches at NQA terminals against the legal market.
+
+use std::collections::HashMap;
+
+use dioxus::prelude::*;
+
+use crate::app::{persist_history, persist_user_state};
+use crate::domain::{
+    evaluate_item, extract_locations, fence_outlets, fence_quotes, AppState, FenceOutlet,
+    FenceQuote, TradeRecord, TradeSide,
+};
+use crate::infra::uex::UexClient;
+use crate::ui::components::kpi_card::KpiCard;
+use crate::ui::{i18n::tr, pages::refinery::load_prices, theme};
+use crate::util::format;
+
+#[component]
+pub fn FencePage() -> Element {
+    let mut state = use_context::<Signal<AppState>>();
+    let mut position = use_signal(|| None::<i32>);
+    let mut followed_detection = use_signal(|| None::<std::time::SystemTime>);
+
+    // Follow positions detected in Game.log; a manual pick holds until the next one.
+    use_effect(move || {
+        let Some(detected) = state.read().detected_position.clone() else {
+            return;
+        };
+        if *followed_detection.peek() == Some(detected.detected_at) {
+            return;
+        }
+        followed_detection.set(Some(detected.detected_at));
+        if let Some(location) = detected.location {
+            position.set(Some(location.terminal_id));
+        }
+    });
+
+    let terminals_resource = use_resource(move || async move {
+        let client = UexClient::new().ok()?;
+        let cache = client.get_terminals().await.ok()?;
+        Some(cache.terminals)
+    });
+    let terminals = terminals_resource
+        .read()
+        .as_ref()
+        .and_then(|t| t.as_ref())
+        .cloned()
+        .unwrap_or_default();
+    let locations = extract_locations(&terminals);
+
+    let hot: Vec<(String, String)> = state.with(|st| {
+        let mut hot: Vec<(String, String)> = st
+            .cargo_items()
+            .iter()
+            .filter(|item| item.is_hot)
+            .map(|item| (item.commodity_id.clone(), item.commodity_name.clone()))
+            .collect();
+        hot.sort();
+        hot.dedup();
+        hot
+    });
+    let prices_loading = use_resource(use_reactive!(|hot| async move {
+        load_prices(state, hot).await;
+    }))
+    .read()
+    .is_none();
+
+    let quotes = state.with(|st| fence_quotes(st.cargo_items(), &st.price_points, &st.nqa_terminal_ids));
+    let mut outlet_ids: Vec<i32> = quotes
+        .iter()
+        .flat_map(|q| q.offers.iter().map(|o| o.terminal_id))
+        .collect();
+    outlet_ids.sort();
+    outlet_ids.dedup();
+
+    let origin = position();
+    let distances_resource = use_resource(use_reactive!(|origin, outlet_ids| async move {
+        let Some(origin) = origin else {
+            return HashMap::new();
+        };
+        if outlet_ids.is_empty() {
+            return HashMap::new();
+        }
+        let Ok(client) = UexClient::new() else {
+            return HashMap::new();
+        };
+        client.get_terminal_distances(origin, &outlet_ids).await.unwrap_or_default()
+    }));
+    let distances_loading = origin.is_some() && distances_resource.read().is_none();
+    let distances = distances_resource.read().clone().unwrap_or_default();
+    let outlets = fence_outlets(&quotes, &distances);
+
+    let legal_total: f64 = quotes.iter().filter_map(FenceQuote::legal_value).sum();
+    let fence_total: f64 = quotes.iter().filter_map(FenceQuote::fence_value).sum();
+    let discount_total: f64 = quotes.iter().filter_map(FenceQuote::discount).sum();
+    let unfenceable = quotes.iter().filter(|q| q.unfenceable()).count();
+
+    let detection_note = state.with(|st| {
+        st.detected_position.as_ref().map(|detected| match &detected.location {
+            Some(location) => format!("📡 {}", tr!("planner.detected", location = location.name)),
+            None => format!("📡 {}", tr!("planner.detected_unknown", raw = detected.raw)),
+        })
+    });
+
+    rsx! {
+        div { class: "space-y-6",
+            section {
+                h2 { class: "text-xl font-semibold {theme::text_secondary()}", {tr!("fence.title")} }
+                p { class: "text-sm {theme::text_muted()}", {tr!("fence.subtitle")} }
+            }
+
+            if quotes.is_empty() {
+                div { class: "{theme::panel_border()} px-6 py-12 text-center",
+                    p { class: "{theme::text_muted()}", {tr!("fence.empty")} }
+                }
+            } else {
+                div { class: "grid gap-4 sm:grid-cols-4",
+                    KpiCard { title: tr!("fence.kpi.legal"), value: format::auec(legal_total), description: None }
+                    KpiCard { title: tr!("fence.kpi.fence"), value: format::auec(fence_total), description: None }
+                    KpiCard { title: tr!("fence.kpi.discount"), value: format::auec(discount_total), description: None }
+                    KpiCard { title: tr!("fence.kpi.unfenceable"), value: unfenceable.to_string(), description: None }
+                }
+
+                if prices_loading {
+                    p { class: "text-sm {theme::text_primary()}", {tr!("fence.loading")} }
+                }
+
+                div { class: "{theme::table_container()}",
+                    table { class: "min-w-full {theme::table_divider()} text-sm",
+                        thead { class: "{theme::table_header()} text-left tracking-wide",
+                            tr {
+                                th { class: "px-4 py-3 font-medium", {tr!("fence.col.commodity")} }
+                                th { class: "px-4 py-3 font-medium text-right", "SCU" }
+                                th { class: "px-4 py-3 font-medium", {tr!("fence.col.legal")} }
+                                th { class: "px-4 py-3 font-medium", {tr!("fence.col.fence")} }
+                                th { class: "px-4 py-3 font-medium text-right", {tr!("fence.col.value")} }
+                                th { class: "px-4 py-3 font-medium text-right", {tr!("fence.col.discount")} }
+                            }
+                        }
+                        tbody { class: "{theme::table_divider()}",
+                            for quote in quotes.iter().cloned() {
+                                QuoteRow { key: "{quote.item_id}", quote }
+                            }
+                        }
+                    }
+                }
+
+                section { class: "space-y-3",
+                    div { class: "flex flex-wrap items-end justify-between gap-4",
+                        div {
+                            h3 { class: "text-lg font-semibold {theme::text_secondary()}", {tr!("fence.outlets")} }
+                            if let Some(note) = detection_note {
+                                p { class: "text-xs {theme::text_muted()}", "{note}" }
+                            }
+                        }
+                        div { class: "w-64",
+                            label { class: "{theme::label_class()}", {tr!("fence.position")} }
+                            select {
+                                class: "mt-1 w-full {theme::input_small()}",
+                                onchange: move |evt| position.set(evt.value().parse().ok()),
+                                option { value: "", selected: origin.is_none(), {tr!("fence.position.none")} }
+                                for location in locations {
+                                    option {
+                                        value: "{location.terminal_id}",
+                                        selected: origin == Some(location.terminal_id),
+                                        "{location.name}"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    if distances_loading {
+                        p { class: "text-sm {theme::text_primary()}", {tr!("fence.distances_loading")} }
+                    }
+                    if outlets.is_empty() {
+                        div { class: "{theme::panel_border()} px-6 py-8 text-center",
+                            p { class: "{theme::text_muted()}", {tr!("fence.outlets.empty")} }
+                        }
+                    } else {
+                        div { class: "{theme::table_container()}",
+                            table { class: "min-w-full {theme::table_divider()} text-sm",
+                                thead { class: "{theme::table_header()} text-left tracking-wide",
+                                    tr {
+                                        th { class: "px-4 py-3 font-medium", {tr!("fence.col.outlet")} }
+                                        th { class: "px-4 py-3 font-medium text-right", {tr!("fence.col.distance")} }
+                                        th { class: "px-4 py-3 font-medium", {tr!("fence.col.buys")} }
+                                        th { class: "px-4 py-3 font-medium text-right", {tr!("fence.col.value")} }
+                                        th { class: "px-4 py-3" }
+                                    }
+                                }
+                                tbody { class: "{theme::table_divider()}",
+                                    for outlet in outlets {
+                                        tr { key: "{outlet.terminal_id}", class: "cvs-row",
+                                            td { class: "px-4 py-3",
+                                                span { class: "font-medium {theme::text_secondary()}", "{outlet.terminal_name}" }
+                                                if let Some(system) = &outlet.system {
+                                                    span { class: "ml-2 text-xs {theme::text_muted()}", "{system}" }
+                                                }
+                                            }
+                                            td { class: "px-4 py-3 text-right {theme::text_muted()}",
+                                                {outlet.distance_gm.map(|d| format!("{} Gm", format::number(d, 1))).unwrap_or_else(|| "—".to_string())}
+                                            }
+                                            td { class: "px-4 py-3 {theme::text_muted()}",
+                                                for item in outlet.items.iter() {
+                                                    span {
+                                                        class: if item.better_elsewhere { "mr-3 opacity-60" } else { "mr-3" },
+                                                        title: if item.better_elsewhere { tr!("fence.better_elsewhere") } else { String::new() },
+                                                        "{item.commodity_name} · {format::auec(item.price)}"
+                                                    }
+                                                }
+                                            }
+                                            td { class: "px-4 py-3 text-right font-semibold {theme::text_primary()}",
+                                                {format::auec(outlet.total_value())}
+                                            }
+                                            td { class: "px-4 py-3 text-right",
+                                                button {
+                                                    class: "{theme::btn_small_active()}",
+                                                    title: tr!("fence.sold_hint"),
+                                                    onclick: {
+                                                        let outlet = outlet.clone();
+                                                        move |_| {
+                                                            state.with_mut(|st| record_fence_sale(st, &outlet));
+                                                            persist_user_state(&state);
+                                                            persist_history(&state);
+                                                        }
+                                                    },
+                                                    {tr!("fence.sold")}
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn QuoteRow(quote: FenceQuote) -> Element {
+    let offer_cell = |offer: Option<&crate::domain::FenceOffer>| match offer {
+        Some(offer) => rsx! {
+            span { class: "{theme::text_secondary()}", {format::auec(offer.price)} }
+            span { class: "ml-2 text-xs {theme::text_muted()}", "{offer.terminal_name}" }
+        },
+        None => rsx! { span { class: "{theme::text_muted()}", "—" } },
+    };
+    rsx! {
+        tr { class: "cvs-row",
+            td { class: "px-4 py-3 font-medium {theme::text_secondary()}",
+                "{quote.commodity_name}"
+                if quote.unfenceable() {
+                    span {
+                        class: "ml-2 rounded bg-rose-500/20 px-2 py-0.5 text-xs text-rose-300",
+                        title: tr!("fence.no_buyer_hint"),
+                        {tr!("fence.no_buyer")}
+                    }
+                }
+            }
+            td { class: "px-4 py-3 text-right {theme::text_muted()}", "{quote.scu}" }
+            td { class: "px-4 py-3", {offer_cell(quote.legal.as_ref())} }
+            td { class: "px-4 py-3", {offer_cell(quote.best_offer())} }
+            td { class: "px-4 py-3 text-right font-semibold {theme::text_primary()}",
+                {quote.fence_value().map(format::auec).unwrap_or_else(|| "—".to_string())}
+            }
+            td { class: "px-4 py-3 text-right text-rose-300",
+                match (quote.discount(), quote.discount_percent()) {
+                    (Some(lost), Some(pct)) => format!("−{} ({}%)", format::auec(lost), format::number(pct, 0)),
+                    (Some(lost), None) => format!("−{}", format::auec(lost)),
+                    _ => "—".to_string(),
+                }
+            }
+        }
+    }
+}
+
+/// Logs the hot items `outlet` buys as sold there and drops them from the manifest.
+fn record_fence_sale(st: &mut AppState, outlet: &FenceOutlet) {
+    let sold: Vec<String> = outlet.items.iter().map(|item| item.item_id.clone()).collect();
+    for item in &outlet.items {
+        let Some(cargo) = st.cargo_items().iter().find(|c| c.id == item.item_id).cloned() else {
+            continue;
+        };
+        let predicted = evaluate_item(
+            &cargo,
+            st.price_points.get(&cargo.commodity_id).map(|p| p.as_slice()),
+        )
+        .ev;
+        let record = TradeRecord::new(
+            &st.session_id,
+            TradeSide::Sell,
+            cargo.commodity_id.clone(),
+            cargo.commodity_name.clone(),
+            cargo.scu,
+            item.price,
+        )
+        .at_terminal(Some(outlet.terminal_id), outlet.terminal_name.clone(), outlet.system.clone())
+        .with_prediction(predicted);
+        st.history.record(record);
+    }
+    st.cargo_items_mut().retain(|item| !sold.contains(&item.id));
+}
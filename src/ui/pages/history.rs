@@ -0,0 +1,413 @@
+//! Trade history — realized profit per session and per run.
+
+use dioxus::prelude::*;
+
+use crate::app::persist_history;
+use crate::domain::{
+    profit_timeline, summarize, AppState, HistorySummary, TradeRecord, TradeSide,
+};
+use crate::ui::components::kpi_card::KpiCard;
+use crate::ui::{i18n::tr, theme};
+use crate::util::format;
+
+/// How many sessions the per-session bar chart shows.
+const SESSION_CHART_LIMIT: usize = 12;
+/// How many ledger rows are listed before "show all".
+const RECENT_TRADES_LIMIT: usize = 25;
+
+#[component]
+pub fn HistoryPage() -> Element {
+    let mut state = use_context::<Signal<AppState>>();
+    let history = state.with(|st| st.history.clone());
+    let current_session = state.with(|st| st.session_id.clone());
+
+    let mut selected_session = use_signal(|| None::<String>);
+    let mut show_all_trades = use_signal(|| false);
+
+    let all_records: Vec<&TradeRecord> = history.records.iter().collect();
+    let overall = summarize(&all_records);
+    let session_summaries = history.session_summaries();
+
+    // Default to the current session if it has trades, else the most recent one.
+    let active_session = selected_session()
+        .filter(|id| session_summaries.iter().any(|(sid, _)| sid == id))
+        .or_else(|| {
+            session_summaries
+                .iter()
+                .find(|(id, _)| *id == current_session)
+                .or(session_summaries.first())
+                .map(|(id, _)| id.clone())
+        });
+
+    let active_records: Vec<&TradeRecord> = active_session
+        .as_deref()
+        .map(|id| history.session_records(id))
+        .unwrap_or_default();
+    let active_summary = summarize(&active_records);
+    let run_summaries = active_session
+        .as_deref()
+        .map(|id| history.run_summaries(id))
+        .unwrap_or_default();
+    let timeline = profit_timeline(&active_records);
+
+    let session_bars: Vec<(String, f64)> = session_summaries
+        .iter()
+        .take(SESSION_CHART_LIMIT)
+        .rev()
+        .map(|(_, summary)| (format::date(summary.started_at), summary.profit))
+        .collect();
+
+    let mut ledger: Vec<TradeRecord> = active_records.iter().map(|r| (*r).clone()).collect();
+    ledger.reverse();
+    let hidden_trades = ledger.len().saturating_sub(RECENT_TRADES_LIMIT);
+    if !show_all_trades() {
+        ledger.truncate(RECENT_TRADES_LIMIT);
+    }
+
+    let prediction_display = overall
+        .prediction_error_pct
+        .map(|err| format!("±{}%", format::number(err, 1)))
+        .unwrap_or_else(|| "—".to_string());
+    let prediction_bias = overall.prediction_bias_pct.map(|bias| {
+        if bias >= 0.0 {
+            tr!("history.bias_above", percent = format::number(bias, 1))
+        } else {
+            tr!("history.bias_below", percent = format::number(bias.abs(), 1))
+        }
+    });
+
+    rsx! {
+        div { class: "space-y-6",
+            section {
+                h2 { class: "text-xl font-semibold {theme::text_secondary()}", {tr!("history.title")} }
+                p { class: "text-sm {theme::text_muted()}",
+                    {tr!("history.subtitle")}
+                }
+            }
+
+            section { class: "grid gap-4 sm:grid-cols-4",
+                KpiCard {
+                    title: tr!("history.kpi.all_time"),
+                    value: format::auec(overall.profit),
+                    description: Some(tr!("history.kpi.all_time_hint", trades = overall.trade_count, sessions = session_summaries.len())),
+                }
+                KpiCard {
+                    title: tr!("history.kpi.session"),
+                    value: format::auec(active_summary.profit),
+                    description: Some(tr!("history.kpi.session_hint", runs = run_summaries.len())),
+                }
+                KpiCard {
+                    title: tr!("history.kpi.per_hour"),
+                    value: active_summary.profit_per_hour.map(format::auec).unwrap_or_else(|| "—".to_string()),
+                    description: Some(tr!("history.kpi.per_hour_hint", duration = format::duration(active_summary.duration_secs()))),
+                }
+                KpiCard {
+                    title: tr!("history.kpi.prediction_error"),
+                    value: prediction_display,
+                    description: prediction_bias,
+                }
+            }
+
+            if history.records.is_empty() {
+                div {
+                    class: "{theme::panel_border()} px-6 py-12 text-center",
+                    p { class: "{theme::text_muted()}", {tr!("history.empty")} }
+                }
+            } else {
+                section { class: "grid gap-6 lg:grid-cols-[1fr,2fr]",
+                    // Session list
+                    div { class: "{theme::table_container()}",
+                        div { class: "{theme::table_header()} px-4 py-3", {tr!("history.sessions")} }
+                        div { class: "{theme::table_divider()} max-h-[28rem] overflow-y-auto",
+                            for (session_id, summary) in session_summaries.iter() {
+                                SessionRow {
+                                    key: "{session_id}",
+                                    summary: summary.clone(),
+                                    is_current: *session_id == current_session,
+                                    active: active_session.as_deref() == Some(session_id.as_str()),
+                                    onclick: {
+                                        let session_id = session_id.clone();
+                                        move |_| selected_session.set(Some(session_id.clone()))
+                                    },
+                                }
+                            }
+                        }
+                    }
+
+                    // Charts + breakdowns for the selected session
+                    div { class: "space-y-4",
+                        div { class: "{theme::panel_border()} p-4",
+                            p { class: "{theme::label_class()} mb-2", {tr!("history.chart.cumulative")} }
+                            ProfitLineChart { points: timeline }
+                        }
+                        div { class: "{theme::panel_border()} p-4",
+                            p { class: "{theme::label_class()} mb-2", {tr!("history.chart.per_session")} }
+                            ProfitBarChart { bars: session_bars }
+                        }
+                        div { class: "grid gap-4 sm:grid-cols-2",
+                            RankingList { title: tr!("history.best_commodities"), entries: active_summary.best_commodities.clone() }
+                            RankingList { title: tr!("history.best_terminals"), entries: active_summary.best_terminals.clone() }
+                        }
+                    }
+                }
+
+                // Runs
+                section { class: "{theme::table_container()}",
+                    table { class: "w-full text-sm",
+                        thead { class: "{theme::table_header()}",
+                            tr {
+                                th { class: "px-4 py-2 text-left", {tr!("history.col.run")} }
+                                th { class: "px-4 py-2 text-left", {tr!("history.col.started")} }
+                                th { class: "px-4 py-2 text-right", {tr!("history.col.trades")} }
+                                th { class: "px-4 py-2 text-right", {tr!("history.col.spent")} }
+                                th { class: "px-4 py-2 text-right", {tr!("history.col.revenue")} }
+                                th { class: "px-4 py-2 text-right", {tr!("routes.col.profit")} }
+                                th { class: "px-4 py-2 text-right", {tr!("history.col.profit_per_hour")} }
+                            }
+                        }
+                        tbody { class: "{theme::table_divider()}",
+                            for (run_id, run) in run_summaries.iter() {
+                                tr { key: "{run_id}",
+                                    td { class: "px-4 py-2 {theme::text_secondary()}", "#{run_id}" }
+                                    td { class: "px-4 py-2 {theme::text_muted()}", "{format::date_time(run.started_at)}" }
+                                    td { class: "px-4 py-2 text-right {theme::text_secondary()}", "{run.trade_count}" }
+                                    td { class: "px-4 py-2 text-right {theme::text_secondary()}", "{format::auec(run.spent)}" }
+                                    td { class: "px-4 py-2 text-right {theme::text_secondary()}", "{format::auec(run.revenue)}" }
+                                    td { class: "px-4 py-2 text-right font-semibold {profit_class(run.profit)}", "{format::auec(run.profit)}" }
+                                    td { class: "px-4 py-2 text-right {theme::text_muted()}",
+                                        "{run.profit_per_hour.map(format::auec).unwrap_or_else(|| \"—\".to_string())}"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+
+                // Ledger
+                section { class: "{theme::table_container()}",
+                    table { class: "w-full text-sm",
+                        thead { class: "{theme::table_header()}",
+                            tr {
+                                th { class: "px-4 py-2 text-left", {tr!("history.col.time")} }
+                                th { class: "px-4 py-2 text-left", {tr!("history.col.side")} }
+                                th { class: "px-4 py-2 text-left", {tr!("table.commodity")} }
+                                th { class: "px-4 py-2 text-right", "SCU" }
+                                th { class: "px-4 py-2 text-right", {tr!("history.col.price_per_scu")} }
+                                th { class: "px-4 py-2 text-right", {tr!("history.col.total")} }
+                                th { class: "px-4 py-2 text-left", {tr!("table.terminal")} }
+                                th { class: "px-4 py-2" }
+                            }
+                        }
+                        tbody { class: "{theme::table_divider()}",
+                            for record in ledger.iter() {
+                                tr { key: "{record.id}",
+                                    td { class: "px-4 py-2 {theme::text_muted()}", "{format::date_time(record.timestamp)}" }
+                                    td { class: "px-4 py-2",
+                                        match record.side {
+                                            TradeSide::Buy => rsx! { span { class: "text-rose-300", {tr!("trade.buy")} } },
+                                            TradeSide::Sell => rsx! { span { class: "text-emerald-300", {tr!("trade.sell")} } },
+                                        }
+                                    }
+                                    td { class: "px-4 py-2 {theme::text_secondary()}", "{record.commodity_name}" }
+                                    td { class: "px-4 py-2 text-right {theme::text_secondary()}", "{record.scu}" }
+                                    td { class: "px-4 py-2 text-right {theme::text_muted()}", "{format::integer(record.price_per_scu)}" }
+                                    td { class: "px-4 py-2 text-right {profit_class(record.cash_flow())}", "{format::auec(record.cash_flow())}" }
+                                    td { class: "px-4 py-2 {theme::text_muted()}", "{record.terminal_name}" }
+                                    td { class: "px-4 py-2 text-right",
+                                        button {
+                                            class: "{theme::btn_small_inactive()}",
+                                            title: tr!("history.delete_entry"),
+                                            onclick: {
+                                                let id = record.id.clone();
+                                                move |_| {
+                                                    state.with_mut(|st| st.history.remove(&id));
+                                                    persist_history(&state);
+                                                }
+                                            },
+                                            "✕"
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    if hidden_trades > 0 && !show_all_trades() {
+                        div { class: "px-4 py-3 text-center",
+                            button {
+                                class: "{theme::link_class()}",
+                                onclick: move |_| show_all_trades.set(true),
+                                {tr!("history.show_older", count = hidden_trades)}
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn SessionRow(
+    summary: HistorySummary,
+    is_current: bool,
+    active: bool,
+    onclick: EventHandler<()>,
+) -> Element {
+    let row_class = if active { "bg-white/5" } else { "hover:bg-white/5" };
+    rsx! {
+        button {
+            class: "w-full px-4 py-3 text-left {row_class}",
+            onclick: move |_| onclick.call(()),
+            div { class: "flex items-center justify-between",
+                span { class: "text-sm {theme::text_secondary()}",
+                    "{format::date_time(summary.started_at)}"
+                    if is_current {
+                        span { class: "ml-2 text-xs {theme::text_primary()}", {tr!("history.current")} }
+                    }
+                }
+                span { class: "text-sm font-semibold {profit_class(summary.profit)}", "{format::auec(summary.profit)}" }
+            }
+            p { class: "text-xs {theme::text_muted()}",
+                {tr!("history.session_meta", trades = summary.trade_count, duration = format::duration(summary.duration_secs()))}
+            }
+        }
+    }
+}
+
+#[component]
+fn RankingList(title: String, entries: Vec<(String, f64)>) -> Element {
+    rsx! {
+        div { class: "{theme::panel_border()} p-4",
+            p { class: "{theme::label_class()} mb-2", "{title}" }
+            if entries.is_empty() {
+                p { class: "text-sm {theme::text_muted()}", "—" }
+            }
+            for (idx, (name, value)) in entries.iter().enumerate() {
+                div { key: "{name}", class: "flex items-center justify-between text-sm",
+                    span { class: "{theme::text_secondary()}", "{idx + 1}. {name}" }
+                    span { class: "{profit_class(*value)}", "{format::auec(*value)}" }
+                }
+            }
+        }
+    }
+}
+
+const CHART_WIDTH: f64 = 400.0;
+const CHART_HEIGHT: f64 = 120.0;
+
+/// Cumulative profit as an inline SVG polyline with a zero baseline.
+#[component]
+fn ProfitLineChart(points: Vec<(u64, f64)>) -> Element {
+    if points.len() < 2 {
+        return rsx! {
+            p { class: "text-sm {theme::text_muted()}", {tr!("history.chart.not_enough")} }
+        };
+    }
+
+    let t_min = points.first().map(|p| p.0).unwrap_or(0) as f64;
+    let t_max = points.last().map(|p| p.0).unwrap_or(0) as f64;
+    let v_min = points.iter().map(|p| p.1).fold(0.0_f64, f64::min);
+    let v_max = points.iter().map(|p| p.1).fold(0.0_f64, f64::max);
+    let t_span = (t_max - t_min).max(1.0);
+    let v_span = (v_max - v_min).max(1.0);
+
+    let scale_y = |v: f64| CHART_HEIGHT - (v - v_min) / v_span * CHART_HEIGHT;
+    let polyline = points
+        .iter()
+        .enumerate()
+        .map(|(idx, (t, v))| {
+            // Spread points evenly when all trades share one timestamp.
+            let x = if t_max > t_min {
+                (*t as f64 - t_min) / t_span * CHART_WIDTH
+            } else {
+                idx as f64 / (points.len() - 1) as f64 * CHART_WIDTH
+            };
+            format!("{x:.1},{:.1}", scale_y(*v))
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    let zero_y = scale_y(0.0);
+
+    rsx! {
+        svg {
+            class: "w-full h-32 {theme::text_primary()}",
+            view_box: "0 0 {CHART_WIDTH} {CHART_HEIGHT}",
+            preserve_aspect_ratio: "none",
+            line {
+                x1: "0", y1: "{zero_y:.1}", x2: "{CHART_WIDTH}", y2: "{zero_y:.1}",
+                stroke: "#475569", stroke_width: "1", stroke_dasharray: "4 4",
+            }
+            polyline {
+                points: "{polyline}",
+                fill: "none",
+                stroke: "currentColor",
+                stroke_width: "2",
+            }
+        }
+        div { class: "mt-1 flex justify-between text-xs {theme::text_muted()}",
+            span { "{format::date_time(t_min as u64)}" }
+            span { "{format::auec(points.last().map(|p| p.1).unwrap_or(0.0))}" }
+            span { "{format::date_time(t_max as u64)}" }
+        }
+    }
+}
+
+/// Profit per session as inline SVG bars around a zero baseline.
+#[component]
+fn ProfitBarChart(bars: Vec<(String, f64)>) -> Element {
+    if bars.is_empty() {
+        return rsx! {
+            p { class: "text-sm {theme::text_muted()}", {tr!("history.chart.no_sessions")} }
+        };
+    }
+
+    let v_max = bars.iter().map(|b| b.1).fold(0.0_f64, f64::max);
+    let v_min = bars.iter().map(|b| b.1).fold(0.0_f64, f64::min);
+    let v_span = (v_max - v_min).max(1.0);
+    let zero_y = v_max / v_span * CHART_HEIGHT;
+    let slot = CHART_WIDTH / bars.len() as f64;
+    let bar_width = slot * 0.7;
+    let rects: Vec<(f64, f64, f64, &'static str, String)> = bars
+        .iter()
+        .enumerate()
+        .map(|(idx, (label, value))| {
+            let height = (value.abs() / v_span * CHART_HEIGHT).max(1.0);
+            let y = if *value >= 0.0 { zero_y - height } else { zero_y };
+            let fill = if *value >= 0.0 { "#34d399" } else { "#f87171" };
+            let x = idx as f64 * slot + (slot - bar_width) / 2.0;
+            (x, y, height, fill, format!("{label}: {}", format::auec(*value)))
+        })
+        .collect();
+
+    rsx! {
+        svg {
+            class: "w-full h-32",
+            view_box: "0 0 {CHART_WIDTH} {CHART_HEIGHT}",
+            preserve_aspect_ratio: "none",
+            for (idx, (x, y, height, fill, tooltip)) in rects.into_iter().enumerate() {
+                rect {
+                    key: "{idx}",
+                    x: "{x:.1}",
+                    y: "{y:.1}",
+                    width: "{bar_width:.1}",
+                    height: "{height:.1}",
+                    fill: fill,
+                    title { "{tooltip}" }
+                }
+            }
+            line {
+                x1: "0", y1: "{zero_y:.1}", x2: "{CHART_WIDTH}", y2: "{zero_y:.1}",
+                stroke: "#475569", stroke_width: "1",
+            }
+        }
+    }
+}
+
+fn profit_class(value: f64) -> &'static str {
+    if value >= 0.0 {
+        "text-emerald-300"
+    } else {
+        "text-rose-300"
+    }
+}
+
@@ -1,13 +1,29 @@
 pub mod best_price;
 pub mod cargo;
+pub mod crew;
+pub mod fence;
+pub mod history;
+pub mod ore_prices;
 pub mod planner;
 pub mod profile_select;
+pub mod refinery;
+pub mod reports;
 pub mod routes;
 pub mod settings;
+pub mod traffic;
+pub mod watchlist;
 
 pub use best_price::BestPricePage;
 pub use cargo::CargoPage;
+pub use crew::CrewPage;
+pub use fence::FencePage;
+pub use history::HistoryPage;
+pub use ore_prices::OrePricesPage;
 pub use planner::PlannerPage;
 pub use profile_select::ProfileSelectPage;
+pub use refinery::RefineryPage;
+pub use reports::ReportsPage;
 pub use routes::RoutesPage;
 pub use settings::SettingsPage;
+pub use traffic::TrafficPage;
+pub use watchlist::WatchlistPage;
@@ -0,0 +1,198 @@
+//! Ore price board — where every mineable ore sells best, raw and refined.
+
+use std::time::{Duration, SystemTime};
+
+use dioxus::prelude::*;
+
+use crate::{
+    domain::{
+        is_mineable, ore_board, sort_ore_board, AppState, OreBoardFilter, OreBoardSort, OreQuote,
+    },
+    infra::config,
+    ui::{i18n::tr, pages::refinery::load_prices, theme},
+    util::format,
+};
+
+/// Prices older than this are flagged on the board.
+const STALE_AFTER: Duration = Duration::from_secs(24 * 3_600);
+
+#[component]
+pub fn OrePricesPage() -> Element {
+    let state = use_context::<Signal<AppState>>();
+
+    let mut sort_by = use_signal(OreBoardSort::default);
+    let mut system_filter = use_signal(String::new);
+    let mut only_refinery = use_signal(|| false);
+    let mut ore_query = use_signal(String::new);
+
+    let commodities = state.with(|st| st.commodities.clone());
+    let wanted: Vec<(String, String)> = commodities
+        .iter()
+        .filter(|c| is_mineable(c))
+        .map(|c| (c.id.clone(), c.name.clone()))
+        .collect();
+    let wanted_count = wanted.len();
+    let prices_loading = use_resource(use_reactive!(|wanted| async move {
+        load_prices(state, wanted).await;
+    }))
+    .read()
+    .is_none();
+
+    let all_rows = state.with(|st| {
+        ore_board(
+            &st.commodities,
+            &st.price_points,
+            &st.sell_locations,
+            &config::current().ranking.params(),
+        )
+    });
+    let priced = state.with(|st| {
+        commodities
+            .iter()
+            .filter(|c| is_mineable(c) && st.price_points.contains_key(&c.id))
+            .count()
+    });
+    let mut systems: Vec<String> = all_rows.iter().map(|r| r.system.clone()).collect();
+    systems.sort();
+    systems.dedup();
+
+    let filter = OreBoardFilter {
+        system: Some(system_filter()).filter(|s| !s.is_empty()),
+        only_refinery_systems: only_refinery(),
+        ore: ore_query(),
+    };
+    let mut rows: Vec<_> = all_rows.into_iter().filter(|r| filter.matches(r)).collect();
+    sort_ore_board(&mut rows, sort_by());
+
+    rsx! {
+        div { class: "space-y-6",
+            section {
+                h2 { class: "text-xl font-semibold {theme::text_secondary()}", {tr!("ores.title")} }
+                p { class: "text-sm {theme::text_muted()}", {tr!("ores.subtitle")} }
+            }
+
+            div { class: "{theme::panel_border()} flex flex-wrap items-end gap-4 px-4 py-4",
+                div { class: "flex-1 min-w-[180px]",
+                    label { class: "{theme::label_class()}", {tr!("ores.filter.ore")} }
+                    input {
+                        class: "mt-1 w-full {theme::input_small()}",
+                        value: ore_query(),
+                        oninput: move |evt| ore_query.set(evt.value()),
+                        placeholder: "Quantainium",
+                    }
+                }
+                div { class: "w-48",
+                    label { class: "{theme::label_class()}", {tr!("ores.filter.system")} }
+                    select {
+                        class: "mt-1 w-full {theme::input_small()}",
+                        onchange: move |evt| system_filter.set(evt.value()),
+                        option { value: "", selected: system_filter().is_empty(), {tr!("ores.filter.all_systems")} }
+                        for system in systems {
+                            option { value: "{system}", selected: system == system_filter(), "{system}" }
+                        }
+                    }
+                }
+                label { class: "flex items-center gap-2 pb-1 text-sm {theme::text_secondary()}",
+                    input {
+                        r#type: "checkbox",
+                        checked: only_refinery(),
+                        onchange: move |evt| only_refinery.set(evt.checked()),
+                    }
+                    {tr!("ores.filter.refinery_only")}
+                }
+            }
+
+            div { class: "flex flex-wrap items-center gap-2",
+                span { class: "text-xs font-semibold uppercase {theme::text_muted()}", {tr!("ores.sort")} }
+                for sort in OreBoardSort::ALL {
+                    button {
+                        class: "{theme::btn_toggle(sort == sort_by())}",
+                        onclick: move |_| sort_by.set(sort),
+                        {sort.label()}
+                    }
+                }
+            }
+
+            if prices_loading {
+                p { class: "text-sm {theme::text_primary()}",
+                    {tr!("ores.loading", loaded = priced, total = wanted_count)}
+                }
+            }
+
+            if rows.is_empty() && !prices_loading {
+                div { class: "{theme::panel_border()} px-6 py-12 text-center",
+                    p { class: "{theme::text_muted()}", {tr!("ores.empty")} }
+                }
+            } else if !rows.is_empty() {
+                div { class: "{theme::table_container()}",
+                    table { class: "min-w-full {theme::table_divider()} text-sm",
+                        thead { class: "{theme::table_header()} text-left tracking-wide",
+                            tr {
+                                th { class: "px-4 py-3 font-medium", {tr!("ores.col.ore")} }
+                                th { class: "px-4 py-3 font-medium", {tr!("ores.col.system")} }
+                                th { class: "px-4 py-3 font-medium", {tr!("ores.col.refined")} }
+                                th { class: "px-4 py-3 font-medium", {tr!("ores.col.raw")} }
+                                th { class: "px-4 py-3 font-medium text-right", {tr!("ores.col.ratio")} }
+                                th { class: "px-4 py-3 font-medium text-right", title: tr!("ores.col.value_hint"), {tr!("ores.col.value")} }
+                                th { class: "px-4 py-3 font-medium text-right", {tr!("ores.col.updated")} }
+                            }
+                        }
+                        tbody { class: "{theme::table_divider()}",
+                            for row in rows {
+                                tr { key: "{row.ore}-{row.system}", class: "cvs-row",
+                                    td { class: "px-4 py-3 font-medium {theme::text_secondary()}", "{row.ore}" }
+                                    td { class: "px-4 py-3 {theme::text_muted()}",
+                                        "{row.system}"
+                                        if row.has_refinery {
+                                            span { class: "ml-1", title: tr!("ores.has_refinery"), "⚗️" }
+                                        }
+                                    }
+                                    QuoteCell { quote: row.refined.clone() }
+                                    QuoteCell { quote: row.raw.clone() }
+                                    td { class: "px-4 py-3 text-right {theme::text_muted()}",
+                                        {row.refined_ratio().map(|r| format!("×{}", format::number(r, 2))).unwrap_or_else(|| "—".to_string())}
+                                    }
+                                    td { class: "px-4 py-3 text-right font-semibold {theme::text_primary()}",
+                                        {row.value_per_scu().map(format::auec).unwrap_or_else(|| "—".to_string())}
+                                    }
+                                    {
+                                        let updated = row.updated_at();
+                                        let exact = updated
+                                            .and_then(|at| at.duration_since(SystemTime::UNIX_EPOCH).ok())
+                                            .map(|since| format::date_time(since.as_secs()))
+                                            .unwrap_or_default();
+                                        let stale = updated
+                                            .and_then(|at| SystemTime::now().duration_since(at).ok())
+                                            .is_some_and(|age| age > STALE_AFTER);
+                                        rsx! {
+                                            td {
+                                                class: if stale { "px-4 py-3 text-right text-amber-300" } else { "px-4 py-3 text-right {theme::text_muted()}" },
+                                                title: exact,
+                                                {updated.map(format::relative_time).unwrap_or_else(|| "—".to_string())}
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn QuoteCell(quote: Option<OreQuote>) -> Element {
+    let Some(quote) = quote else {
+        return rsx! {
+            td { class: "px-4 py-3 {theme::text_muted()}", "—" }
+        };
+    };
+    rsx! {
+        td { class: "px-4 py-3", title: quote.notes.clone().unwrap_or_default(),
+            span { class: "{theme::text_secondary()}", {format::auec(quote.price)} }
+            span { class: "ml-2 text-xs {theme::text_muted()}", "{quote.terminal}" }
+        }
+    }
+}
@@ -2,7 +2,10 @@
 
 use dioxus::prelude::*;
 
-use crate::domain::{AppState, Profile};
+use crate::{
+    domain::{AppState, Profile},
+    ui::i18n::tr,
+};
 
 #[component]
 pub fn ProfileSelectPage() -> Element {
@@ -16,12 +19,12 @@ pub fn ProfileSelectPage() -> Element {
                 // Header
                 div { class: "text-center mb-12",
                     h1 { 
-                        class: "text-4xl font-bold text-slate-100 mb-3",
+                        class: "text-4xl font-bold cvs-text mb-3",
                         "Cargo Value Scanner"
                     }
                     p { 
-                        class: "text-xl text-slate-400",
-                        "Wer bist du heute?"
+                        class: "text-xl cvs-text-muted",
+                        {tr!("profile_select.question")}
                     }
                 }
                 
@@ -30,13 +33,13 @@ pub fn ProfileSelectPage() -> Element {
                     // Pirate
                     ProfileCard {
                         profile: Profile::Pirate,
-                        title: "Pirat",
+                        title: Profile::Pirate.name(),
                         emoji: "🏴‍☠️",
-                        description: "Beute machen, heiße Ware loswerden, lukrative Routen finden.",
+                        description: tr!("profile_select.pirate.description"),
                         features: vec![
-                            "Lukrative Handelsrouten (zum Lauern)",
-                            "Cargo-Verwaltung mit Hot-Markierung",
-                            "NQA-Terminal Übersicht",
+                            tr!("profile_select.pirate.feature_routes"),
+                            tr!("profile_select.pirate.feature_cargo"),
+                            tr!("profile_select.pirate.feature_nqa"),
                         ],
                         enabled: true,
                         on_select: move |_| {
@@ -47,13 +50,13 @@ pub fn ProfileSelectPage() -> Element {
                     // Trader
                     ProfileCard {
                         profile: Profile::Trader,
-                        title: "Händler",
+                        title: Profile::Trader.name(),
                         emoji: "📦",
-                        description: "Günstig kaufen, teuer verkaufen, Routen optimieren.",
+                        description: tr!("profile_select.trader.description"),
                         features: vec![
-                            "Buy & Sell Planner",
-                            "Trade Route Finder",
-                            "Profit-Margen Übersicht",
+                            tr!("profile_select.trader.feature_planner"),
+                            tr!("profile_select.trader.feature_routes"),
+                            tr!("profile_select.trader.feature_margins"),
                         ],
                         enabled: true,
                         on_select: move |_| {
@@ -61,26 +64,28 @@ pub fn ProfileSelectPage() -> Element {
                         },
                     }
                     
-                    // Miner (disabled)
+                    // Miner
                     ProfileCard {
                         profile: Profile::Miner,
-                        title: "Miner",
+                        title: Profile::Miner.name(),
                         emoji: "⛏️",
-                        description: "Erze abbauen, raffinieren, bestmöglich verkaufen.",
+                        description: tr!("profile_select.miner.description"),
                         features: vec![
-                            "Erz-Preise",
-                            "Refinery-Standorte", 
-                            "Mining-Spots",
+                            tr!("profile_select.miner.feature_prices"),
+                            tr!("profile_select.miner.feature_refineries"),
+                            tr!("profile_select.miner.feature_spots"),
                         ],
-                        enabled: false,
-                        on_select: move |_| {},
+                        enabled: true,
+                        on_select: move |_| {
+                            state.with_mut(|s| s.profile = Profile::Miner);
+                        },
                     }
                 }
                 
                 // Footer hint
                 div { class: "text-center mt-12",
-                    p { class: "text-sm text-slate-600",
-                        "Du kannst dein Profil jederzeit in den Einstellungen wechseln."
+                    p { class: "text-sm cvs-text-muted",
+                        {tr!("profile_select.footer")}
                     }
                 }
             }
@@ -91,10 +96,10 @@ pub fn ProfileSelectPage() -> Element {
 #[component]
 fn ProfileCard(
     profile: Profile,
-    title: &'static str,
+    title: String,
     emoji: &'static str,
-    description: &'static str,
-    features: Vec<&'static str>,
+    description: String,
+    features: Vec<String>,
     enabled: bool,
     on_select: EventHandler<()>,
 ) -> Element {
@@ -107,20 +112,20 @@ fn ProfileCard(
     let border_color = match profile {
         Profile::Pirate => "border-[#5c2a1f] hover:border-[#ff9900]/60 hover:bg-[#3b1712]/30",
         Profile::Trader => "border-emerald-500/30 hover:border-emerald-500/60 hover:bg-emerald-500/5",
-        Profile::Miner => "border-amber-500/30",
-        Profile::None => "border-slate-700",
+        Profile::Miner => "border-amber-500/30 hover:border-amber-500/60 hover:bg-amber-500/5",
+        Profile::None => "cvs-border",
     };
     
     let accent_color = match profile {
         Profile::Pirate => "text-[#ff9900]",
         Profile::Trader => "text-emerald-400",
         Profile::Miner => "text-amber-400",
-        Profile::None => "text-slate-400",
+        Profile::None => "cvs-text-muted",
     };
 
     rsx! {
         div {
-            class: "{base_classes} {border_color} bg-slate-900/60",
+            class: "{base_classes} {border_color} cvs-surface",
             onclick: move |_| {
                 if enabled {
                     on_select.call(());
@@ -130,8 +135,8 @@ fn ProfileCard(
             // Soon badge for disabled profiles
             if !enabled {
                 div {
-                    class: "absolute top-3 right-3 rounded-full bg-slate-800 px-2 py-0.5 text-[10px] font-bold text-slate-400 uppercase tracking-wider",
-                    "Soon™"
+                    class: "absolute top-3 right-3 rounded-full bg-slate-800 px-2 py-0.5 text-[10px] font-bold cvs-text-muted uppercase tracking-wider",
+                    {tr!("common.soon")}
                 }
             }
             
@@ -149,7 +154,7 @@ fn ProfileCard(
             
             // Description
             p { 
-                class: "text-sm text-slate-400 mb-4",
+                class: "text-sm cvs-text-muted mb-4",
                 "{description}"
             }
             
@@ -157,8 +162,8 @@ fn ProfileCard(
             ul { class: "space-y-1",
                 for feature in features {
                     li { 
-                        class: "text-xs text-slate-500 flex items-center gap-2",
-                        span { class: "text-slate-600", "›" }
+                        class: "text-xs cvs-text-muted flex items-center gap-2",
+                        span { class: "cvs-text-muted", "›" }
                         "{feature}"
                     }
                 }
@@ -170,7 +175,7 @@ fn ProfileCard(
                     class: "mt-6 text-center opacity-0 group-hover:opacity-100 transition-opacity",
                     span { 
                         class: "text-xs font-semibold {accent_color} uppercase tracking-wide",
-                        "Auswählen →"
+                        {tr!("profile_select.select")}
                     }
                 }
             }
@@ -0,0 +1,533 @@
+//! Refinery calculator — sell raw ore or refine it, and where.
+
+use std::{
+    collections::{BTreeSet, HashMap},
+    time::Duration,
+};
+
+use dioxus::prelude::*;
+use tokio::time::sleep;
+
+use crate::{
+    app::persist_user_state,
+    domain::{
+        best_sell_price, compare_refining, crew_session::CREW_MANIFEST_ID, history::unix_now, raw_commodity, refined_commodity,
+        parse_number, AppState, CacheResource, OreEntry, RefineOption, RefineryJob, ORES,
+    },
+    infra::{config, uex::UexClient},
+    ui::components::{
+        kpi_card::KpiCard,
+        toast::{push_toast, ToastKind, ToastMessage},
+    },
+    ui::{i18n::tr, theme},
+    util::{format, generate_id},
+};
+
+/// Options shown before "show all".
+const TOP_OPTIONS: usize = 10;
+
+#[component]
+pub fn RefineryPage() -> Element {
+    let mut state = use_context::<Signal<AppState>>();
+    let toasts = use_context::<Signal<Vec<ToastMessage>>>();
+
+    let mut ore_input = use_signal(|| ORES[0].to_string());
+    let mut scu_input = use_signal(String::new);
+    let mut quality_input = use_signal(String::new);
+    let mut show_all = use_signal(|| false);
+    let mut kiosk_time = use_signal(String::new);
+
+    let entries = state.with(|st| st.ore_entries.clone());
+    let commodities = state.with(|st| st.commodities.clone());
+    let price_map = state.with(|st| st.price_points.clone());
+
+    // Raw and refined prices for every ore in the hold.
+    let wanted: Vec<(String, String)> = {
+        let ores: BTreeSet<&str> = entries.iter().map(|e| e.ore.as_str()).collect();
+        ores.into_iter()
+            .flat_map(|ore| [raw_commodity(&commodities, ore), refined_commodity(&commodities, ore)])
+            .flatten()
+            .map(|c| (c.id.clone(), c.name.clone()))
+            .collect()
+    };
+    let prices_loading = use_resource(use_reactive!(|wanted| async move {
+        load_prices(state, wanted).await;
+    }))
+    .read()
+    .is_none();
+
+    let comparison = compare_refining(&entries, &commodities, &price_map);
+    let options: Vec<_> = if show_all() {
+        comparison.options.clone()
+    } else {
+        comparison.options.iter().take(TOP_OPTIONS).cloned().collect()
+    };
+    let best = comparison.best().cloned();
+
+    let on_add = move |evt: FormEvent| {
+        evt.prevent_default();
+        let scu = match parse_number(scu_input().trim()) {
+            Some(value) if value > 0.0 => value,
+            _ => {
+                push_toast(toasts, ToastKind::Error, tr!("refinery.toast.scu_invalid"));
+                return;
+            }
+        };
+        let quality = if quality_input().trim().is_empty() {
+            100.0
+        } else {
+            match parse_number(quality_input().trim()) {
+                Some(value) if (0.0..=100.0).contains(&value) => value,
+                _ => {
+                    push_toast(toasts, ToastKind::Error, tr!("refinery.toast.quality_invalid"));
+                    return;
+                }
+            }
+        };
+        state.with_mut(|st| {
+            st.ore_entries.push(OreEntry {
+                id: generate_id("ore"),
+                ore: ore_input(),
+                scu,
+                quality,
+            })
+        });
+        persist_user_state(&state);
+        scu_input.set(String::new());
+        quality_input.set(String::new());
+    };
+
+    // Hands the whole hold to a refinery; the entries move into the job.
+    let mut start_job = move |option: RefineOption| {
+        let duration = match kiosk_time().trim() {
+            "" => None,
+            text => match parse_duration(text) {
+                Some(secs) => Some(secs),
+                None => {
+                    push_toast(toasts, ToastKind::Error, tr!("refinery.toast.kiosk_time_invalid"));
+                    return;
+                }
+            },
+        };
+        state.with_mut(|st| {
+            let inputs = std::mem::take(&mut st.ore_entries);
+            st.refinery_jobs.push(RefineryJob::start(
+                generate_id("refinery-job"),
+                inputs,
+                &option.refinery,
+                &option.method,
+                unix_now(),
+                duration,
+            ));
+        });
+        persist_user_state(&state);
+        kiosk_time.set(String::new());
+        push_toast(
+            toasts,
+            ToastKind::Success,
+            tr!("refinery.toast.job_started", refinery = option.refinery.name),
+        );
+    };
+
+    rsx! {
+        div { class: "space-y-6",
+            section {
+                h2 { class: "text-xl font-semibold {theme::text_secondary()}", {tr!("refinery.title")} }
+                p { class: "text-sm {theme::text_muted()}", {tr!("refinery.subtitle")} }
+            }
+
+            form {
+                class: "{theme::panel_border()} flex flex-wrap items-end gap-4 px-4 py-4",
+                onsubmit: on_add,
+                div { class: "flex-1 min-w-[180px]",
+                    label { class: "{theme::label_class()}", {tr!("refinery.field.ore")} }
+                    select {
+                        class: "mt-1 w-full {theme::input_small()}",
+                        onchange: move |evt| ore_input.set(evt.value()),
+                        for ore in ORES {
+                            option { value: ore, selected: ore == ore_input(), "{ore}" }
+                        }
+                    }
+                }
+                div { class: "w-32",
+                    label { class: "{theme::label_class()}", {tr!("refinery.field.raw_scu")} }
+                    input {
+                        class: "mt-1 w-full {theme::input_small()}",
+                        inputmode: "decimal",
+                        value: scu_input(),
+                        oninput: move |evt| scu_input.set(evt.value()),
+                        placeholder: "32",
+                    }
+                }
+                div { class: "w-32",
+                    label { class: "{theme::label_class()}", {tr!("refinery.field.quality")} }
+                    input {
+                        class: "mt-1 w-full {theme::input_small()}",
+                        inputmode: "decimal",
+                        value: quality_input(),
+                        oninput: move |evt| quality_input.set(evt.value()),
+                        placeholder: "100",
+                        title: tr!("refinery.field.quality_hint"),
+                    }
+                }
+                button { class: "{theme::btn_primary()}", r#type: "submit", {tr!("cargo.add")} }
+            }
+
+            if entries.is_empty() {
+                div { class: "{theme::panel_border()} px-6 py-12 text-center",
+                    p { class: "{theme::text_muted()}", {tr!("refinery.empty")} }
+                }
+            } else {
+                div { class: "{theme::table_container()}",
+                    table { class: "min-w-full {theme::table_divider()} text-sm",
+                        thead { class: "{theme::table_header()} text-left tracking-wide",
+                            tr {
+                                th { class: "px-4 py-3 font-medium", {tr!("refinery.field.ore")} }
+                                th { class: "px-4 py-3 font-medium text-right", {tr!("refinery.field.raw_scu")} }
+                                th { class: "px-4 py-3 font-medium text-right", {tr!("refinery.field.quality")} }
+                                th { class: "px-4 py-3 font-medium text-right", {tr!("refinery.col.ore_scu")} }
+                                th { class: "px-4 py-3 font-medium text-right", {tr!("refinery.col.raw_value")} }
+                                th { class: "px-4 py-3" }
+                            }
+                        }
+                        tbody { class: "{theme::table_divider()}",
+                            for entry in entries.iter().cloned() {
+                                {
+                                    let raw_price = raw_commodity(&commodities, &entry.ore)
+                                        .and_then(|c| price_map.get(&c.id))
+                                        .and_then(|points| best_sell_price(points, None));
+                                    let id = entry.id.clone();
+                                    rsx! {
+                                        tr { key: "{entry.id}", class: "cvs-row",
+                                            td { class: "px-4 py-3 font-medium {theme::text_secondary()}", "{entry.ore}" }
+                                            td { class: "px-4 py-3 text-right {theme::text_secondary()}", {format::number(entry.scu, 1)} }
+                                            td { class: "px-4 py-3 text-right {theme::text_secondary()}", {format!("{} %", format::number(entry.quality, 0))} }
+                                            td { class: "px-4 py-3 text-right {theme::text_secondary()}", {format::number(entry.ore_scu(), 1)} }
+                                            td { class: "px-4 py-3 text-right {theme::text_muted()}",
+                                                {raw_price.map(|p| format::auec(p * entry.scu)).unwrap_or_else(|| tr!("common.not_available"))}
+                                            }
+                                            td { class: "px-4 py-3 text-right",
+                                                button {
+                                                    class: "rounded-md border border-rose-500/40 px-2 py-1 text-[10px] font-semibold uppercase tracking-wide text-rose-200 hover:bg-rose-500/10",
+                                                    onclick: move |_| {
+                                                        state.with_mut(|st| st.ore_entries.retain(|e| e.id != id));
+                                                        persist_user_state(&state);
+                                                    },
+                                                    {tr!("common.remove")}
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+
+                section { class: "grid gap-4 sm:grid-cols-4",
+                    KpiCard {
+                        title: tr!("refinery.kpi.raw"),
+                        value: format::auec_compact(comparison.raw_value),
+                        description: Some(tr!("refinery.kpi.raw_hint")),
+                    }
+                    KpiCard {
+                        title: tr!("refinery.kpi.best_net"),
+                        value: best.as_ref().map(|o| format::auec_compact(o.net_value)).unwrap_or_else(|| "—".to_string()),
+                        description: best.as_ref().map(|o| format!("{} · {}", o.refinery.name, o.method.name)),
+                    }
+                    KpiCard {
+                        title: tr!("refinery.kpi.gain"),
+                        value: best.as_ref().map(|o| format::auec_compact(o.gain_vs_raw)).unwrap_or_else(|| "—".to_string()),
+                        description: Some(tr!("refinery.kpi.gain_hint")),
+                    }
+                    KpiCard {
+                        title: tr!("refinery.kpi.duration"),
+                        value: best.as_ref().map(|o| format::duration(o.duration_secs)).unwrap_or_else(|| "—".to_string()),
+                        description: Some(tr!("refinery.kpi.duration_hint")),
+                    }
+                }
+
+                if prices_loading {
+                    p { class: "text-sm {theme::text_primary()}", {tr!("refinery.loading_prices")} }
+                }
+                if !comparison.refined_unpriced.is_empty() {
+                    p { class: "text-sm text-amber-300",
+                        {tr!("refinery.unpriced_refined", ores = comparison.refined_unpriced.join(", "))}
+                    }
+                }
+                if !comparison.raw_unpriced.is_empty() {
+                    p { class: "text-sm {theme::text_muted()}",
+                        {tr!("refinery.unpriced_raw", ores = comparison.raw_unpriced.join(", "))}
+                    }
+                }
+
+                div { class: "flex flex-wrap items-center justify-end gap-2",
+                    label { class: "text-xs {theme::text_muted()}", {tr!("refinery.kiosk_time")} }
+                    input {
+                        class: "w-24 {theme::input_small()}",
+                        value: kiosk_time(),
+                        oninput: move |evt| kiosk_time.set(evt.value()),
+                        placeholder: "2:30",
+                        title: tr!("refinery.kiosk_time_hint"),
+                    }
+                }
+                div { class: "{theme::table_container()}",
+                    table { class: "min-w-full {theme::table_divider()} text-sm",
+                        thead { class: "{theme::table_header()} text-left tracking-wide",
+                            tr {
+                                th { class: "px-4 py-3 font-medium", {tr!("refinery.col.refinery")} }
+                                th { class: "px-4 py-3 font-medium", {tr!("refinery.col.method")} }
+                                th { class: "px-4 py-3 font-medium text-right", {tr!("refinery.col.refined_scu")} }
+                                th { class: "px-4 py-3 font-medium text-right", {tr!("refinery.col.gross")} }
+                                th { class: "px-4 py-3 font-medium text-right", {tr!("refinery.col.cost")} }
+                                th { class: "px-4 py-3 font-medium text-right", {tr!("refinery.col.net")} }
+                                th { class: "px-4 py-3 font-medium text-right", {tr!("refinery.col.time")} }
+                                th { class: "px-4 py-3 font-medium text-right", {tr!("refinery.col.vs_raw")} }
+                                th { class: "px-4 py-3" }
+                            }
+                        }
+                        tbody { class: "{theme::table_divider()}",
+                            for option in options {
+                                tr {
+                                    key: "{option.refinery.name}-{option.method.name}",
+                                    class: "cvs-row",
+                                    td { class: "px-4 py-3 {theme::text_secondary()}",
+                                        "{option.refinery.name}"
+                                        span { class: "ml-2 text-xs {theme::text_muted()}", "{option.refinery.system}" }
+                                    }
+                                    td { class: "px-4 py-3 {theme::text_muted()}", "{option.method.name}" }
+                                    td { class: "px-4 py-3 text-right {theme::text_secondary()}", {format::number(option.refined_scu, 1)} }
+                                    td { class: "px-4 py-3 text-right {theme::text_secondary()}", {format::integer(option.gross_value)} }
+                                    td { class: "px-4 py-3 text-right {theme::text_muted()}", {format::integer(option.cost)} }
+                                    td { class: "px-4 py-3 text-right font-semibold {theme::text_primary()}", {format::integer(option.net_value)} }
+                                    td { class: "px-4 py-3 text-right {theme::text_muted()}", {format::duration(option.duration_secs)} }
+                                    td {
+                                        class: if option.gain_vs_raw >= 0.0 { "px-4 py-3 text-right text-emerald-300" } else { "px-4 py-3 text-right text-rose-300" },
+                                        {format::integer(option.gain_vs_raw)}
+                                    }
+                                    td { class: "px-4 py-3 text-right",
+                                        button {
+                                            class: "{theme::btn_small_inactive()}",
+                                            title: tr!("refinery.start_hint"),
+                                            onclick: {
+                                                let option = option.clone();
+                                                move |_| start_job(option.clone())
+                                            },
+                                            {tr!("refinery.start")}
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+                if comparison.options.len() > TOP_OPTIONS {
+                    div { class: "flex justify-center",
+                        button {
+                            class: "{theme::btn_small_inactive()}",
+                            onclick: move |_| show_all.toggle(),
+                            if show_all() {
+                                {tr!("refinery.show_top", count = TOP_OPTIONS)}
+                            } else {
+                                {tr!("refinery.show_all", count = comparison.options.len())}
+                            }
+                        }
+                    }
+                }
+                p { class: "text-xs {theme::text_muted()}", {tr!("refinery.estimates_note")} }
+            }
+
+            RefineryJobs {}
+        }
+    }
+}
+
+/// Running jobs with countdowns; finished ones can be collected into a manifest.
+#[component]
+fn RefineryJobs() -> Element {
+    let mut state = use_context::<Signal<AppState>>();
+    let toasts = use_context::<Signal<Vec<ToastMessage>>>();
+    let mut now = use_signal(unix_now);
+    let mut targets = use_signal(HashMap::<String, String>::new);
+
+    use_future(move || async move {
+        loop {
+            sleep(Duration::from_secs(1)).await;
+            now.set(unix_now());
+        }
+    });
+
+    let jobs = state.with(|st| st.refinery_jobs.clone());
+    if jobs.is_empty() {
+        return rsx! {};
+    }
+    let (manifests, active_id) = state.with(|st| {
+        let manifests: Vec<_> = st.own_manifests().cloned().collect();
+        let active = st.active_manifest().map(|m| m.id.clone()).unwrap_or_default();
+        let active = manifests
+            .iter()
+            .find(|m| m.id == active)
+            .or(manifests.first())
+            .map(|m| m.id.clone())
+            .unwrap_or_default();
+        (manifests, active)
+    });
+
+    let collect = {
+        let active_id = active_id.clone();
+        move |mut job: RefineryJob| {
+            let target = targets.peek().get(&job.id).cloned().unwrap_or_else(|| active_id.clone());
+            let commodities = state.with(|st| st.commodities.clone());
+            let (items, dropped) = job.take_cargo(&commodities);
+            let wanted: Vec<(String, String)> = items
+                .iter()
+                .map(|item| (item.commodity_id.clone(), item.commodity_name.clone()))
+                .collect();
+            let collected = !items.is_empty();
+            let manifest_name = state.with_mut(|st| {
+                let manifest = st
+                    .manifests
+                    .iter_mut()
+                    .find(|m| m.id == target && m.id != CREW_MANIFEST_ID)?;
+                for item in items {
+                    manifest.absorb(item);
+                }
+                let name = manifest.label();
+                // Outputs that couldn't become cargo yet stay on the job.
+                if job.is_collected() {
+                    st.refinery_jobs.retain(|j| j.id != job.id);
+                } else if let Some(existing) = st.refinery_jobs.iter_mut().find(|j| j.id == job.id) {
+                    *existing = job.clone();
+                }
+                Some(name)
+            });
+            let Some(manifest_name) = manifest_name else {
+                push_toast(toasts, ToastKind::Error, tr!("refinery.toast.manifest_missing"));
+                return;
+            };
+            persist_user_state(&state);
+            spawn(load_prices(state, wanted));
+            if collected {
+                push_toast(toasts, ToastKind::Success, tr!("refinery.toast.collected", manifest = manifest_name));
+            }
+            if !dropped.is_empty() {
+                push_toast(toasts, ToastKind::Warning, tr!("refinery.toast.skipped", ores = dropped.join(", ")));
+            }
+            if !job.is_collected() {
+                let ores = job.outputs.iter().map(|o| o.ore.clone()).collect::<Vec<_>>().join(", ");
+                push_toast(toasts, ToastKind::Warning, tr!("refinery.toast.kept", ores = ores));
+            }
+        }
+    };
+
+    rsx! {
+        section { class: "space-y-3",
+            h3 { class: "text-lg font-semibold {theme::text_secondary()}", {tr!("refinery.jobs.title")} }
+            for job in jobs {
+                {
+                    let done = job.is_done(now());
+                    let job_id = job.id.clone();
+                    let cancel_id = job.id.clone();
+                    let selected = targets().get(&job.id).cloned().unwrap_or_else(|| active_id.clone());
+                    let outputs = job
+                        .outputs
+                        .iter()
+                        .map(|o| format!("{} {}", format::number(o.scu, 1), o.ore))
+                        .collect::<Vec<_>>()
+                        .join(" · ");
+                    rsx! {
+                        div {
+                            key: "{job.id}",
+                            class: "{theme::panel_border()} flex flex-wrap items-center justify-between gap-4 px-4 py-3",
+                            div {
+                                p { class: "font-medium {theme::text_secondary()}",
+                                    "{job.refinery}"
+                                    span { class: "ml-2 text-xs {theme::text_muted()}", "{job.system} · {job.method}" }
+                                }
+                                p { class: "text-sm {theme::text_muted()}", "{outputs}" }
+                            }
+                            div { class: "flex flex-wrap items-center gap-2",
+                                if done {
+                                    span { class: "text-sm font-semibold text-emerald-300", {tr!("refinery.jobs.done")} }
+                                    select {
+                                        class: "{theme::input_small()}",
+                                        title: tr!("refinery.jobs.target"),
+                                        onchange: move |evt| {
+                                            targets.write().insert(job_id.clone(), evt.value());
+                                        },
+                                        for manifest in manifests.iter() {
+                                            option { value: "{manifest.id}", selected: manifest.id == selected, "{manifest.label()}" }
+                                        }
+                                    }
+                                    button {
+                                        class: "{theme::btn_primary()}",
+                                        onclick: {
+                                            let job = job.clone();
+                                            let mut collect = collect.clone();
+                                            move |_| collect(job.clone())
+                                        },
+                                        {tr!("refinery.jobs.collect")}
+                                    }
+                                } else {
+                                    span {
+                                        class: "font-mono text-sm {theme::text_primary()}",
+                                        title: format::date_time(job.finishes_at()),
+                                        {format_countdown(job.remaining_secs(now()))}
+                                    }
+                                }
+                                button {
+                                    class: "{theme::link_class()}",
+                                    title: tr!("refinery.jobs.remove_hint"),
+                                    onclick: move |_| {
+                                        state.with_mut(|st| st.refinery_jobs.retain(|j| j.id != cancel_id));
+                                        persist_user_state(&state);
+                                    },
+                                    {tr!("common.remove")}
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// `h:mm:ss` countdown.
+fn format_countdown(secs: u64) -> String {
+    format!("{}:{:02}:{:02}", secs / 3_600, (secs % 3_600) / 60, secs % 60)
+}
+
+/// Kiosk times as `h:mm` or plain minutes.
+fn parse_duration(input: &str) -> Option<u64> {
+    match input.split_once(':') {
+        Some((hours, minutes)) => {
+            let hours: u64 = hours.trim().parse().ok()?;
+            let minutes: u64 = minutes.trim().parse().ok().filter(|m| *m < 60)?;
+            Some((hours * 60 + minutes) * 60)
+        }
+        None => input.trim().parse::<u64>().ok().map(|minutes| minutes * 60),
+    }
+}
+
+/// Fetches prices that are missing or older than the price TTL.
+pub async fn load_prices(mut state: Signal<AppState>, commodities: Vec<(String, String)>) {
+    let Ok(client) = UexClient::new() else {
+        return;
+    };
+    let ttl = config::current().cache.price_ttl();
+    for (id, name) in commodities {
+        let resource = CacheResource::Prices(id.clone());
+        let fresh = state.with(|st| st.price_points.contains_key(&id) && !st.is_stale(&resource, ttl));
+        if fresh {
+            continue;
+        }
+        match client.get_prices(&id, Some(&name)).await {
+            Ok(payload) => state.with_mut(|st| {
+                st.price_points.insert(id.clone(), payload.data);
+                st.cache.record_fetch(resource, payload.fetched_at);
+            }),
+            Err(err) => tracing::warn!(commodity = %name, %err, "failed to load prices"),
+        }
+    }
+}
@@ -0,0 +1,242 @@
+//! Reports — render the current load, plan and session through a text template.
+
+use dioxus::prelude::*;
+
+use crate::domain::{
+    calculate_best_value_plan, evaluate_cargo_items, history::unix_now, rank_best_prices_with,
+    render_report, report::PLACEHOLDERS, report_presets, report_scope, AppState, CargoManifest,
+    ReportSource, ReportTemplate,
+};
+use crate::infra::{config, export::save_text_with_dialog};
+use crate::ui::components::toast::{push_toast, ToastKind, ToastMessage};
+use crate::ui::{
+    i18n::{self, tr},
+    pages::best_price::copy_text_to_clipboard,
+    theme,
+};
+
+const EXTENSIONS: [&str; 2] = ["txt", "md"];
+
+#[component]
+pub fn ReportsPage() -> Element {
+    let state = use_context::<Signal<AppState>>();
+    let toasts = use_context::<Signal<Vec<ToastMessage>>>();
+
+    let mut templates = report_presets();
+    templates.extend(config::current().reports.templates);
+    let mut draft = use_signal(|| templates[0].clone());
+    let current = draft();
+
+    let preview = state.with(|st| {
+        let manifest = st.active_manifest().cloned().unwrap_or_else(CargoManifest::default_manifest);
+        let evaluation = evaluate_cargo_items(&manifest.items, &st.price_points);
+        let best_prices = rank_best_prices_with(
+            &manifest.items,
+            &st.price_points,
+            &st.sell_locations,
+            &config::current().ranking.params(),
+        );
+        let plan = calculate_best_value_plan(&manifest.items, &st.price_points, &st.nqa_terminal_ids);
+        let scope = report_scope(&ReportSource {
+            manifest: &manifest,
+            evaluation: &evaluation,
+            best_prices: &best_prices,
+            plan: Some(&plan),
+            history: &st.history,
+            session_id: &st.session_id,
+            now: unix_now(),
+        });
+        render_report(&current.body, &scope)
+    });
+    let rendered = preview.as_ref().ok().cloned().unwrap_or_default();
+
+    let save_templates = move |edit: &dyn Fn(&mut Vec<ReportTemplate>)| {
+        let mut next = config::file_config();
+        edit(&mut next.reports.templates);
+        match config::update(next) {
+            Ok(()) => {
+                push_toast(toasts, ToastKind::Success, tr!("reports.toast.saved"));
+                true
+            }
+            Err(err) => {
+                push_toast(toasts, ToastKind::Error, tr!("settings.toast.config_not_saved", error = err));
+                false
+            }
+        }
+    };
+
+    let on_save = move |_| {
+        let template = draft();
+        save_templates(&|list: &mut Vec<ReportTemplate>| match list.iter_mut().find(|t| t.id == template.id) {
+            Some(existing) => *existing = template.clone(),
+            None => list.push(template.clone()),
+        });
+    };
+
+    let on_save_copy = move |_| {
+        let mut copy = draft();
+        copy.id = format!("report-{}", uuid::Uuid::new_v4());
+        if copy.name.trim().is_empty() || report_presets().iter().any(|p| p.name == copy.name) {
+            copy.name = tr!("reports.copy_name", name = copy.name);
+        }
+        if save_templates(&|list: &mut Vec<ReportTemplate>| list.push(copy.clone())) {
+            draft.set(copy);
+        }
+    };
+
+    let on_delete = move |_| {
+        let id = draft().id;
+        if save_templates(&|list: &mut Vec<ReportTemplate>| list.retain(|t| t.id != id)) {
+            draft.set(report_presets()[0].clone());
+        }
+    };
+
+    let on_copy = {
+        let rendered = rendered.clone();
+        move |_| {
+            if copy_text_to_clipboard(&rendered) {
+                push_toast(toasts, ToastKind::Success, tr!("reports.toast.copied"));
+            }
+        }
+    };
+
+    let on_export = {
+        let rendered = rendered.clone();
+        let current = current.clone();
+        move |_| {
+            let rendered = rendered.clone();
+            let extension = current.extension.clone();
+            let file_name = format!("cvs-report.{extension}");
+            spawn(async move {
+                match save_text_with_dialog(&rendered, &file_name, &extension).await {
+                    Ok(Some(path)) => push_toast(
+                        toasts,
+                        ToastKind::Success,
+                        tr!("export.toast.done", format = extension.to_uppercase(), path = path.display()),
+                    ),
+                    Ok(None) => {}
+                    Err(err) => push_toast(toasts, ToastKind::Error, tr!("export.toast.failed", error = err)),
+                }
+            });
+        }
+    };
+
+    rsx! {
+        div { class: "space-y-6",
+            section {
+                h2 { class: "text-xl font-semibold {theme::text_secondary()}", {tr!("reports.title")} }
+                p { class: "text-sm {theme::text_muted()}", {tr!("reports.subtitle")} }
+            }
+
+            div { class: "grid gap-6 lg:grid-cols-2",
+                div { class: "{theme::panel_border()} space-y-3 p-4",
+                    div { class: "flex flex-wrap items-end gap-3",
+                        div { class: "flex-1",
+                            label { class: "{theme::label_class()}", {tr!("reports.template")} }
+                            select {
+                                class: "mt-1 w-full {theme::input_small()}",
+                                onchange: {
+                                    let templates = templates.clone();
+                                    move |evt: FormEvent| {
+                                        if let Some(template) = templates.iter().find(|t| t.id == evt.value()) {
+                                            draft.set(template.clone());
+                                        }
+                                    }
+                                },
+                                for template in templates.iter() {
+                                    option {
+                                        value: "{template.id}",
+                                        selected: template.id == current.id,
+                                        if template.is_preset() {
+                                            {tr!("reports.preset_name", name = template.name)}
+                                        } else {
+                                            "{template.name}"
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                        div { class: "w-24",
+                            label { class: "{theme::label_class()}", {tr!("reports.extension")} }
+                            select {
+                                class: "mt-1 w-full {theme::input_small()}",
+                                onchange: move |evt| draft.with_mut(|d| d.extension = evt.value()),
+                                for extension in EXTENSIONS {
+                                    option { value: extension, selected: current.extension == extension, ".{extension}" }
+                                }
+                            }
+                        }
+                    }
+                    if !current.is_preset() {
+                        div {
+                            label { class: "{theme::label_class()}", {tr!("reports.name")} }
+                            input {
+                                class: "mt-1 w-full {theme::input_small()}",
+                                value: "{current.name}",
+                                oninput: move |evt| draft.with_mut(|d| d.name = evt.value()),
+                            }
+                        }
+                    }
+                    textarea {
+                        class: "h-80 w-full rounded-lg p-3 font-mono text-xs cvs-input",
+                        spellcheck: false,
+                        value: "{current.body}",
+                        oninput: move |evt| draft.with_mut(|d| d.body = evt.value()),
+                    }
+                    if current.is_preset() {
+                        p { class: "text-xs {theme::text_muted()}", {tr!("reports.preset_hint")} }
+                    }
+                    div { class: "flex flex-wrap gap-2",
+                        if !current.is_preset() {
+                            button { class: "{theme::btn_small_active()}", disabled: preview.is_err(), onclick: on_save, {tr!("common.save")} }
+                        }
+                        button { class: "{theme::btn_small_inactive()}", disabled: preview.is_err(), onclick: on_save_copy, {tr!("reports.save_copy")} }
+                        if !current.is_preset() {
+                            button { class: "{theme::btn_small_inactive()}", onclick: on_delete, {tr!("reports.delete")} }
+                        }
+                    }
+                }
+
+                div { class: "{theme::panel_border()} space-y-3 p-4",
+                    div { class: "flex items-center justify-between gap-3",
+                        h3 { class: "{theme::label_class()}", {tr!("reports.preview")} }
+                        div { class: "flex gap-2",
+                            button { class: "{theme::btn_small_inactive()}", disabled: rendered.trim().is_empty(), onclick: on_copy, {tr!("common.copy")} }
+                            button {
+                                class: "{theme::btn_small_inactive()}",
+                                disabled: rendered.trim().is_empty(),
+                                onclick: on_export,
+                                "⬇ .{current.extension}"
+                            }
+                        }
+                    }
+                    match &preview {
+                        Ok(text) => rsx! {
+                            pre { class: "h-80 overflow-auto whitespace-pre-wrap rounded-lg p-3 text-xs cvs-input", "{text}" }
+                        },
+                        Err(err) => rsx! {
+                            p { class: "rounded-lg border border-rose-500/30 bg-rose-500/10 px-3 py-2 text-xs text-rose-200",
+                                {tr!("reports.error", error = err)}
+                            }
+                        },
+                    }
+                }
+            }
+
+            section { class: "{theme::panel_border()} p-4",
+                h3 { class: "{theme::label_class()}", {tr!("reports.placeholders")} }
+                p { class: "mt-1 text-xs {theme::text_muted()}", {tr!("reports.syntax")} }
+                table { class: "mt-3 text-xs",
+                    tbody {
+                        for (name, key) in PLACEHOLDERS.iter() {
+                            tr { key: "{name}",
+                                td { class: "whitespace-pre pr-6 py-0.5 font-mono {theme::text_secondary()}", "{name}" }
+                                td { class: "py-0.5 {theme::text_muted()}", {i18n::t(key)} }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
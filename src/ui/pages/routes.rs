@@ -2,96 +2,14 @@
 
 use dioxus::prelude::*;
 
-use crate::domain::{
-    calculate_routes_for_commodity, sort_routes, AppState, Profile, TradeRoute, TradeRouteSort,
-};
-use crate::infra::cache::{load_routes_cache, save_routes_cache, RoutesCache};
+use crate::domain::{sort_routes, AppState, Profile, TradeRoute, TradeRouteSort};
+use crate::infra::config;
+use crate::infra::export::{route_rows, ExportPayload};
+use crate::infra::routes::load_routes;
 use crate::infra::uex::UexClient;
-
-// ============================================
-// THEME HELPERS - Manufacturer-specific styles
-// ============================================
-
-fn btn_active(profile: Profile) -> &'static str {
-    match profile {
-        Profile::Pirate => "rounded-lg px-5 py-2.5 text-sm font-semibold bg-[#3b1712] text-[#ff9900] border border-[#5c2a1f] drake-glow drake-flicker",
-        Profile::Trader => "rounded-lg px-5 py-2.5 text-sm font-semibold bg-sky-500/20 text-sky-300 border border-sky-500/40 misc-glow",
-        Profile::Miner => "rounded-lg px-5 py-2.5 text-sm font-semibold bg-orange-500/20 text-orange-300 border border-orange-500/40 argo-glow",
-        Profile::None => "rounded-lg px-5 py-2.5 text-sm font-semibold bg-indigo-500/20 text-indigo-300 border border-indigo-500/40",
-    }
-}
-
-fn btn_inactive(profile: Profile) -> &'static str {
-    match profile {
-        Profile::Pirate => "rounded-lg px-5 py-2.5 text-sm text-[#d4523a]/70 border border-[#3b1712] hover:border-[#5c2a1f] hover:text-[#ff9900]",
-        Profile::Trader => "rounded-lg px-5 py-2.5 text-sm text-slate-400 border border-slate-700 hover:border-sky-600 hover:text-sky-300",
-        Profile::Miner => "rounded-lg px-5 py-2.5 text-sm text-slate-400 border border-slate-700 hover:border-orange-600 hover:text-orange-300",
-        Profile::None => "rounded-lg px-5 py-2.5 text-sm text-slate-400 border border-slate-700 hover:border-slate-600",
-    }
-}
-
-fn btn_small_active(profile: Profile) -> &'static str {
-    match profile {
-        Profile::Pirate => "rounded px-2 py-1 text-xs font-semibold bg-[#3b1712] text-[#ff9900] border border-[#5c2a1f]",
-        Profile::Trader => "rounded px-2 py-1 text-xs font-semibold bg-sky-500/20 text-sky-300 border border-sky-500/40",
-        Profile::Miner => "rounded px-2 py-1 text-xs font-semibold bg-orange-500/20 text-orange-300 border border-orange-500/40",
-        Profile::None => "rounded px-2 py-1 text-xs font-semibold bg-indigo-500/20 text-indigo-300 border border-indigo-500/40",
-    }
-}
-
-fn btn_small_inactive(profile: Profile) -> &'static str {
-    match profile {
-        Profile::Pirate => "rounded px-2 py-1 text-xs text-[#d4523a]/60 border border-[#3b1712] hover:border-[#5c2a1f] hover:text-[#ff9900]",
-        Profile::Trader => "rounded px-2 py-1 text-xs text-slate-500 border border-slate-700 hover:border-sky-600 hover:text-sky-300",
-        Profile::Miner => "rounded px-2 py-1 text-xs text-slate-500 border border-slate-700 hover:border-orange-600 hover:text-orange-300",
-        Profile::None => "rounded px-2 py-1 text-xs text-slate-500 border border-slate-700 hover:border-slate-600 hover:text-slate-300",
-    }
-}
-
-fn input_class(profile: Profile) -> &'static str {
-    match profile {
-        Profile::Pirate => "rounded-lg border border-[#3b1712] bg-[#1a0a08] px-4 py-2.5 text-sm text-[#d4523a] focus:border-[#ff9900] focus:outline-none",
-        Profile::Trader => "rounded-lg border border-slate-700 bg-slate-950 px-4 py-2.5 text-sm text-slate-100 focus:border-sky-500 focus:outline-none",
-        Profile::Miner => "rounded-lg border border-slate-700 bg-slate-950 px-4 py-2.5 text-sm text-slate-100 focus:border-orange-500 focus:outline-none",
-        Profile::None => "rounded-lg border border-slate-700 bg-slate-950 px-4 py-2.5 text-sm text-slate-100 focus:border-indigo-500 focus:outline-none",
-    }
-}
-
-fn panel_border(profile: Profile) -> &'static str {
-    match profile {
-        Profile::Pirate => "rounded-xl border border-[#5c2a1f] bg-[#3b1712]/40",
-        Profile::Trader => "rounded-xl border border-sky-800/50 bg-slate-900/40",
-        Profile::Miner => "rounded-xl border border-orange-800/50 bg-slate-900/40",
-        Profile::None => "rounded-xl border border-slate-800 bg-slate-900/40",
-    }
-}
-
-fn table_container(profile: Profile) -> &'static str {
-    match profile {
-        Profile::Pirate => "rounded-xl border border-[#3b1712] bg-[#1a0a08]/60 overflow-hidden",
-        Profile::Trader => "rounded-xl border border-sky-900/40 bg-slate-900/40 overflow-hidden",
-        Profile::Miner => "rounded-xl border border-orange-900/40 bg-slate-900/40 overflow-hidden",
-        Profile::None => "rounded-xl border border-slate-800 bg-slate-900/40 overflow-hidden",
-    }
-}
-
-fn table_header(profile: Profile) -> &'static str {
-    match profile {
-        Profile::Pirate => "border-b border-[#3b1712] bg-[#3b1712]/50 text-xs uppercase text-[#d4523a]/80",
-        Profile::Trader => "border-b border-sky-900/40 bg-sky-950/30 text-xs uppercase text-sky-400/70",
-        Profile::Miner => "border-b border-orange-900/40 bg-orange-950/30 text-xs uppercase text-orange-400/70",
-        Profile::None => "border-b border-slate-800 bg-slate-900/60 text-xs uppercase text-slate-500",
-    }
-}
-
-fn table_divider(profile: Profile) -> &'static str {
-    match profile {
-        Profile::Pirate => "divide-y divide-[#3b1712]/60",
-        Profile::Trader => "divide-y divide-sky-900/30",
-        Profile::Miner => "divide-y divide-orange-900/30",
-        Profile::None => "divide-y divide-slate-800",
-    }
-}
+use crate::ui::components::export_buttons::ExportButtons;
+use crate::ui::{i18n::tr, keyboard, theme};
+use crate::util::format;
 
 /// Trade scope: within one system or across systems.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
@@ -120,6 +38,7 @@ pub fn RoutesPage() -> Element {
     let mut trade_scope = use_signal(|| TradeScope::Stellar);
     let mut stations_only = use_signal(|| false); // filter out planetary locations
     let mut selected_route = use_signal(|| None::<TradeRoute>); // for detail panel
+    let mut cursor = use_signal(|| None::<usize>); // keyboard-highlighted row
     let mut system_filter = use_signal(|| String::new()); // empty = all systems
     let mut force_refresh = use_signal(|| false);
     
@@ -128,59 +47,8 @@ pub fn RoutesPage() -> Element {
         let nqa = nqa_terminal_ids.clone();
         let refresh = force_refresh();
         async move {
-            // Try to load from cache first (unless force refresh)
-            if !refresh {
-                if let Some(cache) = load_routes_cache() {
-                    println!("[routes] Using cached routes ({} routes, age: {})", cache.routes.len(), cache.age_string());
-                    return Some(cache.routes);
-                }
-            }
-            
-            // Cache miss or expired - fetch fresh data
-            println!("[routes] Fetching fresh route data from API...");
-            
             let client = UexClient::new().ok()?;
-            
-            // Get commodities
-            let commodities_result = client.get_commodities().await.ok()?;
-            let commodities = commodities_result.data;
-            
-            // Get ALL trade commodities (not just 25)
-            let trade_commodities: Vec<_> = commodities.iter()
-                .filter(|c| matches!(c.category.as_str(), 
-                    "Agricultural" | "Food" | "Gas" | "Medical" | "Metal" | 
-                    "Mineral" | "Scrap" | "Vice" | "Drug" | "Hallucinogen"))
-                .collect();
-            
-            println!("[routes] Loading prices for {} commodities...", trade_commodities.len());
-            
-            let mut all_routes = Vec::new();
-            
-            for commodity in trade_commodities {
-                if let Ok(prices_result) = client.get_prices(&commodity.id, Some(&commodity.name)).await {
-                    let routes = calculate_routes_for_commodity(
-                        &commodity.id,
-                        &commodity.name,
-                        false, // TODO: get is_illegal from commodity
-                        &prices_result.data,
-                        &nqa,
-                    );
-                    all_routes.extend(routes);
-                }
-            }
-            
-            println!("[routes] Calculated {} total routes", all_routes.len());
-            
-            // Save to cache
-            let cache = RoutesCache::new(all_routes.clone());
-            let _ = save_routes_cache(&cache);
-            
-            // Reset force refresh flag
-            if refresh {
-                // Note: can't set signal in async, but the flag served its purpose
-            }
-            
-            Some(all_routes)
+            load_routes(&client, &nqa, refresh).await.ok()
         }
     });
     
@@ -226,17 +94,21 @@ pub fn RoutesPage() -> Element {
         }
     }
     
-    // Pirate filter: only high value cargo (>5000 aUEC/SCU)
+    let config = config::current();
+
+    // Pirate filter: only high value cargo (`ranking.pirate_min_buy_price`, 5000 aUEC/SCU by default)
     if only_high_value() {
-        routes.retain(|r| r.buy_price >= 5000.0);
+        let min_price = config.ranking.pirate_min_buy_price;
+        routes.retain(|r| r.buy_price >= min_price);
     }
     
     // Sort
     let scu = scu_input();
     sort_routes(&mut routes, sort_by(), scu, true);
     
-    // Limit to top 100
-    routes.truncate(100);
+    // Limit to top N (`ui.max_routes`)
+    routes.truncate(config.ui.max_routes);
+    let routes_export = ExportPayload::Routes(route_rows(&routes, scu));
     
     rsx! {
         div { class: "space-y-6",
@@ -244,88 +116,86 @@ pub fn RoutesPage() -> Element {
             section {
                 class: "flex flex-wrap items-center justify-between gap-4",
                 div {
-                    h2 { class: "text-xl font-semibold text-slate-100", 
-                        if is_pirate { "Lukrative Routen" } else { "Trade Routes" }
+                    h2 { class: "text-xl font-semibold cvs-text", 
+                        if is_pirate { {tr!("routes.title_pirate")} } else { {tr!("routes.title_trader")} }
                     }
-                    p { class: "text-sm text-slate-400", 
+                    p { class: "text-sm cvs-text-muted", 
                         if is_pirate {
-                            "wo sich das lauern lohnt — wertvolle fracht, viel traffic"
+                            {tr!("routes.subtitle_pirate")}
                         } else {
-                            "profitable routen zum handeln — buy low, sell high"
+                            {tr!("routes.subtitle_trader")}
                         }
                     }
                 }
+                if !routes_loading {
+                    ExportButtons { payload: routes_export }
+                }
                 if routes_loading {
                     div { 
-                        class: match profile {
-                            Profile::Pirate => "flex items-center gap-2 text-[#ff9900]",
-                            Profile::Trader => "flex items-center gap-2 text-sky-400",
-                            Profile::Miner => "flex items-center gap-2 text-orange-400",
-                            Profile::None => "flex items-center gap-2 text-indigo-400",
-                        },
+                        class: "flex items-center gap-2 {theme::text_primary()}",
                         span { class: "animate-spin", "⟳" }
-                        span { class: "text-sm", "lade preise..." }
+                        span { class: "text-sm", {tr!("routes.loading")} }
                     }
                 }
             }
             
             // Filters - different for pirate vs trader
             div {
-                class: "{panel_border(profile)} p-5 space-y-4",
+                class: "{theme::panel_border()} p-5 space-y-4",
                 
                 // Row 1: Main filter buttons (always same elements)
                 div { class: "flex flex-wrap gap-x-8 gap-y-4 items-start",
                     
                     // Stellar/Interstellar toggle (both profiles)
                     div {
-                        label { class: "block text-xs font-semibold uppercase text-slate-500 mb-2", "Reichweite" }
+                        label { class: "block text-xs font-semibold uppercase cvs-text-muted mb-2", {tr!("routes.scope")} }
                         div { class: "flex gap-2",
                             // Stellar with sub-menu
                             div { class: "flex flex-col items-center",
                                 button {
                                     class: if trade_scope() == TradeScope::Stellar {
-                                        format!("w-full {}", btn_active(profile))
+                                        format!("w-full {}", theme::btn_active())
                                     } else {
-                                        format!("w-full {}", btn_inactive(profile))
+                                        format!("w-full {}", theme::btn_inactive())
                                     },
                                     onclick: move |_| trade_scope.set(TradeScope::Stellar),
-                                    "🌍 Stellar"
+                                    {format!("🌍 {}", tr!("routes.stellar"))}
                                 }
                                 // System sub-buttons (only when stellar)
                                 if trade_scope() == TradeScope::Stellar {
                                     div { class: "flex gap-1 mt-2",
                                         button {
                                             class: if system_filter().is_empty() {
-                                                btn_small_active(profile)
+                                                theme::btn_small_active()
                                             } else {
-                                                btn_small_inactive(profile)
+                                                theme::btn_small_inactive()
                                             },
                                             onclick: move |_| system_filter.set(String::new()),
-                                            "Alle"
+                                            {tr!("routes.all_systems")}
                                         }
                                         button {
                                             class: if system_filter() == "Stanton" {
-                                                btn_small_active(profile)
+                                                theme::btn_small_active()
                                             } else {
-                                                btn_small_inactive(profile)
+                                                theme::btn_small_inactive()
                                             },
                                             onclick: move |_| system_filter.set("Stanton".to_string()),
                                             "Stanton"
                                         }
                                         button {
                                             class: if system_filter() == "Pyro" {
-                                                btn_small_active(profile)
+                                                theme::btn_small_active()
                                             } else {
-                                                btn_small_inactive(profile)
+                                                theme::btn_small_inactive()
                                             },
                                             onclick: move |_| system_filter.set("Pyro".to_string()),
                                             "Pyro"
                                         }
                                         button {
                                             class: if system_filter() == "Nyx" {
-                                                btn_small_active(profile)
+                                                theme::btn_small_active()
                                             } else {
-                                                btn_small_inactive(profile)
+                                                theme::btn_small_inactive()
                                             },
                                             onclick: move |_| system_filter.set("Nyx".to_string()),
                                             "Nyx"
@@ -335,12 +205,12 @@ pub fn RoutesPage() -> Element {
                             }
                             button {
                                 class: if trade_scope() == TradeScope::Interstellar {
-                                    format!("{} self-start", btn_active(profile))
+                                    format!("{} self-start", theme::btn_active())
                                 } else {
-                                    format!("{} self-start", btn_inactive(profile))
+                                    format!("{} self-start", theme::btn_inactive())
                                 },
                                 onclick: move |_| trade_scope.set(TradeScope::Interstellar),
-                                "🚀 Interstellar"
+                                {format!("🚀 {}", tr!("routes.interstellar"))}
                             }
                         }
                     }
@@ -348,11 +218,11 @@ pub fn RoutesPage() -> Element {
                     // Stations only toggle (skip planetary landings)
                     if !is_pirate {
                         div {
-                            label { class: "block text-xs font-semibold uppercase text-slate-500 mb-2", "Typ" }
+                            label { class: "block text-xs font-semibold uppercase cvs-text-muted mb-2", {tr!("routes.type")} }
                             button {
-                                class: if stations_only() { btn_active(profile) } else { btn_inactive(profile) },
+                                class: if stations_only() { theme::btn_active() } else { theme::btn_inactive() },
                                 onclick: move |_| stations_only.set(!stations_only()),
-                                "🛰️ Nur Stationen"
+                                {format!("🛰️ {}", tr!("routes.stations_only"))}
                             }
                         }
                     }
@@ -360,9 +230,9 @@ pub fn RoutesPage() -> Element {
                     // Trader-only: SCU input
                     if !is_pirate {
                         div {
-                            label { class: "block text-xs font-semibold uppercase text-slate-500 mb-2", "SCU" }
+                            label { class: "block text-xs font-semibold uppercase cvs-text-muted mb-2", "SCU" }
                             input {
-                                class: format!("w-32 {}", input_class(profile)),
+                                class: format!("w-32 {}", theme::input_class()),
                                 r#type: "text",
                                 inputmode: "numeric",
                                 value: "{scu_input}",
@@ -378,10 +248,10 @@ pub fn RoutesPage() -> Element {
                     // Trader-only: Max invest
                     if !is_pirate {
                         div {
-                            label { class: "block text-xs font-semibold uppercase text-slate-500 mb-2", "Max Invest" }
+                            label { class: "block text-xs font-semibold uppercase cvs-text-muted mb-2", {tr!("routes.max_invest")} }
                             input {
-                                class: format!("w-40 {}", input_class(profile)),
-                                placeholder: "z.B. 100000",
+                                class: format!("w-40 {}", theme::input_class()),
+                                placeholder: tr!("routes.max_invest_placeholder"),
                                 value: "{max_invest_input}",
                                 oninput: move |e| max_invest_input.set(e.value()),
                             }
@@ -391,21 +261,21 @@ pub fn RoutesPage() -> Element {
                     // Pirate-only: High value toggle
                     if is_pirate {
                         div {
-                            label { class: "block text-xs font-semibold uppercase text-slate-500 mb-2", "Filter" }
+                            label { class: "block text-xs font-semibold uppercase cvs-text-muted mb-2", {tr!("routes.filter")} }
                             button {
-                                class: if only_high_value() { btn_active(profile) } else { btn_inactive(profile) },
+                                class: if only_high_value() { theme::btn_active() } else { theme::btn_inactive() },
                                 onclick: move |_| only_high_value.set(!only_high_value()),
-                                "💎 Nur Wertvoll"
+                                {format!("💎 {}", tr!("routes.high_value_only"))}
                             }
                         }
                     }
                     
                     // Refresh button
                     div {
-                        label { class: "block text-xs font-semibold uppercase text-slate-500 mb-2 invisible", "Refresh" }
+                        label { class: "block text-xs font-semibold uppercase cvs-text-muted mb-2 invisible", {tr!("common.refresh")} }
                         button {
-                            class: btn_inactive(profile),
-                            title: "Preise neu laden",
+                            class: theme::btn_inactive(),
+                            title: tr!("routes.reload_prices"),
                             onclick: move |_| force_refresh.set(true),
                             "🔄"
                         }
@@ -415,17 +285,17 @@ pub fn RoutesPage() -> Element {
                 // Row 2: Sort buttons (centered)
                 div { class: "flex justify-center pt-2",
                     div {
-                        label { class: "block text-xs font-semibold uppercase text-slate-500 mb-2 text-center", "Sortieren" }
+                        label { class: "block text-xs font-semibold uppercase cvs-text-muted mb-2 text-center", {tr!("routes.sort")} }
                         div { class: "flex gap-2",
                             if is_pirate {
-                                SortButton { current: sort_by(), target: TradeRouteSort::CargoValue, on_click: move |_| sort_by.set(TradeRouteSort::CargoValue), label: "Wert", profile: profile }
-                                SortButton { current: sort_by(), target: TradeRouteSort::ActivityScore, on_click: move |_| sort_by.set(TradeRouteSort::ActivityScore), label: "Traffic", profile: profile }
-                                SortButton { current: sort_by(), target: TradeRouteSort::ProfitPerGm, on_click: move |_| sort_by.set(TradeRouteSort::ProfitPerGm), label: "Profit/Gm", profile: profile }
+                                SortButton { current: sort_by(), target: TradeRouteSort::CargoValue, on_click: move |_| sort_by.set(TradeRouteSort::CargoValue), label: TradeRouteSort::CargoValue.label() }
+                                SortButton { current: sort_by(), target: TradeRouteSort::ActivityScore, on_click: move |_| sort_by.set(TradeRouteSort::ActivityScore), label: TradeRouteSort::ActivityScore.label() }
+                                SortButton { current: sort_by(), target: TradeRouteSort::ProfitPerGm, on_click: move |_| sort_by.set(TradeRouteSort::ProfitPerGm), label: TradeRouteSort::ProfitPerGm.label() }
                             } else {
-                                SortButton { current: sort_by(), target: TradeRouteSort::ProfitPerGm, on_click: move |_| sort_by.set(TradeRouteSort::ProfitPerGm), label: "Profit/Gm", profile: profile }
-                                SortButton { current: sort_by(), target: TradeRouteSort::RoiPercent, on_click: move |_| sort_by.set(TradeRouteSort::RoiPercent), label: "ROI %", profile: profile }
-                                SortButton { current: sort_by(), target: TradeRouteSort::ProfitTotal, on_click: move |_| sort_by.set(TradeRouteSort::ProfitTotal), label: "Profit", profile: profile }
-                                SortButton { current: sort_by(), target: TradeRouteSort::CargoValue, on_click: move |_| sort_by.set(TradeRouteSort::CargoValue), label: "Wert", profile: profile }
+                                SortButton { current: sort_by(), target: TradeRouteSort::ProfitPerGm, on_click: move |_| sort_by.set(TradeRouteSort::ProfitPerGm), label: TradeRouteSort::ProfitPerGm.label() }
+                                SortButton { current: sort_by(), target: TradeRouteSort::RoiPercent, on_click: move |_| sort_by.set(TradeRouteSort::RoiPercent), label: TradeRouteSort::RoiPercent.label() }
+                                SortButton { current: sort_by(), target: TradeRouteSort::ProfitTotal, on_click: move |_| sort_by.set(TradeRouteSort::ProfitTotal), label: TradeRouteSort::ProfitTotal.label() }
+                                SortButton { current: sort_by(), target: TradeRouteSort::CargoValue, on_click: move |_| sort_by.set(TradeRouteSort::CargoValue), label: TradeRouteSort::CargoValue.label() }
                             }
                         }
                     }
@@ -443,32 +313,64 @@ pub fn RoutesPage() -> Element {
             
             // Routes table
             if !routes.is_empty() {
-                div { class: table_container(profile),
+                div {
+                    class: theme::table_container(),
+                    tabindex: 0,
+                    title: tr!("routes.keyboard_hint"),
+                    onkeydown: {
+                        let routes = routes.clone();
+                        move |evt: KeyboardEvent| {
+                            let last = routes.len().saturating_sub(1);
+                            let current = cursor().map(|c| c.min(last));
+                            let target = match evt.key() {
+                                Key::ArrowDown => Some(current.map_or(0, |c| (c + 1).min(last))),
+                                Key::ArrowUp => Some(current.map_or(0, |c| c.saturating_sub(1))),
+                                Key::Enter => {
+                                    if let (Some(c), false) = (current, is_pirate) {
+                                        selected_route.set(routes.get(c).cloned());
+                                    }
+                                    None
+                                }
+                                Key::Escape | Key::Delete => {
+                                    selected_route.set(None);
+                                    None
+                                }
+                                _ => return,
+                            };
+                            evt.prevent_default();
+                            if let Some(index) = target {
+                                cursor.set(Some(index));
+                                keyboard::scroll_into_view(&route_row_id(index));
+                            }
+                        }
+                    },
                     div { class: "overflow-x-auto",
                         table { class: "w-full text-sm",
-                            thead { class: table_header(profile),
+                            thead { class: theme::table_header(),
                                 tr {
-                                    th { class: "px-4 py-3 text-left", "Ware" }
+                                    th { class: "px-4 py-3 text-left", {tr!("table.commodity")} }
                                     th { class: "px-4 py-3 text-left", 
-                                        if is_pirate { "Route (Start → Ziel)" } else { "Kaufen bei" }
+                                        if is_pirate { {tr!("routes.col.route")} } else { {tr!("routes.col.buy_at")} }
                                     }
                                     if !is_pirate {
-                                        th { class: "px-4 py-3 text-left", "Verkaufen bei" }
+                                        th { class: "px-4 py-3 text-left", {tr!("routes.col.sell_at")} }
                                     }
                                     th { class: "px-4 py-3 text-right", 
-                                        if is_pirate { "Wert/SCU" } else { "Invest" }
+                                        if is_pirate { {tr!("routes.col.value_per_scu")} } else { {tr!("routes.col.invest")} }
                                     }
-                                    th { class: "px-4 py-3 text-right", "Profit" }
+                                    th { class: "px-4 py-3 text-right", {tr!("routes.col.profit")} }
                                     if !is_pirate {
                                         th { class: "px-4 py-3 text-right", "ROI" }
                                     }
-                                    th { class: "px-4 py-3 text-right", "Traffic" }
+                                    th { class: "px-4 py-3 text-right", {tr!("routes.col.traffic")} }
                                 }
                             }
-                            tbody { class: table_divider(profile),
-                                for route in routes.iter() {
+                            tbody { class: theme::table_divider(),
+                                for (index, route) in routes.iter().enumerate() {
                                     RouteRow { 
                                         route: route.clone(), 
+                                        index,
+                                        highlighted: cursor() == Some(index),
                                         scu: scu_input(), 
                                         is_pirate: is_pirate,
                                         on_click: if !is_pirate {
@@ -482,13 +384,13 @@ pub fn RoutesPage() -> Element {
                         }
                     }
                 }
-                p { class: "text-xs text-slate-500 text-center", 
-                    "zeigt top {routes.len()} routen" 
+                p { class: "text-xs cvs-text-muted text-center", 
+                    {tr!("routes.showing_top", count = routes.len())}
                 }
             } else if !routes_loading {
                 div {
-                    class: format!("{} px-6 py-12 text-center", panel_border(profile)),
-                    p { class: "text-slate-400", "keine profitablen routen gefunden" }
+                    class: format!("{} px-6 py-12 text-center", theme::panel_border()),
+                    p { class: "cvs-text-muted", {tr!("routes.none_found")} }
                 }
             }
         }
@@ -500,21 +402,11 @@ fn SortButton(
     current: TradeRouteSort,
     target: TradeRouteSort,
     on_click: EventHandler<()>,
-    label: &'static str,
-    profile: Profile,
+    label: String,
 ) -> Element {
     let active = current == target;
-    let class = match (profile, active) {
-        (Profile::Pirate, true) => "rounded-lg px-4 py-2 text-sm font-semibold bg-[#3b1712] text-[#ff9900] border border-[#5c2a1f] drake-glow",
-        (Profile::Pirate, false) => "rounded-lg px-4 py-2 text-sm text-[#d4523a]/70 hover:text-[#ff9900] border border-transparent hover:border-[#5c2a1f]",
-        (Profile::Trader, true) => "rounded-lg px-4 py-2 text-sm font-semibold bg-sky-500/20 text-sky-300 border border-sky-500/40 misc-glow",
-        (Profile::Trader, false) => "rounded-lg px-4 py-2 text-sm text-slate-400 hover:text-sky-300 border border-transparent hover:border-sky-700",
-        (Profile::Miner, true) => "rounded-lg px-4 py-2 text-sm font-semibold bg-orange-500/20 text-orange-300 border border-orange-500/40 argo-glow",
-        (Profile::Miner, false) => "rounded-lg px-4 py-2 text-sm text-slate-400 hover:text-orange-300 border border-transparent hover:border-orange-700",
-        (_, true) => "rounded-lg px-4 py-2 text-sm font-semibold bg-indigo-500/20 text-indigo-300 border border-indigo-500/40",
-        (_, false) => "rounded-lg px-4 py-2 text-sm text-slate-400 hover:text-slate-200 border border-transparent hover:border-slate-700",
-    };
-    
+    let class = theme::btn_toggle(active);
+
     rsx! {
         button {
             class: "{class}",
@@ -524,9 +416,15 @@ fn SortButton(
     }
 }
 
+fn route_row_id(index: usize) -> String {
+    format!("route-row-{index}")
+}
+
 #[component]
 fn RouteRow(
     route: TradeRoute,
+    index: usize,
+    highlighted: bool,
     scu: u32,
     is_pirate: bool,
     on_click: Option<EventHandler<TradeRoute>>,
@@ -540,19 +438,20 @@ fn RouteRow(
     } else if activity >= 5 {
         "text-yellow-400"
     } else {
-        "text-slate-500"
+        "cvs-text-muted"
     };
     
-    let row_class = if on_click.is_some() {
-        "hover:bg-slate-800/50 transition-colors cursor-pointer"
-    } else {
-        "hover:bg-slate-800/50 transition-colors"
+    let row_class = match (on_click.is_some(), highlighted) {
+        (true, _) => theme::table_row(highlighted),
+        (false, true) => "cvs-row cvs-row-selected",
+        (false, false) => "cvs-row",
     };
     
     let route_for_click = route.clone();
     
     rsx! {
         tr { 
+            id: route_row_id(index),
             class: "{row_class}",
             onclick: move |_| {
                 if let Some(ref handler) = on_click {
@@ -562,7 +461,7 @@ fn RouteRow(
             // Commodity
             td { class: "px-4 py-3",
                 div { class: "flex items-center gap-2",
-                    span { class: "text-slate-100 font-medium", "{route.commodity_name}" }
+                    span { class: "cvs-text font-medium", "{route.commodity_name}" }
                     if route.is_illegal {
                         span { class: "text-[10px] text-red-400", "⚠️" }
                     }
@@ -576,10 +475,10 @@ fn RouteRow(
                 // Pirate: show route as single cell (Start → Ziel)
                 td { class: "px-4 py-3",
                     div {
-                        p { class: "text-slate-200", 
+                        p { class: "cvs-text", 
                             "{short_name(&route.buy_terminal_name)} → {short_name(&route.sell_terminal_name)}" 
                         }
-                        p { class: "text-xs text-slate-500",
+                        p { class: "text-xs cvs-text-muted",
                             "{route.buy_system.as_deref().unwrap_or(\"?\")} → {route.sell_system.as_deref().unwrap_or(\"?\")}"
                         }
                     }
@@ -588,44 +487,44 @@ fn RouteRow(
                 // Trader: separate buy/sell columns
                 td { class: "px-4 py-3",
                     div {
-                        p { class: "text-slate-200", "{short_name(&route.buy_terminal_name)}" }
-                        p { class: "text-xs text-slate-500", 
-                            "{route.buy_system.as_deref().unwrap_or(\"\")} · {route.buy_price:.0} aUEC"
+                        p { class: "cvs-text", "{short_name(&route.buy_terminal_name)}" }
+                        p { class: "text-xs cvs-text-muted", 
+                            "{route.buy_system.as_deref().unwrap_or(\"\")} · {format::auec(route.buy_price)}"
                         }
                     }
                 }
                 td { class: "px-4 py-3",
                     div {
-                        p { class: "text-slate-200", "{short_name(&route.sell_terminal_name)}" }
-                        p { class: "text-xs text-slate-500",
-                            "{route.sell_system.as_deref().unwrap_or(\"\")} · {route.sell_price:.0} aUEC"
+                        p { class: "cvs-text", "{short_name(&route.sell_terminal_name)}" }
+                        p { class: "text-xs cvs-text-muted",
+                            "{route.sell_system.as_deref().unwrap_or(\"\")} · {format::auec(route.sell_price)}"
                         }
                     }
                 }
             }
             
             // Value/Invest
-            td { class: "px-4 py-3 text-right text-slate-300",
+            td { class: "px-4 py-3 text-right cvs-text",
                 if is_pirate {
-                    "{route.buy_price:.0}"
+                    "{format::integer(route.buy_price)}"
                 } else {
-                    "{format_auec(qty.invest)}"
+                    "{format::compact(qty.invest)}"
                 }
             }
             
             // Profit
             td { class: "px-4 py-3 text-right font-semibold text-amber-400",
                 if is_pirate {
-                    "+{route.profit_per_scu:.0}"
+                    "+{format::integer(route.profit_per_scu)}"
                 } else {
-                    "+{format_auec(qty.profit_total)}"
+                    "+{format::compact(qty.profit_total)}"
                 }
             }
             
             // ROI (trader only)
             if !is_pirate {
-                td { class: "px-4 py-3 text-right text-indigo-300",
-                    "{route.roi_percent:.1}%"
+                td { class: "px-4 py-3 text-right {theme::text_primary()}",
+                    "{format::number(route.roi_percent, 1)}%"
                 }
             }
             
@@ -650,35 +549,6 @@ fn short_name(name: &str) -> &str {
         .unwrap_or(name)
 }
 
-fn format_auec(value: f64) -> String {
-    let rounded = value.round() as i64;
-    if rounded.abs() >= 1_000_000 {
-        format!("{:.1}M", value / 1_000_000.0)
-    } else if rounded.abs() >= 1_000 {
-        format!("{:.0}k", value / 1_000.0)
-    } else {
-        format!("{}", rounded)
-    }
-}
-
-fn format_auec_full(value: f64) -> String {
-    let rounded = value.round() as i64;
-    // Manual thousands formatting
-    let s = format!("{}", rounded.abs());
-    let mut result = String::new();
-    for (i, c) in s.chars().rev().enumerate() {
-        if i > 0 && i % 3 == 0 {
-            result.push('.');
-        }
-        result.push(c);
-    }
-    if rounded < 0 {
-        format!("-{} aUEC", result.chars().rev().collect::<String>())
-    } else {
-        format!("{} aUEC", result.chars().rev().collect::<String>())
-    }
-}
-
 /// Inline panel showing detailed route information.
 #[component]
 fn RouteDetailPanel(
@@ -693,18 +563,18 @@ fn RouteDetailPanel(
     
     rsx! {
         div {
-            class: "rounded-xl border-2 border-sky-500/50 bg-slate-900/80 p-6 mb-4 misc-glow",
+            class: "{theme::panel_solid()} p-6 mb-4",
             // Header
             div {
                 class: "flex items-center justify-between mb-6",
                 div {
-                    h3 { class: "text-xl font-bold text-slate-100", "📦 {route.commodity_name}" }
-                    p { class: "text-sm text-slate-400", "Route Details" }
+                    h3 { class: "text-xl font-bold cvs-text", "📦 {route.commodity_name}" }
+                    p { class: "text-sm cvs-text-muted", {tr!("routes.detail.title")} }
                 }
                 button {
-                    class: "rounded-lg px-3 py-1 text-sm text-slate-400 border border-slate-700 hover:bg-slate-800 hover:text-slate-200",
+                    class: "rounded-lg px-3 py-1 text-sm cvs-btn",
                     onclick: move |_| on_close.call(()),
-                    "✕ Schließen"
+                    {format!("✕ {}", tr!("common.close"))}
                 }
             }
             
@@ -713,32 +583,32 @@ fn RouteDetailPanel(
                 // Buy
                 div {
                     class: "rounded-xl border border-amber-500/30 bg-amber-500/5 p-4",
-                    p { class: "text-xs font-semibold uppercase text-amber-400/70 mb-2", "1️⃣ KAUFEN" }
-                    p { class: "text-lg font-semibold text-slate-100", 
+                    p { class: "text-xs font-semibold uppercase text-amber-400/70 mb-2", {format!("1️⃣ {}", tr!("routes.detail.buy"))} }
+                    p { class: "text-lg font-semibold cvs-text", 
                         "{buy_type} {short_name(&route.buy_terminal_name)}" 
                     }
-                    p { class: "text-sm text-slate-400 mb-3",
+                    p { class: "text-sm cvs-text-muted mb-3",
                         "{route.buy_system.as_deref().unwrap_or(\"?\")}"
                     }
                     div { class: "space-y-1 text-sm",
-                        p { class: "text-slate-300", "× {qty.quantity} SCU @ {route.buy_price:.0} aUEC" }
-                        p { class: "text-amber-300 font-semibold", "Invest: {format_auec_full(qty.invest)}" }
+                        p { class: "cvs-text", "× {qty.quantity} SCU @ {format::auec(route.buy_price)}" }
+                        p { class: "text-amber-300 font-semibold", {tr!("routes.detail.invest", amount = format::auec(qty.invest))} }
                     }
                 }
                 
                 // Sell
                 div {
                     class: "rounded-xl border border-sky-500/30 bg-sky-500/5 p-4",
-                    p { class: "text-xs font-semibold uppercase text-sky-400/70 mb-2", "2️⃣ VERKAUFEN" }
-                    p { class: "text-lg font-semibold text-slate-100", 
+                    p { class: "text-xs font-semibold uppercase text-sky-400/70 mb-2", {format!("2️⃣ {}", tr!("routes.detail.sell"))} }
+                    p { class: "text-lg font-semibold cvs-text", 
                         "{sell_type} {short_name(&route.sell_terminal_name)}" 
                     }
-                    p { class: "text-sm text-slate-400 mb-3",
+                    p { class: "text-sm cvs-text-muted mb-3",
                         "{route.sell_system.as_deref().unwrap_or(\"?\")}"
                     }
                     div { class: "space-y-1 text-sm",
-                        p { class: "text-slate-300", "× {qty.quantity} SCU @ {route.sell_price:.0} aUEC" }
-                        p { class: "text-sky-300 font-semibold", "Erlös: {format_auec_full(qty.quantity as f64 * route.sell_price)}" }
+                        p { class: "cvs-text", "× {qty.quantity} SCU @ {format::auec(route.sell_price)}" }
+                        p { class: "text-sky-300 font-semibold", {tr!("routes.detail.revenue", amount = format::auec(qty.quantity as f64 * route.sell_price))} }
                     }
                 }
             }
@@ -747,7 +617,7 @@ fn RouteDetailPanel(
             if qty.quantity < scu {
                 div {
                     class: "rounded-lg border border-amber-500/30 bg-amber-500/10 px-4 py-2 mb-4 text-sm text-amber-200",
-                    "⚠️ Nur {qty.max_tradeable} SCU handelbar (Stock/Nachfrage limitiert)"
+                    {format!("⚠️ {}", tr!("routes.detail.limited", scu = qty.max_tradeable))}
                 }
             }
             
@@ -755,16 +625,16 @@ fn RouteDetailPanel(
             div {
                 class: "flex items-center justify-between rounded-xl bg-sky-950/40 border border-sky-900/30 px-6 py-4",
                 div {
-                    p { class: "text-xs text-slate-500 uppercase", "Profit" }
-                    p { class: "text-2xl font-bold text-amber-400", "+{format_auec_full(qty.profit_total)}" }
+                    p { class: "text-xs cvs-text-muted uppercase", {tr!("routes.col.profit")} }
+                    p { class: "text-2xl font-bold text-amber-400", "+{format::auec(qty.profit_total)}" }
                 }
                 div { class: "text-center",
-                    p { class: "text-xs text-slate-500 uppercase", "ROI" }
-                    p { class: "text-xl font-semibold text-sky-300", "{route.roi_percent:.1}%" }
+                    p { class: "text-xs cvs-text-muted uppercase", "ROI" }
+                    p { class: "text-xl font-semibold text-sky-300", "{format::number(route.roi_percent, 1)}%" }
                 }
                 div { class: "text-right",
-                    p { class: "text-xs text-slate-500 uppercase", "Profit/SCU" }
-                    p { class: "text-lg text-slate-200", "{route.profit_per_scu:.0} aUEC" }
+                    p { class: "text-xs cvs-text-muted uppercase", {tr!("routes.detail.profit_per_scu")} }
+                    p { class: "text-lg cvs-text", "{format::auec(route.profit_per_scu)}" }
                 }
             }
         }
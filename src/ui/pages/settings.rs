@@ -3,12 +3,20 @@ use dioxus::prelude::*;
 use crate::{
     app::persist_user_state,
     domain::{AppState, CacheResource, Profile, ProfitabilityParams},
+    infra::{
+        config::{self, AppConfig, WebhookConfig, WebhookTarget},
+        webhook::{self, ShareKind},
+    },
     ui::{
         components::toast::{push_toast, ToastKind, ToastMessage},
+        i18n::{self, tr, LOCALE},
         pages::cargo::request_price_fetch,
+        theme,
     },
     util::{
-        assets,
+        assets, format,
+        i18n::Locale,
+        palette::{Palette, ThemeChoice},
         version::{self, APP_AUTHOR, APP_NAME, APP_REPO_URL},
     },
 };
@@ -32,7 +40,7 @@ pub fn SettingsPage() -> Element {
             .map(|(resource, time)| {
                 (
                     cache_label(resource),
-                    crate::ui::pages::cargo::humanize_age(*time),
+                    format::relative_time(*time),
                 )
             })
             .collect::<Vec<_>>()
@@ -56,7 +64,7 @@ pub fn SettingsPage() -> Element {
                     push_toast(
                         toasts.clone(),
                         ToastKind::Success,
-                        "Updated profitability parameters.",
+                        tr!("settings.toast.params_updated"),
                     );
                 }
                 Err(message) => {
@@ -70,7 +78,7 @@ pub fn SettingsPage() -> Element {
         let mut state = state.clone();
         let toasts = toasts.clone();
         move |_| {
-            let defaults = ProfitabilityParams::default();
+            let defaults = config::current().profiles.default_profitability;
             risk_pct_input.set(format!("{:.2}", defaults.risk_pct));
             crew_hourly_input.set(format!("{:.0}", defaults.crew_hourly));
             crew_size_input.set(defaults.crew_size.to_string());
@@ -80,7 +88,7 @@ pub fn SettingsPage() -> Element {
             push_toast(
                 toasts.clone(),
                 ToastKind::Info,
-                "Restored default profitability parameters.",
+                tr!("settings.toast.params_reset"),
             );
         }
     };
@@ -125,7 +133,7 @@ pub fn SettingsPage() -> Element {
             push_toast(
                 toasts.clone(),
                 ToastKind::Info,
-                "Cleared cached timestamps. Data will refresh on next fetch.",
+                tr!("settings.toast.cache_cleared"),
             );
         }
     };
@@ -136,8 +144,7 @@ pub fn SettingsPage() -> Element {
         let price_request = price_request.clone();
         move |_| {
             let commodities: Vec<_> = state.with(|st| {
-                st.cargo_items
-                    .iter()
+                st.all_cargo_items()
                     .map(|item| item.commodity_id.clone())
                     .collect()
             });
@@ -146,10 +153,10 @@ pub fn SettingsPage() -> Element {
                 push_toast(
                     toasts.clone(),
                     ToastKind::Info,
-                    "Refreshing price data for tracked commodities...",
+                    tr!("settings.toast.refreshing"),
                 );
             } else {
-                push_toast(toasts.clone(), ToastKind::Warning, "No cargo items yet.");
+                push_toast(toasts.clone(), ToastKind::Warning, tr!("settings.toast.no_cargo"));
             }
         }
     };
@@ -170,106 +177,112 @@ pub fn SettingsPage() -> Element {
     
     rsx! {
         div { class: "space-y-8",
+            LanguageSection {}
+            AppearanceSection {}
+
             // Profile section
             section {
-                class: "rounded-xl border border-slate-800 bg-slate-900/40 p-6",
-                h2 { class: "text-sm font-semibold uppercase tracking-wide text-slate-500", "Profil" }
+                class: "rounded-xl border cvs-border cvs-surface p-6",
+                h2 { class: "text-sm font-semibold uppercase tracking-wide cvs-text-muted", {tr!("settings.profile.title")} }
                 div { class: "mt-4 flex items-center justify-between",
                     div { class: "flex items-center gap-3",
                         span { class: "text-3xl", "{current_profile.emoji()}" }
                         div {
-                            p { class: "font-semibold text-slate-100", "{current_profile.name()}" }
-                            p { class: "text-xs text-slate-500", "Aktives Spielerprofil" }
+                            p { class: "font-semibold cvs-text", "{current_profile.name()}" }
+                            p { class: "text-xs cvs-text-muted", {tr!("settings.profile.active")} }
                         }
                     }
                     button {
-                        class: "rounded-lg border border-slate-600 px-4 py-2 text-xs font-semibold uppercase tracking-wide text-slate-200 hover:bg-slate-800",
+                        class: "rounded-lg px-4 py-2 text-xs font-semibold uppercase tracking-wide cvs-btn",
                         onclick: on_change_profile,
-                        "Profil wechseln"
+                        {tr!("settings.profile.change")}
                     }
                 }
             }
             
             section {
-                class: "rounded-xl border border-slate-800 bg-slate-900/40 p-6",
-                h2 { class: "text-sm font-semibold uppercase tracking-wide text-slate-500", "Profitability Parameters" }
+                class: "rounded-xl border cvs-border cvs-surface p-6",
+                h2 { class: "text-sm font-semibold uppercase tracking-wide cvs-text-muted", {tr!("settings.params.title")} }
                 div { class: "mt-4 grid gap-4 sm:grid-cols-2",
                     div {
-                        label { class: "block text-xs font-semibold uppercase text-slate-500", "Risk % (0-0.40)" }
+                        label { class: "block text-xs font-semibold uppercase cvs-text-muted", {tr!("settings.params.risk")} }
                         input {
-                            class: "mt-1 w-full rounded-lg border border-slate-700 bg-slate-950 px-3 py-2 text-sm text-slate-100 focus:border-indigo-500 focus:outline-none",
+                            class: "mt-1 w-full {theme::input_small()}",
                             value: risk_pct_input(),
                             oninput: move |evt| risk_pct_input.set(evt.value()),
                         }
                     }
                     div {
-                        label { class: "block text-xs font-semibold uppercase text-slate-500", "Crew hourly cost" }
+                        label { class: "block text-xs font-semibold uppercase cvs-text-muted", {tr!("settings.params.crew_hourly")} }
                         input {
-                            class: "mt-1 w-full rounded-lg border border-slate-700 bg-slate-950 px-3 py-2 text-sm text-slate-100 focus:border-indigo-500 focus:outline-none",
+                            class: "mt-1 w-full {theme::input_small()}",
                             value: crew_hourly_input(),
                             oninput: move |evt| crew_hourly_input.set(evt.value()),
                         }
                     }
                     div {
-                        label { class: "block text-xs font-semibold uppercase text-slate-500", "Crew size" }
+                        label { class: "block text-xs font-semibold uppercase cvs-text-muted", {tr!("settings.params.crew_size")} }
                         input {
-                            class: "mt-1 w-full rounded-lg border border-slate-700 bg-slate-950 px-3 py-2 text-sm text-slate-100 focus:border-indigo-500 focus:outline-none",
+                            class: "mt-1 w-full {theme::input_small()}",
                             value: crew_size_input(),
                             oninput: move |evt| crew_size_input.set(evt.value()),
                         }
                     }
                     div {
-                        label { class: "block text-xs font-semibold uppercase text-slate-500", "Trip time (minutes)" }
+                        label { class: "block text-xs font-semibold uppercase cvs-text-muted", {tr!("settings.params.trip_time")} }
                         input {
-                            class: "mt-1 w-full rounded-lg border border-slate-700 bg-slate-950 px-3 py-2 text-sm text-slate-100 focus:border-indigo-500 focus:outline-none",
+                            class: "mt-1 w-full {theme::input_small()}",
                             value: time_minutes_input(),
                             oninput: move |evt| time_minutes_input.set(evt.value()),
                         }
                     }
                 }
                 div { class: "mt-4 flex gap-3",
-                    button { class: "rounded-lg bg-indigo-500 px-4 py-2 text-xs font-semibold uppercase tracking-wide text-white hover:bg-indigo-400", onclick: on_apply, "Apply" }
-                    button { class: "rounded-lg border border-slate-600 px-4 py-2 text-xs font-semibold uppercase tracking-wide text-slate-200 hover:bg-slate-800", onclick: on_reset, "Reset Defaults" }
+                    button { class: "rounded-lg px-4 py-2 text-xs font-semibold uppercase tracking-wide cvs-btn-primary", onclick: on_apply, {tr!("common.apply")} }
+                    button { class: "rounded-lg px-4 py-2 text-xs font-semibold uppercase tracking-wide cvs-btn", onclick: on_reset, {tr!("settings.params.reset")} }
                 }
             }
 
+            ConfigSection {}
+            WebhooksSection {}
+
             section {
-                class: "rounded-xl border border-slate-800 bg-slate-900/40 p-6",
-                h2 { class: "text-sm font-semibold uppercase tracking-wide text-slate-500", "Cache Status" }
+                class: "rounded-xl border cvs-border cvs-surface p-6",
+                h2 { class: "text-sm font-semibold uppercase tracking-wide cvs-text-muted", {tr!("settings.cache.title")} }
                 if cache_entries.is_empty() {
-                    p { class: "mt-3 text-sm text-slate-400", "No cached fetches yet." }
+                    p { class: "mt-3 text-sm cvs-text-muted", {tr!("settings.cache.empty")} }
                 } else {
                     ul {
-                        class: "mt-3 space-y-2 text-sm text-slate-300",
+                        class: "mt-3 space-y-2 text-sm cvs-text",
                         for (label, age) in cache_entries {
-                            li { class: "flex items-center justify-between rounded-lg border border-slate-800 bg-slate-900/60 px-3 py-2",
+                            li { class: "flex items-center justify-between rounded-lg border cvs-border cvs-surface px-3 py-2",
                                 span { "{label}" }
-                                span { class: "text-xs text-slate-500", "{age}" }
+                                span { class: "text-xs cvs-text-muted", "{age}" }
                             }
                         }
                     }
                 }
-                button { class: "mt-4 rounded-lg border border-amber-500/40 px-4 py-2 text-xs font-semibold uppercase tracking-wide text-amber-200 hover:bg-amber-500/10", onclick: on_clear_cache, "Clear Cache Timestamps" }
+                button { class: "mt-4 rounded-lg border border-amber-500/40 px-4 py-2 text-xs font-semibold uppercase tracking-wide text-amber-200 hover:bg-amber-500/10", onclick: on_clear_cache, {tr!("settings.cache.clear")} }
             }
 
             section {
-                class: "rounded-xl border border-slate-800 bg-slate-900/40 p-6",
-                h2 { class: "text-sm font-semibold uppercase tracking-wide text-slate-500", "Data Controls" }
-                p { class: "mt-2 text-sm text-slate-400", "Trigger background refreshes or inspect the cache lifecycle." }
+                class: "rounded-xl border cvs-border cvs-surface p-6",
+                h2 { class: "text-sm font-semibold uppercase tracking-wide cvs-text-muted", {tr!("settings.data.title")} }
+                p { class: "mt-2 text-sm cvs-text-muted", {tr!("settings.data.subtitle")} }
                 div { class: "mt-3 flex gap-3",
-                    button { class: "rounded-lg border border-indigo-500/40 px-4 py-2 text-xs font-semibold uppercase tracking-wide text-indigo-200 hover:bg-indigo-500/10", onclick: on_refresh_prices, "Refresh Price Data" }
+                    button { class: "rounded-lg px-4 py-2 text-xs font-semibold uppercase tracking-wide cvs-btn-accent", onclick: on_refresh_prices, {tr!("settings.data.refresh")} }
                 }
             }
 
             section {
-                class: "rounded-xl border border-slate-800 bg-slate-900/40 p-6",
+                class: "rounded-xl border cvs-border cvs-surface p-6",
                 div { class: "flex flex-wrap items-center justify-between gap-3",
                     div {
-                        h2 { class: "text-sm font-semibold uppercase tracking-wide text-slate-500", "{APP_NAME}" }
-                        p { class: "text-xs uppercase tracking-wide text-slate-500", "Built by {APP_AUTHOR}" }
+                        h2 { class: "text-sm font-semibold uppercase tracking-wide cvs-text-muted", "{APP_NAME}" }
+                        p { class: "text-xs uppercase tracking-wide cvs-text-muted", {tr!("settings.about.built_by", author = APP_AUTHOR)} }
                     }
                     span {
-                        class: "rounded-full border border-slate-700 bg-slate-950 px-3 py-1 text-xs font-semibold text-slate-200",
+                        class: "rounded-full px-3 py-1 text-xs font-semibold cvs-input",
                         "{current_version_label}"
                     }
                 }
@@ -281,21 +294,21 @@ pub fn SettingsPage() -> Element {
                         class: "rounded-lg border border-emerald-500/40 px-4 py-2 text-xs font-semibold uppercase tracking-wide text-emerald-200 hover:bg-emerald-500/10 disabled:cursor-not-allowed disabled:opacity-60",
                         onclick: on_check_updates,
                         disabled: disable_update_button,
-                        "Check for updates"
+                        {tr!("settings.update.check")}
                     }
                     a {
                         href: APP_REPO_URL,
                         target: "_blank",
                         rel: "noreferrer",
-                        class: "rounded-lg border border-indigo-500/40 px-4 py-2 text-xs font-semibold uppercase tracking-wide text-indigo-200 hover:bg-indigo-500/10",
-                        "Update"
+                        class: "rounded-lg px-4 py-2 text-xs font-semibold uppercase tracking-wide cvs-btn-accent",
+                        {tr!("settings.update.open")}
                     }
                 }
             }
 
             section {
-                class: "flex flex-col items-center gap-3 rounded-xl border border-slate-800 bg-slate-900/40 p-6 text-center text-slate-400",
-                h2 { class: "text-sm font-semibold uppercase tracking-wide text-slate-500", "Data Attribution" }
+                class: "flex flex-col items-center gap-3 rounded-xl border cvs-border cvs-surface p-6 text-center cvs-text-muted",
+                h2 { class: "text-sm font-semibold uppercase tracking-wide cvs-text-muted", {tr!("settings.attribution.title")} }
                 a {
                     href: "https://uexcorp.space",
                     target: "_blank",
@@ -309,11 +322,534 @@ pub fn SettingsPage() -> Element {
                 }
                 p {
                     class: "text-sm",
-                    "Prices and commodity metadata provided courtesy of United Express (UEX)."
+                    {tr!("settings.attribution.body")}
                 }
                 p {
-                    class: "text-xs text-slate-500",
-                    "Thank you to UEX for keeping the ‘verse informed."
+                    class: "text-xs cvs-text-muted",
+                    {tr!("settings.attribution.thanks")}
+                }
+            }
+        }
+    }
+}
+
+/// Interface language; switches immediately and is stored in `ui.locale`.
+#[component]
+fn LanguageSection() -> Element {
+    let toasts = use_context::<Signal<Vec<ToastMessage>>>();
+    let current = LOCALE();
+
+    let on_change = move |evt: FormEvent| {
+        let Ok(locale) = evt.value().parse::<Locale>() else {
+            return;
+        };
+        i18n::set_locale(locale);
+        let mut next = config::file_config();
+        next.ui.locale = locale;
+        if let Err(err) = config::update(next) {
+            push_toast(toasts, ToastKind::Error, tr!("settings.toast.config_not_saved", error = err));
+        }
+    };
+
+    rsx! {
+        section {
+            class: "rounded-xl border cvs-border cvs-surface p-6",
+            h2 { class: "text-sm font-semibold uppercase tracking-wide cvs-text-muted", {tr!("settings.language.title")} }
+            div { class: "mt-4 flex flex-wrap items-center justify-between gap-4",
+                p { class: "text-xs cvs-text-muted", {tr!("settings.language.hint")} }
+                select {
+                    class: "{theme::input_small()}",
+                    onchange: on_change,
+                    for locale in Locale::ALL {
+                        option { value: locale.code(), selected: locale == current, "{locale.native_name()}" }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Colour theme, chosen independently of the gameplay profile; stored in
+/// `ui.theme` and, for the custom palette, `ui.custom_theme`.
+#[component]
+fn AppearanceSection() -> Element {
+    let state = use_context::<Signal<AppState>>();
+    let toasts = use_context::<Signal<Vec<ToastMessage>>>();
+    let settings = theme::THEME();
+
+    let save = move |choice: ThemeChoice, custom: Palette| {
+        theme::set_theme(choice, custom.clone());
+        let mut next = config::file_config();
+        next.ui.theme = choice;
+        next.ui.custom_theme = custom;
+        if let Err(err) = config::update(next) {
+            push_toast(toasts, ToastKind::Error, tr!("settings.toast.config_not_saved", error = err));
+        }
+    };
+
+    let on_choice = move |evt: FormEvent| {
+        if let Ok(choice) = evt.value().parse::<ThemeChoice>() {
+            save(choice, theme::THEME().custom);
+        }
+    };
+
+    rsx! {
+        section {
+            class: "rounded-xl border cvs-border cvs-surface p-6",
+            h2 { class: "text-sm font-semibold uppercase tracking-wide cvs-text-muted", {tr!("settings.theme.title")} }
+            div { class: "mt-4 flex flex-wrap items-center justify-between gap-4",
+                p { class: "text-xs cvs-text-muted", {tr!("settings.theme.hint")} }
+                select {
+                    class: "{theme::input_small()}",
+                    onchange: on_choice,
+                    for choice in ThemeChoice::ALL {
+                        option { value: choice.code(), selected: choice == settings.choice, {theme_label(choice)} }
+                    }
+                }
+            }
+            if settings.choice == ThemeChoice::Custom {
+                p { class: "mt-4 text-xs cvs-text-muted", {tr!("settings.theme.custom_hint")} }
+                div { class: "mt-3 grid gap-3 sm:grid-cols-2 lg:grid-cols-3",
+                    for token in Palette::TOKENS {
+                        {
+                            let value = settings.custom.get(token).unwrap_or_default().to_string();
+                            rsx! {
+                                label { key: "{token}", class: "flex items-center gap-3 text-xs cvs-text-muted",
+                                    span {
+                                        class: "h-6 w-6 shrink-0 rounded border cvs-border",
+                                        style: "background-color: {value}",
+                                    }
+                                    span { class: "flex-1", {token_label(token)} }
+                                    input {
+                                        class: "w-28 font-mono {theme::input_small()}",
+                                        value: "{value}",
+                                        spellcheck: false,
+                                        onchange: move |evt: FormEvent| {
+                                            let mut custom = theme::THEME().custom;
+                                            custom.set(token, evt.value().trim().to_string());
+                                            if let Some((_, value)) = custom.invalid_token() {
+                                                push_toast(
+                                                    toasts,
+                                                    ToastKind::Error,
+                                                    tr!("settings.theme.invalid_color", value = value),
+                                                );
+                                                return;
+                                            }
+                                            save(ThemeChoice::Custom, custom);
+                                        },
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+                button {
+                    class: "mt-4 rounded-lg px-4 py-2 text-xs font-semibold uppercase tracking-wide cvs-btn",
+                    onclick: move |_| {
+                        let profile = state.with(|st| st.profile);
+                        let base = Palette::for_choice(ThemeChoice::Auto, profile, &Palette::default());
+                        save(ThemeChoice::Custom, base);
+                    },
+                    {tr!("settings.theme.reset_custom")}
+                }
+            }
+        }
+    }
+}
+
+fn theme_label(choice: ThemeChoice) -> String {
+    match choice {
+        ThemeChoice::Auto => tr!("settings.theme.auto"),
+        ThemeChoice::Drake => tr!("settings.theme.drake"),
+        ThemeChoice::Misc => tr!("settings.theme.misc"),
+        ThemeChoice::Argo => tr!("settings.theme.argo"),
+        ThemeChoice::Neutral => tr!("settings.theme.neutral"),
+        ThemeChoice::HighContrast => tr!("settings.theme.high_contrast"),
+        ThemeChoice::Custom => tr!("settings.theme.custom"),
+    }
+}
+
+fn token_label(token: &str) -> String {
+    match token {
+        "background" => tr!("settings.theme.token.background"),
+        "surface" => tr!("settings.theme.token.surface"),
+        "surface_raised" => tr!("settings.theme.token.surface_raised"),
+        "border" => tr!("settings.theme.token.border"),
+        "border_strong" => tr!("settings.theme.token.border_strong"),
+        "text" => tr!("settings.theme.token.text"),
+        "text_muted" => tr!("settings.theme.token.text_muted"),
+        "accent" => tr!("settings.theme.token.accent"),
+        "primary" => tr!("settings.theme.token.primary"),
+        "on_primary" => tr!("settings.theme.token.on_primary"),
+        "glow" => tr!("settings.theme.token.glow"),
+        other => other.to_string(),
+    }
+}
+
+/// Text inputs for the editable parts of `AppConfig`.
+#[derive(Clone, Debug, PartialEq)]
+struct ConfigForm {
+    base_url: String,
+    request_timeout_secs: String,
+    watch_poll_minutes: String,
+    price_ttl_minutes: String,
+    terminal_ttl_hours: String,
+    routes_ttl_hours: String,
+    home_system: String,
+    cross_system_penalty: String,
+    armistice_penalty: String,
+    hotspot_penalty: String,
+    hotspots: String,
+    suggestions_per_item: String,
+    pirate_min_buy_price: String,
+    max_routes: String,
+    toast_dismiss_secs: String,
+    default_profile: Profile,
+    api_enabled: bool,
+    api_port: String,
+    game_log_enabled: bool,
+    game_log_path: String,
+    game_log_trades: bool,
+}
+
+impl ConfigForm {
+    fn from_config(config: &AppConfig) -> Self {
+        Self {
+            base_url: config.network.base_url.clone(),
+            request_timeout_secs: config.network.request_timeout_secs.to_string(),
+            watch_poll_minutes: (config.network.watch_poll_secs / 60).to_string(),
+            price_ttl_minutes: (config.cache.price_ttl_secs / 60).to_string(),
+            terminal_ttl_hours: (config.cache.terminal_ttl_secs / 3600).to_string(),
+            routes_ttl_hours: (config.cache.routes_ttl_secs / 3600).to_string(),
+            home_system: config.ranking.home_system.clone(),
+            cross_system_penalty: format!("{:.0}", config.ranking.cross_system_penalty),
+            armistice_penalty: format!("{:.0}", config.ranking.armistice_penalty),
+            hotspot_penalty: format!("{:.0}", config.ranking.hotspot_penalty),
+            hotspots: config.ranking.hotspots.join(", "),
+            suggestions_per_item: config.ranking.suggestions_per_item.to_string(),
+            pirate_min_buy_price: format!("{:.0}", config.ranking.pirate_min_buy_price),
+            max_routes: config.ui.max_routes.to_string(),
+            toast_dismiss_secs: config.ui.toast_dismiss_secs.to_string(),
+            default_profile: config.profiles.default_profile,
+            api_enabled: config.api.enabled,
+            api_port: config.api.port.to_string(),
+            game_log_enabled: config.game_log.enabled,
+            game_log_path: config.game_log.path.clone(),
+            game_log_trades: config.game_log.capture_trades,
+        }
+    }
+
+    /// Apply the form on top of `base`, keeping fields the form doesn't edit.
+    fn apply(&self, base: &AppConfig) -> Result<AppConfig, String> {
+        fn num<T: std::str::FromStr>(label: String, value: &str) -> Result<T, String> {
+            value
+                .trim()
+                .parse()
+                .map_err(|_| tr!("settings.error.not_a_number", field = label))
+        }
+
+        let mut config = base.clone();
+        config.network.base_url = self.base_url.trim().to_string();
+        config.network.request_timeout_secs = num(tr!("settings.config.request_timeout_s"), &self.request_timeout_secs)?;
+        config.network.watch_poll_secs = num::<u64>(tr!("settings.config.watch_interval_min"), &self.watch_poll_minutes)? * 60;
+        config.cache.price_ttl_secs = num::<u64>(tr!("settings.config.price_ttl_min"), &self.price_ttl_minutes)? * 60;
+        config.cache.terminal_ttl_secs = num::<u64>(tr!("settings.config.terminal_ttl_h"), &self.terminal_ttl_hours)? * 3600;
+        config.cache.routes_ttl_secs = num::<u64>(tr!("settings.config.routes_ttl_h"), &self.routes_ttl_hours)? * 3600;
+        config.ranking.home_system = self.home_system.trim().to_string();
+        config.ranking.cross_system_penalty = num(tr!("settings.config.cross_system_penalty"), &self.cross_system_penalty)?;
+        config.ranking.armistice_penalty = num(tr!("settings.config.armistice_penalty"), &self.armistice_penalty)?;
+        config.ranking.hotspot_penalty = num(tr!("settings.config.hotspot_penalty"), &self.hotspot_penalty)?;
+        config.ranking.hotspots = self
+            .hotspots
+            .split(',')
+            .map(|spot| spot.trim().to_string())
+            .filter(|spot| !spot.is_empty())
+            .collect();
+        config.ranking.suggestions_per_item = num(tr!("settings.config.suggestions_per_item"), &self.suggestions_per_item)?;
+        config.ranking.pirate_min_buy_price = num(tr!("settings.config.pirate_min_price"), &self.pirate_min_buy_price)?;
+        config.ui.max_routes = num(tr!("settings.config.max_routes"), &self.max_routes)?;
+        config.ui.toast_dismiss_secs = num(tr!("settings.config.toast_duration_s"), &self.toast_dismiss_secs)?;
+        config.profiles.default_profile = self.default_profile;
+        config.api.enabled = self.api_enabled;
+        config.api.port = num(tr!("settings.config.api_port"), &self.api_port)?;
+        config.game_log.enabled = self.game_log_enabled;
+        config.game_log.path = self.game_log_path.trim().to_string();
+        config.game_log.capture_trades = self.game_log_trades;
+        Ok(config)
+    }
+}
+
+/// (group, label, current value, setter) for one text input in the config form.
+type ConfigField = (String, String, String, fn(&mut ConfigForm, String));
+
+#[component]
+fn ConfigSection() -> Element {
+    let toasts = use_context::<Signal<Vec<ToastMessage>>>();
+    let mut form = use_signal(|| ConfigForm::from_config(&config::file_config()));
+    let env_overrides = config::active_env_overrides();
+
+    let on_save = move |_| {
+        let result = form()
+            .apply(&config::file_config())
+            .and_then(|next| config::update(next).map_err(|err| err.to_string()));
+        match result {
+            Ok(()) => push_toast(toasts, ToastKind::Success, tr!("settings.toast.config_saved")),
+            Err(message) => push_toast(toasts, ToastKind::Error, tr!("settings.toast.config_not_saved", error = message)),
+        }
+    };
+
+    let on_defaults = move |_| {
+        form.set(ConfigForm::from_config(&AppConfig::default()));
+        push_toast(toasts, ToastKind::Info, tr!("settings.toast.defaults_loaded"));
+    };
+
+    let input_class = format!("mt-1 w-full {}", theme::input_small());
+    let label_class = "block text-xs font-semibold uppercase cvs-text-muted";
+
+    let group_network = tr!("settings.group.network");
+    let group_cache = tr!("settings.group.cache");
+    let group_ranking = tr!("settings.group.ranking");
+    let group_ui = tr!("settings.group.ui");
+    let group_api = tr!("settings.group.api");
+    let group_game_log = tr!("settings.group.game_log");
+    let group_profiles = tr!("settings.group.profiles");
+    let fields: Vec<ConfigField> = vec![
+        (group_network.clone(), tr!("settings.config.base_url"), form().base_url, |f, v| f.base_url = v),
+        (group_network.clone(), tr!("settings.config.request_timeout_s"), form().request_timeout_secs, |f, v| f.request_timeout_secs = v),
+        (group_network.clone(), tr!("settings.config.watch_interval_min"), form().watch_poll_minutes, |f, v| f.watch_poll_minutes = v),
+        (group_cache.clone(), tr!("settings.config.price_ttl_min"), form().price_ttl_minutes, |f, v| f.price_ttl_minutes = v),
+        (group_cache.clone(), tr!("settings.config.terminal_ttl_h"), form().terminal_ttl_hours, |f, v| f.terminal_ttl_hours = v),
+        (group_cache.clone(), tr!("settings.config.routes_ttl_h"), form().routes_ttl_hours, |f, v| f.routes_ttl_hours = v),
+        (group_ranking.clone(), tr!("settings.config.home_system"), form().home_system, |f, v| f.home_system = v),
+        (group_ranking.clone(), tr!("settings.config.cross_system_penalty"), form().cross_system_penalty, |f, v| f.cross_system_penalty = v),
+        (group_ranking.clone(), tr!("settings.config.armistice_penalty"), form().armistice_penalty, |f, v| f.armistice_penalty = v),
+        (group_ranking.clone(), tr!("settings.config.hotspot_penalty"), form().hotspot_penalty, |f, v| f.hotspot_penalty = v),
+        (group_ranking.clone(), tr!("settings.config.hotspots"), form().hotspots, |f, v| f.hotspots = v),
+        (group_ranking.clone(), tr!("settings.config.suggestions_per_item"), form().suggestions_per_item, |f, v| f.suggestions_per_item = v),
+        (group_ranking.clone(), tr!("settings.config.pirate_min_price"), form().pirate_min_buy_price, |f, v| f.pirate_min_buy_price = v),
+        (group_ui.clone(), tr!("settings.config.max_routes"), form().max_routes, |f, v| f.max_routes = v),
+        (group_ui.clone(), tr!("settings.config.toast_duration_s"), form().toast_dismiss_secs, |f, v| f.toast_dismiss_secs = v),
+        (group_api.clone(), tr!("settings.config.api_port_restart"), form().api_port, |f, v| f.api_port = v),
+        (group_game_log.clone(), tr!("settings.config.game_log_path"), form().game_log_path, |f, v| f.game_log_path = v),
+    ];
+
+    rsx! {
+        section {
+            class: "rounded-xl border cvs-border cvs-surface p-6",
+            h2 { class: "text-sm font-semibold uppercase tracking-wide cvs-text-muted", {tr!("settings.config.title")} }
+            p { class: "mt-2 text-sm cvs-text-muted", {tr!("settings.config.subtitle")} }
+            if !env_overrides.is_empty() {
+                p { class: "mt-2 text-xs text-amber-300",
+                    {tr!("settings.config.env_overrides", vars = env_overrides.join(", "))}
+                }
+            }
+            div { class: "mt-4 grid gap-4 sm:grid-cols-3",
+                for (group, label, value, setter) in fields {
+                    div { key: "{label}",
+                        label { class: "{label_class}", "{group} · {label}" }
+                        input {
+                            class: "{input_class}",
+                            value: "{value}",
+                            oninput: move |evt| form.with_mut(|f| setter(f, evt.value())),
+                        }
+                    }
+                }
+                div {
+                    label { class: "{label_class}", {format!("{group_profiles} · {}", tr!("settings.config.default_profile"))} }
+                    select {
+                        class: "{input_class}",
+                        onchange: move |evt| {
+                            let profile = match evt.value().as_str() {
+                                "pirate" => Profile::Pirate,
+                                "trader" => Profile::Trader,
+                                _ => Profile::None,
+                            };
+                            form.with_mut(|f| f.default_profile = profile);
+                        },
+                        option { value: "none", selected: form().default_profile == Profile::None, {tr!("settings.config.ask_on_start")} }
+                        option { value: "pirate", selected: form().default_profile == Profile::Pirate, "{Profile::Pirate.name()}" }
+                        option { value: "trader", selected: form().default_profile == Profile::Trader, "{Profile::Trader.name()}" }
+                    }
+                }
+                div {
+                    label { class: "{label_class}", {format!("{group_api} · {}", tr!("settings.config.api_server"))} }
+                    select {
+                        class: "{input_class}",
+                        onchange: move |evt| form.with_mut(|f| f.api_enabled = evt.value() == "on"),
+                        option { value: "off", selected: !form().api_enabled, {tr!("common.off")} }
+                        option { value: "on", selected: form().api_enabled, {tr!("settings.config.api_on")} }
+                    }
+                }
+                div {
+                    label { class: "{label_class}", {format!("{group_game_log} · {}", tr!("settings.config.follow_position"))} }
+                    select {
+                        class: "{input_class}",
+                        onchange: move |evt| form.with_mut(|f| f.game_log_enabled = evt.value() == "on"),
+                        option { value: "off", selected: !form().game_log_enabled, {tr!("common.off")} }
+                        option { value: "on", selected: form().game_log_enabled, {tr!("settings.config.on_restart")} }
+                    }
+                }
+                div {
+                    label { class: "{label_class}", {format!("{group_game_log} · {}", tr!("settings.config.capture_trades"))} }
+                    select {
+                        class: "{input_class}",
+                        onchange: move |evt| form.with_mut(|f| f.game_log_trades = evt.value() == "on"),
+                        option { value: "on", selected: form().game_log_trades, {tr!("settings.config.capture_on")} }
+                        option { value: "off", selected: !form().game_log_trades, {tr!("settings.config.capture_off")} }
+                    }
+                }
+            }
+            div { class: "mt-4 flex gap-3",
+                button { class: "rounded-lg px-4 py-2 text-xs font-semibold uppercase tracking-wide cvs-btn-primary", onclick: on_save, {tr!("common.save")} }
+                button { class: "rounded-lg px-4 py-2 text-xs font-semibold uppercase tracking-wide cvs-btn", onclick: on_defaults, {tr!("settings.config.load_defaults")} }
+            }
+        }
+    }
+}
+
+/// Discord webhook targets and the embed templates used when sharing.
+#[component]
+fn WebhooksSection() -> Element {
+    let toasts = use_context::<Signal<Vec<ToastMessage>>>();
+    let mut form = use_signal(|| config::file_config().webhooks);
+    let mut interval_input = use_signal(|| form.peek().min_interval_secs.to_string());
+
+    let on_save = move |_| {
+        let mut next = config::file_config();
+        next.webhooks = form();
+        match interval_input().trim().parse() {
+            Ok(secs) => next.webhooks.min_interval_secs = secs,
+            Err(_) => {
+                push_toast(toasts, ToastKind::Error, tr!("settings.toast.config_not_saved", error = tr!("settings.webhooks.interval_invalid")));
+                return;
+            }
+        }
+        next.webhooks.targets.retain(|t| !t.url.trim().is_empty());
+        match config::update(next) {
+            Ok(()) => {
+                form.set(config::file_config().webhooks);
+                push_toast(toasts, ToastKind::Success, tr!("settings.toast.config_saved"));
+            }
+            Err(err) => push_toast(toasts, ToastKind::Error, tr!("settings.toast.config_not_saved", error = err)),
+        }
+    };
+
+    let on_test = move |target: WebhookTarget| {
+        move |_| {
+            let target = target.clone();
+            spawn(async move {
+                let message = webhook::test_message(&target.username);
+                match webhook::client().post(&target.url, &message, form.peek().min_interval()).await {
+                    Ok(()) => push_toast(toasts, ToastKind::Success, tr!("share.toast.sent", target = target.name)),
+                    Err(err) => push_toast(toasts, ToastKind::Error, tr!("share.toast.failed", error = err)),
+                }
+            });
+        }
+    };
+
+    let targets = form().targets;
+    let templates = form().templates;
+
+    rsx! {
+        section {
+            class: "rounded-xl border cvs-border cvs-surface p-6",
+            h2 { class: "text-sm font-semibold uppercase tracking-wide cvs-text-muted", {tr!("settings.webhooks.title")} }
+            p { class: "mt-2 text-sm cvs-text-muted", {tr!("settings.webhooks.subtitle")} }
+
+            div { class: "mt-4 space-y-2",
+                for (index, target) in targets.into_iter().enumerate() {
+                    div { key: "{index}", class: "flex flex-wrap items-center gap-2",
+                        input {
+                            class: "w-36 {theme::input_small()}",
+                            placeholder: tr!("settings.webhooks.name"),
+                            value: "{target.name}",
+                            oninput: move |evt| form.with_mut(|f| f.targets[index].name = evt.value()),
+                        }
+                        input {
+                            class: "min-w-64 flex-1 font-mono {theme::input_small()}",
+                            placeholder: "https://discord.com/api/webhooks/…",
+                            value: "{target.url}",
+                            oninput: move |evt| form.with_mut(|f| f.targets[index].url = evt.value().trim().to_string()),
+                        }
+                        input {
+                            class: "w-36 {theme::input_small()}",
+                            placeholder: tr!("settings.webhooks.username"),
+                            value: "{target.username}",
+                            oninput: move |evt| form.with_mut(|f| f.targets[index].username = evt.value()),
+                        }
+                        button {
+                            class: theme::btn_small_toggle(target.enabled),
+                            onclick: move |_| form.with_mut(|f| f.targets[index].enabled = !f.targets[index].enabled),
+                            if target.enabled { {tr!("common.on")} } else { {tr!("common.off")} }
+                        }
+                        button {
+                            class: "{theme::btn_small_inactive()}",
+                            disabled: target.url.is_empty(),
+                            title: tr!("settings.webhooks.test_hint"),
+                            onclick: on_test(target.clone()),
+                            {tr!("settings.webhooks.test")}
+                        }
+                        button {
+                            class: "{theme::btn_small_inactive()}",
+                            onclick: move |_| {
+                                form.with_mut(|f| {
+                                    f.targets.remove(index);
+                                });
+                            },
+                            "✕"
+                        }
+                    }
+                }
+                button {
+                    class: "{theme::btn_small_inactive()}",
+                    onclick: move |_| {
+                        form.with_mut(|f| {
+                            let name = tr!("settings.webhooks.default_name", n = f.targets.len() + 1);
+                            f.targets.push(WebhookTarget { name, ..WebhookTarget::default() });
+                        });
+                    },
+                    {format!("+ {}", tr!("settings.webhooks.add"))}
+                }
+            }
+
+            div { class: "mt-4 w-48",
+                label { class: "{theme::label_class()}", {tr!("settings.webhooks.min_interval")} }
+                input {
+                    class: "mt-1 w-full {theme::input_small()}",
+                    inputmode: "numeric",
+                    value: "{interval_input}",
+                    oninput: move |evt| interval_input.set(evt.value()),
+                }
+            }
+
+            h3 { class: "mt-6 text-xs font-semibold uppercase cvs-text-muted", {tr!("settings.webhooks.templates")} }
+            div { class: "mt-2 grid gap-4 sm:grid-cols-2",
+                for kind in ShareKind::ALL {
+                    div { key: "{kind.label()}", class: "space-y-1",
+                        label { class: "{theme::label_class()}", {kind.label()} }
+                        input {
+                            class: "w-full {theme::input_small()}",
+                            value: "{kind.template(&templates).title}",
+                            oninput: move |evt| form.with_mut(|f| kind.template_mut(&mut f.templates).title = evt.value()),
+                        }
+                        textarea {
+                            class: "h-16 w-full rounded-lg p-2 text-sm cvs-input",
+                            value: "{kind.template(&templates).description}",
+                            oninput: move |evt| form.with_mut(|f| kind.template_mut(&mut f.templates).description = evt.value()),
+                        }
+                        p { class: "text-xs cvs-text-muted",
+                            {tr!("settings.webhooks.placeholders", list = kind.placeholders().iter().map(|p| format!("{{{p}}}")).collect::<Vec<_>>().join(" "))}
+                        }
+                    }
+                }
+            }
+
+            div { class: "mt-4 flex gap-3",
+                button { class: "rounded-lg px-4 py-2 text-xs font-semibold uppercase tracking-wide cvs-btn-primary", onclick: on_save, {tr!("common.save")} }
+                button {
+                    class: "rounded-lg px-4 py-2 text-xs font-semibold uppercase tracking-wide cvs-btn",
+                    onclick: move |_| form.with_mut(|f| f.templates = WebhookConfig::default().templates),
+                    {tr!("settings.webhooks.reset_templates")}
                 }
             }
         }
@@ -329,22 +865,22 @@ fn parse_params(
     let risk_pct: f64 = risk_pct
         .trim()
         .parse()
-        .map_err(|_| "Risk % must be a number between 0 and 0.4")?;
+        .map_err(|_| tr!("settings.error.risk_number"))?;
     if !(0.0..=0.4).contains(&risk_pct) {
-        return Err("Risk % must be between 0.0 and 0.4".to_string());
+        return Err(tr!("settings.error.risk_range"));
     }
     let crew_hourly: f64 = crew_hourly
         .trim()
         .parse()
-        .map_err(|_| "Crew hourly cost must be numeric")?;
+        .map_err(|_| tr!("settings.error.crew_hourly"))?;
     let crew_size: u8 = crew_size
         .trim()
         .parse()
-        .map_err(|_| "Crew size must be numeric")?;
+        .map_err(|_| tr!("settings.error.crew_size"))?;
     let time_minutes: u16 = time_minutes
         .trim()
         .parse()
-        .map_err(|_| "Trip time must be numeric")?;
+        .map_err(|_| tr!("settings.error.trip_time"))?;
 
     Ok(ProfitabilityParams {
         risk_pct,
@@ -356,9 +892,9 @@ fn parse_params(
 
 fn cache_label(resource: &CacheResource) -> String {
     match resource {
-        CacheResource::Commodities => "Commodities".to_string(),
-        CacheResource::SellLocations => "Locations".to_string(),
-        CacheResource::Prices(id) => format!("Prices ({id})"),
+        CacheResource::Commodities => tr!("settings.cache.commodities"),
+        CacheResource::SellLocations => tr!("settings.cache.locations"),
+        CacheResource::Prices(id) => tr!("settings.cache.prices", commodity = id),
     }
 }
 
@@ -380,10 +916,10 @@ impl Default for UpdateState {
 fn render_update_status(state: &UpdateState) -> Element {
     match state {
         UpdateState::Idle => rsx! {
-            p { class: "text-sm text-slate-400", "Press \"Check for updates\" to compare your build against the latest release tag on GitHub." }
+            p { class: "text-sm cvs-text-muted", {tr!("settings.update.idle")} }
         },
         UpdateState::Checking => rsx! {
-            p { class: "text-sm text-indigo-200", "Checking for updates…" }
+            p { class: "text-sm {theme::text_primary()}", {tr!("settings.update.checking")} }
         },
         UpdateState::UpToDate { latest_tag } => {
             let label = latest_tag
@@ -392,7 +928,7 @@ fn render_update_status(state: &UpdateState) -> Element {
             rsx! {
                 span {
                     class: "inline-flex items-center gap-2 rounded-full border border-emerald-500/40 bg-emerald-500/10 px-3 py-1 text-xs font-semibold text-emerald-200",
-                    "Up to date"
+                    {tr!("settings.update.up_to_date")}
                     span { class: "font-normal text-emerald-300", "{label}" }
                 }
             }
@@ -400,12 +936,12 @@ fn render_update_status(state: &UpdateState) -> Element {
         UpdateState::UpdateAvailable { latest_tag } => rsx! {
             span {
                 class: "inline-flex items-center gap-2 rounded-full border border-amber-500/40 bg-amber-500/10 px-3 py-1 text-xs font-semibold text-amber-200",
-                "Update available"
+                {tr!("settings.update.available")}
                 span { class: "font-normal text-amber-300", "{latest_tag}" }
             }
         },
         UpdateState::Failed(message) => rsx! {
-            p { class: "text-sm text-rose-300", "Update check failed: {message}" }
+            p { class: "text-sm text-rose-300", {tr!("settings.update.failed", error = message)} }
         },
     }
 }
@@ -0,0 +1,389 @@
+//! Traffic heatmap — where to lurk, ranked by hauler traffic and cargo value.
+
+use std::collections::HashMap;
+
+use dioxus::prelude::*;
+
+use crate::domain::{
+    history::unix_now, interdiction_points, traffic::TREND_WINDOW_SECS, traffic_board, AppState,
+    InterdictionPoint, TrafficBoard, TrafficHistory, Trend,
+};
+use crate::infra::cache::load_traffic_history;
+use crate::infra::config;
+use crate::infra::routes::load_routes;
+use crate::infra::uex::UexClient;
+use crate::ui::{i18n::tr, theme};
+use crate::util::format;
+
+/// Highest-flow routes split into quantum lanes for interdiction candidates.
+const INTERDICTION_ROUTES: usize = 250;
+
+/// Interdiction candidates whose lane length is looked up (one UEX request each).
+const DISTANCE_LOOKUPS: usize = 25;
+
+/// Which ranking the board shows.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TrafficView {
+    #[default]
+    Terminals,
+    Systems,
+    Lanes,
+    Interdiction,
+}
+
+#[component]
+pub fn TrafficPage() -> Element {
+    let state = use_context::<Signal<AppState>>();
+    let nqa_terminal_ids = state.with(|s| s.nqa_terminal_ids.clone());
+
+    let mut view = use_signal(TrafficView::default);
+    let mut system_filter = use_signal(String::new); // empty = all systems
+    let mut stations_only = use_signal(|| false);
+    let mut force_refresh = use_signal(|| false);
+
+    // Routes carry every listing's report counts; loading them also records
+    // the traffic snapshot the trends are built from.
+    let board_resource = use_resource(move || {
+        let nqa = nqa_terminal_ids.clone();
+        let refresh = force_refresh();
+        async move {
+            let client = UexClient::new().ok()?;
+            let routes = load_routes(&client, &nqa, refresh).await.ok()?;
+            let terminals = client.get_terminals().await.map(|c| c.terminals).unwrap_or_default();
+            let points = interdiction_points(&routes, &terminals, INTERDICTION_ROUTES);
+            Some((traffic_board(&routes), load_traffic_history(), points))
+        }
+    });
+    let loading = board_resource.read().is_none();
+    let (board, history, points): (TrafficBoard, TrafficHistory, Vec<InterdictionPoint>) =
+        board_resource
+            .read()
+            .as_ref()
+            .and_then(|r| r.clone())
+            .unwrap_or_default();
+
+    // Lane lengths, only while the interdiction view is open.
+    let distances = use_resource(move || {
+        let pairs: Vec<(i32, i32)> = if view() == TrafficView::Interdiction {
+            board_resource
+                .read()
+                .as_ref()
+                .and_then(|r| r.as_ref())
+                .map(|(_, _, points)| {
+                    points
+                        .iter()
+                        .filter_map(|p| p.sample_terminals)
+                        .take(DISTANCE_LOOKUPS)
+                        .collect()
+                })
+                .unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+        async move {
+            let mut distances = HashMap::new();
+            let Ok(client) = UexClient::new() else {
+                return distances;
+            };
+            for (from, to) in pairs {
+                if let Ok(Some(gm)) = client.get_terminal_distance(from, to).await {
+                    distances.insert((from, to), gm);
+                }
+            }
+            distances
+        }
+    });
+    let distances = distances.read().clone().unwrap_or_default();
+
+    let now = unix_now();
+    let limit = config::current().ui.max_routes;
+    let sys_filter = system_filter();
+    let in_system = |system: &Option<String>| {
+        sys_filter.is_empty() || system.as_deref() == Some(sys_filter.as_str())
+    };
+    let mut systems: Vec<String> = board.systems.iter().map(|s| s.name.clone()).collect();
+    systems.sort();
+
+    let terminals: Vec<_> = board
+        .terminals
+        .iter()
+        .filter(|t| in_system(&t.system) && (!stations_only() || !t.is_planetary))
+        .take(limit)
+        .cloned()
+        .collect();
+    let lanes: Vec<_> = board
+        .lanes
+        .iter()
+        .filter(|l| in_system(&l.from_system) || in_system(&l.to_system))
+        .take(limit)
+        .cloned()
+        .collect();
+    let points: Vec<_> = points
+        .into_iter()
+        .filter(|p| sys_filter.is_empty() || p.system == sys_filter)
+        .take(limit)
+        .collect();
+    let recent_snapshots = history
+        .snapshots
+        .iter()
+        .filter(|s| s.taken_at + TREND_WINDOW_SECS >= now)
+        .count();
+
+    rsx! {
+        div { class: "space-y-6",
+            section {
+                class: "flex flex-wrap items-center justify-between gap-4",
+                div {
+                    h2 { class: "text-xl font-semibold cvs-text", {tr!("traffic.title")} }
+                    p { class: "text-sm cvs-text-muted", {tr!("traffic.subtitle")} }
+                }
+                if loading {
+                    div {
+                        class: "flex items-center gap-2 {theme::text_primary()}",
+                        span { class: "animate-spin", "⟳" }
+                        span { class: "text-sm", {tr!("routes.loading")} }
+                    }
+                }
+            }
+
+            div {
+                class: "{theme::panel_border()} p-5 flex flex-wrap gap-x-8 gap-y-4 items-start",
+                div {
+                    label { class: "block text-xs font-semibold uppercase cvs-text-muted mb-2", {tr!("traffic.view")} }
+                    div { class: "flex gap-2",
+                        for (target, label) in [
+                            (TrafficView::Terminals, tr!("traffic.view.terminals")),
+                            (TrafficView::Systems, tr!("traffic.view.systems")),
+                            (TrafficView::Lanes, tr!("traffic.view.lanes")),
+                            (TrafficView::Interdiction, tr!("traffic.view.interdiction")),
+                        ] {
+                            button {
+                                class: "{theme::btn_toggle(view() == target)}",
+                                onclick: move |_| view.set(target),
+                                "{label}"
+                            }
+                        }
+                    }
+                }
+                if view() != TrafficView::Systems {
+                    div {
+                        label { class: "block text-xs font-semibold uppercase cvs-text-muted mb-2", {tr!("traffic.system")} }
+                        select {
+                            class: "w-40 {theme::input_small()}",
+                            onchange: move |evt| system_filter.set(evt.value()),
+                            option { value: "", selected: system_filter().is_empty(), {tr!("routes.all_systems")} }
+                            for system in systems {
+                                option { value: "{system}", selected: system == system_filter(), "{system}" }
+                            }
+                        }
+                    }
+                }
+                if view() == TrafficView::Terminals {
+                    div {
+                        label { class: "block text-xs font-semibold uppercase cvs-text-muted mb-2", {tr!("routes.type")} }
+                        button {
+                            class: if stations_only() { theme::btn_active() } else { theme::btn_inactive() },
+                            onclick: move |_| stations_only.set(!stations_only()),
+                            {format!("🛰️ {}", tr!("routes.stations_only"))}
+                        }
+                    }
+                }
+                div {
+                    label { class: "block text-xs font-semibold uppercase cvs-text-muted mb-2 invisible", {tr!("common.refresh")} }
+                    button {
+                        class: theme::btn_inactive(),
+                        title: tr!("routes.reload_prices"),
+                        onclick: move |_| force_refresh.set(true),
+                        "🔄"
+                    }
+                }
+            }
+
+            if !loading && view() == TrafficView::Interdiction {
+                p { class: "text-xs {theme::text_muted()}", {tr!("traffic.interdiction.hint", routes = INTERDICTION_ROUTES)} }
+                InterdictionTable { points, distances }
+            } else if !loading && recent_snapshots < 2 {
+                p { class: "text-xs {theme::text_muted()}", {tr!("traffic.trend_pending")} }
+            }
+
+            if !loading && view() != TrafficView::Interdiction {
+                div { class: "{theme::table_container()}",
+                    table { class: "min-w-full {theme::table_divider()} text-sm",
+                        thead { class: "{theme::table_header()} text-left tracking-wide",
+                            tr {
+                                th { class: "px-4 py-3 font-medium w-10", "#" }
+                                match view() {
+                                    TrafficView::Terminals => rsx! {
+                                        th { class: "px-4 py-3 font-medium", {tr!("traffic.col.terminal")} }
+                                        th { class: "px-4 py-3 font-medium", {tr!("traffic.col.top_commodity")} }
+                                    },
+                                    TrafficView::Systems => rsx! {
+                                        th { class: "px-4 py-3 font-medium", {tr!("traffic.col.system")} }
+                                        th { class: "px-4 py-3 font-medium text-right", {tr!("traffic.col.terminals")} }
+                                    },
+                                    TrafficView::Lanes => rsx! {
+                                        th { class: "px-4 py-3 font-medium", {tr!("traffic.col.lane")} }
+                                        th { class: "px-4 py-3 font-medium text-right", {tr!("traffic.col.commodities")} }
+                                    },
+                                    // Rendered by `InterdictionTable`.
+                                    TrafficView::Interdiction => rsx! {},
+                                }
+                                th { class: "px-4 py-3 font-medium w-48", title: tr!("traffic.col.heat_hint"), {tr!("traffic.col.heat")} }
+                                th { class: "px-4 py-3 font-medium text-right", title: tr!("traffic.col.reports_hint"), {tr!("traffic.col.reports")} }
+                                th { class: "px-4 py-3 font-medium text-right", title: tr!("traffic.col.cargo_value_hint"), {tr!("traffic.col.cargo_value")} }
+                                if view() != TrafficView::Lanes {
+                                    th { class: "px-4 py-3 font-medium text-right", title: tr!("traffic.col.trend_hint"), {tr!("traffic.col.trend")} }
+                                }
+                            }
+                        }
+                        tbody { class: "{theme::table_divider()}",
+                            match view() {
+                                TrafficView::Terminals => rsx! {
+                                    for (rank, terminal) in terminals.into_iter().enumerate() {
+                                        tr { key: "{terminal.terminal_id}", class: "cvs-row",
+                                            td { class: "px-4 py-3 {theme::text_muted()}", "{rank + 1}" }
+                                            td { class: "px-4 py-3",
+                                                span { class: "font-medium {theme::text_secondary()}", "{terminal.name}" }
+                                                span { class: "ml-2 text-xs {theme::text_muted()}", {terminal.system.clone().unwrap_or_default()} }
+                                                if terminal.is_nqa {
+                                                    span { class: "ml-2 text-xs text-amber-300", title: tr!("traffic.nqa_hint"), "NQA" }
+                                                }
+                                            }
+                                            td { class: "px-4 py-3 {theme::text_muted()}", {terminal.top_commodity.clone().unwrap_or_else(|| "—".to_string())} }
+                                            HeatCell { index: terminal.index }
+                                            td { class: "px-4 py-3 text-right {theme::text_secondary()}", "{terminal.reports}" }
+                                            td { class: "px-4 py-3 text-right {theme::text_secondary()}", {format::auec_compact(terminal.cargo_value)} }
+                                            TrendCell { trend: history.terminal_trend(terminal.terminal_id, now) }
+                                        }
+                                    }
+                                },
+                                TrafficView::Systems => rsx! {
+                                    for (rank, system) in board.systems.iter().cloned().enumerate() {
+                                        tr { key: "{system.name}", class: "cvs-row",
+                                            td { class: "px-4 py-3 {theme::text_muted()}", "{rank + 1}" }
+                                            td { class: "px-4 py-3 font-medium {theme::text_secondary()}", "{system.name}" }
+                                            td { class: "px-4 py-3 text-right {theme::text_muted()}", "{system.terminals}" }
+                                            HeatCell { index: system.index }
+                                            td { class: "px-4 py-3 text-right {theme::text_secondary()}", "{system.reports}" }
+                                            td { class: "px-4 py-3 text-right {theme::text_secondary()}", {format::auec_compact(system.cargo_value)} }
+                                            TrendCell { trend: history.system_trend(&system.name, now) }
+                                        }
+                                    }
+                                },
+                                TrafficView::Lanes => rsx! {
+                                    for (rank, lane) in lanes.into_iter().enumerate() {
+                                        tr { key: "{lane.from_id}-{lane.to_id}", class: "cvs-row",
+                                            td { class: "px-4 py-3 {theme::text_muted()}", "{rank + 1}" }
+                                            td { class: "px-4 py-3 {theme::text_secondary()}",
+                                                "{lane.from} → {lane.to}"
+                                                if lane.from_system != lane.to_system {
+                                                    span { class: "ml-2 text-xs {theme::text_muted()}",
+                                                        {format!("{} → {}", lane.from_system.clone().unwrap_or_default(), lane.to_system.clone().unwrap_or_default())}
+                                                    }
+                                                }
+                                            }
+                                            td { class: "px-4 py-3 text-right {theme::text_muted()}", "{lane.commodities}" }
+                                            HeatCell { index: lane.index }
+                                            td { class: "px-4 py-3 text-right {theme::text_secondary()}", "{lane.reports}" }
+                                            td { class: "px-4 py-3 text-right {theme::text_secondary()}", {format::auec_compact(lane.cargo_value)} }
+                                        }
+                                    }
+                                },
+                                TrafficView::Interdiction => rsx! {},
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Quantum lanes ranked by the cargo value flowing along them.
+#[component]
+fn InterdictionTable(points: Vec<InterdictionPoint>, distances: HashMap<(i32, i32), f64>) -> Element {
+    let max_flow = points.first().map(|p| p.value_flow).unwrap_or(0.0);
+    rsx! {
+        div { class: "{theme::table_container()}",
+            table { class: "min-w-full {theme::table_divider()} text-sm",
+                thead { class: "{theme::table_header()} text-left tracking-wide",
+                    tr {
+                        th { class: "px-4 py-3 font-medium w-10", "#" }
+                        th { class: "px-4 py-3 font-medium", {tr!("traffic.col.quantum_lane")} }
+                        th { class: "px-4 py-3 font-medium", {tr!("traffic.col.top_commodity")} }
+                        th { class: "px-4 py-3 font-medium w-48", {tr!("traffic.col.heat")} }
+                        th { class: "px-4 py-3 font-medium text-right", {tr!("traffic.col.routes")} }
+                        th { class: "px-4 py-3 font-medium text-right", title: tr!("traffic.col.flow_hint"), {tr!("traffic.col.flow")} }
+                        th { class: "px-4 py-3 font-medium text-right", title: tr!("traffic.col.share_hint"), {tr!("traffic.col.share")} }
+                        th { class: "px-4 py-3 font-medium text-right", title: tr!("traffic.col.length_hint"), {tr!("traffic.col.length")} }
+                    }
+                }
+                tbody { class: "{theme::table_divider()}",
+                    for (rank, point) in points.into_iter().enumerate() {
+                        tr { key: "{point.system}-{point.ends.0}-{point.ends.1}", class: "cvs-row",
+                            td { class: "px-4 py-3 {theme::text_muted()}", "{rank + 1}" }
+                            td { class: "px-4 py-3",
+                                span { class: "font-medium {theme::text_secondary()}", "{point.ends.0} ↔ {point.ends.1}" }
+                                span { class: "ml-2 text-xs {theme::text_muted()}", "{point.system}" }
+                            }
+                            td { class: "px-4 py-3 {theme::text_muted()}", {point.commodities.join(", ")} }
+                            HeatCell { index: if max_flow > 0.0 { 100.0 * point.value_flow / max_flow } else { 0.0 } }
+                            td { class: "px-4 py-3 text-right {theme::text_secondary()}", "{point.routes}" }
+                            td { class: "px-4 py-3 text-right font-semibold {theme::text_primary()}", {format::auec_compact(point.value_flow)} }
+                            td { class: "px-4 py-3 text-right {theme::text_muted()}", {format!("{} %", format::number(point.share * 100.0, 1))} }
+                            td { class: "px-4 py-3 text-right {theme::text_muted()}",
+                                {point
+                                    .sample_terminals
+                                    .and_then(|pair| distances.get(&pair))
+                                    .map(|gm| format!("{} Gm", format::number(*gm, 1)))
+                                    .unwrap_or_else(|| "—".to_string())}
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Heat index as a bar, hotter colours for busier spots.
+#[component]
+fn HeatCell(index: f64) -> Element {
+    let color = if index >= 66.0 {
+        "bg-rose-500"
+    } else if index >= 33.0 {
+        "bg-amber-400"
+    } else {
+        "bg-emerald-500"
+    };
+    rsx! {
+        td { class: "px-4 py-3",
+            div { class: "flex items-center gap-2",
+                div { class: "h-2 flex-1 rounded-full cvs-surface",
+                    div { class: "h-2 rounded-full {color}", style: "width: {index:.0}%" }
+                }
+                span { class: "w-8 text-right text-xs {theme::text_muted()}", {format::number(index, 0)} }
+            }
+        }
+    }
+}
+
+#[component]
+fn TrendCell(trend: Trend) -> Element {
+    let Some(change) = trend.change() else {
+        return rsx! {
+            td { class: "px-4 py-3 text-right {theme::text_muted()}", "—" }
+        };
+    };
+    let color = match change {
+        c if c > 0 => "text-emerald-300",
+        c if c < 0 => "text-rose-300",
+        _ => "cvs-text-muted",
+    };
+    rsx! {
+        td { class: "px-4 py-3 text-right whitespace-nowrap",
+            span { class: "font-mono text-xs {theme::text_muted()}", {trend.sparkline()} }
+            span { class: "ml-2 text-xs {color}", {format!("{change:+}")} }
+        }
+    }
+}
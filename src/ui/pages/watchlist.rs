@@ -0,0 +1,316 @@
+//! Price watchlist — alert when a commodity crosses a price threshold.
+
+use dioxus::prelude::*;
+
+use crate::{
+    app::poll_watchlist,
+    domain::{AppState, TradeSide, WatchDirection, WatchRule},
+    infra::{config, uex::UexClient},
+    ui::components::toast::{push_toast, ToastKind, ToastMessage},
+    ui::{i18n::tr, theme},
+    util::format,
+};
+
+#[component]
+pub fn WatchlistPage() -> Element {
+    let mut state = use_context::<Signal<AppState>>();
+    let toasts = use_context::<Signal<Vec<ToastMessage>>>();
+    let poll_minutes = (config::current().network.watch_poll_secs / 60).max(1);
+
+    let mut commodity_query = use_signal(String::new);
+    let mut terminal_query = use_signal(String::new);
+    let mut system_query = use_signal(String::new);
+    let mut threshold_input = use_signal(String::new);
+    let mut side = use_signal(|| TradeSide::Sell);
+    let mut direction = use_signal(|| WatchDirection::Above);
+    let mut checking = use_signal(|| false);
+
+    let rules = use_signal(|| config::current().watchlist.rules);
+
+    // Rules live in config.json; every edit goes through `config::update`.
+    let save_rules = move |edit: &dyn Fn(&mut Vec<WatchRule>)| {
+        let mut next = config::file_config();
+        edit(&mut next.watchlist.rules);
+        match config::update(next) {
+            Ok(()) => {
+                let mut rules = rules;
+                rules.set(config::current().watchlist.rules);
+                true
+            }
+            Err(err) => {
+                push_toast(toasts, ToastKind::Error, tr!("settings.toast.config_not_saved", error = err));
+                false
+            }
+        }
+    };
+
+    let commodities = state.with(|st| st.commodities.clone());
+    let rules = rules();
+    let hits: Vec<_> = state.with(|st| {
+        rules
+            .iter()
+            .map(|rule| st.watch_alerts.hit(&rule.id).cloned())
+            .collect()
+    });
+
+    let terminals_resource = use_resource(move || async move {
+        let client = UexClient::new().ok()?;
+        let cache = client.get_terminals().await.ok()?;
+        Some(cache.terminals)
+    });
+    let terminals = terminals_resource
+        .read()
+        .as_ref()
+        .and_then(|t| t.as_ref())
+        .cloned()
+        .unwrap_or_default();
+    let mut systems: Vec<String> = terminals.iter().filter_map(|t| t.system.clone()).collect();
+    systems.sort();
+    systems.dedup();
+
+    let on_add = {
+        let terminals = terminals.clone();
+        move |evt: FormEvent| {
+            evt.prevent_default();
+            let query = commodity_query().trim().to_string();
+            let commodity = state.with(|st| {
+                st.commodities
+                    .iter()
+                    .find(|c| c.name.eq_ignore_ascii_case(&query) || c.id.eq_ignore_ascii_case(&query))
+                    .cloned()
+            });
+            let Some(commodity) = commodity else {
+                push_toast(toasts, ToastKind::Error, tr!("cargo.toast.commodity_not_found"));
+                return;
+            };
+
+            let threshold = match threshold_input().trim().replace(',', ".").parse::<f64>() {
+                Ok(value) if value.is_finite() && value > 0.0 => value,
+                _ => {
+                    push_toast(toasts, ToastKind::Error, tr!("watch.toast.threshold_invalid"));
+                    return;
+                }
+            };
+
+            let terminal_name = terminal_query().trim().to_string();
+            let terminal = if terminal_name.is_empty() {
+                None
+            } else {
+                match terminals.iter().find(|t| t.name.eq_ignore_ascii_case(&terminal_name)) {
+                    Some(terminal) => Some(terminal.clone()),
+                    None => {
+                        push_toast(toasts, ToastKind::Error, tr!("watch.toast.terminal_not_found"));
+                        return;
+                    }
+                }
+            };
+            let system = Some(system_query().trim().to_string()).filter(|s| !s.is_empty());
+
+            let rule = WatchRule {
+                id: uuid::Uuid::new_v4().to_string(),
+                commodity_id: commodity.id.clone(),
+                commodity_name: commodity.name.clone(),
+                terminal_id: terminal.as_ref().map(|t| t.id),
+                terminal_name: terminal.as_ref().map(|t| t.name.clone()),
+                system: if terminal.is_some() { None } else { system },
+                side: side(),
+                threshold,
+                direction: direction(),
+                enabled: true,
+            };
+            let description = rule.describe();
+            if !save_rules(&|rules: &mut Vec<WatchRule>| rules.push(rule.clone())) {
+                return;
+            }
+            commodity_query.set(String::new());
+            terminal_query.set(String::new());
+            threshold_input.set(String::new());
+            push_toast(toasts, ToastKind::Success, tr!("watch.toast.added", rule = description));
+        }
+    };
+
+    let on_check_now = move |_| {
+        if checking() {
+            return;
+        }
+        checking.set(true);
+        spawn(async move {
+            if let Ok(client) = UexClient::new() {
+                poll_watchlist(&client, state, toasts).await;
+            }
+            checking.set(false);
+        });
+    };
+
+    rsx! {
+        div { class: "space-y-6",
+            section { class: "flex flex-wrap items-center justify-between gap-4",
+                div {
+                    h2 { class: "text-xl font-semibold {theme::text_secondary()}", {tr!("watch.title")} }
+                    p { class: "text-sm {theme::text_muted()}",
+                        {tr!("watch.subtitle", minutes = poll_minutes)}
+                    }
+                }
+                button {
+                    class: "{theme::btn_inactive()}",
+                    disabled: checking(),
+                    onclick: on_check_now,
+                    if checking() { {tr!("watch.checking")} } else { {tr!("watch.check_now")} }
+                }
+            }
+
+            form {
+                class: "{theme::panel_border()} flex flex-wrap items-end gap-4 px-4 py-4",
+                onsubmit: on_add,
+                div { class: "flex-1 min-w-[180px]",
+                    label { class: "{theme::label_class()}", {tr!("cargo.field.commodity")} }
+                    input {
+                        class: "mt-1 w-full {theme::input_small()}",
+                        value: commodity_query(),
+                        oninput: move |evt| commodity_query.set(evt.value()),
+                        list: "watch-commodity-list",
+                        placeholder: tr!("watch.commodity_placeholder"),
+                    }
+                    datalist { id: "watch-commodity-list",
+                        for commodity in commodities.iter() {
+                            option { value: commodity.name.clone() }
+                        }
+                    }
+                }
+                div { class: "w-28",
+                    label { class: "{theme::label_class()}", {tr!("history.col.side")} }
+                    select {
+                        class: "mt-1 w-full {theme::input_small()}",
+                        onchange: move |evt| side.set(if evt.value() == "buy" { TradeSide::Buy } else { TradeSide::Sell }),
+                        option { value: "sell", selected: side() == TradeSide::Sell, {tr!("trade.sell")} }
+                        option { value: "buy", selected: side() == TradeSide::Buy, {tr!("trade.buy")} }
+                    }
+                }
+                div { class: "w-24",
+                    label { class: "{theme::label_class()}", {tr!("watch.when")} }
+                    select {
+                        class: "mt-1 w-full {theme::input_small()}",
+                        onchange: move |evt| direction.set(if evt.value() == "below" { WatchDirection::Below } else { WatchDirection::Above }),
+                        option { value: "above", selected: direction() == WatchDirection::Above, "≥" }
+                        option { value: "below", selected: direction() == WatchDirection::Below, "≤" }
+                    }
+                }
+                div { class: "w-32",
+                    label { class: "{theme::label_class()}", "aUEC / SCU" }
+                    input {
+                        class: "mt-1 w-full {theme::input_small()}",
+                        inputmode: "decimal",
+                        value: threshold_input(),
+                        oninput: move |evt| threshold_input.set(evt.value()),
+                        placeholder: "3000",
+                    }
+                }
+                div { class: "flex-1 min-w-[180px]",
+                    label { class: "{theme::label_class()}", {tr!("watch.terminal")} }
+                    input {
+                        class: "mt-1 w-full {theme::input_small()}",
+                        value: terminal_query(),
+                        oninput: move |evt| terminal_query.set(evt.value()),
+                        list: "watch-terminal-list",
+                        placeholder: tr!("watch.scope.any"),
+                    }
+                    datalist { id: "watch-terminal-list",
+                        for terminal in terminals.iter() {
+                            option { value: terminal.name.clone() }
+                        }
+                    }
+                }
+                div { class: "w-32",
+                    label { class: "{theme::label_class()}", {tr!("watch.system")} }
+                    select {
+                        class: "mt-1 w-full {theme::input_small()}",
+                        onchange: move |evt| system_query.set(evt.value()),
+                        option { value: "", {tr!("watch.any_system")} }
+                        for system in systems.iter() {
+                            option { value: system.clone(), selected: system_query() == *system, "{system}" }
+                        }
+                    }
+                }
+                button {
+                    class: "{theme::btn_primary()}",
+                    r#type: "submit",
+                    {tr!("watch.add_rule")}
+                }
+            }
+
+            if rules.is_empty() {
+                div {
+                    class: "{theme::panel_border()} px-6 py-12 text-center",
+                    p { class: "{theme::text_muted()}", {tr!("watch.empty")} }
+                }
+            } else {
+                div { class: "{theme::table_container()}",
+                    div { class: "{theme::table_divider()}",
+                        for (rule, hit) in rules.iter().zip(hits.into_iter()) {
+                            WatchRuleRow {
+                                key: "{rule.id}",
+                                rule: rule.clone(),
+                                status: hit.map(|hit| match hit.system {
+                                    Some(system) => format!(
+                                        "{} · {system}",
+                                        tr!("watch.hit", price = format::integer(hit.price), terminal = hit.terminal_name)
+                                    ),
+                                    None => tr!("watch.hit", price = format::integer(hit.price), terminal = hit.terminal_name),
+                                }),
+                                on_toggle: move |id: String| {
+                                    save_rules(&|rules: &mut Vec<WatchRule>| {
+                                        if let Some(rule) = rules.iter_mut().find(|rule| rule.id == id) {
+                                            rule.enabled = !rule.enabled;
+                                        }
+                                    });
+                                    state.with_mut(|st| st.watch_alerts.reset(&id));
+                                },
+                                on_remove: move |id: String| {
+                                    save_rules(&|rules: &mut Vec<WatchRule>| rules.retain(|rule| rule.id != id));
+                                    state.with_mut(|st| st.watch_alerts.reset(&id));
+                                },
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn WatchRuleRow(
+    rule: WatchRule,
+    status: Option<String>,
+    on_toggle: EventHandler<String>,
+    on_remove: EventHandler<String>,
+) -> Element {
+    let toggle_id = rule.id.clone();
+    let remove_id = rule.id.clone();
+    let row_opacity = if rule.enabled { "" } else { "opacity-50" };
+
+    rsx! {
+        div { class: "flex items-center justify-between gap-4 px-4 py-3 text-sm {row_opacity}",
+            div {
+                p { class: "{theme::text_secondary()}", "{rule.describe()}" }
+                match status {
+                    Some(status) => rsx! { p { class: "text-xs text-emerald-300", {format!("🔔 {}", tr!("watch.triggered", status = status))} } },
+                    None => rsx! { p { class: "text-xs {theme::text_muted()}", {tr!("watch.not_triggered")} } },
+                }
+            }
+            div { class: "flex gap-2",
+                button {
+                    class: if rule.enabled { theme::btn_small_active() } else { theme::btn_small_inactive() },
+                    onclick: move |_| on_toggle.call(toggle_id.clone()),
+                    if rule.enabled { {tr!("common.on")} } else { {tr!("common.off")} }
+                }
+                button {
+                    class: "{theme::btn_small_inactive()}",
+                    title: tr!("watch.remove_rule"),
+                    onclick: move |_| on_remove.call(remove_id.clone()),
+                    "✕"
+                }
+            }
+        }
+    }
+}
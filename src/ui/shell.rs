@@ -2,8 +2,75 @@ use dioxus::prelude::*;
 
 use crate::app::Route;
 use crate::domain::{AppState, Profile};
+use crate::ui::components::command_palette::{CommandPalette, PaletteAction, PaletteEntry};
+use crate::ui::keyboard::{GlobalShortcuts, Shortcut, COMMODITY_FOCUS, REFRESH_SELECTED};
+use crate::ui::{i18n::tr, theme};
 use crate::ui::pages::ProfileSelectPage;
 
+/// A header navigation entry; Alt+<position> jumps to it.
+#[derive(Clone, PartialEq)]
+struct NavTab {
+    route: Route,
+    icon: &'static str,
+    name: String,
+    /// Show only the icon; the name goes into the tooltip.
+    icon_only: bool,
+}
+
+impl NavTab {
+    fn new(route: Route, icon: &'static str, name: String) -> Self {
+        NavTab { route, icon, name, icon_only: false }
+    }
+
+    fn icon(route: Route, icon: &'static str, name: String) -> Self {
+        NavTab { route, icon, name, icon_only: true }
+    }
+
+    fn button_label(&self) -> String {
+        match (self.icon_only, self.icon.is_empty()) {
+            (true, _) => self.icon.to_string(),
+            (false, true) => self.name.clone(),
+            (false, false) => format!("{} {}", self.icon, self.name),
+        }
+    }
+}
+
+fn nav_tabs(profile: Profile) -> Vec<NavTab> {
+    let mut tabs = match profile {
+        Profile::Pirate => vec![
+            NavTab::new(Route::Routes {}, "🎯", tr!("nav.lurk")),
+            NavTab::new(Route::Traffic {}, "🔥", tr!("nav.heatmap")),
+            NavTab::new(Route::Cargo {}, "📦", tr!("nav.loot")),
+            NavTab::new(Route::Planner {}, "💰", tr!("nav.sell")),
+            NavTab::new(Route::Fence {}, "🕶️", tr!("nav.fence")),
+        ],
+        Profile::Trader => vec![
+            NavTab::new(Route::Routes {}, "🗺️", tr!("nav.routes")),
+            NavTab::new(Route::Cargo {}, "📦", tr!("nav.cargo")),
+            NavTab::new(Route::Planner {}, "💰", tr!("nav.sell")),
+        ],
+        Profile::Miner => vec![
+            NavTab::new(Route::Refinery {}, "⚗️", tr!("nav.refinery")),
+            NavTab::new(Route::OrePrices {}, "💎", tr!("nav.ore_prices")),
+            NavTab::new(Route::Cargo {}, "📦", tr!("nav.cargo")),
+            NavTab::new(Route::Planner {}, "💰", tr!("nav.sell")),
+        ],
+        _ => vec![
+            NavTab::new(Route::Cargo {}, "📦", tr!("nav.cargo")),
+            NavTab::new(Route::Planner {}, "💰", tr!("nav.sell")),
+            NavTab::new(Route::BestPrice {}, "", tr!("nav.best_price")),
+        ],
+    };
+    tabs.extend([
+        NavTab::icon(Route::Watchlist {}, "🔔", tr!("nav.watchlist")),
+        NavTab::icon(Route::Crew {}, "👥", tr!("nav.crew")),
+        NavTab::icon(Route::History {}, "📈", tr!("nav.history")),
+        NavTab::icon(Route::Reports {}, "📝", tr!("nav.reports")),
+        NavTab::icon(Route::Settings {}, "⚙️", tr!("nav.settings")),
+    ]);
+    tabs
+}
+
 #[component]
 pub fn Shell(children: Element) -> Element {
     let state = use_context::<Signal<AppState>>();
@@ -11,8 +78,9 @@ pub fn Shell(children: Element) -> Element {
     
     // Show profile selector if no profile selected
     if !profile.is_selected() {
+        let (root_class, root_style) = theme::root(profile);
         return rsx! {
-            div { class: "min-h-screen bg-slate-950 text-slate-100 font-sans",
+            div { class: "min-h-screen font-sans {root_class}", style: "{root_style}",
                 ProfileSelectPage {}
             }
         };
@@ -25,39 +93,72 @@ pub fn Shell(children: Element) -> Element {
     
     // Profile tagline
     let tagline = match profile {
-        Profile::Pirate => "wir laden das für dich um",
-        Profile::Trader => "was soll das kosten?!",
-        Profile::Miner => "rocks go brrrr",
-        Profile::None => "",
-    };
-    
-    // Profile-specific header border color
-    let header_class = match profile {
-        Profile::Pirate => "border-b border-[#3b1712] bg-[#1a0a08]/95 backdrop-blur px-6 py-4",
-        Profile::Trader => "border-b border-sky-900/40 bg-slate-950/90 backdrop-blur px-6 py-4",
-        Profile::Miner => "border-b border-orange-900/40 bg-slate-950/90 backdrop-blur px-6 py-4",
-        Profile::None => "border-b border-slate-900/60 bg-slate-950/80 backdrop-blur px-6 py-4",
+        Profile::Pirate => tr!("shell.tagline.pirate"),
+        Profile::Trader => tr!("shell.tagline.trader"),
+        Profile::Miner => tr!("shell.tagline.miner"),
+        Profile::None => String::new(),
     };
     
-    // Profile-specific title color
-    let title_class = match profile {
-        Profile::Pirate => "text-xl font-semibold tracking-tight text-[#ff9900]",
-        Profile::Trader => "text-xl font-semibold tracking-tight text-sky-200",
-        Profile::Miner => "text-xl font-semibold tracking-tight text-orange-200",
-        Profile::None => "text-xl font-semibold tracking-tight",
+    let (root_class, root_style) = theme::root(profile);
+
+    let tabs = nav_tabs(profile);
+    let mut palette_open = use_signal(|| false);
+    let palette_pages: Vec<PaletteEntry> = tabs
+        .iter()
+        .enumerate()
+        .map(|(index, tab)| {
+            PaletteEntry::new(
+                tr!("palette.go_to", page = tab.name),
+                format!("Alt+{}", index + 1),
+                PaletteAction::Navigate(tab.route.clone()),
+            )
+        })
+        .collect();
+
+    let on_shortcut = {
+        let tabs = tabs.clone();
+        let current_route = current_route.clone();
+        move |shortcut: Shortcut| {
+            let position = tabs.iter().position(|tab| tab.route == current_route);
+            let target = match shortcut {
+                Shortcut::Palette => {
+                    palette_open.toggle();
+                    None
+                }
+                Shortcut::Tab(index) => Some(index),
+                Shortcut::NextTab => Some(position.map_or(0, |p| (p + 1) % tabs.len())),
+                Shortcut::PreviousTab => {
+                    Some(position.map_or(0, |p| (p + tabs.len() - 1) % tabs.len()))
+                }
+                Shortcut::FocusCommodity => {
+                    *COMMODITY_FOCUS.write() = Some(String::new());
+                    if current_route != (Route::Cargo {}) {
+                        nav.push(Route::Cargo {});
+                    }
+                    None
+                }
+                Shortcut::RefreshSelected => {
+                    *REFRESH_SELECTED.write() += 1;
+                    None
+                }
+            };
+            if let Some(tab) = target.and_then(|index| tabs.get(index)) {
+                nav.push(tab.route.clone());
+            }
+        }
     };
-    
+
     rsx! {
-        div { class: "min-h-screen bg-slate-950 text-slate-100 font-sans",
+        div { class: "min-h-screen font-sans {root_class}", style: "{root_style}",
             header {
-                class: "{header_class}",
+                class: "cvs-header px-6 py-4",
                 div { class: "mx-auto grid max-w-6xl grid-cols-[1fr_auto_1fr] items-center gap-4",
                     // Left: Profile name + tagline
                     div { class: "flex items-center gap-3",
                         span { class: "text-2xl", "{profile.emoji()}" }
                         div {
-                            h1 { class: "{title_class}", "{profile.name()}" }
-                            p { class: "text-xs text-slate-500 italic", "{tagline}" }
+                            h1 { class: "text-xl font-semibold tracking-tight {theme::text_primary()}", "{profile.name()}" }
+                            p { class: "text-xs italic {theme::text_muted()}", "{tagline}" }
                         }
                     }
                     
@@ -66,46 +167,41 @@ pub fn Shell(children: Element) -> Element {
                         ProfileButton {
                             active: profile == Profile::Pirate,
                             onclick: move |_| state_mut.with_mut(|s| s.profile = Profile::Pirate),
-                            label: "🏴‍☠️ Pirat",
-                            theme: Profile::Pirate,
+                            label: format!("🏴‍☠️ {}", Profile::Pirate.name()),
                         }
                         ProfileButton {
                             active: profile == Profile::Trader,
                             onclick: move |_| state_mut.with_mut(|s| s.profile = Profile::Trader),
-                            label: "📦 Händler",
-                            theme: Profile::Trader,
+                            label: format!("📦 {}", Profile::Trader.name()),
                         }
-                        button {
-                            class: "min-w-[6rem] rounded-lg px-3 py-1.5 text-sm text-slate-600 border border-slate-800 cursor-not-allowed opacity-50",
-                            disabled: true,
-                            title: "Coming Soon™",
-                            "⛏️ Miner"
+                        ProfileButton {
+                            active: profile == Profile::Miner,
+                            onclick: move |_| state_mut.with_mut(|s| s.profile = Profile::Miner),
+                            label: format!("⛏️ {}", Profile::Miner.name()),
                         }
                     }
                     
                     // Right: Navigation
                     nav { class: "flex gap-2 text-sm justify-end",
-                        match profile {
-                            Profile::Pirate => rsx! {
-                                NavButton { active: matches!(current_route, Route::Routes {}), onclick: move |_| { nav.push(Route::Routes {}); }, label: "🎯 Lauern", profile: profile }
-                                NavButton { active: matches!(current_route, Route::Cargo {}), onclick: move |_| { nav.push(Route::Cargo {}); }, label: "📦 Beute", profile: profile }
-                                NavButton { active: matches!(current_route, Route::Planner {}), onclick: move |_| { nav.push(Route::Planner {}); }, label: "💰 Verkauf", profile: profile }
-                            },
-                            Profile::Trader => rsx! {
-                                NavButton { active: matches!(current_route, Route::Routes {}), onclick: move |_| { nav.push(Route::Routes {}); }, label: "🗺️ Routen", profile: profile }
-                                NavButton { active: matches!(current_route, Route::Cargo {}), onclick: move |_| { nav.push(Route::Cargo {}); }, label: "📦 Cargo", profile: profile }
-                                NavButton { active: matches!(current_route, Route::Planner {}), onclick: move |_| { nav.push(Route::Planner {}); }, label: "💰 Verkauf", profile: profile }
-                            },
-                            _ => rsx! {
-                                NavButton { active: matches!(current_route, Route::Cargo {}), onclick: move |_| { nav.push(Route::Cargo {}); }, label: "📦 Cargo", profile: profile }
-                                NavButton { active: matches!(current_route, Route::Planner {}), onclick: move |_| { nav.push(Route::Planner {}); }, label: "💰 Verkauf", profile: profile }
-                                NavButton { active: matches!(current_route, Route::BestPrice {}), onclick: move |_| { nav.push(Route::BestPrice {}); }, label: "Best Price", profile: profile }
-                            },
+                        for (index, tab) in tabs.iter().enumerate() {
+                            NavButton {
+                                key: "{index}",
+                                active: current_route == tab.route,
+                                onclick: {
+                                    let route = tab.route.clone();
+                                    move |_| { nav.push(route.clone()); }
+                                },
+                                label: tab.button_label(),
+                                title: format!("{} (Alt+{})", tab.name, index + 1),
+                            }
                         }
-                        NavButton { active: matches!(current_route, Route::Settings {}), onclick: move |_| { nav.push(Route::Settings {}); }, label: "⚙️", profile: profile }
                     }
                 }
             }
+            GlobalShortcuts { on_shortcut }
+            if palette_open() {
+                CommandPalette { pages: palette_pages, on_close: move |_| palette_open.set(false) }
+            }
             main { class: "mx-auto max-w-6xl px-6 py-10",
                 {children}
             }
@@ -117,43 +213,16 @@ pub fn Shell(children: Element) -> Element {
 fn NavButton(
     active: bool,
     onclick: EventHandler<()>,
-    label: &'static str,
-    profile: Profile,
+    label: String,
+    #[props(default)]
+    title: String,
 ) -> Element {
-    let class = match (profile, active) {
-        // Drake/Pirate - CRT amber with glow
-        (Profile::Pirate, true) => {
-            "min-w-[5.5rem] rounded-lg border border-[#5c2a1f] bg-[#3b1712] px-4 py-2 font-semibold text-[#ff9900] drake-glow drake-flicker drake-crt"
-        }
-        (Profile::Pirate, false) => {
-            "min-w-[5.5rem] rounded-lg border border-[#3b1712] px-4 py-2 text-[#d4523a]/70 transition hover:border-[#5c2a1f] hover:bg-[#3b1712]/40 hover:text-[#ff9900]"
-        }
-        // MISC/Trader - Sky blue clean
-        (Profile::Trader, true) => {
-            "min-w-[5.5rem] rounded-lg border border-sky-500/60 bg-sky-500/15 px-4 py-2 font-semibold text-sky-300 misc-glow"
-        }
-        (Profile::Trader, false) => {
-            "min-w-[5.5rem] rounded-lg border border-slate-700 px-4 py-2 text-slate-400 transition hover:border-sky-700 hover:bg-sky-900/20 hover:text-sky-300"
-        }
-        // Argo/Miner - Orange industrial
-        (Profile::Miner, true) => {
-            "min-w-[5.5rem] rounded-lg border border-orange-500/60 bg-orange-500/15 px-4 py-2 font-semibold text-orange-300 argo-glow"
-        }
-        (Profile::Miner, false) => {
-            "min-w-[5.5rem] rounded-lg border border-slate-700 px-4 py-2 text-slate-400 transition hover:border-orange-700 hover:bg-orange-900/20 hover:text-orange-300"
-        }
-        // Default fallback
-        (_, true) => {
-            "min-w-[5.5rem] rounded-lg border border-indigo-500/60 bg-indigo-500/15 px-4 py-2 font-semibold text-indigo-300"
-        }
-        (_, false) => {
-            "min-w-[5.5rem] rounded-lg border border-transparent px-4 py-2 text-slate-400 transition hover:border-slate-700 hover:bg-slate-900/80 hover:text-slate-200"
-        }
-    };
-    
+    let class = theme::nav_button(active);
+
     rsx! {
         button {
             class: "{class}",
+            title: "{title}",
             onclick: move |_| onclick.call(()),
             "{label}"
         }
@@ -164,33 +233,14 @@ fn NavButton(
 fn ProfileButton(
     active: bool,
     onclick: EventHandler<()>,
-    label: &'static str,
-    theme: Profile,
+    label: String,
 ) -> Element {
-    let class = match (theme, active) {
-        (Profile::Pirate, true) => {
-            "min-w-[6rem] rounded-lg px-3 py-1.5 text-sm font-semibold bg-[#3b1712] text-[#ff9900] border border-[#5c2a1f] drake-glow"
-        }
-        (Profile::Pirate, false) => {
-            "min-w-[6rem] rounded-lg px-3 py-1.5 text-sm text-[#d4523a]/60 border border-[#3b1712] hover:border-[#5c2a1f] hover:text-[#ff9900] transition"
-        }
-        (Profile::Trader, true) => {
-            "min-w-[6rem] rounded-lg px-3 py-1.5 text-sm font-semibold bg-sky-500/20 text-sky-300 border border-sky-500/40 misc-glow"
-        }
-        (Profile::Trader, false) => {
-            "min-w-[6rem] rounded-lg px-3 py-1.5 text-sm text-slate-500 border border-slate-800 hover:border-sky-600 hover:text-sky-400 transition"
-        }
-        (Profile::Miner, true) => {
-            "min-w-[6rem] rounded-lg px-3 py-1.5 text-sm font-semibold bg-orange-500/20 text-orange-300 border border-orange-500/40 argo-glow"
-        }
-        (Profile::Miner, false) => {
-            "min-w-[6rem] rounded-lg px-3 py-1.5 text-sm text-slate-500 border border-slate-800 hover:border-orange-600 hover:text-orange-400 transition"
-        }
-        _ => {
-            "min-w-[6rem] rounded-lg px-3 py-1.5 text-sm text-slate-500 border border-slate-800 hover:border-slate-600 hover:text-slate-300 transition"
-        }
+    let class = if active {
+        "min-w-[6rem] rounded-lg px-3 py-1.5 text-sm font-semibold cvs-btn-active"
+    } else {
+        "min-w-[6rem] rounded-lg px-3 py-1.5 text-sm cvs-btn"
     };
-    
+
     rsx! {
         button {
             class: "{class}",
@@ -0,0 +1,175 @@
+//! Numbers, aUEC amounts, SCU and times formatted for the active locale.
+//!
+//! English groups thousands with `,` and uses `.` for decimals; German swaps
+//! them. "Full" amounts show every digit, "compact" ones shorten to `k`/`M`
+//! for dense tables and cards.
+
+use std::time::SystemTime;
+
+use time::{format_description, OffsetDateTime};
+
+use super::i18n::{current_locale, tf, Locale};
+
+fn separators(locale: Locale) -> (char, char) {
+    match locale {
+        Locale::En => (',', '.'),
+        Locale::De => ('.', ','),
+    }
+}
+
+fn number_in(locale: Locale, value: f64, decimals: usize) -> String {
+    if !value.is_finite() {
+        return "—".to_string();
+    }
+    let (group, decimal) = separators(locale);
+    let text = format!("{:.*}", decimals, value.abs());
+    let (whole, fraction) = text.split_once('.').unwrap_or((&text, ""));
+
+    let mut grouped = String::with_capacity(whole.len() + whole.len() / 3);
+    for (index, digit) in whole.chars().enumerate() {
+        if index > 0 && (whole.len() - index) % 3 == 0 {
+            grouped.push(group);
+        }
+        grouped.push(digit);
+    }
+
+    let is_zero = text.chars().all(|c| c == '0' || c == '.');
+    let sign = if value < 0.0 && !is_zero { "-" } else { "" };
+    if fraction.is_empty() {
+        format!("{sign}{grouped}")
+    } else {
+        format!("{sign}{grouped}{decimal}{fraction}")
+    }
+}
+
+fn compact_in(locale: Locale, value: f64) -> String {
+    let magnitude = value.abs();
+    if magnitude >= 999_950.0 {
+        format!("{}M", number_in(locale, value / 1_000_000.0, 1))
+    } else if magnitude >= 9_999.5 {
+        format!("{}k", number_in(locale, value / 1_000.0, 0))
+    } else if magnitude >= 999.5 {
+        format!("{}k", number_in(locale, value / 1_000.0, 1))
+    } else {
+        number_in(locale, value, 0)
+    }
+}
+
+/// `value` with locale thousands and decimal separators.
+pub fn number(value: f64, decimals: usize) -> String {
+    number_in(current_locale(), value, decimals)
+}
+
+/// Whole number with thousands separators, e.g. `12,345`.
+pub fn integer(value: f64) -> String {
+    number(value, 0)
+}
+
+/// Short form for dense layouts: `950`, `1.5k`, `48k`, `1.2M`.
+pub fn compact(value: f64) -> String {
+    compact_in(current_locale(), value)
+}
+
+/// Full currency amount, e.g. `12,345 aUEC`.
+pub fn auec(value: f64) -> String {
+    format!("{} aUEC", integer(value))
+}
+
+/// Compact currency amount, e.g. `1.2M aUEC`.
+pub fn auec_compact(value: f64) -> String {
+    format!("{} aUEC", compact(value))
+}
+
+/// Cargo volume, e.g. `1,024 SCU`.
+pub fn scu(value: f64) -> String {
+    format!("{} SCU", integer(value))
+}
+
+/// How long ago `at` was, e.g. "5 min ago".
+pub fn relative_time(at: SystemTime) -> String {
+    age(SystemTime::now().duration_since(at).unwrap_or_default().as_secs())
+}
+
+/// An age in seconds as "5 min ago", "3 h ago", ...
+pub fn age(secs: u64) -> String {
+    if secs < 60 {
+        tf("time.seconds_ago", &[("n", secs.to_string())])
+    } else if secs < 3_600 {
+        tf("time.minutes_ago", &[("n", (secs / 60).to_string())])
+    } else if secs < 86_400 {
+        tf("time.hours_ago", &[("n", (secs / 3_600).to_string())])
+    } else {
+        tf("time.days_ago", &[("n", (secs / 86_400).to_string())])
+    }
+}
+
+/// A span of time, e.g. "45m" or "2h 5m".
+pub fn duration(secs: u64) -> String {
+    let minutes = ((secs % 3_600) / 60).to_string();
+    if secs < 3_600 {
+        tf("time.duration_minutes", &[("m", minutes)])
+    } else {
+        tf(
+            "time.duration_hours",
+            &[("h", (secs / 3_600).to_string()), ("m", minutes)],
+        )
+    }
+}
+
+fn date_pattern(locale: Locale) -> &'static str {
+    match locale {
+        Locale::En => "[year]-[month]-[day]",
+        Locale::De => "[day].[month].[year]",
+    }
+}
+
+fn format_unix(secs: u64, pattern: &str) -> Option<String> {
+    let datetime = OffsetDateTime::from_unix_timestamp(secs as i64).ok()?;
+    let format = format_description::parse(pattern).ok()?;
+    datetime.format(&format).ok()
+}
+
+/// Calendar date of a Unix timestamp (UTC).
+pub fn date(secs: u64) -> String {
+    format_unix(secs, date_pattern(current_locale())).unwrap_or_default()
+}
+
+/// Date and time of a Unix timestamp. Shown in UTC; the `time` crate can't
+/// read the local offset safely in a multi-threaded process.
+pub fn date_time(secs: u64) -> String {
+    let pattern = format!("{} [hour]:[minute]", date_pattern(current_locale()));
+    format_unix(secs, &pattern)
+        .map(|text| format!("{text} UTC"))
+        .unwrap_or_else(|| "—".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn groups_thousands_per_locale() {
+        assert_eq!(number_in(Locale::En, 1_234_567.0, 0), "1,234,567");
+        assert_eq!(number_in(Locale::De, 1_234_567.0, 0), "1.234.567");
+        assert_eq!(number_in(Locale::En, -1_234.56, 1), "-1,234.6");
+        assert_eq!(number_in(Locale::De, -1_234.56, 1), "-1.234,6");
+        assert_eq!(number_in(Locale::En, 999.0, 0), "999");
+        assert_eq!(number_in(Locale::En, -0.2, 0), "0");
+    }
+
+    #[test]
+    fn compacts_large_values() {
+        assert_eq!(compact_in(Locale::En, 950.0), "950");
+        assert_eq!(compact_in(Locale::En, 1_500.0), "1.5k");
+        assert_eq!(compact_in(Locale::De, 1_500.0), "1,5k");
+        assert_eq!(compact_in(Locale::En, 48_200.0), "48k");
+        assert_eq!(compact_in(Locale::En, 999_999.0), "1.0M");
+        assert_eq!(compact_in(Locale::De, -2_260_000.0), "-2,3M");
+    }
+
+    #[test]
+    fn formats_dates_per_locale() {
+        assert_eq!(format_unix(0, date_pattern(Locale::En)).as_deref(), Some("1970-01-01"));
+        assert_eq!(format_unix(0, date_pattern(Locale::De)).as_deref(), Some("01.01.1970"));
+    }
+}
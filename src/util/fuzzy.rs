@@ -0,0 +1,71 @@
+//! Fuzzy matching for the command palette.
+//!
+//! A query matches when its characters appear in the candidate in order,
+//! ignoring case and spaces. Matches at word starts and runs of consecutive
+//! characters score higher, so "bapo" ranks "Baijini Point" above
+//! "Baijini Point Admin Office".
+
+/// Score of `candidate` for `query`, or `None` when it doesn't match.
+/// An empty query matches everything with score 0.
+pub fn score(query: &str, candidate: &str) -> Option<u32> {
+    let query: Vec<char> = query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect();
+    if query.is_empty() {
+        return Some(0);
+    }
+    let text: Vec<char> = candidate.chars().flat_map(char::to_lowercase).collect();
+
+    let mut total = 0;
+    let mut previous: Option<usize> = None;
+    let mut from = 0;
+    for wanted in query {
+        let index = from + text[from..].iter().position(|&c| c == wanted)?;
+        total += 1;
+        if previous.is_some_and(|p| p + 1 == index) {
+            total += 4;
+        }
+        if index == 0 || !text[index - 1].is_alphanumeric() {
+            total += 6;
+        }
+        previous = Some(index);
+        from = index + 1;
+    }
+    // Prefer shorter candidates among equal matches.
+    Some(total * 100 - (text.len() as u32).min(99))
+}
+
+/// Indices of the `limit` best matches for `query` among `candidates`,
+/// best first; ties keep their original order.
+pub fn rank<'a>(query: &str, candidates: impl IntoIterator<Item = &'a str>, limit: usize) -> Vec<usize> {
+    let mut scored: Vec<(u32, usize)> = candidates
+        .into_iter()
+        .enumerate()
+        .filter_map(|(index, text)| score(query, text).map(|s| (s, index)))
+        .collect();
+    scored.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+    scored.into_iter().take(limit).map(|(_, index)| index).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn requires_characters_in_order() {
+        assert!(score("lrv", "Lorville").is_some());
+        assert!(score("LORV", "lorville").is_some());
+        assert!(score("vrl", "Lorville").is_none());
+        assert_eq!(score("  ", "anything"), Some(0));
+    }
+
+    #[test]
+    fn ranks_word_starts_and_short_names_first() {
+        let names = ["Baijini Point Admin Office", "Laranite", "Baijini Point", "Port Tressler"];
+        assert_eq!(rank("bapo", names, 10), vec![2, 0]);
+        assert_eq!(rank("plan from bai", ["Plan from Baijini Point", "Plan from Area18"], 10), vec![0]);
+        assert_eq!(rank("", names, 2), vec![0, 1]);
+    }
+}
@@ -0,0 +1,227 @@
+//! Message catalogs (`locales/*.json`) and the active locale.
+//!
+//! Keys are flat, dotted strings (`cargo.add_button`); placeholders are written
+//! `{name}` and filled by [`tf`]. A key missing from a catalog falls back to
+//! English and then to the key itself. The catalog tests make sure that never
+//! ships.
+
+use std::{
+    collections::HashMap,
+    fmt,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicU8, Ordering},
+        OnceLock,
+    },
+};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    #[default]
+    En,
+    De,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 2] = [Locale::En, Locale::De];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::De => "de",
+        }
+    }
+
+    /// Name of the language in that language, for the picker.
+    pub fn native_name(self) -> &'static str {
+        match self {
+            Locale::En => "English",
+            Locale::De => "Deutsch",
+        }
+    }
+
+    fn source(self) -> &'static str {
+        match self {
+            Locale::En => include_str!("../../locales/en.json"),
+            Locale::De => include_str!("../../locales/de.json"),
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+impl FromStr for Locale {
+    type Err = String;
+
+    /// Accepts codes and POSIX-style tags ("de", "de_DE.UTF-8").
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let lower = value.trim().to_ascii_lowercase();
+        Locale::ALL
+            .into_iter()
+            .find(|locale| {
+                lower == locale.code()
+                    || lower.starts_with(&format!("{}_", locale.code()))
+                    || lower.starts_with(&format!("{}-", locale.code()))
+            })
+            .ok_or_else(|| format!("unknown locale {value:?}"))
+    }
+}
+
+static CURRENT: AtomicU8 = AtomicU8::new(0);
+static CATALOGS: [OnceLock<HashMap<String, String>>; 2] = [OnceLock::new(), OnceLock::new()];
+
+pub fn set_locale(locale: Locale) {
+    CURRENT.store(locale as u8, Ordering::Relaxed);
+}
+
+pub fn current_locale() -> Locale {
+    match CURRENT.load(Ordering::Relaxed) {
+        1 => Locale::De,
+        _ => Locale::En,
+    }
+}
+
+fn catalog(locale: Locale) -> &'static HashMap<String, String> {
+    CATALOGS[locale.index()].get_or_init(|| {
+        serde_json::from_str(locale.source()).unwrap_or_else(|err| {
+            tracing::error!(locale = locale.code(), error = %err, "invalid message catalog");
+            HashMap::new()
+        })
+    })
+}
+
+/// Message for `key` in `locale`, if that catalog has it.
+pub fn lookup(locale: Locale, key: &str) -> Option<&'static str> {
+    catalog(locale).get(key).map(String::as_str)
+}
+
+/// Message for `key` in the current locale.
+pub fn t(key: &str) -> String {
+    let locale = current_locale();
+    lookup(locale, key)
+        .or_else(|| lookup(Locale::En, key))
+        .unwrap_or(key)
+        .to_string()
+}
+
+/// Message for `key` with `{name}` placeholders filled from `args`.
+pub fn tf(key: &str, args: &[(&str, String)]) -> String {
+    fill(&t(key), args)
+}
+
+fn fill(template: &str, args: &[(&str, String)]) -> String {
+    args.iter().fold(template.to_string(), |text, (name, value)| {
+        text.replace(&format!("{{{name}}}"), value)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeSet, fs, path::Path};
+
+    use super::*;
+
+    fn placeholders(message: &str) -> BTreeSet<&str> {
+        message
+            .split('{')
+            .skip(1)
+            .filter_map(|rest| rest.split_once('}').map(|(name, _)| name))
+            .collect()
+    }
+
+    /// String literals passed to `t(`, `tf(` or `tr!(` anywhere under `src/`.
+    fn keys_used_in_source() -> BTreeSet<String> {
+        fn visit(dir: &Path, keys: &mut BTreeSet<String>) {
+            for entry in fs::read_dir(dir).unwrap() {
+                let path = entry.unwrap().path();
+                if path.is_dir() {
+                    visit(&path, keys);
+                } else if path.extension().is_some_and(|ext| ext == "rs") {
+                    collect(&fs::read_to_string(&path).unwrap(), keys);
+                }
+            }
+        }
+
+        fn collect(source: &str, keys: &mut BTreeSet<String>) {
+            for call in ["t(\"", "tf(\"", "tr!(\""] {
+                for (index, _) in source.match_indices(call) {
+                    let preceded_by_ident = source[..index]
+                        .chars()
+                        .next_back()
+                        .is_some_and(|c| c.is_alphanumeric() || c == '_');
+                    if preceded_by_ident {
+                        continue;
+                    }
+                    let rest = &source[index + call.len()..];
+                    if let Some(end) = rest.find('"') {
+                        keys.insert(rest[..end].to_string());
+                    }
+                }
+            }
+        }
+
+        let mut keys = BTreeSet::new();
+        visit(&Path::new(env!("CARGO_MANIFEST_DIR")).join("src"), &mut keys);
+        keys
+    }
+
+    #[test]
+    fn catalogs_parse_and_have_the_same_keys() {
+        let english: BTreeSet<_> = catalog(Locale::En).keys().collect();
+        assert!(!english.is_empty(), "en catalog failed to parse");
+        for locale in Locale::ALL {
+            let keys: BTreeSet<_> = catalog(locale).keys().collect();
+            let missing: Vec<_> = english.difference(&keys).collect();
+            let extra: Vec<_> = keys.difference(&english).collect();
+            assert!(missing.is_empty(), "{locale} is missing keys: {missing:?}");
+            assert!(extra.is_empty(), "{locale} has keys English lacks: {extra:?}");
+        }
+    }
+
+    #[test]
+    fn messages_are_filled_in_and_keep_their_placeholders() {
+        for locale in Locale::ALL {
+            for (key, message) in catalog(locale) {
+                assert!(!message.trim().is_empty(), "{locale}: {key} is empty");
+                let english = lookup(Locale::En, key).unwrap_or_default();
+                assert_eq!(
+                    placeholders(message),
+                    placeholders(english),
+                    "{locale}: {key} placeholders differ from English"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn every_key_used_in_code_exists() {
+        let used = keys_used_in_source();
+        assert!(used.len() > 50, "key scan found only {} keys", used.len());
+        for locale in Locale::ALL {
+            let missing: Vec<_> = used
+                .iter()
+                .filter(|key| lookup(locale, key).is_none())
+                .collect();
+            assert!(missing.is_empty(), "{locale} is missing keys used in code: {missing:?}");
+        }
+    }
+
+    #[test]
+    fn fills_placeholders_and_parses_locale_tags() {
+        assert_eq!(fill("{n} of {total}", &[("n", "2".into()), ("total", "5".into())]), "2 of 5");
+        assert_eq!("de_DE.UTF-8".parse(), Ok(Locale::De));
+        assert_eq!("EN".parse(), Ok(Locale::En));
+        assert!("fr".parse::<Locale>().is_err());
+    }
+}
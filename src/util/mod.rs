@@ -1,6 +1,11 @@
 use std::sync::atomic::{AtomicUsize, Ordering};
 
 pub mod assets;
+pub mod format;
+pub mod fuzzy;
+pub mod i18n;
+pub mod notify;
+pub mod palette;
 pub mod persistence;
 pub mod version;
 
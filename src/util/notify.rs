@@ -0,0 +1,27 @@
+//! Native desktop notifications. Without the `desktop` feature this is a no-op.
+
+use crate::util::version::APP_NAME;
+
+/// Show a system notification without blocking the UI thread.
+pub fn desktop_notify(summary: &str, body: &str) {
+    #[cfg(feature = "desktop")]
+    {
+        let summary = summary.to_string();
+        let body = body.to_string();
+        std::thread::spawn(move || {
+            if let Err(err) = notify_rust::Notification::new()
+                .appname(APP_NAME)
+                .summary(&summary)
+                .body(&body)
+                .show()
+            {
+                tracing::warn!(error = %err, "failed to show a desktop notification");
+            }
+        });
+    }
+
+    #[cfg(not(feature = "desktop"))]
+    {
+        let _ = (APP_NAME, summary, body);
+    }
+}
@@ -0,0 +1,302 @@
+//! Colour themes as palettes of CSS custom properties.
+//!
+//! Every page styles itself through the `cvs-*` classes in `assets/main.css`,
+//! which only read `--cvs-*` variables. A theme is therefore just a
+//! [`Palette`]: the built-in manufacturer looks, a high-contrast one and a
+//! user-defined one from `config.json`. Which palette applies is a
+//! [`ThemeChoice`], independent of the gameplay profile unless it is `Auto`.
+
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::Profile;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ThemeChoice {
+    /// Follow the gameplay profile (Drake for pirates, MISC for traders, ...).
+    #[default]
+    Auto,
+    Drake,
+    Misc,
+    Argo,
+    Neutral,
+    HighContrast,
+    Custom,
+}
+
+impl ThemeChoice {
+    pub const ALL: [ThemeChoice; 7] = [
+        ThemeChoice::Auto,
+        ThemeChoice::Drake,
+        ThemeChoice::Misc,
+        ThemeChoice::Argo,
+        ThemeChoice::Neutral,
+        ThemeChoice::HighContrast,
+        ThemeChoice::Custom,
+    ];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            ThemeChoice::Auto => "auto",
+            ThemeChoice::Drake => "drake",
+            ThemeChoice::Misc => "misc",
+            ThemeChoice::Argo => "argo",
+            ThemeChoice::Neutral => "neutral",
+            ThemeChoice::HighContrast => "high_contrast",
+            ThemeChoice::Custom => "custom",
+        }
+    }
+
+    /// The concrete theme once `Auto` is resolved against `profile`.
+    pub fn resolve(self, profile: Profile) -> ThemeChoice {
+        match (self, profile) {
+            (ThemeChoice::Auto, Profile::Pirate) => ThemeChoice::Drake,
+            (ThemeChoice::Auto, Profile::Trader) => ThemeChoice::Misc,
+            (ThemeChoice::Auto, Profile::Miner) => ThemeChoice::Argo,
+            (ThemeChoice::Auto, Profile::None) => ThemeChoice::Neutral,
+            (choice, _) => choice,
+        }
+    }
+}
+
+impl fmt::Display for ThemeChoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+impl FromStr for ThemeChoice {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let normalized = value.trim().to_ascii_lowercase().replace('-', "_");
+        ThemeChoice::ALL
+            .into_iter()
+            .find(|choice| choice.code() == normalized)
+            .ok_or_else(|| format!("unknown theme {value:?}"))
+    }
+}
+
+/// Colour tokens of a theme. Values are CSS hex colours (`#rgb`, `#rrggbb`
+/// or `#rrggbbaa`).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Palette {
+    /// Page background.
+    pub background: String,
+    /// Panels, cards and inputs.
+    pub surface: String,
+    /// Table headers, active buttons and other raised areas.
+    pub surface_raised: String,
+    pub border: String,
+    /// Borders of active or focused elements.
+    pub border_strong: String,
+    pub text: String,
+    pub text_muted: String,
+    /// Highlights: titles, links, active navigation.
+    pub accent: String,
+    /// Background of primary buttons.
+    pub primary: String,
+    /// Text on primary buttons.
+    pub on_primary: String,
+    /// Glow around active elements; fully transparent disables it.
+    pub glow: String,
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Palette::neutral()
+    }
+}
+
+impl Palette {
+    /// Token names in the order the Settings editor shows them.
+    pub const TOKENS: [&'static str; 11] = [
+        "background",
+        "surface",
+        "surface_raised",
+        "border",
+        "border_strong",
+        "text",
+        "text_muted",
+        "accent",
+        "primary",
+        "on_primary",
+        "glow",
+    ];
+
+    fn from_tokens(tokens: [&str; 11]) -> Self {
+        let [background, surface, surface_raised, border, border_strong, text, text_muted, accent, primary, on_primary, glow] =
+            tokens.map(str::to_string);
+        Palette {
+            background,
+            surface,
+            surface_raised,
+            border,
+            border_strong,
+            text,
+            text_muted,
+            accent,
+            primary,
+            on_primary,
+            glow,
+        }
+    }
+
+    /// Drake Interplanetary: grimy brown-red with amber CRT highlights.
+    pub fn drake() -> Self {
+        Palette::from_tokens([
+            "#0f0605", "#1a0a08", "#3b1712", "#3b1712", "#5c2a1f", "#d4523a", "#a3402e", "#ff9900",
+            "#3b1712", "#ff9900", "#ff990066",
+        ])
+    }
+
+    /// MISC: clean slate with sky-blue accents.
+    pub fn misc() -> Self {
+        Palette::from_tokens([
+            "#020617", "#0f172a", "#082f49", "#0c4a6e", "#0ea5e9", "#cbd5e1", "#64748b", "#7dd3fc",
+            "#0ea5e9", "#ffffff", "#38bdf840",
+        ])
+    }
+
+    /// ARGO Astronautics: industrial black with orange accents.
+    pub fn argo() -> Self {
+        Palette::from_tokens([
+            "#020617", "#0f172a", "#431407", "#7c2d12", "#f97316", "#cbd5e1", "#64748b", "#fdba74",
+            "#f97316", "#ffffff", "#f9731666",
+        ])
+    }
+
+    pub fn neutral() -> Self {
+        Palette::from_tokens([
+            "#020617", "#0f172a", "#1e293b", "#1e293b", "#6366f1", "#cbd5e1", "#64748b", "#a5b4fc",
+            "#6366f1", "#ffffff", "#00000000",
+        ])
+    }
+
+    /// Pure black and white with yellow highlights; meets WCAG AAA contrast.
+    pub fn high_contrast() -> Self {
+        Palette::from_tokens([
+            "#000000", "#000000", "#1a1a1a", "#ffffff", "#ffff00", "#ffffff", "#f0f0f0", "#ffff00",
+            "#ffff00", "#000000", "#00000000",
+        ])
+    }
+
+    /// Palette for a resolved theme; `custom` is used for [`ThemeChoice::Custom`].
+    pub fn for_choice(choice: ThemeChoice, profile: Profile, custom: &Palette) -> Palette {
+        match choice.resolve(profile) {
+            ThemeChoice::Drake => Palette::drake(),
+            ThemeChoice::Misc => Palette::misc(),
+            ThemeChoice::Argo => Palette::argo(),
+            ThemeChoice::HighContrast => Palette::high_contrast(),
+            ThemeChoice::Custom => custom.clone(),
+            ThemeChoice::Auto | ThemeChoice::Neutral => Palette::neutral(),
+        }
+    }
+
+    pub fn get(&self, token: &str) -> Option<&str> {
+        self.values()
+            .into_iter()
+            .find(|(name, _)| *name == token)
+            .map(|(_, value)| value)
+    }
+
+    pub fn set(&mut self, token: &str, value: String) {
+        let slot = match token {
+            "background" => &mut self.background,
+            "surface" => &mut self.surface,
+            "surface_raised" => &mut self.surface_raised,
+            "border" => &mut self.border,
+            "border_strong" => &mut self.border_strong,
+            "text" => &mut self.text,
+            "text_muted" => &mut self.text_muted,
+            "accent" => &mut self.accent,
+            "primary" => &mut self.primary,
+            "on_primary" => &mut self.on_primary,
+            "glow" => &mut self.glow,
+            _ => return,
+        };
+        *slot = value;
+    }
+
+    fn values(&self) -> [(&'static str, &str); 11] {
+        [
+            ("background", &self.background),
+            ("surface", &self.surface),
+            ("surface_raised", &self.surface_raised),
+            ("border", &self.border),
+            ("border_strong", &self.border_strong),
+            ("text", &self.text),
+            ("text_muted", &self.text_muted),
+            ("accent", &self.accent),
+            ("primary", &self.primary),
+            ("on_primary", &self.on_primary),
+            ("glow", &self.glow),
+        ]
+    }
+
+    /// The first token that is not a hex colour, with its value.
+    pub fn invalid_token(&self) -> Option<(&'static str, String)> {
+        self.values()
+            .into_iter()
+            .find(|(_, value)| !is_hex_color(value))
+            .map(|(name, value)| (name, value.to_string()))
+    }
+
+    /// Inline `style` declarations setting the `--cvs-*` variables.
+    pub fn css_vars(&self) -> String {
+        self.values()
+            .into_iter()
+            .map(|(name, value)| format!("--cvs-{}: {value};", name.replace('_', "-")))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+fn is_hex_color(value: &str) -> bool {
+    value
+        .strip_prefix('#')
+        .is_some_and(|hex| matches!(hex.len(), 3 | 6 | 8) && hex.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_palettes_are_valid_hex() {
+        for palette in [
+            Palette::drake(),
+            Palette::misc(),
+            Palette::argo(),
+            Palette::neutral(),
+            Palette::high_contrast(),
+        ] {
+            assert_eq!(palette.invalid_token(), None);
+        }
+    }
+
+    #[test]
+    fn auto_follows_profile_and_explicit_choice_does_not() {
+        assert_eq!(ThemeChoice::Auto.resolve(Profile::Pirate), ThemeChoice::Drake);
+        assert_eq!(ThemeChoice::Misc.resolve(Profile::Pirate), ThemeChoice::Misc);
+        assert_eq!(
+            Palette::for_choice(ThemeChoice::HighContrast, Profile::Trader, &Palette::drake()),
+            Palette::high_contrast()
+        );
+        assert_eq!("high-contrast".parse(), Ok(ThemeChoice::HighContrast));
+    }
+
+    #[test]
+    fn rejects_non_hex_custom_tokens_and_emits_css_variables() {
+        let mut custom = Palette::neutral();
+        custom.set("accent", "red; background: url(x)".into());
+        assert_eq!(custom.invalid_token().map(|(name, _)| name), Some("accent"));
+
+        let css = Palette::neutral().css_vars();
+        assert!(css.contains("--cvs-surface-raised: #1e293b;"));
+        assert_eq!(css.matches("--cvs-").count(), Palette::TOKENS.len());
+    }
+}
@@ -1,43 +1,413 @@
 use std::fs;
-use std::io;
-use std::path::PathBuf;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use directories::ProjectDirs;
-use serde_json::Error as SerdeError;
+use serde::de::DeserializeOwned;
+use serde_json::{Error as SerdeError, Value};
 
-use crate::domain::app_state::PersistedState;
+use crate::domain::{
+    app_state::{PersistedState, STATE_SCHEMA_VERSION},
+    history::TradeHistory,
+    manifest::{CargoManifest, DEFAULT_MANIFEST_ID},
+};
+use crate::util::i18n::t;
 
 const APP_QUALIFIER: &str = "com";
 const APP_ORG: &str = "CargoValueScanner";
 const APP_NAME: &str = "CargoValueScanner";
 
-fn data_file() -> Option<PathBuf> {
-    ProjectDirs::from(APP_QUALIFIER, APP_ORG, APP_NAME)
-        .map(|dirs| dirs.config_dir().join("state.json"))
+/// A JSON file owned by the persistence layer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StoreFile {
+    State,
+    History,
+    Config,
 }
 
-pub fn load_persisted_state() -> Option<PersistedState> {
-    let path = data_file()?;
-    let data = fs::read_to_string(path).ok()?;
-    serde_json::from_str(&data).ok()
+impl StoreFile {
+    fn file_name(self) -> &'static str {
+        match self {
+            StoreFile::State => "state.json",
+            StoreFile::History => "history.json",
+            StoreFile::Config => "config.json",
+        }
+    }
+
+    pub fn label(self) -> String {
+        t(match self {
+            StoreFile::State => "recovery.file.state",
+            StoreFile::History => "recovery.file.history",
+            StoreFile::Config => "recovery.file.config",
+        })
+    }
+
+    pub(crate) fn path(self) -> Option<PathBuf> {
+        config_file(self.file_name())
+    }
+
+    pub(crate) fn backup_path(self) -> Option<PathBuf> {
+        self.path().map(|path| with_suffix(&path, ".bak"))
+    }
+
+    /// Saves are refused while a corrupt file awaits a recovery decision, so
+    /// the damaged data is never overwritten behind the user's back.
+    pub(crate) fn save_lock(self) -> &'static AtomicBool {
+        static STATE_LOCK: AtomicBool = AtomicBool::new(false);
+        static HISTORY_LOCK: AtomicBool = AtomicBool::new(false);
+        static CONFIG_LOCK: AtomicBool = AtomicBool::new(false);
+        match self {
+            StoreFile::State => &STATE_LOCK,
+            StoreFile::History => &HISTORY_LOCK,
+            StoreFile::Config => &CONFIG_LOCK,
+        }
+    }
+}
+
+/// Path of a file in the app's config directory.
+pub fn config_file(name: &str) -> Option<PathBuf> {
+    ProjectDirs::from(APP_QUALIFIER, APP_ORG, APP_NAME).map(|dirs| dirs.config_dir().join(name))
+}
+
+/// Upgrades a raw `state.json` document by one schema version. Index `n`
+/// migrates version `n` to `n + 1`.
+type Migration = fn(&mut Value);
+
+const STATE_MIGRATIONS: &[Migration] = &[migrate_state_v0_to_v1, migrate_state_v1_to_v2];
+
+/// v0 is every file written before the schema was versioned. `profile` and
+/// `watchlist` were added later and may be missing.
+fn migrate_state_v0_to_v1(doc: &mut Value) {
+    if let Some(object) = doc.as_object_mut() {
+        object.entry("profile").or_insert_with(|| Value::from("None"));
+        object.entry("watchlist").or_insert_with(|| Value::Array(Vec::new()));
+    }
+}
+
+/// v2 replaced the single `cargo_items` list with named manifests.
+fn migrate_state_v1_to_v2(doc: &mut Value) {
+    if let Some(object) = doc.as_object_mut() {
+        let items = object
+            .remove("cargo_items")
+            .unwrap_or_else(|| Value::Array(Vec::new()));
+        let default = CargoManifest::default_manifest();
+        let manifest = serde_json::json!({
+            "id": default.id,
+            "name": default.name,
+            "ship": null,
+            "items": items,
+        });
+        object.insert("manifests".into(), Value::Array(vec![manifest]));
+        object.insert("active_manifest_id".into(), Value::from(DEFAULT_MANIFEST_ID));
+    }
+}
+
+fn migrate_state(mut doc: Value) -> Result<Value, PersistLoadError> {
+    let mut version = doc
+        .get("schema_version")
+        .and_then(Value::as_u64)
+        .unwrap_or(0) as u32;
+    if version > STATE_SCHEMA_VERSION {
+        return Err(PersistLoadError::UnsupportedVersion(version));
+    }
+    while version < STATE_SCHEMA_VERSION {
+        STATE_MIGRATIONS[version as usize](&mut doc);
+        version += 1;
+    }
+    if let Some(object) = doc.as_object_mut() {
+        object.insert("schema_version".into(), Value::from(version));
+    }
+    Ok(doc)
+}
+
+/// `Ok(None)` means there is no saved state yet.
+pub fn load_persisted_state() -> Result<Option<PersistedState>, PersistLoadError> {
+    load_file(StoreFile::State, migrate_state)
+}
+
+pub fn load_state_backup() -> Result<Option<PersistedState>, PersistLoadError> {
+    load_backup(StoreFile::State, migrate_state)
 }
 
 pub fn save_persisted_state(state: &PersistedState) -> Result<(), PersistSaveError> {
-    let path = data_file().ok_or(PersistSaveError::StorageUnavailable)?;
+    let json = serde_json::to_string_pretty(state)?;
+    save_file(StoreFile::State, &json)
+}
+
+pub fn load_history() -> Result<Option<TradeHistory>, PersistLoadError> {
+    load_file(StoreFile::History, Ok)
+}
+
+pub fn load_history_backup() -> Result<Option<TradeHistory>, PersistLoadError> {
+    load_backup(StoreFile::History, Ok)
+}
+
+pub fn save_history(history: &TradeHistory) -> Result<(), PersistSaveError> {
+    let json = serde_json::to_string(history)?;
+    save_file(StoreFile::History, &json)
+}
+
+/// Move an unreadable file aside (keeping it for manual rescue) and allow
+/// saving again. Returns where the file was moved.
+pub fn quarantine(file: StoreFile) -> Result<Option<PathBuf>, PersistSaveError> {
+    let path = file.path().ok_or(PersistSaveError::StorageUnavailable)?;
+    quarantine_at(&path, file.save_lock())
+}
+
+pub(crate) fn quarantine_at(path: &Path, lock: &AtomicBool) -> Result<Option<PathBuf>, PersistSaveError> {
+    let moved = if path.exists() {
+        let target = with_suffix(path, &format!(".corrupt-{}", crate::domain::history::unix_now()));
+        fs::rename(path, &target)?;
+        Some(target)
+    } else {
+        None
+    };
+    lock.store(false, Ordering::SeqCst);
+    Ok(moved)
+}
+
+fn load_file<T: DeserializeOwned>(
+    file: StoreFile,
+    migrate: fn(Value) -> Result<Value, PersistLoadError>,
+) -> Result<Option<T>, PersistLoadError> {
+    let path = file.path().ok_or(PersistLoadError::StorageUnavailable)?;
+    load_at(&path, file.save_lock(), migrate)
+}
+
+/// Locks saving only when the file itself is bad; an unreadable disk says
+/// nothing about the data, and a later save may well succeed.
+fn load_at<T: DeserializeOwned>(
+    path: &Path,
+    lock: &AtomicBool,
+    migrate: fn(Value) -> Result<Value, PersistLoadError>,
+) -> Result<Option<T>, PersistLoadError> {
+    let result = read_document(path, migrate);
+    if result.as_ref().is_err_and(PersistLoadError::is_damaged) {
+        lock.store(true, Ordering::SeqCst);
+    }
+    result
+}
+
+fn load_backup<T: DeserializeOwned>(
+    file: StoreFile,
+    migrate: fn(Value) -> Result<Value, PersistLoadError>,
+) -> Result<Option<T>, PersistLoadError> {
+    let path = file.backup_path().ok_or(PersistLoadError::StorageUnavailable)?;
+    read_document(&path, migrate)
+}
+
+fn read_document<T: DeserializeOwned>(
+    path: &Path,
+    migrate: fn(Value) -> Result<Value, PersistLoadError>,
+) -> Result<Option<T>, PersistLoadError> {
+    let data = match fs::read_to_string(path) {
+        Ok(data) => data,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err.into()),
+    };
+    let doc: Value = serde_json::from_str(&data)?;
+    let doc = migrate(doc)?;
+    Ok(Some(serde_json::from_value(doc)?))
+}
+
+/// Keep the previous file as `.bak`, then replace it atomically.
+fn save_file(file: StoreFile, json: &str) -> Result<(), PersistSaveError> {
+    let path = file.path().ok_or(PersistSaveError::StorageUnavailable)?;
+    save_at(&path, file.save_lock(), json)
+}
+
+fn save_at(path: &Path, lock: &AtomicBool, json: &str) -> Result<(), PersistSaveError> {
+    if lock.load(Ordering::SeqCst) {
+        return Err(PersistSaveError::RecoveryPending);
+    }
+    back_up(path)?;
+    write_atomic(path, json.as_bytes())?;
+    Ok(())
+}
+
+/// Copy an existing file to `.bak` before it gets replaced.
+pub(crate) fn back_up(path: &Path) -> io::Result<()> {
+    if path.exists() {
+        fs::copy(path, with_suffix(path, ".bak"))?;
+    }
+    Ok(())
+}
+
+/// Write to a temp file next to `path` and rename it over the target, so a
+/// crash mid-write never leaves a truncated file behind.
+pub fn write_atomic(path: &Path, contents: &[u8]) -> io::Result<()> {
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent)?;
     }
-    let json = serde_json::to_string_pretty(state)?;
-    fs::write(path, json)?;
-    Ok(())
+    let tmp = with_suffix(path, ".tmp");
+    {
+        let mut handle = fs::File::create(&tmp)?;
+        handle.write_all(contents)?;
+        handle.sync_all()?;
+    }
+    fs::rename(&tmp, path)
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(suffix);
+    PathBuf::from(name)
 }
 
 #[derive(Debug, thiserror::Error)]
 pub enum PersistSaveError {
     #[error("storage directory unavailable")]
     StorageUnavailable,
+    #[error("saving paused until the damaged file is recovered")]
+    RecoveryPending,
     #[error(transparent)]
     Io(#[from] io::Error),
     #[error(transparent)]
     Serde(#[from] SerdeError),
 }
+
+#[derive(Debug, thiserror::Error)]
+pub enum PersistLoadError {
+    #[error("storage directory unavailable")]
+    StorageUnavailable,
+    #[error("file was written by a newer version (schema {0})")]
+    UnsupportedVersion(u32),
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error("file is damaged: {0}")]
+    Corrupt(#[from] SerdeError),
+}
+
+impl PersistLoadError {
+    /// The file itself can't be used and needs a recovery decision, as
+    /// opposed to storage that couldn't be reached.
+    pub fn is_damaged(&self) -> bool {
+        matches!(self, PersistLoadError::Corrupt(_) | PersistLoadError::UnsupportedVersion(_))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{app_state::Profile, history::TradeSide};
+
+    const V0: &str = include_str!("../../tests/fixtures/state/state_v0.json");
+    const V0_MINIMAL: &str = include_str!("../../tests/fixtures/state/state_v0_minimal.json");
+    const V1: &str = include_str!("../../tests/fixtures/state/state_v1.json");
+    const FUTURE: &str = include_str!("../../tests/fixtures/state/state_future.json");
+
+    /// A fresh directory under the system temp dir, removed on drop.
+    struct Scratch(PathBuf);
+
+    impl Scratch {
+        fn new() -> Self {
+            let dir = std::env::temp_dir().join(format!("cvs-persistence-{}", uuid::Uuid::new_v4()));
+            fs::create_dir_all(&dir).unwrap();
+            Self(dir)
+        }
+
+        fn file(&self, name: &str, contents: &str) -> PathBuf {
+            let path = self.0.join(name);
+            fs::write(&path, contents).unwrap();
+            path
+        }
+    }
+
+    impl Drop for Scratch {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn migrated(fixture: &str) -> PersistedState {
+        let doc = migrate_state(serde_json::from_str(fixture).unwrap()).unwrap();
+        assert_eq!(doc["schema_version"], STATE_SCHEMA_VERSION);
+        serde_json::from_value(doc).unwrap()
+    }
+
+    #[test]
+    fn migrates_unversioned_state_into_the_default_manifest() {
+        let state = migrated(V0);
+        assert_eq!(state.profile, Profile::Pirate);
+        assert_eq!(state.active_manifest_id, DEFAULT_MANIFEST_ID);
+        let [manifest] = state.manifests.as_slice() else {
+            panic!("expected one manifest, got {:?}", state.manifests);
+        };
+        assert_eq!(manifest.id, DEFAULT_MANIFEST_ID);
+        let items: Vec<_> = manifest.items.iter().map(|i| (i.commodity_name.as_str(), i.scu, i.is_hot)).collect();
+        assert_eq!(items, vec![("Laranite", 32, false), ("Gold", 12, true)]);
+        assert_eq!(state.profitability.crew_size, 2);
+        assert!(state.watchlist.is_empty());
+
+        let minimal = migrated(V0_MINIMAL);
+        assert_eq!(minimal.profile, Profile::None);
+        assert!(minimal.manifests[0].items.is_empty());
+    }
+
+    #[test]
+    fn migrates_v1_state_keeping_the_watchlist() {
+        let state = migrated(V1);
+        assert_eq!(state.profile, Profile::Trader);
+        assert_eq!(state.manifests[0].items[0].scu, 64);
+        assert_eq!(state.watchlist.len(), 1);
+        assert_eq!(state.watchlist[0].side, TradeSide::Sell);
+        assert!(state.watchlist[0].enabled);
+    }
+
+    #[test]
+    fn current_state_round_trips_unchanged() {
+        let state = migrated(V0);
+        let doc = serde_json::to_value(&state).unwrap();
+        assert_eq!(migrate_state(doc.clone()).unwrap(), doc);
+    }
+
+    #[test]
+    fn newer_or_damaged_files_pause_saving_until_quarantined() {
+        let scratch = Scratch::new();
+        for (name, contents) in [("future.json", FUTURE), ("damaged.json", "{\"profile\": ")] {
+            let lock = AtomicBool::new(false);
+            let path = scratch.file(name, contents);
+            let loaded: Result<Option<PersistedState>, _> = load_at(&path, &lock, migrate_state);
+            match (name, loaded) {
+                ("future.json", Err(PersistLoadError::UnsupportedVersion(99))) => {}
+                ("damaged.json", Err(PersistLoadError::Corrupt(_))) => {}
+                (_, other) => panic!("{name}: {other:?}"),
+            }
+            assert!(lock.load(Ordering::SeqCst));
+            assert!(matches!(save_at(&path, &lock, "{}"), Err(PersistSaveError::RecoveryPending)));
+            assert_eq!(fs::read_to_string(&path).unwrap(), contents);
+
+            let moved = quarantine_at(&path, &lock).unwrap().unwrap();
+            assert_eq!(fs::read_to_string(moved).unwrap(), contents);
+            assert!(!path.exists());
+            save_at(&path, &lock, "{}").unwrap();
+        }
+    }
+
+    #[test]
+    fn missing_or_unreadable_files_do_not_lock_saving() {
+        let scratch = Scratch::new();
+        let lock = AtomicBool::new(false);
+        let missing: Option<PersistedState> = load_at(&scratch.0.join("state.json"), &lock, migrate_state).unwrap();
+        assert!(missing.is_none());
+        // Reading a directory fails with an I/O error, not a parse error.
+        let unreadable: Result<Option<PersistedState>, _> = load_at(&scratch.0, &lock, migrate_state);
+        assert!(matches!(unreadable, Err(PersistLoadError::Io(_))));
+        assert!(!lock.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn saves_replace_the_file_atomically_and_keep_a_backup() {
+        let scratch = Scratch::new();
+        let lock = AtomicBool::new(false);
+        let path = scratch.0.join("nested").join("state.json");
+        save_at(&path, &lock, "first").unwrap();
+        assert!(!with_suffix(&path, ".bak").exists());
+        save_at(&path, &lock, "second").unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "second");
+        assert_eq!(fs::read_to_string(with_suffix(&path, ".bak")).unwrap(), "first");
+        assert!(!with_suffix(&path, ".tmp").exists());
+    }
+}